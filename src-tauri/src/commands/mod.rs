@@ -1,14 +1,20 @@
 use crate::dispatcher::EditorDispatcher;
-use crate::editors::EditorRegistry;
+use crate::editors::{self, EditorRegistry, OpenMode, SelfTestReport};
 use crate::git_command_log::{GitCommandLogEntry, GIT_COMMAND_LOG};
-use crate::protocol_handler::{GitHandler, GitRef, WorkingTreeStatus, WorkspaceMatch};
-use crate::settings::{Settings, SettingsManager, WorkspaceSync};
+use crate::git_host::{self, BulkCloneOptions, BulkCloneSummary};
+use crate::protocol_handler::{
+    BlameLine, CloneStrategy, GitHandler, GitRef, ProtocolHandler, ResolvedBlob, RevisionResolver,
+    WorkingTreeStatus, WorkspaceMatch, WorktreeInfo,
+};
+use crate::settings::{NormalizedPath, Settings, SettingsManager, WorkspaceSync};
+use crate::settings_sync::{SyncManager, SyncPullOutcome};
 use crate::tracker::ActiveEditorTracker;
+use crate::workspace_mru::ActiveWorkspaceTracker;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tauri::State;
+use tauri::{Emitter, State};
 
 #[cfg(target_os = "macos")]
 fn set_dark_titlebar(window: &tauri::WebviewWindow) {
@@ -59,6 +65,11 @@ pub struct EditorInfo {
     pub detected: bool,
     pub workspace: Option<String>,
     pub last_seen: Option<i64>,
+    pub requires_configuration: bool,
+    pub configured: bool,
+    /// Every installed flavor for editors backed by a `VariantResolver`
+    /// (e.g. vim/gvim/MacVim) - empty for single-binary editors.
+    pub variants: Vec<editors::EditorVariant>,
 }
 
 #[tauri::command]
@@ -76,11 +87,38 @@ pub fn get_settings_path(settings_manager: State<'_, Arc<SettingsManager>>) -> S
 #[tauri::command]
 pub async fn save_settings(
     settings_manager: State<'_, Arc<SettingsManager>>,
+    sync_manager: State<'_, Arc<SyncManager>>,
     settings: Settings,
 ) -> Result<(), String> {
     settings_manager
         .save(settings)
         .await
+        .map_err(|e| e.to_string())?;
+
+    // Best-effort: a sync repo that isn't configured yet (or a transient
+    // git failure) shouldn't block the settings save itself.
+    if let Err(e) = sync_manager.commit_settings() {
+        tracing::warn!("Failed to commit settings.yaml to sync repo: {}", e);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn sync_push_settings(
+    sync_manager: State<'_, Arc<SyncManager>>,
+) -> Result<(), String> {
+    sync_manager.sync_push().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn sync_pull_settings(
+    sync_manager: State<'_, Arc<SyncManager>>,
+    registry: State<'_, Arc<EditorRegistry>>,
+) -> Result<SyncPullOutcome, String> {
+    sync_manager
+        .sync_pull(&registry.list_editors(), editors::known_terminal_preference_names())
+        .await
         .map_err(|e| e.to_string())
 }
 
@@ -90,6 +128,9 @@ pub struct WorkspaceDisplayInfo {
     pub path: String,
     pub editor: Option<String>,
     pub is_discovered: bool,
+    pub tags: Vec<String>,
+    pub project_kind: Option<String>,
+    pub framework: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -101,6 +142,7 @@ pub struct AllWorkspaces {
 #[tauri::command]
 pub async fn get_all_workspaces(
     settings_manager: State<'_, Arc<SettingsManager>>,
+    workspace_tracker: State<'_, Arc<ActiveWorkspaceTracker>>,
 ) -> Result<AllWorkspaces, String> {
     let settings = settings_manager.get().await;
 
@@ -116,6 +158,11 @@ pub async fn get_all_workspaces(
                 .unwrap_or("unknown")
                 .to_string()
         });
+        let project_info = ws
+            .normalized_path
+            .as_deref()
+            .and_then(crate::project_kind::detect);
+
         let info = WorkspaceDisplayInfo {
             name,
             path: ws.path.clone(),
@@ -125,21 +172,48 @@ pub async fn get_all_workspaces(
                 Some(ws.editor.clone())
             },
             is_discovered: ws.auto_discovered,
+            tags: ws.tags.clone(),
+            project_kind: project_info.as_ref().map(|info| info.kind.clone()),
+            framework: project_info.and_then(|info| info.framework),
         };
 
+        let normalized_path = ws.normalized_path.clone().map(NormalizedPath::into_path_buf);
         if ws.auto_discovered {
-            discovered.push(info);
+            discovered.push((normalized_path, info));
         } else {
-            explicit.push(info);
+            explicit.push((normalized_path, info));
         }
     }
 
+    let ranked = workspace_tracker.ranked_workspaces().await;
+    sort_by_frecency(&mut explicit, &ranked);
+    sort_by_frecency(&mut discovered, &ranked);
+
     Ok(AllWorkspaces {
-        explicit,
-        discovered,
+        explicit: explicit.into_iter().map(|(_, info)| info).collect(),
+        discovered: discovered.into_iter().map(|(_, info)| info).collect(),
     })
 }
 
+/// Reorders `workspaces` to match `ranked`'s frecency order, keeping
+/// workspaces `ranked` has no opinion on (never opened, or not yet probed)
+/// in their original relative position at the end - a stable partial sort
+/// rather than treating "unranked" as a tiebreak within the ranked set.
+fn sort_by_frecency(
+    workspaces: &mut [(Option<PathBuf>, WorkspaceDisplayInfo)],
+    ranked: &[PathBuf],
+) {
+    let position: HashMap<&PathBuf, usize> =
+        ranked.iter().enumerate().map(|(i, path)| (path, i)).collect();
+
+    workspaces.sort_by_key(|(path, _)| {
+        path.as_ref()
+            .and_then(|path| position.get(path))
+            .copied()
+            .unwrap_or(usize::MAX)
+    });
+}
+
 #[tauri::command]
 pub async fn promote_workspace(
     settings_manager: State<'_, Arc<SettingsManager>>,
@@ -149,8 +223,7 @@ pub async fn promote_workspace(
     let mut settings = settings_manager.get().await;
 
     // Check if already exists
-    let normalized_path = shellexpand::tilde(&path);
-    let target_path = PathBuf::from(normalized_path.as_ref());
+    let target_path = crate::settings::normalize_lexical(&path);
 
     for ws in &settings.workspaces {
         if let Some(ref existing) = ws.normalized_path {
@@ -167,6 +240,9 @@ pub async fn promote_workspace(
             name: Some(name),
             editor: String::new(),
             auto_discovered: false,
+            enable_paths: None,
+            disable_paths: None,
+            tags: Vec::new(),
             normalized_path: Some(target_path),
         });
 
@@ -186,12 +262,12 @@ pub async fn sync_workspaces(
 #[tauri::command]
 pub async fn delete_workspace(
     settings_manager: State<'_, Arc<SettingsManager>>,
+    protocol_handler: State<'_, Arc<ProtocolHandler>>,
     path: String,
 ) -> Result<(), String> {
     let mut settings = settings_manager.get().await;
 
-    let normalized_path = shellexpand::tilde(&path);
-    let target_path = PathBuf::from(normalized_path.as_ref());
+    let target_path = crate::settings::normalize_lexical(&path);
 
     let mut found_index = None;
     let mut was_auto_discovered = false;
@@ -218,11 +294,79 @@ pub async fn delete_workspace(
             .save(settings)
             .await
             .map_err(|e| e.to_string())?;
+
+        protocol_handler.stop_watching_workspace(&target_path);
     }
 
     Ok(())
 }
 
+#[tauri::command]
+pub async fn set_workspace_tags(
+    settings_manager: State<'_, Arc<SettingsManager>>,
+    path: String,
+    tags: Vec<String>,
+) -> Result<(), String> {
+    let mut settings = settings_manager.get().await;
+
+    let target_path = crate::settings::normalize_lexical(&path);
+
+    let workspace = settings
+        .workspaces
+        .iter_mut()
+        .find(|ws| ws.normalized_path.as_deref() == Some(target_path.as_path()))
+        .ok_or_else(|| format!("No workspace found for path '{}'", path))?;
+    workspace.tags = tags;
+
+    settings_manager
+        .save(settings)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_workspaces_by_tag(
+    settings_manager: State<'_, Arc<SettingsManager>>,
+    tag: String,
+) -> Result<Vec<WorkspaceDisplayInfo>, String> {
+    let settings = settings_manager.get().await;
+
+    Ok(settings
+        .workspaces
+        .iter()
+        .filter(|ws| ws.tags.iter().any(|t| t == &tag))
+        .map(|ws| {
+            let name = ws.name.clone().unwrap_or_else(|| {
+                ws.normalized_path
+                    .as_ref()
+                    .and_then(|p| p.file_name())
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("unknown")
+                    .to_string()
+            });
+
+            let project_info = ws
+                .normalized_path
+                .as_deref()
+                .and_then(crate::project_kind::detect);
+
+            WorkspaceDisplayInfo {
+                name,
+                path: ws.path.clone(),
+                editor: if ws.editor.is_empty() {
+                    None
+                } else {
+                    Some(ws.editor.clone())
+                },
+                is_discovered: ws.auto_discovered,
+                tags: ws.tags.clone(),
+                project_kind: project_info.as_ref().map(|info| info.kind.clone()),
+                framework: project_info.and_then(|info| info.framework),
+            }
+        })
+        .collect())
+}
+
 #[tauri::command]
 pub async fn get_editor_testbed_data(
     registry: State<'_, Arc<EditorRegistry>>,
@@ -241,6 +385,8 @@ pub async fn get_editor_testbed_data(
                 .await
                 .ok()
                 .unwrap_or_default();
+            let requires_configuration = registry.requires_configuration(&editor_id);
+            let variants = manager.variants().await;
 
             editors.push(EditorInfo {
                 editor_id: editor_id.clone(),
@@ -249,6 +395,10 @@ pub async fn get_editor_testbed_data(
                 detected: !instances.is_empty(),
                 workspace: instances.first().and_then(|inst| inst.workspace.clone()),
                 last_seen: last_seen_data.editors.get(&editor_id).copied(),
+                requires_configuration,
+                configured: !requires_configuration
+                    || settings.defaults.editor_paths.contains_key(&editor_id),
+                variants,
             });
         }
     }
@@ -261,11 +411,228 @@ pub async fn get_editor_testbed_data(
     })
 }
 
+#[derive(Clone, Serialize)]
+pub struct AvailableEditorInfo {
+    pub id: String,
+    pub name: String,
+}
+
+/// Lists "Open With"-style external editors installed on the machine (see
+/// `external_editors::discover`), for the clone dialog's editor picker.
+/// Each one is registered into `registry` under its own id as a side
+/// effect, so selecting it and saving the id into `WorkspaceConfig.editor`
+/// dispatches through `EditorDispatcher` exactly like a built-in editor.
+#[tauri::command]
+pub async fn get_available_editors(
+    registry: State<'_, Arc<EditorRegistry>>,
+) -> Result<Vec<AvailableEditorInfo>, String> {
+    let discovered = crate::external_editors::discover();
+
+    let infos = discovered
+        .iter()
+        .map(|editor| AvailableEditorInfo {
+            id: editor.id.clone(),
+            name: editor.name.clone(),
+        })
+        .collect();
+
+    for editor in discovered {
+        registry.register_external(&editor.id, &editor.name, &editor.exec_template, editor.terminal);
+    }
+
+    Ok(infos)
+}
+
+/// Lists applications registered to open `path`'s specific file type (see
+/// `external_editors::list_openers`), for an "Open With" context menu on
+/// that one file rather than the clone dialog's always-available roster.
+/// Each one is registered into `registry` under its own id, same as
+/// `get_available_editors`, so `open_with` can dispatch to it by id.
+#[tauri::command]
+pub async fn get_openers_for_path(
+    path: String,
+    registry: State<'_, Arc<EditorRegistry>>,
+) -> Result<Vec<AvailableEditorInfo>, String> {
+    let discovered = crate::external_editors::list_openers(Path::new(&path));
+
+    let infos = discovered
+        .iter()
+        .map(|editor| AvailableEditorInfo {
+            id: editor.id.clone(),
+            name: editor.name.clone(),
+        })
+        .collect();
+
+    for editor in discovered {
+        registry.register_external(&editor.id, &editor.name, &editor.exec_template, editor.terminal);
+    }
+
+    Ok(infos)
+}
+
+/// Ranked "Open With" suggestions for `path`: installed built-in editors
+/// that declare MIME/extension support for it (`EditorRegistry::suggest_editors`,
+/// ordered so an IDE outranks a lightweight editor for a source file)
+/// followed by whatever OS-level `.desktop`/LaunchServices entries
+/// `get_openers_for_path` would surface, skipping any id a built-in
+/// suggestion already covers.
+#[tauri::command]
+pub async fn get_editor_suggestions(
+    path: String,
+    registry: State<'_, Arc<EditorRegistry>>,
+) -> Result<Vec<AvailableEditorInfo>, String> {
+    let suggestions = registry.suggest_editors(Path::new(&path)).await;
+    let mut seen: std::collections::HashSet<String> =
+        suggestions.iter().map(|s| s.id.clone()).collect();
+
+    let mut infos: Vec<AvailableEditorInfo> = suggestions
+        .into_iter()
+        .map(|s| AvailableEditorInfo { id: s.id, name: s.display_name })
+        .collect();
+
+    for editor in crate::external_editors::list_openers(Path::new(&path)) {
+        if seen.insert(editor.id.clone()) {
+            registry.register_external(&editor.id, &editor.name, &editor.exec_template, editor.terminal);
+            infos.push(AvailableEditorInfo { id: editor.id, name: editor.name });
+        }
+    }
+
+    Ok(infos)
+}
+
+/// Opens `path` with the "Open With" choice `editor_id` previously surfaced
+/// by `get_openers_for_path` - a thin dispatcher call, since that command
+/// already registered it into `registry` under this id.
+#[tauri::command]
+pub async fn open_with(
+    dispatcher: State<'_, Arc<EditorDispatcher>>,
+    path: String,
+    editor_id: String,
+) -> Result<(), String> {
+    dispatcher
+        .open(&path, None, None, OpenMode::NewWindow, Some(editor_id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Starts watch-and-reopen mode for a `WorkspaceMatch` the user chose from
+/// a chooser or opened directly: once `full_file_path` changes on disk
+/// (an external build step or generator rewriting it), it's reopened with
+/// the same `line`/`column`.
+#[tauri::command]
+pub async fn watch_workspace_file(
+    protocol_handler: State<'_, Arc<ProtocolHandler>>,
+    workspace_path: String,
+    full_file_path: String,
+    line: Option<usize>,
+    column: Option<usize>,
+) -> Result<(), String> {
+    protocol_handler
+        .watch_workspace_file(
+            PathBuf::from(workspace_path),
+            PathBuf::from(full_file_path),
+            line,
+            column,
+        )
+        .map_err(|e| e.to_string())
+}
+
+/// Stops every watch registered under `workspace_path` - the counterpart to
+/// `watch_workspace_file`, for a user-initiated "stop watching" action.
+#[tauri::command]
+pub async fn unwatch_workspace(
+    protocol_handler: State<'_, Arc<ProtocolHandler>>,
+    workspace_path: String,
+) -> Result<(), String> {
+    protocol_handler.stop_watching_workspace(Path::new(&workspace_path));
+    Ok(())
+}
+
+/// Opens `path`'s containing folder in the OS file manager with `path`
+/// itself selected, the way a file manager's own "Reveal"/"Show in Folder"
+/// action would - Finder's `open -R`, Explorer's `/select,`, and on Linux
+/// the `org.freedesktop.FileManager1.ShowItems` D-Bus method the major file
+/// managers (Nautilus, Dolphin, Nemo, ...) all implement, falling back to
+/// just opening the parent directory if that call isn't available.
+#[tauri::command]
+pub async fn reveal_in_file_manager(path: String) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        crate::sandbox_env::spawn_external("open", &["-R", &path])
+            .map_err(|e| format!("Failed to reveal {}: {}", path, e))?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::sandbox_env::spawn_external("explorer", &[&format!("/select,{}", path)])
+            .map_err(|e| format!("Failed to reveal {}: {}", path, e))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let file_uri = format!("file://{}", path);
+        let dbus_result = crate::sandbox_env::spawn_external(
+            "dbus-send",
+            &[
+                "--session",
+                "--print-reply",
+                "--dest=org.freedesktop.FileManager1",
+                "/org/freedesktop/FileManager1",
+                "org.freedesktop.FileManager1.ShowItems",
+                &format!("array:string:{}", file_uri),
+                "string:",
+            ],
+        )
+        .and_then(|mut child| child.wait());
+
+        if !matches!(dbus_result, Ok(status) if status.success()) {
+            let parent = Path::new(&path).parent().unwrap_or(Path::new(&path));
+            crate::sandbox_env::spawn_external("xdg-open", &[parent.to_string_lossy().as_ref()])
+                .map_err(|e| format!("Failed to reveal {}: {}", path, e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Diagnostic report for the configured default editor/terminal combo,
+/// returned by the `doctor` command so a user on a fresh machine can tell
+/// whether `defaults.editor` plus `defaults.preferred_terminal` will
+/// actually work before they rely on it.
+#[derive(Clone, Serialize)]
+pub struct DoctorReport {
+    pub editor: SelfTestReport,
+    /// Packaging runtime this process is running under (`"flatpak"`,
+    /// `"snap"`, `"appimage"`), if any - lets a user tell whether a broken
+    /// editor launch is a packaging-environment issue before digging
+    /// further.
+    pub packaging_mode: Option<&'static str>,
+}
+
+#[tauri::command]
+pub async fn doctor(
+    registry: State<'_, Arc<EditorRegistry>>,
+    settings_manager: State<'_, Arc<SettingsManager>>,
+) -> Result<DoctorReport, String> {
+    let settings = settings_manager.get().await;
+    let editor_id = &settings.defaults.editor;
+
+    let manager = registry
+        .get(editor_id)
+        .ok_or_else(|| format!("Unknown editor '{}' in defaults.editor", editor_id))?;
+
+    Ok(DoctorReport {
+        editor: manager.self_test().await,
+        packaging_mode: editors::packaging_mode(),
+    })
+}
+
 #[tauri::command]
 pub async fn test_open_file(
     dispatcher: State<'_, Arc<EditorDispatcher>>,
     editor_id: String,
     test_file_path: Option<String>,
+    mode: Option<OpenMode>,
 ) -> Result<String, String> {
     let file_path = test_file_path.unwrap_or_else(|| {
         let manifest_dir = env!("CARGO_MANIFEST_DIR");
@@ -273,7 +640,13 @@ pub async fn test_open_file(
     });
 
     dispatcher
-        .open(&file_path, Some(50), None, true, Some(editor_id.clone()))
+        .open(
+            &file_path,
+            Some(50),
+            None,
+            mode.unwrap_or(OpenMode::NewWindow),
+            Some(editor_id.clone()),
+        )
         .await
         .map_err(|e| e.to_string())?;
 
@@ -286,11 +659,11 @@ pub async fn open_in_editor(
     path: String,
     line: Option<usize>,
     column: Option<usize>,
-    new_window: bool,
+    mode: OpenMode,
     editor: Option<String>,
 ) -> Result<(), String> {
     dispatcher
-        .open(&path, line, column, new_window, editor)
+        .open(&path, line, column, mode, editor)
         .await
         .map_err(|e| e.to_string())
 }
@@ -381,25 +754,11 @@ pub async fn detect_source_folder() -> Result<String, String> {
 }
 
 fn count_git_repos(dir: &Path) -> Result<usize, std::io::Error> {
-    let mut count = 0;
-
     if !dir.is_dir() {
         return Ok(0);
     }
 
-    for entry in std::fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            let git_path = path.join(".git");
-            if git_path.exists() {
-                count += 1;
-            }
-        }
-    }
-
-    Ok(count)
+    Ok(crate::repo_discovery::scan_for_repos_default(dir).count())
 }
 
 #[derive(Clone, Serialize)]
@@ -441,7 +800,7 @@ pub async fn workspace_chosen(
             &workspace_match.full_file_path.to_string_lossy(),
             data.line,
             data.column,
-            false,
+            OpenMode::NewWindow,
             None,
         )
         .await
@@ -495,6 +854,16 @@ pub fn get_git_revision_info(workspace_path: String, rev: String) -> Result<Stri
     GitHandler::get_revision_info(&path, &rev).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_blame_at_revision(
+    workspace_path: String,
+    file_path: String,
+    rev: String,
+) -> Result<Vec<BlameLine>, String> {
+    let path = PathBuf::from(&workspace_path);
+    GitHandler::get_blame_at_revision(&path, &file_path, &rev).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn open_file_at_revision(
     workspace_path: String,
@@ -504,6 +873,7 @@ pub async fn open_file_at_revision(
     column: Option<usize>,
     checkout: bool,
     dispatcher: State<'_, Arc<EditorDispatcher>>,
+    protocol_handler: State<'_, Arc<crate::protocol_handler::ProtocolHandler>>,
 ) -> Result<(), String> {
     let workspace = PathBuf::from(&workspace_path);
 
@@ -512,14 +882,28 @@ pub async fn open_file_at_revision(
 
         tracing::info!("Checking out from {} to {}", current_ref, rev);
 
-        GitHandler::checkout_revision(&workspace, &rev).map_err(|e| e.to_string())?;
+        // Checkout mutates the working tree, so it takes the repo's
+        // mutation lock for its duration; read-only lookups never do.
+        let git_cache = protocol_handler.git_cache().clone();
+        let lock = git_cache.mutation_lock(&workspace);
+        let checkout_workspace = workspace.clone();
+        let checkout_rev = rev.clone();
+        tokio::task::spawn_blocking(move || {
+            let _guard = lock.blocking_write();
+            GitHandler::checkout_revision(&checkout_workspace, &checkout_rev)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+        git_cache.invalidate(&workspace);
 
         tracing::info!("Successfully checked out to {}", rev);
 
         let full_path = workspace.join(&file_path);
 
         dispatcher
-            .open(&full_path.to_string_lossy(), line, column, false, None)
+            .open(&full_path.to_string_lossy(), line, column, OpenMode::NewWindow, None)
             .await
             .map_err(|e| e.to_string())?;
     } else {
@@ -538,7 +922,7 @@ pub async fn open_file_at_revision(
             .map_err(|e| format!("Failed to write temp file: {}", e))?;
 
         dispatcher
-            .open(&temp_file.to_string_lossy(), line, column, true, None)
+            .open(&temp_file.to_string_lossy(), line, column, OpenMode::NewWindow, None)
             .await
             .map_err(|e| e.to_string())?;
     }
@@ -548,6 +932,63 @@ pub async fn open_file_at_revision(
     Ok(())
 }
 
+/// Content returned by `preview_blob_at_revision`, a thin wire-format wrapper
+/// around [`ResolvedBlob`] - `content` is decoded as UTF-8 rather than raw
+/// bytes since, like `get_file_at_revision`, this is for showing source
+/// files in a preview pane, not arbitrary binary blobs.
+#[derive(Clone, Serialize)]
+pub struct PreviewedBlob {
+    pub commit_oid: String,
+    pub content: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+/// Reads a file's content at `git_ref` straight out of the repository's
+/// object database via [`RevisionResolver`], without checking anything out -
+/// unlike `open_file_at_revision`'s non-checkout path, which writes a temp
+/// file and opens it in an external editor, this is meant for an in-app
+/// preview pane that just wants the text.
+#[tauri::command]
+pub async fn preview_blob_at_revision(
+    workspace_path: String,
+    file_path: String,
+    git_ref: GitRef,
+    remote: Option<String>,
+    line: Option<usize>,
+    column: Option<usize>,
+) -> Result<PreviewedBlob, String> {
+    let workspace = PathBuf::from(&workspace_path);
+
+    let ResolvedBlob {
+        commit_oid,
+        content,
+        line,
+        column,
+    } = tokio::task::spawn_blocking(move || {
+        RevisionResolver::resolve(
+            &workspace,
+            &file_path,
+            &git_ref,
+            remote.as_deref(),
+            line,
+            column,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    let content = String::from_utf8(content).map_err(|_| "File content is not valid UTF-8".to_string())?;
+
+    Ok(PreviewedBlob {
+        commit_oid,
+        content,
+        line,
+        column,
+    })
+}
+
 #[tauri::command]
 pub fn revision_dialog_cancelled() -> Result<(), String> {
     *REVISION_DIALOG_DATA.lock() = None;
@@ -564,9 +1005,215 @@ pub fn refresh_working_tree_status(workspace_path: String) -> Result<WorkingTree
     GitHandler::get_working_tree_status(&path).map_err(|e| e.to_string())
 }
 
+/// The `CancelToken` for whichever working-tree-status scan is currently
+/// running, if any, so a newer refresh request can cancel a stale one
+/// rather than both scans racing to update the UI.
+static STATUS_SCAN_CANCEL_TOKEN: parking_lot::Mutex<Option<crate::git_command_log::CancelToken>> =
+    parking_lot::Mutex::new(None);
+
+/// Like `refresh_working_tree_status`, but for very large working trees:
+/// streams batches of parsed entries as `working-tree-status-batch` events
+/// instead of blocking the whole refresh on one `git status` call. Cancels
+/// any previous in-flight scan first, since only the most recent refresh
+/// request matters.
+#[tauri::command]
+pub async fn stream_working_tree_status(
+    workspace_path: String,
+    app: tauri::AppHandle,
+) -> Result<WorkingTreeStatus, String> {
+    if let Some(previous) = STATUS_SCAN_CANCEL_TOKEN.lock().as_ref() {
+        previous.cancel();
+    }
+
+    let cancel = crate::git_command_log::CancelToken::new();
+    *STATUS_SCAN_CANCEL_TOKEN.lock() = Some(cancel.clone());
+
+    let path = PathBuf::from(&workspace_path);
+    let batch_app = app.clone();
+    let batch_cancel = cancel.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        GitHandler::stream_working_tree_status(
+            &path,
+            crate::protocol_handler::STATUS_BATCH_SIZE,
+            &batch_cancel,
+            |batch| {
+                let _ = batch_app.emit("working-tree-status-batch", &batch);
+            },
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string());
+
+    *STATUS_SCAN_CANCEL_TOKEN.lock() = None;
+
+    result
+}
+
+#[tauri::command]
+pub fn working_tree_status_scan_cancelled() -> Result<(), String> {
+    if let Some(token) = STATUS_SCAN_CANCEL_TOKEN.lock().as_ref() {
+        token.cancel();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_git_operation_state(
+    workspace_path: String,
+) -> Result<crate::protocol_handler::GitOperationState, String> {
+    let path = PathBuf::from(&workspace_path);
+    GitHandler::check_git_operation_state(&path).map_err(|e| e.to_string())
+}
+
+/// Aborts whichever merge/rebase/cherry-pick/bisect `get_git_operation_state`
+/// reported in progress, so the UI's "abort" button has something to call
+/// instead of leaving the user to the command line.
+#[tauri::command]
+pub async fn abort_git_operation(
+    workspace_path: String,
+    protocol_handler: State<'_, Arc<crate::protocol_handler::ProtocolHandler>>,
+) -> Result<crate::protocol_handler::OperationActionResult, String> {
+    let workspace = PathBuf::from(&workspace_path);
+    let git_cache = protocol_handler.git_cache().clone();
+    let lock = git_cache.mutation_lock(&workspace);
+
+    let result = tokio::task::spawn_blocking(move || {
+        let _guard = lock.blocking_write();
+        GitHandler::abort_operation(&workspace)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    git_cache.invalidate(&PathBuf::from(&workspace_path));
+
+    Ok(result)
+}
+
+/// Continues whichever merge/rebase/cherry-pick `get_git_operation_state`
+/// reported in progress, for the UI's "continue" button once the user has
+/// resolved conflicts.
+#[tauri::command]
+pub async fn continue_git_operation(
+    workspace_path: String,
+    protocol_handler: State<'_, Arc<crate::protocol_handler::ProtocolHandler>>,
+) -> Result<crate::protocol_handler::OperationActionResult, String> {
+    let workspace = PathBuf::from(&workspace_path);
+    let git_cache = protocol_handler.git_cache().clone();
+    let lock = git_cache.mutation_lock(&workspace);
+
+    let result = tokio::task::spawn_blocking(move || {
+        let _guard = lock.blocking_write();
+        GitHandler::continue_operation(&workspace)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    git_cache.invalidate(&PathBuf::from(&workspace_path));
+
+    Ok(result)
+}
+
+/// Like checking out from `open_file_at_revision`, but autostashes a dirty
+/// working tree instead of refusing to proceed - lets the revision preview
+/// flow offer "stash my changes and show me this revision" rather than
+/// forcing the user to stash manually first.
+#[tauri::command]
+pub async fn checkout_revision_stashing(
+    workspace_path: String,
+    rev: String,
+    protocol_handler: State<'_, Arc<crate::protocol_handler::ProtocolHandler>>,
+) -> Result<crate::protocol_handler::CheckoutStashResult, String> {
+    let workspace = PathBuf::from(&workspace_path);
+    let git_cache = protocol_handler.git_cache().clone();
+    let lock = git_cache.mutation_lock(&workspace);
+
+    let result = tokio::task::spawn_blocking(move || {
+        let _guard = lock.blocking_write();
+        GitHandler::checkout_revision_stashing(&workspace, &rev)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    git_cache.invalidate(&PathBuf::from(&workspace_path));
+
+    Ok(result)
+}
+
+/// Pops the stash `checkout_revision_stashing` created, restoring the
+/// working tree it set aside before the preview checkout.
+#[tauri::command]
+pub async fn restore_autostash(
+    workspace_path: String,
+    protocol_handler: State<'_, Arc<crate::protocol_handler::ProtocolHandler>>,
+) -> Result<crate::protocol_handler::RestoreStashResult, String> {
+    let workspace = PathBuf::from(&workspace_path);
+    let git_cache = protocol_handler.git_cache().clone();
+    let lock = git_cache.mutation_lock(&workspace);
+
+    let result = tokio::task::spawn_blocking(move || {
+        let _guard = lock.blocking_write();
+        GitHandler::restore_autostash(&workspace)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    git_cache.invalidate(&PathBuf::from(&workspace_path));
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn list_git_worktrees(workspace_path: String) -> Result<Vec<WorktreeInfo>, String> {
+    let path = PathBuf::from(&workspace_path);
+    GitHandler::list_worktrees(&path).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_worktree(
+    workspace_path: String,
+    worktree_path: String,
+    force: bool,
+    protocol_handler: State<'_, Arc<crate::protocol_handler::ProtocolHandler>>,
+) -> Result<(), String> {
+    let workspace = PathBuf::from(&workspace_path);
+    let worktree = PathBuf::from(&worktree_path);
+
+    let git_cache = protocol_handler.git_cache().clone();
+    let lock = git_cache.mutation_lock(&workspace);
+    let remove_workspace = workspace.clone();
+    let remove_worktree = worktree.clone();
+    tokio::task::spawn_blocking(move || {
+        let _guard = lock.blocking_write();
+        GitHandler::remove_worktree(&remove_workspace, &remove_worktree, force)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    git_cache.invalidate(&workspace);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_worktree_pinned(
+    workspace_path: String,
+    worktree_path: String,
+    pinned: bool,
+) -> Result<(), String> {
+    let workspace = PathBuf::from(&workspace_path);
+    let worktree = PathBuf::from(&worktree_path);
+    GitHandler::set_worktree_pinned(&workspace, &worktree, pinned).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn create_worktree_and_open(
     dispatcher: State<'_, Arc<EditorDispatcher>>,
+    protocol_handler: State<'_, Arc<crate::protocol_handler::ProtocolHandler>>,
     workspace_path: String,
     workspace_name: String,
     branch_or_commit: String,
@@ -576,9 +1223,28 @@ pub async fn create_worktree_and_open(
 ) -> Result<(), String> {
     let workspace = PathBuf::from(&workspace_path);
 
-    // Create worktree (reuses existing if available)
-    let worktree_path = GitHandler::create_worktree(&workspace, &workspace_name, &branch_or_commit)
-        .map_err(|e| e.to_string())?;
+    // Adding a worktree mutates the repo's administrative state (git's
+    // `worktrees/` directory), so it takes the mutation lock like checkout
+    // does; read-only lookups never do.
+    let git_cache = protocol_handler.git_cache().clone();
+    let lock = git_cache.mutation_lock(&workspace);
+    let worktree_workspace = workspace.clone();
+    let worktree_name = workspace_name.clone();
+    let worktree_rev = branch_or_commit.clone();
+    let worktree_path = tokio::task::spawn_blocking(move || {
+        let _guard = lock.blocking_write();
+        GitHandler::create_worktree(
+            &worktree_workspace,
+            &worktree_name,
+            &worktree_rev,
+            crate::protocol_handler::DEFAULT_MAX_WORKTREES,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    git_cache.invalidate(&workspace);
 
     // Build full file path in worktree
     let full_path = worktree_path.join(&file_path);
@@ -594,7 +1260,7 @@ pub async fn create_worktree_and_open(
 
     // Open in editor
     dispatcher
-        .open(&full_path.to_string_lossy(), line, column, false, None)
+        .open(&full_path.to_string_lossy(), line, column, OpenMode::NewWindow, None)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -728,6 +1394,7 @@ pub async fn test_protocol_url(
             line,
             column,
             git_ref,
+            clone_strategy,
         }) => {
             GIT_COMMAND_LOG.log_request(
                 &url,
@@ -749,6 +1416,8 @@ pub async fn test_protocol_url(
                 column,
                 git_ref: git_ref_display,
                 git_ref_kind: git_ref.clone(),
+                clone_strategy,
+                editor: None,
             });
 
             let window = tauri::WebviewWindowBuilder::new(
@@ -770,6 +1439,45 @@ pub async fn test_protocol_url(
 
             Ok(())
         }
+        Ok(HandleResult::ShowBulkCloneDialog {
+            host,
+            owner,
+            include_forks,
+            include_archived,
+        }) => {
+            GIT_COMMAND_LOG.log_request(
+                &url,
+                true,
+                "bulk_clone_dialog",
+                &format!("Offering to bulk-clone {} from {}", owner, host),
+                duration,
+            );
+            set_bulk_clone_dialog_data(BulkCloneDialogData {
+                host,
+                owner,
+                include_forks,
+                include_archived,
+            });
+
+            let window = tauri::WebviewWindowBuilder::new(
+                &app,
+                "bulk-clone-dialog",
+                tauri::WebviewUrl::App("bulk-clone-dialog.html".into()),
+            )
+            .title("Clone Organization")
+            .inner_size(520.0, 420.0)
+            .center()
+            .resizable(false)
+            .always_on_top(true)
+            .focused(true)
+            .build()
+            .map_err(|e| e.to_string())?;
+
+            #[cfg(target_os = "macos")]
+            set_dark_titlebar(&window);
+
+            Ok(())
+        }
         Ok(HandleResult::OpenInBrowser { url: browser_url }) => {
             GIT_COMMAND_LOG.log_request(
                 &url,
@@ -802,11 +1510,23 @@ pub struct CloneDialogData {
     pub git_ref: Option<String>,
     #[serde(skip)]
     pub git_ref_kind: Option<GitRef>,
+    pub clone_strategy: CloneStrategy,
+    /// Editor id to open the clone with once it finishes, chosen from
+    /// `get_available_editors()` via `set_clone_editor`. Saved into the new
+    /// workspace's `editor` field so later opens use the same editor.
+    #[serde(default)]
+    pub editor: Option<String>,
 }
 
 static CLONE_DIALOG_DATA: parking_lot::Mutex<Option<CloneDialogData>> =
     parking_lot::Mutex::new(None);
 
+/// The `CancelToken` for whichever clone is currently in flight, if any, so
+/// `clone_cancelled` can actually stop it rather than just forgetting the
+/// dialog state while the clone keeps running in the background.
+static CLONE_CANCEL_TOKEN: parking_lot::Mutex<Option<crate::git_command_log::CancelToken>> =
+    parking_lot::Mutex::new(None);
+
 pub fn set_clone_dialog_data(data: CloneDialogData) {
     *CLONE_DIALOG_DATA.lock() = Some(data);
 }
@@ -816,40 +1536,97 @@ pub(crate) fn git_ref_display(git_ref: &GitRef) -> String {
         GitRef::Branch(value) => value.clone(),
         GitRef::Tag(value) => format!("tag {}", value),
         GitRef::Commit(value) => format!("commit {}", value),
+        GitRef::Jj(value) => format!("jj {}", value),
+        GitRef::Version(value) => format!("version {}", value),
     }
 }
 
 #[tauri::command]
-pub fn get_clone_dialog_data() -> Result<CloneDialogData, String> {
-    CLONE_DIALOG_DATA
+pub async fn get_clone_dialog_data(
+    settings_manager: State<'_, Arc<SettingsManager>>,
+) -> Result<CloneDialogData, String> {
+    let mut data = CLONE_DIALOG_DATA
         .lock()
         .clone()
-        .ok_or_else(|| "No clone dialog data available".to_string())
+        .ok_or_else(|| "No clone dialog data available".to_string())?;
+
+    // Show the user the canonical URL a shorthand like `gh:owner/repo`
+    // expands to, rather than the raw shorthand they typed.
+    let settings = settings_manager.get().await;
+    data.remote_url = crate::git_url::expand(&data.remote_url, &settings.defaults.custom_git_vendors);
+
+    Ok(data)
 }
 
 #[tauri::command]
 pub async fn clone_and_open(
     dispatcher: State<'_, Arc<EditorDispatcher>>,
     settings_manager: State<'_, Arc<SettingsManager>>,
+    app: tauri::AppHandle,
 ) -> Result<(), String> {
     let data = CLONE_DIALOG_DATA
         .lock()
         .clone()
         .ok_or_else(|| "No clone dialog data available".to_string())?;
 
-    let target_path = PathBuf::from(&data.clone_path);
+    let settings = settings_manager.get().await;
+    let remote_url = crate::git_url::expand(&data.remote_url, &settings.defaults.custom_git_vendors);
+    drop(settings);
+
+    // `clone_path` may arrive as a `wsl://` deep-link URI rather than a real
+    // filesystem path - translate it to the `\\wsl$\...` UNC form Windows can
+    // actually clone and open through before touching the filesystem.
+    let clone_path = match crate::wsl::detect(&data.clone_path) {
+        Some(wsl_target) => crate::wsl::to_windows_unc(&wsl_target),
+        None => data.clone_path.clone(),
+    };
+    let target_path = PathBuf::from(&clone_path);
+
+    // Stream clone progress to the frontend as it arrives rather than
+    // waiting for the whole (possibly large) clone to finish.
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let progress_app = app.clone();
+    let progress_forwarder = tokio::spawn(async move {
+        while let Some(event) = progress_rx.recv().await {
+            let _ = progress_app.emit("clone-progress", &event);
+        }
+    });
 
-    GitHandler::clone_repo(&data.remote_url, &target_path, data.git_ref_kind.as_ref())
-        .map_err(|e| e.to_string())?;
+    let cancel = crate::git_command_log::CancelToken::new();
+    *CLONE_CANCEL_TOKEN.lock() = Some(cancel.clone());
+
+    let clone_result = GitHandler::clone(
+        &remote_url,
+        &target_path,
+        data.git_ref_kind.as_ref(),
+        progress_tx,
+        cancel.clone(),
+    )
+    .await;
+
+    let _ = progress_forwarder.await;
+    *CLONE_CANCEL_TOKEN.lock() = None;
+
+    if let Err(e) = clone_result {
+        // Don't leave a half-cloned directory behind, whether the clone
+        // failed outright or was cut short by `clone_cancelled`.
+        let _ = std::fs::remove_dir_all(&target_path);
+        let _ = app.emit("clone-error", e.to_string());
+        return Err(e.to_string());
+    }
+    let _ = app.emit("clone-complete", ());
 
     // Add new workspace to settings
     let mut settings = settings_manager.get().await;
     settings.workspaces.push(crate::settings::WorkspaceConfig {
-        path: data.clone_path.clone(),
+        path: clone_path.clone(),
         name: Some(data.workspace_name.clone()),
-        editor: String::new(),
+        editor: data.editor.clone().unwrap_or_default(),
         auto_discovered: false,
-        normalized_path: Some(target_path.clone()),
+        enable_paths: None,
+        disable_paths: None,
+        tags: Vec::new(),
+        normalized_path: Some(NormalizedPath::from_existing(target_path.clone())),
     });
     settings_manager
         .save(settings)
@@ -863,8 +1640,8 @@ pub async fn clone_and_open(
             &full_file_path.to_string_lossy(),
             data.line,
             data.column,
-            false,
-            None,
+            OpenMode::NewWindow,
+            data.editor.clone(),
         )
         .await
         .map_err(|e| e.to_string())?;
@@ -885,12 +1662,71 @@ pub fn update_clone_path(new_path: String) -> Result<(), String> {
     }
 }
 
+#[tauri::command]
+pub fn set_clone_editor(editor_id: String) -> Result<(), String> {
+    let mut data = CLONE_DIALOG_DATA.lock();
+    if let Some(ref mut clone_data) = *data {
+        clone_data.editor = if editor_id.is_empty() { None } else { Some(editor_id) };
+        Ok(())
+    } else {
+        Err("No clone dialog data available".to_string())
+    }
+}
+
 #[tauri::command]
 pub fn clone_cancelled() -> Result<(), String> {
+    if let Some(token) = CLONE_CANCEL_TOKEN.lock().as_ref() {
+        token.cancel();
+    }
     *CLONE_DIALOG_DATA.lock() = None;
     Ok(())
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BulkCloneDialogData {
+    pub host: String,
+    pub owner: String,
+    pub include_forks: bool,
+    pub include_archived: bool,
+}
+
+static BULK_CLONE_DIALOG_DATA: parking_lot::Mutex<Option<BulkCloneDialogData>> =
+    parking_lot::Mutex::new(None);
+
+pub fn set_bulk_clone_dialog_data(data: BulkCloneDialogData) {
+    *BULK_CLONE_DIALOG_DATA.lock() = Some(data);
+}
+
+#[tauri::command]
+pub fn get_bulk_clone_dialog_data() -> Result<BulkCloneDialogData, String> {
+    BULK_CLONE_DIALOG_DATA
+        .lock()
+        .clone()
+        .ok_or_else(|| "No bulk clone dialog data available".to_string())
+}
+
+#[tauri::command]
+pub async fn bulk_clone_from_host(
+    settings_manager: State<'_, Arc<SettingsManager>>,
+    host: String,
+    owner: String,
+    options: BulkCloneOptions,
+) -> Result<BulkCloneSummary, String> {
+    let summary = git_host::bulk_clone(&settings_manager, &host, &owner, &options)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    *BULK_CLONE_DIALOG_DATA.lock() = None;
+
+    Ok(summary)
+}
+
+#[tauri::command]
+pub fn bulk_clone_cancelled() -> Result<(), String> {
+    *BULK_CLONE_DIALOG_DATA.lock() = None;
+    Ok(())
+}
+
 #[tauri::command]
 pub fn get_protocol_registration_status(
 ) -> Result<crate::protocol_registration::ProtocolRegistrationStatus, String> {
@@ -956,27 +1792,32 @@ pub fn open_logs_directory() -> Result<(), String> {
 
     #[cfg(target_os = "macos")]
     {
-        std::process::Command::new("open")
-            .arg(&info.path)
-            .spawn()
+        crate::sandbox_env::spawn_external("open", &[&info.path])
             .map_err(|e| format!("Failed to open logs directory: {}", e))?;
     }
 
     #[cfg(target_os = "windows")]
     {
-        std::process::Command::new("explorer")
-            .arg(&info.path)
-            .spawn()
+        crate::sandbox_env::spawn_external("explorer", &[&info.path])
             .map_err(|e| format!("Failed to open logs directory: {}", e))?;
     }
 
     #[cfg(target_os = "linux")]
     {
-        std::process::Command::new("xdg-open")
-            .arg(&info.path)
-            .spawn()
+        crate::sandbox_env::spawn_external("xdg-open", &[&info.path])
             .map_err(|e| format!("Failed to open logs directory: {}", e))?;
     }
 
     Ok(())
 }
+
+/// Fetches recent buffered log entries for the in-app log viewer, without
+/// having to open the OS's own crash-dump folder. New entries since the
+/// call also arrive live over the `logs://entry` event.
+#[tauri::command]
+pub fn get_recent_logs(max_lines: usize, min_level: String) -> Result<Vec<crate::log_buffer::LogRecord>, String> {
+    let level: tracing::Level = min_level
+        .parse()
+        .map_err(|_| format!("Invalid log level: {}", min_level))?;
+    Ok(crate::log_buffer::recent(max_lines, level))
+}
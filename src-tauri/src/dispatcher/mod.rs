@@ -1,8 +1,9 @@
-use crate::editors::{EditorRegistry, OpenOptions};
+use crate::editors::{EditorManager, EditorRegistry, OpenMode, OpenOptions};
 use crate::git_command_log::GIT_COMMAND_LOG;
 use crate::path_validator::PathValidator;
 use crate::settings::SettingsManager;
 use crate::tracker::ActiveEditorTracker;
+use crate::workspace_mru::ActiveWorkspaceTracker;
 use anyhow::{Context, Result};
 use std::path::Path;
 use std::sync::Arc;
@@ -14,6 +15,7 @@ pub struct EditorDispatcher {
     path_validator: Arc<PathValidator>,
     editor_registry: Arc<EditorRegistry>,
     tracker: Arc<ActiveEditorTracker>,
+    workspace_tracker: Arc<ActiveWorkspaceTracker>,
 }
 
 impl EditorDispatcher {
@@ -22,12 +24,14 @@ impl EditorDispatcher {
         path_validator: Arc<PathValidator>,
         editor_registry: Arc<EditorRegistry>,
         tracker: Arc<ActiveEditorTracker>,
+        workspace_tracker: Arc<ActiveWorkspaceTracker>,
     ) -> Self {
         Self {
             settings_manager,
             path_validator,
             editor_registry,
             tracker,
+            workspace_tracker,
         }
     }
 
@@ -36,13 +40,13 @@ impl EditorDispatcher {
         path_str: &str,
         line: Option<usize>,
         column: Option<usize>,
-        new_window: bool,
+        mode: OpenMode,
         editor_hint: Option<String>,
     ) -> Result<()> {
         let start = Instant::now();
         info!(
-            "open() called with path: {}, line: {:?}, column: {:?}, editor_hint: {:?}",
-            path_str, line, column, editor_hint
+            "open() called with path: {}, line: {:?}, column: {:?}, mode: {:?}, editor_hint: {:?}",
+            path_str, line, column, mode, editor_hint
         );
 
         let validated_path = self
@@ -66,6 +70,25 @@ impl EditorDispatcher {
             .get(&editor_id)
             .ok_or_else(|| anyhow::anyhow!("Editor '{}' not found in registry", editor_id))?;
 
+        if manager.requires_configuration()
+            && self.settings_manager.get_editor_path(&editor_id).await.is_none()
+        {
+            let duration = start.elapsed();
+            GIT_COMMAND_LOG.log_editor_launch(
+                &editor_id,
+                path_str,
+                line,
+                false,
+                Some(&format!("Editor '{}' requires configuration", editor_id)),
+                duration,
+            );
+            return Err(anyhow::anyhow!(
+                "Editor '{}' requires configuration: set defaults.editor_paths.{}",
+                editor_id,
+                editor_id
+            ));
+        }
+
         if is_directory && !manager.supports_folders() {
             let duration = start.elapsed();
             GIT_COMMAND_LOG.log_editor_launch(
@@ -88,7 +111,35 @@ impl EditorDispatcher {
         let is_installed = manager.is_installed().await;
         info!("Editor '{}' is_installed: {}", editor_id, is_installed);
 
-        if !is_installed {
+        let (editor_id, manager) = if is_installed {
+            (editor_id, manager)
+        } else if editor_id != "terminal" && !is_directory {
+            // Fall back to $VISUAL/$EDITOR/vi rather than failing outright,
+            // so a headless/SSH session with no GUI editor installed can
+            // still open the file somewhere.
+            let fallback = self.editor_registry.get("terminal");
+            match fallback {
+                Some(fallback) if fallback.is_installed().await => {
+                    info!(
+                        "Editor '{}' is not installed, falling back to terminal ($EDITOR)",
+                        editor_id
+                    );
+                    ("terminal".to_string(), fallback)
+                }
+                _ => {
+                    let duration = start.elapsed();
+                    GIT_COMMAND_LOG.log_editor_launch(
+                        &editor_id,
+                        path_str,
+                        line,
+                        false,
+                        Some(&format!("Editor '{}' is not installed", editor_id)),
+                        duration,
+                    );
+                    return Err(anyhow::anyhow!("Editor '{}' is not installed", editor_id));
+                }
+            }
+        } else {
             let duration = start.elapsed();
             GIT_COMMAND_LOG.log_editor_launch(
                 &editor_id,
@@ -99,19 +150,57 @@ impl EditorDispatcher {
                 duration,
             );
             return Err(anyhow::anyhow!("Editor '{}' is not installed", editor_id));
-        }
+        };
 
         let terminal_preference = self.settings_manager.get_preferred_terminal().await;
 
+        let resolved_mode = if mode == OpenMode::AddToWorkspace {
+            self.resolve_add_to_workspace(&manager, &validated_path).await
+        } else {
+            mode
+        };
+
+        let wsl_target = crate::wsl::detect(&validated_path.to_string_lossy());
+
+        // mozrunner-style: start the editor already `cd`'d into the
+        // workspace root rather than wherever Sorcery itself runs from, so
+        // a terminal editor like vim doesn't land in `$HOME`.
+        let working_directory = self
+            .settings_manager
+            .get_workspace_for_path(&validated_path)
+            .await
+            .and_then(|workspace| workspace.normalized_path)
+            .map(|path| path.into_path_buf())
+            .or_else(|| {
+                if is_directory {
+                    Some(validated_path.clone())
+                } else {
+                    validated_path.parent().map(Path::to_path_buf)
+                }
+            });
+
+        let generate_compilation_db = self.settings_manager.generates_compilation_db().await;
+
         let options = OpenOptions {
             line: if is_directory { None } else { line },
             column: if is_directory { None } else { column },
-            new_window,
+            mode: resolved_mode,
             terminal_preference: Some(terminal_preference),
+            wsl_target,
+            working_directory,
+            env: std::collections::HashMap::new(),
+            generate_compilation_db,
+            preferred_variant: None,
+            // The editor this dispatches to should outlive Sorcery - see
+            // `OpenOptions::detached`.
+            detached: true,
         };
 
         info!("Calling manager.open() for {}", editor_id);
-        let result = manager.open(&validated_path, &options).await;
+        let result = self
+            .editor_registry
+            .reuse_then_open(&editor_id, &validated_path, &options)
+            .await;
 
         let duration = start.elapsed();
 
@@ -123,6 +212,21 @@ impl EditorDispatcher {
                     editor_id
                 );
                 GIT_COMMAND_LOG.log_editor_launch(&editor_id, path_str, line, true, None, duration);
+
+                let workspace_path = self
+                    .settings_manager
+                    .get_workspace_for_path(&validated_path)
+                    .await
+                    .and_then(|workspace| workspace.normalized_path);
+                if let Some(workspace_path) = workspace_path {
+                    self.workspace_tracker.record_open(&workspace_path).await;
+                }
+
+                if options.generate_compilation_db {
+                    if let Err(e) = manager.prepare_compilation_db(&validated_path).await {
+                        tracing::warn!("Failed to generate compile_commands.json: {}", e);
+                    }
+                }
             }
             Err(e) => {
                 GIT_COMMAND_LOG.log_editor_launch(
@@ -139,6 +243,40 @@ impl EditorDispatcher {
         result.map_err(|e| anyhow::anyhow!("Failed to open in {}: {}", editor_id, e))
     }
 
+    /// Looks for a running instance of `manager` whose workspace already
+    /// contains `path`, so it can be handed `OpenMode::AddToWorkspace`
+    /// instead of spawning a new window. Falls back to `NewWindow` if no
+    /// running instance reports a workspace `path` is under - including
+    /// editors like Xcode/Zed whose `get_running_instances` doesn't track
+    /// workspaces yet, so there's nothing sensible to add to.
+    async fn resolve_add_to_workspace(
+        &self,
+        manager: &Arc<dyn EditorManager>,
+        path: &Path,
+    ) -> OpenMode {
+        let instances = match manager.get_running_instances().await {
+            Ok(instances) => instances,
+            Err(e) => {
+                debug!("Failed to list running instances: {}", e);
+                Vec::new()
+            }
+        };
+
+        let has_matching_instance = instances.iter().any(|instance| {
+            instance
+                .workspace
+                .as_ref()
+                .is_some_and(|workspace| path.starts_with(workspace))
+        });
+
+        if has_matching_instance {
+            OpenMode::AddToWorkspace
+        } else {
+            debug!("No running instance has {:?} open, opening a new window instead", path);
+            OpenMode::NewWindow
+        }
+    }
+
     async fn determine_editor(&self, path: &Path, editor_hint: Option<String>) -> Result<String> {
         if let Some(hint) = editor_hint {
             if hint == "most-recent" {
@@ -161,6 +299,12 @@ impl EditorDispatcher {
                     );
                     return Ok(workspace.editor);
                 }
+                if let Some(tag_editor) =
+                    self.settings_manager.resolve_tag_editor(&workspace.tags).await
+                {
+                    debug!("Using tag editor: {} for path {:?}", tag_editor, path);
+                    return Ok(tag_editor);
+                }
                 debug!("Workspace editor is empty, falling back to default");
                 true
             } else {
@@ -0,0 +1,157 @@
+use super::process_async::output_with_timeout;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// One installed copy of an editor found by scanning the OS's own
+/// application registry - `.desktop` entries on Linux, `Info.plist`
+/// bundles on macOS, the App Paths registry key on Windows - rather than
+/// the fixed candidate list `BinaryLocator`/`find_binary` checks. Looked up
+/// by `EditorManager::id()` rather than a free-text name, so it only ever
+/// reports an install we can confidently attribute to one of our own
+/// managers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredApp {
+    pub id: String,
+    pub display_name: String,
+    pub binary_path: PathBuf,
+}
+
+/// Linux: defers entirely to `tracker::desktop_entries::DesktopEntryRegistry`,
+/// which already scans every `$XDG_DATA_DIRS/applications` `.desktop` file
+/// once and maps a recognized `Name` to one of our editor ids - built for
+/// resolving a tracked window back to an editor, reused here to resolve an
+/// editor id forward to its real installed binary.
+#[cfg(target_os = "linux")]
+pub fn linux_find_binary(editor_id: &str) -> Option<PathBuf> {
+    crate::tracker::desktop_entries::DesktopEntryRegistry::scan().find_binary(editor_id)
+}
+
+/// macOS bundle identifiers for the handful of editors whose `find_binary`
+/// already special-cases a fixed `/Applications/*.app` path, so discovery
+/// can confirm the same install (or one moved somewhere `find_binary`
+/// wouldn't think to look) by its bundle id instead.
+#[cfg(target_os = "macos")]
+fn macos_bundle_id_for_editor(editor_id: &str) -> Option<&'static str> {
+    match editor_id {
+        "vscode" => Some("com.microsoft.VSCode"),
+        "vscodium" => Some("com.vscodium"),
+        "zed" => Some("dev.zed.Zed"),
+        "sublime" => Some("com.sublimetext.4"),
+        _ => None,
+    }
+}
+
+/// macOS: scans `/Applications`, `~/Applications`, and
+/// `/System/Library/CoreServices/Applications` for a `.app` bundle whose
+/// `Info.plist` declares the bundle id `editor_id` maps to.
+#[cfg(target_os = "macos")]
+pub fn macos_find_binary(editor_id: &str) -> Option<PathBuf> {
+    let target_bundle_id = macos_bundle_id_for_editor(editor_id)?;
+
+    let mut app_dirs = vec![
+        PathBuf::from("/Applications"),
+        PathBuf::from("/System/Library/CoreServices/Applications"),
+    ];
+    if let Some(home) = dirs::home_dir() {
+        app_dirs.push(home.join("Applications"));
+    }
+
+    for dir in app_dirs {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let bundle_path = entry.path();
+            if bundle_path.extension().and_then(|e| e.to_str()) != Some("app") {
+                continue;
+            }
+
+            let Some((bundle_id, executable)) = read_bundle_info(&bundle_path) else {
+                continue;
+            };
+            if bundle_id != target_bundle_id {
+                continue;
+            }
+
+            let binary = bundle_path.join("Contents/MacOS").join(executable);
+            if binary.exists() {
+                return Some(binary);
+            }
+        }
+    }
+
+    None
+}
+
+/// Pulls `CFBundleIdentifier`/`CFBundleExecutable` out of an `Info.plist` by
+/// scanning its raw XML text for each key's neighboring `<string>` value,
+/// the same best-effort text approach `external_editors::discover` takes
+/// rather than linking a `plist`-parsing crate for two fields.
+#[cfg(target_os = "macos")]
+fn read_bundle_info(bundle_path: &std::path::Path) -> Option<(String, String)> {
+    let info_plist = bundle_path.join("Contents/Info.plist");
+    let contents = std::fs::read_to_string(&info_plist)
+        .map_err(|e| debug!("Failed to read {:?}: {}", info_plist, e))
+        .ok()?;
+
+    let bundle_id = plist_string_value(&contents, "CFBundleIdentifier")?;
+    let executable = plist_string_value(&contents, "CFBundleExecutable")?;
+    Some((bundle_id, executable))
+}
+
+/// Finds `<key>key_name</key>` in an Info.plist's XML text and returns the
+/// contents of the `<string>` element immediately following it.
+#[cfg(target_os = "macos")]
+fn plist_string_value(contents: &str, key_name: &str) -> Option<String> {
+    let key_tag = format!("<key>{}</key>", key_name);
+    let after_key = &contents[contents.find(&key_tag)? + key_tag.len()..];
+
+    let open_tag = "<string>";
+    let start = after_key.find(open_tag)? + open_tag.len();
+    let end = after_key[start..].find("</string>")? + start;
+
+    Some(after_key[start..end].trim().to_string())
+}
+
+/// Windows executable stems (the App Paths registry value name, minus
+/// `.exe`) for the editors whose `find_binary` already special-cases a
+/// fixed install path on this platform.
+#[cfg(target_os = "windows")]
+fn windows_exe_name_for_editor(editor_id: &str) -> Option<&'static str> {
+    match editor_id {
+        "vscode" => Some("code"),
+        "zed" => Some("zed"),
+        "sublime" => Some("subl"),
+        _ => None,
+    }
+}
+
+/// Windows: reads the App Paths registry key (`reg query`, matching how
+/// `VSCodeManager::find_binary` already shells out to `where` rather than
+/// linking a registry crate) for the install location of `editor_id`'s exe.
+#[cfg(target_os = "windows")]
+pub async fn windows_find_binary(editor_id: &str) -> Option<PathBuf> {
+    let exe_name = windows_exe_name_for_editor(editor_id)?;
+    let key = format!(
+        r"HKLM\Software\Microsoft\Windows\CurrentVersion\App Paths\{}.exe",
+        exe_name
+    );
+
+    let mut cmd = tokio::process::Command::new("reg");
+    cmd.args(["query", &key, "/ve"]);
+    let output = output_with_timeout(cmd).await?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let path = stdout
+        .lines()
+        .find(|line| line.contains("REG_SZ"))
+        .and_then(|line| line.split("REG_SZ").nth(1))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())?;
+
+    let path = PathBuf::from(path);
+    path.exists().then_some(path)
+}
@@ -0,0 +1,119 @@
+use super::process_async::output_with_timeout;
+use std::path::PathBuf;
+use tokio::process::Command;
+use tracing::debug;
+
+/// Shared "find an editor's binary on disk" probe, used by managers whose
+/// own candidate list would otherwise just be another copy of the same
+/// package-manager prefixes. Centralizing it means a new install location
+/// (a new Linux distro convention, a new Homebrew root) only has to be
+/// taught here once.
+pub struct BinaryLocator;
+
+impl BinaryLocator {
+    /// Resolves `name` by checking, in priority order: both Homebrew roots
+    /// present on this host (Apple Silicon's `/opt/homebrew` ahead of
+    /// Intel's `/usr/local`, so a Mac with both installed prefers the
+    /// native one), `/snap/bin`, exported flatpak binaries, the standard
+    /// `/usr/bin`/`/usr/local/bin` pair, and finally `which`/`where` on
+    /// `PATH`.
+    pub async fn find(name: &str) -> Option<PathBuf> {
+        for candidate in Self::candidates(name) {
+            if candidate.exists() {
+                debug!("Found {} at {:?}", name, candidate);
+                return Some(candidate);
+            }
+        }
+
+        Self::find_on_path(name).await
+    }
+
+    fn candidates(name: &str) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+
+        #[cfg(target_os = "macos")]
+        {
+            candidates.push(PathBuf::from(format!("/opt/homebrew/bin/{}", name)));
+            candidates.push(PathBuf::from(format!("/usr/local/bin/{}", name)));
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            candidates.push(PathBuf::from(format!("/snap/bin/{}", name)));
+            for exports_dir in Self::flatpak_export_dirs() {
+                candidates.push(exports_dir.join(name));
+            }
+            candidates.push(PathBuf::from(format!("/usr/bin/{}", name)));
+            candidates.push(PathBuf::from(format!("/usr/local/bin/{}", name)));
+        }
+
+        candidates
+    }
+
+    #[cfg(target_os = "linux")]
+    fn flatpak_export_dirs() -> Vec<PathBuf> {
+        let mut dirs = vec![PathBuf::from("/var/lib/flatpak/exports/bin")];
+        if let Some(home) = dirs::home_dir() {
+            dirs.push(home.join(".local/share/flatpak/exports/bin"));
+        }
+        dirs
+    }
+
+    async fn find_on_path(name: &str) -> Option<PathBuf> {
+        #[cfg(target_os = "windows")]
+        let finder = "where";
+        #[cfg(not(target_os = "windows"))]
+        let finder = "which";
+
+        let mut cmd = Command::new(finder);
+        cmd.arg(name);
+        let output = output_with_timeout(cmd).await?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let path_str = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()?
+            .trim()
+            .to_string();
+
+        if path_str.is_empty() {
+            return None;
+        }
+
+        let path = PathBuf::from(path_str);
+        if path.exists() {
+            debug!("Found {} via {}: {:?}", name, finder, path);
+            Some(path)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_include_both_homebrew_roots_on_macos() {
+        #[cfg(target_os = "macos")]
+        {
+            let candidates = BinaryLocator::candidates("vim");
+            assert!(candidates.contains(&PathBuf::from("/opt/homebrew/bin/vim")));
+            assert!(candidates.contains(&PathBuf::from("/usr/local/bin/vim")));
+        }
+    }
+
+    #[test]
+    fn candidates_include_snap_and_standard_prefixes_on_linux() {
+        #[cfg(target_os = "linux")]
+        {
+            let candidates = BinaryLocator::candidates("vim");
+            assert!(candidates.contains(&PathBuf::from("/snap/bin/vim")));
+            assert!(candidates.contains(&PathBuf::from("/usr/bin/vim")));
+            assert!(candidates.contains(&PathBuf::from("/usr/local/bin/vim")));
+        }
+    }
+}
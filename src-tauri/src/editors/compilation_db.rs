@@ -0,0 +1,138 @@
+use super::traits::{EditorError, EditorResult};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// One entry of a clang compilation database, as consumed by external
+/// LSP/indexers (clangd, sourcekit-lsp) - see
+/// https://clang.llvm.org/docs/JSONCompilationDatabase.html.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompileCommand {
+    pub directory: String,
+    pub file: String,
+    pub arguments: Vec<String>,
+}
+
+/// Source extensions a `CompileC`/`CompileSwift` header line's trailing
+/// tokens are checked against to find the file the invocation is for.
+const SOURCE_EXTENSIONS: &[&str] = &["swift", "m", "mm", "c", "cc", "cpp", "cxx"];
+
+/// Finds the `.xcworkspace` (preferred, since it covers projects pulled in
+/// via CocoaPods/SPM) or failing that `.xcodeproj` directly under `dir`, and
+/// returns the `xcodebuild` argument pair that selects it.
+pub fn xcode_project_arg(dir: &Path) -> Option<[String; 2]> {
+    let entries = std::fs::read_dir(dir).ok()?;
+    let mut xcodeproj = None;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("xcworkspace") {
+            return Some(["-workspace".to_string(), path.to_string_lossy().into_owned()]);
+        }
+        if path.extension().and_then(|e| e.to_str()) == Some("xcodeproj") {
+            xcodeproj = Some(path);
+        }
+    }
+
+    xcodeproj.map(|path| ["-project".to_string(), path.to_string_lossy().into_owned()])
+}
+
+/// Scans an `xcodebuild`/`swift build` log for `CompileC`/`CompileSwift`
+/// invocations and turns each into a `CompileCommand`.
+///
+/// The relevant section of an `xcodebuild` log looks like:
+/// ```text
+/// CompileC /path/to/Foo.o /path/to/Foo.m normal x86_64 objective-c ...
+///     cd /path/to/project
+///     export LANG=en_US.US-ASCII
+///     /usr/bin/clang -x objective-c ... -c /path/to/Foo.m -o /path/to/Foo.o
+/// ```
+/// We track the working directory from the most recent `cd` line and pair
+/// the header's source file with the first subsequent line that looks like
+/// an actual compiler invocation (contains a `-c`/`-module-name` flag).
+pub fn parse_xcodebuild_log(log: &str) -> Vec<CompileCommand> {
+    let mut commands = Vec::new();
+    let mut directory = String::new();
+    let mut pending_file: Option<String> = None;
+
+    for line in log.lines() {
+        let trimmed = line.trim();
+
+        if let Some(dir) = trimmed.strip_prefix("cd ") {
+            directory = dir.trim().to_string();
+            continue;
+        }
+
+        if trimmed.starts_with("CompileC") || trimmed.starts_with("CompileSwift") {
+            pending_file = source_file_from_header(trimmed);
+            continue;
+        }
+
+        let Some(file) = &pending_file else {
+            continue;
+        };
+
+        if !looks_like_compiler_invocation(trimmed) {
+            continue;
+        }
+
+        commands.push(CompileCommand {
+            directory: directory.clone(),
+            file: file.clone(),
+            arguments: super::process_scan::split_command_line(trimmed),
+        });
+        pending_file = None;
+    }
+
+    commands
+}
+
+fn source_file_from_header(header: &str) -> Option<String> {
+    header
+        .split_whitespace()
+        .find(|token| {
+            SOURCE_EXTENSIONS
+                .iter()
+                .any(|ext| token.ends_with(&format!(".{ext}")))
+        })
+        .map(str::to_string)
+}
+
+fn looks_like_compiler_invocation(line: &str) -> bool {
+    (line.contains("clang") || line.contains("swiftc"))
+        && (line.contains(" -c ") || line.contains("-module-name"))
+}
+
+/// Writes the aggregated compilation database to
+/// `<workspace>/compile_commands.json`.
+pub fn write_compile_commands(workspace: &Path, commands: &[CompileCommand]) -> EditorResult<()> {
+    let json = serde_json::to_string_pretty(commands)
+        .map_err(|e| EditorError::Other(e.to_string()))?;
+    let dest: PathBuf = workspace.join("compile_commands.json");
+    std::fs::write(&dest, json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_compile_c_invocation() {
+        let log = "\
+CompileC /build/Foo.o /src/Foo.m normal x86_64 objective-c com.apple.compilers.llvm.clang.1_0.compiler
+    cd /src
+    export LANG=en_US.US-ASCII
+    /usr/bin/clang -x objective-c -c /src/Foo.m -o /build/Foo.o
+";
+        let commands = parse_xcodebuild_log(log);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].directory, "/src");
+        assert_eq!(commands[0].file, "/src/Foo.m");
+        assert!(commands[0].arguments.contains(&"/src/Foo.m".to_string()));
+    }
+
+    #[test]
+    fn ignores_lines_before_any_header() {
+        assert!(parse_xcodebuild_log("Build settings from command line:\n    SDKROOT = iphoneos\n").is_empty());
+    }
+}
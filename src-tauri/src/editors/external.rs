@@ -0,0 +1,231 @@
+use super::launch::{posix_shell_quote, windows_shell_quote, Launch};
+use super::process::{EditorProcess, EditorProcessRegistry};
+use super::terminal::TerminalApp;
+use super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Wraps an editor discovered via `external_editors::discover` - an OS-level
+/// "open with" entry rather than one of our hardcoded integrations - so it
+/// can be dispatched through the same `EditorRegistry`/`EditorDispatcher`
+/// path as a built-in editor once the user picks it from the clone dialog's
+/// editor list.
+pub struct ExternalEditorManager {
+    id: String,
+    display_name: String,
+    /// The OS's own launch command, with field codes (`%f`, `%u`, ...)
+    /// still in place - expanded to the real path in `command_line`.
+    exec_template: String,
+    /// `Terminal=true` on the source `.desktop` entry - `open()` runs
+    /// `exec_template` through a `TerminalApp` instead of spawning it bare,
+    /// the same way our own terminal-based managers (`VimManager`,
+    /// `KakouneManager`, ...) do.
+    terminal: bool,
+    processes: EditorProcessRegistry,
+}
+
+impl ExternalEditorManager {
+    pub fn new(id: &str, display_name: &str, exec_template: &str, terminal: bool) -> Self {
+        Self {
+            id: id.to_string(),
+            display_name: display_name.to_string(),
+            exec_template: exec_template.to_string(),
+            terminal,
+            processes: EditorProcessRegistry::new(),
+        }
+    }
+
+    /// Expands `%f`/`%F`/`%u`/`%U` field codes to `path` and drops anything
+    /// else (`%i`, `%c`, `%k`, ...), matching how desktop entries (and their
+    /// macOS/Windows equivalents) are meant to be invoked for a single file.
+    /// `open()` runs the result through `sh -c`/`cmd /c`, so the expanded
+    /// path is quoted for whichever of those it'll actually be parsed by -
+    /// a naive `"{}"` wrap corrupts on an embedded quote, and on Unix also
+    /// on a `$`, backtick, or other shell metacharacter.
+    fn command_line(&self, path: &Path) -> String {
+        let quoted = if cfg!(target_os = "windows") {
+            windows_shell_quote(&path.display().to_string())
+        } else {
+            posix_shell_quote(&path.display().to_string())
+        };
+        let mut result = String::with_capacity(self.exec_template.len());
+        let mut chars = self.exec_template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c == '%' {
+                if let Some(&code) = chars.peek() {
+                    chars.next();
+                    match code {
+                        // `%f`/`%F`/`%u`/`%U` are the XDG desktop-entry field
+                        // codes; `%1` is the Windows shell `OpenWithProgids`
+                        // equivalent.
+                        'f' | 'F' | 'u' | 'U' | '1' => result.push_str(&quoted),
+                        '%' => result.push('%'),
+                        _ => {}
+                    }
+                    continue;
+                }
+            }
+            result.push(c);
+        }
+
+        result.trim().to_string()
+    }
+
+    /// Expands `exec_template` into an argv, for spawning through a
+    /// `TerminalApp` rather than `command_line`'s shell-quoted string: a
+    /// terminal launches the editor directly (no shell in between), so
+    /// `path` is substituted in raw rather than quoted for `sh`/`cmd`.
+    fn command_argv(&self, path: &Path) -> Vec<String> {
+        let path_str = path.display().to_string();
+        self.exec_template
+            .split_whitespace()
+            .filter_map(|token| match token {
+                "%f" | "%F" | "%u" | "%U" | "%1" => Some(path_str.clone()),
+                "%i" | "%c" | "%k" => None,
+                other => Some(other.replace("%%", "%")),
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EditorManager for ExternalEditorManager {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    async fn find_binary(&self) -> Option<PathBuf> {
+        self.exec_template.split_whitespace().next().map(PathBuf::from)
+    }
+
+    async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
+        self.find_binary().await.ok_or(EditorError::BinaryNotFound)?;
+
+        if self.terminal {
+            let mut argv = self.command_argv(path);
+            if argv.is_empty() {
+                return Err(EditorError::Other(format!(
+                    "{} has an empty Exec command",
+                    self.display_name
+                )));
+            }
+            let program = argv.remove(0);
+            debug!("Launching {} in a terminal via: {} {:?}", self.display_name, program, argv);
+
+            let terminal = TerminalApp::detect_installed_with_preference(options.terminal_preference.as_deref())
+                .ok_or_else(|| EditorError::Other("No terminal emulator found".to_string()))?;
+            terminal
+                .launch_editor(
+                    &program,
+                    &argv,
+                    options.working_directory.as_deref(),
+                    &options.env,
+                    options.detached,
+                )
+                .map_err(EditorError::LaunchFailed)?;
+
+            return Ok(());
+        }
+
+        let command_line = self.command_line(path);
+        debug!("Launching {} via: {}", self.display_name, command_line);
+
+        let shell = if cfg!(target_os = "windows") { "cmd" } else { "sh" };
+        let shell_flag = if cfg!(target_os = "windows") { "/C" } else { "-c" };
+
+        let child = Launch::new(Path::new(shell))
+            .arg(shell_flag)
+            .arg(&command_line)
+            .detached(options.detached)
+            .start()
+            .map_err(|e| EditorError::LaunchFailed(e.to_string()))?;
+
+        self.processes.register(
+            EditorProcess::owned(child),
+            path.to_path_buf(),
+            None,
+            None,
+        );
+
+        Ok(())
+    }
+
+    async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
+        Ok(self.processes.running_instances())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_line_expands_file_field_codes() {
+        let manager = ExternalEditorManager::new(
+            "external:textedit",
+            "TextEdit",
+            "/usr/bin/textedit --unity-launch %F",
+            false,
+        );
+        assert_eq!(
+            manager.command_line(Path::new("/tmp/repo/README.md")),
+            "/usr/bin/textedit --unity-launch '/tmp/repo/README.md'"
+        );
+    }
+
+    #[test]
+    fn command_line_keeps_literal_percent() {
+        let manager = ExternalEditorManager::new(
+            "external:progress",
+            "Progress App",
+            "/usr/bin/app --progress=100%% %f",
+            false,
+        );
+        assert_eq!(
+            manager.command_line(Path::new("/tmp/f.txt")),
+            "/usr/bin/app --progress=100% '/tmp/f.txt'"
+        );
+    }
+
+    #[test]
+    fn command_line_escapes_a_path_with_a_single_quote() {
+        let manager = ExternalEditorManager::new(
+            "external:textedit",
+            "TextEdit",
+            "/usr/bin/textedit %F",
+            false,
+        );
+        assert_eq!(
+            manager.command_line(Path::new("/tmp/it's a file.rs")),
+            "/usr/bin/textedit '/tmp/it'\\''s a file.rs'"
+        );
+    }
+
+    #[test]
+    fn command_argv_substitutes_the_path_unquoted() {
+        let manager = ExternalEditorManager::new("external:nano", "Nano", "nano %F", true);
+        assert_eq!(
+            manager.command_argv(Path::new("/tmp/it's a file.rs")),
+            vec!["nano".to_string(), "/tmp/it's a file.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn command_argv_drops_non_path_field_codes() {
+        let manager = ExternalEditorManager::new("external:micro", "Micro", "micro --progress=100%% %f %i", true);
+        assert_eq!(
+            manager.command_argv(Path::new("/tmp/f.txt")),
+            vec![
+                "micro".to_string(),
+                "--progress=100%".to_string(),
+                "/tmp/f.txt".to_string(),
+            ]
+        );
+    }
+}
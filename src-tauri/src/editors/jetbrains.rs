@@ -1,6 +1,11 @@
+use super::binary_locator::BinaryLocator;
+use super::process::EditorProcess;
+use super::process_async::output_with_timeout;
 use super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
 use async_trait::async_trait;
 use parking_lot::RwLock;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{Duration, SystemTime};
@@ -11,11 +16,40 @@ struct BinaryCache {
     timestamp: SystemTime,
 }
 
+/// One discovered installation of a JetBrains product - a specific Toolbox
+/// channel/version, or the single standalone install outside Toolbox.
+/// `list_installations` can surface more than one of these for a product id
+/// at once, unlike `find_binary`, which only ever returns its single
+/// preferred pick.
+#[derive(Debug, Clone, Serialize)]
+pub struct JetBrainsInstallation {
+    /// The Toolbox product id, e.g. `"idea"` - not to be confused with
+    /// `JetBrainsManager::id`, which is Sorcery's own editor id.
+    pub product_id: String,
+    /// `"ch-0"`/`"ch-1"` for a Toolbox install, `None` for a standalone one.
+    pub channel: Option<String>,
+    /// Parsed from the Toolbox version subdir name, or `product-info.json`
+    /// when that isn't a plain version string (or for standalone installs).
+    pub version: Option<String>,
+    pub install_root: PathBuf,
+    pub binary: PathBuf,
+}
+
+/// A tracked launch, paired with the workspace path it was opened with so
+/// `get_running_instances` can report it - `resolve_add_to_workspace` relies
+/// on that to decide whether a new open can reuse the window instead of
+/// spawning another one.
+struct TrackedInstance {
+    process: EditorProcess,
+    workspace: Option<PathBuf>,
+}
+
 pub struct JetBrainsManager {
     id: String,
     display_name: String,
     toolbox_id: String,
     cache: RwLock<Option<BinaryCache>>,
+    instances: RwLock<Vec<TrackedInstance>>,
 }
 
 impl JetBrainsManager {
@@ -25,6 +59,7 @@ impl JetBrainsManager {
             display_name: display_name.to_string(),
             toolbox_id: toolbox_id.to_string(),
             cache: RwLock::new(None),
+            instances: RwLock::new(Vec::new()),
         }
     }
 
@@ -50,42 +85,6 @@ impl JetBrainsManager {
         });
     }
 
-    #[cfg(target_os = "macos")]
-    async fn find_toolbox_binary_macos(&self) -> Option<PathBuf> {
-        let toolbox_apps =
-            dirs::home_dir()?.join("Library/Application Support/JetBrains/Toolbox/apps");
-
-        if !toolbox_apps.exists() {
-            return None;
-        }
-
-        let product_dir = toolbox_apps.join(&self.toolbox_id);
-        let app_name = format!("{}.app", self.display_name);
-
-        if product_dir.exists() {
-            for channel in &["ch-0", "ch-1"] {
-                let channel_dir = product_dir.join(channel);
-                if !channel_dir.exists() {
-                    continue;
-                }
-
-                if let Some(latest_version) = Self::find_latest_subdir(&channel_dir) {
-                    let app_path = latest_version.join(&app_name);
-                    if app_path.exists() {
-                        debug!(
-                            "Found {} Toolbox installation at {:?}",
-                            self.display_name, app_path
-                        );
-                        return Some(app_path);
-                    }
-                }
-            }
-        }
-
-        // Fallback heuristic: search across all Toolbox products
-        self.find_any_toolbox_mac_app(&toolbox_apps, &app_name)
-    }
-
     #[cfg(target_os = "macos")]
     fn find_any_toolbox_mac_app(&self, toolbox_root: &Path, app_name: &str) -> Option<PathBuf> {
         let products = std::fs::read_dir(toolbox_root).ok()?;
@@ -133,57 +132,294 @@ impl JetBrainsManager {
         entries.first().map(|(path, _)| path.clone())
     }
 
+    /// Every subdirectory of `dir`, newest-modified first - the full set
+    /// `find_latest_subdir` picks just the head of, so `list_installations`
+    /// can report every installed version instead of only the newest.
+    fn version_subdirs(dir: &Path) -> Vec<PathBuf> {
+        if !dir.exists() {
+            return Vec::new();
+        }
+
+        let mut entries: Vec<_> = match std::fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| {
+                    let mtime = e.metadata().ok()?.modified().ok()?;
+                    Some((e.path(), mtime))
+                })
+                .collect(),
+            Err(_) => return Vec::new(),
+        };
+
+        entries.sort_by(|a, b| b.1.cmp(&a.1));
+        entries.into_iter().map(|(path, _)| path).collect()
+    }
+
+    /// A Toolbox version subdir's name is the version itself (e.g.
+    /// `"2023.3.2"`), so that's tried first; `product-info.json` is the
+    /// fallback for layouts where it isn't.
+    fn resolve_toolbox_version(version_dir: &Path) -> Option<String> {
+        let name = version_dir.file_name()?.to_string_lossy().to_string();
+        if name.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+            return Some(name);
+        }
+        Self::read_product_info_version(version_dir)
+    }
+
+    /// Reads the `"version"` field out of a JetBrains install's
+    /// `product-info.json`, present at the top of every install root since
+    /// the 2020.1 launcher layout.
+    fn read_product_info_version(install_root: &Path) -> Option<String> {
+        let contents = std::fs::read_to_string(install_root.join("product-info.json")).ok()?;
+        let info: serde_json::Value = serde_json::from_str(&contents).ok()?;
+        info.get("version")?.as_str().map(|s| s.to_string())
+    }
+
+    /// Recovers the Toolbox channel (`"ch-0"`/`"ch-1"`) from a path found via
+    /// the cross-product heuristic search, which doesn't already know it the
+    /// way the direct by-`toolbox_id` walk does.
+    fn infer_channel(path: &Path) -> Option<String> {
+        path.components().find_map(|c| match c.as_os_str().to_str()? {
+            name @ ("ch-0" | "ch-1") => Some(name.to_string()),
+            _ => None,
+        })
+    }
+
+    /// Picks the installation `find_binary` should launch when several are
+    /// present: channel `ch-0` before `ch-1` before any other Toolbox
+    /// channel before a standalone install, falling back to the
+    /// most-recently-modified install root to break ties within a channel.
+    fn pick_preferred(installations: &[JetBrainsInstallation]) -> Option<&JetBrainsInstallation> {
+        installations.iter().min_by_key(|install| {
+            let channel_rank = match install.channel.as_deref() {
+                Some("ch-0") => 0,
+                Some("ch-1") => 1,
+                Some(_) => 2,
+                None => 3,
+            };
+            let age = std::fs::metadata(&install.install_root)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .unwrap_or(Duration::MAX);
+            (channel_rank, age)
+        })
+    }
+
     #[cfg(target_os = "macos")]
-    async fn find_standalone_binary_macos(&self) -> Option<PathBuf> {
-        let app_name = format!("{}.app", self.display_name);
+    async fn list_installations_macos(&self) -> Vec<JetBrainsInstallation> {
+        let mut installations = Vec::new();
 
-        // Check both /Applications and ~/Applications
-        let candidates = vec![
-            PathBuf::from("/Applications").join(&app_name),
-            dirs::home_dir()?.join("Applications").join(&app_name),
-        ];
+        if let Some(home) = dirs::home_dir() {
+            let toolbox_apps = home.join("Library/Application Support/JetBrains/Toolbox/apps");
+            let product_dir = toolbox_apps.join(&self.toolbox_id);
+            let app_name = format!("{}.app", self.display_name);
 
-        for app_path in candidates {
-            if app_path.exists() {
-                debug!("Found {} standalone at {:?}", self.display_name, app_path);
-                return Some(app_path);
+            for channel in &["ch-0", "ch-1"] {
+                for version_dir in Self::version_subdirs(&product_dir.join(channel)) {
+                    let app_path = version_dir.join(&app_name);
+                    if !app_path.exists() {
+                        continue;
+                    }
+
+                    installations.push(JetBrainsInstallation {
+                        product_id: self.toolbox_id.clone(),
+                        channel: Some(channel.to_string()),
+                        version: Self::resolve_toolbox_version(&version_dir),
+                        install_root: version_dir,
+                        binary: app_path,
+                    });
+                }
+            }
+
+            // `toolbox_id` doesn't always match the on-disk product dir
+            // name - fall back to the same cross-product heuristic search
+            // `find_binary` always has, just as one more list entry instead
+            // of an immediate pick.
+            if installations.is_empty() {
+                if let Some(app_path) = self.find_any_toolbox_mac_app(&toolbox_apps, &app_name) {
+                    let install_root = app_path
+                        .parent()
+                        .map(|p| p.to_path_buf())
+                        .unwrap_or_else(|| app_path.clone());
+                    installations.push(JetBrainsInstallation {
+                        product_id: self.toolbox_id.clone(),
+                        channel: Self::infer_channel(&app_path),
+                        version: Self::resolve_toolbox_version(&install_root),
+                        install_root,
+                        binary: app_path,
+                    });
+                }
             }
         }
 
-        None
+        if let Some(binary) = self.find_standalone_binary_macos().await {
+            installations.push(JetBrainsInstallation {
+                product_id: self.toolbox_id.clone(),
+                channel: None,
+                version: Self::read_product_info_version(&binary.join("Contents/Resources")),
+                install_root: binary.clone(),
+                binary,
+            });
+        }
+
+        installations
     }
 
     #[cfg(target_os = "windows")]
-    async fn find_toolbox_binary_windows(&self) -> Option<PathBuf> {
-        let toolbox_apps = dirs::data_local_dir()?.join("JetBrains\\Toolbox\\apps");
+    async fn list_installations_windows(&self) -> Vec<JetBrainsInstallation> {
+        let mut installations = Vec::new();
 
-        if toolbox_apps.exists() {
+        if let Some(toolbox_apps) = dirs::data_local_dir().map(|d| d.join("JetBrains\\Toolbox\\apps")) {
             let product_dir = toolbox_apps.join(&self.toolbox_id);
 
-            if product_dir.exists() {
-                for channel in &["ch-0", "ch-1"] {
-                    let channel_dir = product_dir.join(channel);
-                    if let Some(latest_version) = Self::find_latest_subdir(&channel_dir) {
-                        let bin_dir = latest_version.join("bin");
-                        if let Some(exe) = Self::pick_windows_exe(&bin_dir, &self.id) {
-                            debug!(
-                                "Found {} Toolbox installation at {:?}",
-                                self.display_name, exe
-                            );
-                            return Some(exe);
-                        }
-                    }
+            for channel in &["ch-0", "ch-1"] {
+                for version_dir in Self::version_subdirs(&product_dir.join(channel)) {
+                    let bin_dir = version_dir.join("bin");
+                    let Some(exe) = Self::pick_windows_exe(&bin_dir, &self.id) else {
+                        continue;
+                    };
+
+                    installations.push(JetBrainsInstallation {
+                        product_id: self.toolbox_id.clone(),
+                        channel: Some(channel.to_string()),
+                        version: Self::resolve_toolbox_version(&version_dir),
+                        install_root: version_dir,
+                        binary: exe,
+                    });
+                }
+            }
+
+            if installations.is_empty() {
+                if let Some(exe) = self.find_any_toolbox_windows_exe(&toolbox_apps) {
+                    let install_root = exe
+                        .parent()
+                        .and_then(|bin| bin.parent())
+                        .map(|root| root.to_path_buf())
+                        .unwrap_or_else(|| exe.clone());
+                    installations.push(JetBrainsInstallation {
+                        product_id: self.toolbox_id.clone(),
+                        channel: Self::infer_channel(&exe),
+                        version: Self::resolve_toolbox_version(&install_root),
+                        install_root,
+                        binary: exe,
+                    });
+                }
+            }
+        }
+
+        if let Some(exe) = self.find_standalone_binary_windows() {
+            let install_root = exe
+                .parent()
+                .and_then(|bin| bin.parent())
+                .map(|root| root.to_path_buf())
+                .unwrap_or_else(|| exe.clone());
+            installations.push(JetBrainsInstallation {
+                version: Self::read_product_info_version(&install_root),
+                product_id: self.toolbox_id.clone(),
+                channel: None,
+                install_root,
+                binary: exe,
+            });
+        }
+
+        installations
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn list_installations_linux(&self) -> Vec<JetBrainsInstallation> {
+        let mut installations = Vec::new();
+
+        if let Some(toolbox_apps) = dirs::data_local_dir().map(|d| d.join("JetBrains/Toolbox/apps")) {
+            let product_dir = toolbox_apps.join(&self.toolbox_id);
+
+            for channel in &["ch-0", "ch-1"] {
+                for version_dir in Self::version_subdirs(&product_dir.join(channel)) {
+                    let bin_dir = version_dir.join("bin");
+                    let Some(script) = Self::pick_linux_script(&bin_dir, &self.id) else {
+                        continue;
+                    };
+
+                    installations.push(JetBrainsInstallation {
+                        product_id: self.toolbox_id.clone(),
+                        channel: Some(channel.to_string()),
+                        version: Self::resolve_toolbox_version(&version_dir),
+                        install_root: version_dir,
+                        binary: script,
+                    });
                 }
             }
 
-            // Fallback heuristic search
-            if let Some(exe) = self.find_any_toolbox_windows_exe(&toolbox_apps) {
-                return Some(exe);
+            if installations.is_empty() {
+                if let Some(script) = self.find_any_toolbox_linux_script(&toolbox_apps) {
+                    let install_root = script
+                        .parent()
+                        .and_then(|bin| bin.parent())
+                        .map(|root| root.to_path_buf())
+                        .unwrap_or_else(|| script.clone());
+                    installations.push(JetBrainsInstallation {
+                        product_id: self.toolbox_id.clone(),
+                        channel: Self::infer_channel(&script),
+                        version: Self::resolve_toolbox_version(&install_root),
+                        install_root,
+                        binary: script,
+                    });
+                }
             }
         }
 
-        // Standalone installations in Program Files
-        self.find_standalone_binary_windows()
+        if let Some(script) = self.find_standalone_binary_linux().await {
+            let install_root = script
+                .parent()
+                .and_then(|bin| bin.parent())
+                .map(|root| root.to_path_buf())
+                .unwrap_or_else(|| script.clone());
+            installations.push(JetBrainsInstallation {
+                version: Self::read_product_info_version(&install_root),
+                product_id: self.toolbox_id.clone(),
+                channel: None,
+                install_root,
+                binary: script,
+            });
+        }
+
+        installations
+    }
+
+    /// Every installed build of this product Sorcery can find - every
+    /// Toolbox `ch-0`/`ch-1` version directory plus any standalone install -
+    /// so a caller can show a version picker or pin `OpenOptions` to a
+    /// specific one instead of always taking whichever `find_binary` prefers.
+    pub async fn list_installations(&self) -> Vec<JetBrainsInstallation> {
+        #[cfg(target_os = "macos")]
+        return self.list_installations_macos().await;
+
+        #[cfg(target_os = "windows")]
+        return self.list_installations_windows().await;
+
+        #[cfg(target_os = "linux")]
+        return self.list_installations_linux().await;
+    }
+
+    #[cfg(target_os = "macos")]
+    async fn find_standalone_binary_macos(&self) -> Option<PathBuf> {
+        let app_name = format!("{}.app", self.display_name);
+
+        // Check both /Applications and ~/Applications
+        let candidates = vec![
+            PathBuf::from("/Applications").join(&app_name),
+            dirs::home_dir()?.join("Applications").join(&app_name),
+        ];
+
+        for app_path in candidates {
+            if app_path.exists() {
+                debug!("Found {} standalone at {:?}", self.display_name, app_path);
+                return Some(app_path);
+            }
+        }
+
+        None
     }
 
     #[cfg(target_os = "windows")]
@@ -300,37 +536,6 @@ impl JetBrainsManager {
         files.first().map(|e| e.path())
     }
 
-    #[cfg(target_os = "linux")]
-    async fn find_toolbox_binary_linux(&self) -> Option<PathBuf> {
-        let toolbox_apps = dirs::data_local_dir()?.join("JetBrains/Toolbox/apps");
-
-        if toolbox_apps.exists() {
-            let product_dir = toolbox_apps.join(&self.toolbox_id);
-
-            if product_dir.exists() {
-                for channel in &["ch-0", "ch-1"] {
-                    let channel_dir = product_dir.join(channel);
-                    if let Some(latest_version) = Self::find_latest_subdir(&channel_dir) {
-                        let bin_dir = latest_version.join("bin");
-                        if let Some(script) = Self::pick_linux_script(&bin_dir, &self.id) {
-                            debug!(
-                                "Found {} Toolbox installation at {:?}",
-                                self.display_name, script
-                            );
-                            return Some(script);
-                        }
-                    }
-                }
-            }
-
-            // Fallback heuristic search
-            if let Some(script) = self.find_any_toolbox_linux_script(&toolbox_apps) {
-                return Some(script);
-            }
-        }
-
-        None
-    }
 
     #[cfg(target_os = "linux")]
     fn find_any_toolbox_linux_script(&self, toolbox_root: &Path) -> Option<PathBuf> {
@@ -396,39 +601,58 @@ impl JetBrainsManager {
 
     #[cfg(target_os = "linux")]
     async fn find_standalone_binary_linux(&self) -> Option<PathBuf> {
-        // Check common locations and PATH
-        let candidates = vec![
-            PathBuf::from(format!("/usr/local/bin/{}", self.toolbox_id)),
-            PathBuf::from(format!("/usr/bin/{}", self.toolbox_id)),
-            PathBuf::from(format!("/snap/bin/{}", self.toolbox_id)),
-            PathBuf::from(format!("/opt/{}/bin/{}.sh", self.toolbox_id, self.toolbox_id)),
-        ];
+        // JetBrains' own installer script doesn't land in any prefix
+        // `BinaryLocator` knows about, so check it first.
+        let installer_script = PathBuf::from(format!(
+            "/opt/{}/bin/{}.sh",
+            self.toolbox_id, self.toolbox_id
+        ));
+        if installer_script.exists() {
+            debug!("Found {} standalone at {:?}", self.display_name, installer_script);
+            return Some(installer_script);
+        }
 
-        for path in candidates {
-            if path.exists() {
-                debug!("Found {} standalone at {:?}", self.display_name, path);
-                return Some(path);
-            }
+        if let Some(path) = BinaryLocator::find(&self.toolbox_id).await {
+            debug!("Found {} standalone at {:?}", self.display_name, path);
+            return Some(path);
         }
 
-        // Fallback: use `which` to find in PATH
-        if let Ok(output) = Command::new("which").arg(&self.toolbox_id).output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    let path = PathBuf::from(path_str);
-                    if path.exists() {
-                        debug!("Found {} via which: {:?}", self.display_name, path);
-                        return Some(path);
-                    }
-                }
+        // Catches installs under arbitrary prefixes (and flatpak-exported
+        // apps) that the candidates above don't cover.
+        if let Some(path) = crate::tracker::DesktopEntryRegistry::scan().find_binary(&self.id) {
+            if path.exists() {
+                debug!("Found {} via desktop entry: {:?}", self.display_name, path);
+                return Some(path);
             }
         }
 
         None
     }
 
-    fn spawn_editor(&self, binary: &Path, args: &[String]) -> Result<(), String> {
+    /// Spawns the editor and returns a handle for it where one is available.
+    /// On macOS the `open -n -a` launcher we spawn detaches immediately, so
+    /// there's no `Child` left to track once it exits - `resolve_instance_pid`
+    /// instead polls for the real app process by matching its bundle path,
+    /// and the handle falls back to `None` if that times out without finding
+    /// it (the launch itself still succeeded).
+    ///
+    /// `cwd`/`env` come straight from `OpenOptions` (mozrunner's `Runner`-style
+    /// `env`/`envs`/`arg` surface) - set on the `Command` ahead of
+    /// `normalize_launch_env` so a caller's explicit value (e.g. `JAVA_HOME`)
+    /// wins over whatever the sandboxed-runtime cleanup would otherwise leave.
+    async fn spawn_editor(
+        &self,
+        binary: &Path,
+        args: &[String],
+        cwd: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<Option<EditorProcess>, String> {
+        // macOS's `open -n -a` and Windows' `cmd /c start` both already hand
+        // off to a fully independent launcher, so `detached` only changes
+        // anything on Linux, where the binary is spawned directly below.
+        let _ = detached;
+
         #[cfg(target_os = "macos")]
         {
             use std::process::Stdio;
@@ -442,11 +666,22 @@ impl JetBrainsManager {
                 cmd.arg(arg);
             }
 
-            cmd.stdin(Stdio::null())
+            super::launch::normalize_launch_env(&mut cmd);
+            if let Some(dir) = cwd {
+                cmd.current_dir(dir);
+            }
+            cmd.envs(env);
+
+            let mut launcher = cmd
+                .stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .spawn()
                 .map_err(|e| e.to_string())?;
+            let _ = launcher.wait();
+
+            let pattern = format!("/Applications/{}.app", self.display_name);
+            return Ok(Self::resolve_instance_pid(&pattern).await.map(EditorProcess::detached));
         }
 
         #[cfg(target_os = "windows")]
@@ -465,27 +700,111 @@ impl JetBrainsManager {
                 cmd.arg(arg);
             }
 
-            cmd.stdin(Stdio::null())
+            super::launch::normalize_launch_env(&mut cmd);
+            if let Some(dir) = cwd {
+                cmd.current_dir(dir);
+            }
+            cmd.envs(env);
+
+            let mut launcher = cmd
+                .stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .spawn()
                 .map_err(|e| e.to_string())?;
+            let _ = launcher.wait();
+
+            let image_name = binary
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| self.display_name.clone());
+            return Ok(Self::resolve_instance_pid(&image_name).await.map(EditorProcess::detached));
         }
 
         #[cfg(target_os = "linux")]
         {
             use std::process::Stdio;
 
-            Command::new(binary)
-                .args(args)
+            // `build_launch_command` runs `normalize_launch_env` before
+            // returning, which restores (or strips) `LD_LIBRARY_PATH`,
+            // `GTK_PATH`, `GST_PLUGIN_SYSTEM_PATH`, `GIO_MODULE_DIR`, etc.
+            // and de-pollutes `PATH`/`XDG_DATA_DIRS` of entries rooted under
+            // `$APPDIR`/`$SNAP`/flatpak's `/app` - without it, an
+            // AppImage/Flatpak/Snap-packaged Sorcery would hand the
+            // JetBrains launcher script its own sandboxed libraries instead
+            // of the host's, and it'd load the wrong ones or fail silently.
+            let mut cmd = super::launch::build_launch_command(binary, args);
+            if let Some(dir) = cwd {
+                cmd.current_dir(dir);
+            }
+            cmd.envs(env);
+            if detached {
+                super::launch::detach_command(&mut cmd);
+            }
+
+            let child = cmd
                 .stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .spawn()
                 .map_err(|e| e.to_string())?;
+
+            Ok(Some(EditorProcess::owned(child)))
         }
+    }
 
-        Ok(())
+    /// Polls `pgrep -f pattern` (macOS) / `tasklist` (Windows) a few times
+    /// for a pid matching `pattern`, since the real editor process can take
+    /// a moment to appear after the launcher that spawned it has already
+    /// exited.
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    async fn resolve_instance_pid(pattern: &str) -> Option<u32> {
+        const ATTEMPTS: u32 = 10;
+        const RETRY_DELAY: Duration = Duration::from_millis(200);
+
+        for attempt in 0..ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+
+            #[cfg(target_os = "macos")]
+            {
+                let mut cmd = tokio::process::Command::new("pgrep");
+                cmd.arg("-f").arg(pattern);
+                let output = output_with_timeout(cmd).await?;
+                if let Some(pid) = String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .find_map(|line| line.parse::<u32>().ok())
+                {
+                    return Some(pid);
+                }
+            }
+
+            #[cfg(target_os = "windows")]
+            {
+                let mut cmd = tokio::process::Command::new("tasklist");
+                cmd.args(["/FI", &format!("IMAGENAME eq {}", pattern), "/FO", "CSV", "/NH"]);
+                let output = output_with_timeout(cmd).await?;
+                if let Some(pid) = String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .find_map(|line| line.split(',').nth(1))
+                    .and_then(|field| field.trim_matches('"').parse::<u32>().ok())
+                {
+                    return Some(pid);
+                }
+            }
+        }
+
+        warn!("Could not resolve a pid matching {:?} after launch", pattern);
+        None
+    }
+
+    /// Drops any tracked instance whose process has already exited, so
+    /// `instances` doesn't grow without bound across repeated opens.
+    fn prune_exited_instances(&self) {
+        self.instances
+            .write()
+            .retain_mut(|tracked| tracked.process.try_status().is_none());
     }
 }
 
@@ -503,47 +822,22 @@ impl EditorManager for JetBrainsManager {
         true
     }
 
+    fn supported_extensions(&self) -> &[&str] {
+        crate::file_types::source_code_extensions()
+    }
+
+    /// The preferred installation's binary - see [`JetBrainsManager::list_installations`]
+    /// for the full set and [`JetBrainsManager::pick_preferred`] for how
+    /// "preferred" is decided.
     async fn find_binary(&self) -> Option<PathBuf> {
         if let Some(cached) = self.get_cached_binary() {
             return Some(cached);
         }
 
-        #[cfg(target_os = "macos")]
-        {
-            if let Some(path) = self.find_toolbox_binary_macos().await {
-                self.cache_binary(Some(path.clone()));
-                return Some(path);
-            }
-
-            if let Some(path) = self.find_standalone_binary_macos().await {
-                self.cache_binary(Some(path.clone()));
-                return Some(path);
-            }
-        }
-
-        #[cfg(target_os = "windows")]
-        {
-            if let Some(path) = self.find_toolbox_binary_windows().await {
-                self.cache_binary(Some(path.clone()));
-                return Some(path);
-            }
-        }
-
-        #[cfg(target_os = "linux")]
-        {
-            if let Some(path) = self.find_toolbox_binary_linux().await {
-                self.cache_binary(Some(path.clone()));
-                return Some(path);
-            }
-
-            if let Some(path) = self.find_standalone_binary_linux().await {
-                self.cache_binary(Some(path.clone()));
-                return Some(path);
-            }
-        }
-
-        self.cache_binary(None);
-        None
+        let installations = self.list_installations().await;
+        let binary = Self::pick_preferred(&installations).map(|install| install.binary.clone());
+        self.cache_binary(binary.clone());
+        binary
     }
 
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
@@ -568,63 +862,60 @@ impl EditorManager for JetBrainsManager {
 
         debug!("Launching {} with args: {:?}", self.display_name, args);
 
-        // Try to launch, with auto-retry on failure
-        let launch_result = self.spawn_editor(&binary, &args);
-
-        if let Err(e) = launch_result {
-            warn!(
-                "Failed to launch {}: {}. Invalidating cache and retrying...",
-                self.display_name, e
-            );
+        self.prune_exited_instances();
 
-            // Invalidate cache
-            self.cache_binary(None);
+        let cwd = options.working_directory.as_deref();
 
-            // Retry with fresh binary discovery
-            if let Some(retry_binary) = self.find_binary().await {
+        // Try to launch, with auto-retry on failure
+        let process = match self.spawn_editor(&binary, &args, cwd, &options.env, options.detached).await {
+            Ok(process) => process,
+            Err(e) => {
+                warn!(
+                    "Failed to launch {}: {}. Invalidating cache and retrying...",
+                    self.display_name, e
+                );
+
+                // Invalidate cache
+                self.cache_binary(None);
+
+                // Retry with fresh binary discovery
+                let retry_binary = self.find_binary().await.ok_or_else(|| {
+                    EditorError::LaunchFailed(e.to_string())
+                })?;
                 debug!("Retrying with fresh binary: {:?}", retry_binary);
-                return self
-                    .spawn_editor(&retry_binary, &args)
-                    .map_err(|e| EditorError::LaunchFailed(format!("Retry failed: {}", e)));
+                self.spawn_editor(&retry_binary, &args, cwd, &options.env, options.detached)
+                    .await
+                    .map_err(|e| EditorError::LaunchFailed(format!("Retry failed: {}", e)))?
             }
+        };
 
-            return Err(EditorError::LaunchFailed(e.to_string()));
+        if let Some(process) = process {
+            self.instances.write().push(TrackedInstance {
+                process,
+                workspace: Some(path.to_path_buf()),
+            });
         }
 
         Ok(())
     }
 
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
-        #[cfg(target_os = "macos")]
-        {
-            let pattern = format!("/Applications/{}.app", self.display_name);
-            let output = Command::new("pgrep")
-                .arg("-f")
-                .arg(&pattern)
-                .output()
-                .map_err(|e| EditorError::Other(e.to_string()))?;
-
-            if !output.status.success() {
-                return Ok(Vec::new());
-            }
-
-            let pids_str = String::from_utf8_lossy(&output.stdout);
-            let instances: Vec<EditorInstance> = pids_str
-                .lines()
-                .filter_map(|line| line.parse::<u32>().ok())
-                .map(|pid| EditorInstance {
-                    pid,
-                    workspace: None,
-                    window_title: None,
-                })
-                .collect();
-
-            Ok(instances)
-        }
+        self.prune_exited_instances();
+
+        let instances = self
+            .instances
+            .read()
+            .iter()
+            .map(|tracked| EditorInstance {
+                pid: tracked.process.pid(),
+                workspace: tracked
+                    .workspace
+                    .as_ref()
+                    .map(|path| path.display().to_string()),
+                window_title: None,
+            })
+            .collect();
 
-        #[cfg(not(target_os = "macos"))]
-        {
-            Ok(Vec::new())
-        }
+        Ok(instances)
     }
 }
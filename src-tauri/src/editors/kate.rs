@@ -1,14 +1,20 @@
+use super::binary_locator::BinaryLocator;
+use super::launch::{build_launch_command, detach_command};
+use super::process::{EditorProcess, EditorProcessRegistry};
 use super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use tracing::debug;
 
-pub struct KateManager;
+pub struct KateManager {
+    processes: EditorProcessRegistry,
+}
 
 impl KateManager {
     pub fn new() -> Self {
-        Self
+        Self {
+            processes: EditorProcessRegistry::new(),
+        }
     }
 }
 
@@ -22,16 +28,12 @@ impl EditorManager for KateManager {
         "Kate"
     }
 
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain", "text/"]
+    }
+
     async fn find_binary(&self) -> Option<PathBuf> {
-        if let Ok(output) = Command::new("which").arg("kate").output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    return Some(PathBuf::from(path_str));
-                }
-            }
-        }
-        None
+        BinaryLocator::find("kate").await
     }
 
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
@@ -57,15 +59,28 @@ impl EditorManager for KateManager {
 
         debug!("Launching Kate with args: {:?}", args);
 
-        Command::new(&binary)
-            .args(&args)
+        let mut cmd = build_launch_command(&binary, &args);
+        if let Some(dir) = &options.working_directory {
+            cmd.current_dir(dir);
+        }
+        if options.detached {
+            detach_command(&mut cmd);
+        }
+        let child = cmd
             .spawn()
             .map_err(|e| EditorError::LaunchFailed(e.to_string()))?;
 
+        self.processes.register(
+            EditorProcess::owned(child),
+            path.to_path_buf(),
+            options.line,
+            options.column,
+        );
+
         Ok(())
     }
 
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
-        Ok(Vec::new())
+        Ok(self.processes.running_instances())
     }
 }
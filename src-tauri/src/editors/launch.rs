@@ -0,0 +1,539 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+
+/// Env vars that AppImage/Flatpak/Snap runtimes rewrite to point at our own
+/// bundled libraries, and that break GTK/GStreamer-based editors when they
+/// leak into the child process we spawn.
+const CONTAMINATING_VARS: &[&str] = &[
+    "LD_LIBRARY_PATH",
+    "GTK_PATH",
+    "GTK_EXE_PREFIX",
+    "GTK_DATA_PREFIX",
+    "GST_PLUGIN_PATH",
+    "GST_PLUGIN_SYSTEM_PATH",
+    "GST_PLUGIN_SCANNER",
+    "GIO_MODULE_DIR",
+    "GDK_PIXBUF_MODULE_FILE",
+];
+
+/// `:`-separated list vars that accumulate duplicate entries (ours
+/// prepended ahead of the system's) as they pass through nested runtimes.
+const LIST_VARS_TO_DEDUP: &[&str] = &["PATH", "XDG_DATA_DIRS", "PYTHONPATH"];
+
+/// Whether we're running from an AppImage - either still mounted (`APPDIR`
+/// set) or via the `APPIMAGE` pointer the runtime always exports.
+pub fn is_appimage() -> bool {
+    std::env::var_os("APPIMAGE").is_some() || std::env::var_os("APPDIR").is_some()
+}
+
+/// Whether we're running inside a Flatpak sandbox.
+pub fn is_flatpak() -> bool {
+    std::env::var_os("FLATPAK_ID").is_some()
+        || std::env::var("container").is_ok_and(|v| v == "flatpak")
+}
+
+/// Whether we're running inside a Snap confinement.
+pub fn is_snap() -> bool {
+    std::env::var_os("SNAP").is_some()
+}
+
+/// Which packaging runtime (if any) this process is running under, for
+/// surfacing on the `doctor` report - knowing *why* an editor's launch
+/// environment needed sanitizing is as useful as knowing it was.
+pub fn packaging_mode() -> Option<&'static str> {
+    if is_flatpak() {
+        Some("flatpak")
+    } else if is_snap() {
+        Some("snap")
+    } else if is_appimage() {
+        Some("appimage")
+    } else {
+        None
+    }
+}
+
+/// Root directory of the bundle we're running from, if any - entries a
+/// PATH-list variable inherits that live under this prefix came from our
+/// own packaging, not the host system, and should be stripped before
+/// they're handed to an editor/terminal we spawn.
+fn bundle_prefix() -> Option<PathBuf> {
+    if let Some(appdir) = std::env::var_os("APPDIR") {
+        return Some(PathBuf::from(appdir));
+    }
+    if is_flatpak() {
+        return Some(
+            std::env::var_os("FLATPAK_DEST")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("/app")),
+        );
+    }
+    std::env::var_os("SNAP").map(PathBuf::from)
+}
+
+/// Normalizes a `:`-separated PATH-list value: drops empty entries, drops
+/// any entry rooted under `bundle_prefix` (our own packaging's injected
+/// dirs), and de-duplicates repeats by keeping each entry's last (i.e.
+/// lowest-priority) occurrence rather than its first, so a bundle-injected
+/// copy earlier in the list doesn't shadow the system one that follows it.
+///
+/// Containment is checked against both the raw entry and its canonicalized
+/// form: Snap's `current` and Flatpak's `active` are symlinks to a
+/// versioned directory, so an entry reached through one of those links
+/// wouldn't textually start with `bundle_prefix` even though it resolves
+/// under it. Canonicalization is best-effort - an entry that doesn't exist
+/// on disk (stale PATH cruft, a container-only path) just falls back to the
+/// raw comparison.
+fn normalize_pathlist(value: &str, bundle_prefix: Option<&Path>) -> String {
+    let canonical_prefix = bundle_prefix.and_then(|prefix| std::fs::canonicalize(prefix).ok());
+
+    let entries: Vec<&str> = value
+        .split(':')
+        .filter(|entry| !entry.is_empty())
+        .filter(|entry| {
+            bundle_prefix
+                .map(|prefix| {
+                    if Path::new(entry).starts_with(prefix) {
+                        return false;
+                    }
+                    match (canonical_prefix.as_deref(), std::fs::canonicalize(entry)) {
+                        (Some(canonical_prefix), Ok(canonical_entry)) => {
+                            !canonical_entry.starts_with(canonical_prefix)
+                        }
+                        _ => true,
+                    }
+                })
+                .unwrap_or(true)
+        })
+        .collect();
+
+    let mut last_index: HashMap<&str, usize> = HashMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        last_index.insert(entry, i);
+    }
+
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(i, entry)| last_index.get(*entry) == Some(i))
+        .map(|(_, entry)| *entry)
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Normalizes the environment a spawned editor/terminal inherits: restores
+/// (or strips) variables our own AppImage/Flatpak/Snap packaging is known
+/// to contaminate, normalizes `PATH`/`XDG_DATA_DIRS`/`PYTHONPATH` (stripping
+/// bundle-rooted entries and de-duplicating), and drops any variable that's
+/// present but empty. Call this on every `Command` right before
+/// `.spawn()`/`.output()`/`.status()`.
+pub fn normalize_launch_env(cmd: &mut Command) {
+    for var in CONTAMINATING_VARS {
+        // Our packaging wrapper scripts stash the pre-packaging value
+        // (if any) under `SORCERY_ORIG_<VAR>` before rewriting it for our
+        // own process; restore that, or strip the variable entirely if
+        // there was nothing to restore.
+        let orig_key = format!("SORCERY_ORIG_{}", var);
+        match std::env::var(&orig_key) {
+            Ok(value) if !value.is_empty() => {
+                cmd.env(var, value);
+            }
+            _ => {
+                cmd.env_remove(var);
+            }
+        }
+    }
+
+    let prefix = bundle_prefix();
+    for var in LIST_VARS_TO_DEDUP {
+        if let Ok(value) = std::env::var(var) {
+            let normalized = normalize_pathlist(&value, prefix.as_deref());
+            if normalized.is_empty() {
+                cmd.env_remove(var);
+            } else {
+                cmd.env(var, normalized);
+            }
+        }
+    }
+
+    for (key, value) in std::env::vars() {
+        if value.is_empty() {
+            cmd.env_remove(key);
+        }
+    }
+}
+
+/// Flatpak exports app binaries as wrapper scripts under
+/// `<installation>/exports/bin/<app-id>`, with the basename being the app
+/// id itself.
+fn flatpak_app_id(binary: &Path) -> Option<String> {
+    let path_str = binary.to_string_lossy();
+    if path_str.contains("/flatpak/exports/bin/") {
+        binary
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    } else {
+        None
+    }
+}
+
+/// Snap exports app binaries under `/snap/bin/<name>`.
+fn snap_app_name(binary: &Path) -> Option<String> {
+    if binary.starts_with("/snap/bin") {
+        binary
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    } else {
+        None
+    }
+}
+
+/// Fully decouples the process `cmd` is about to spawn from Sorcery, so a
+/// GUI editor survives the app quitting instead of dying (or being
+/// reparented to init in some awkward half-orphaned state) along with it.
+///
+/// On Unix, `setsid()` in a `pre_exec` hook moves the child into a new
+/// session before it execs, so it has no controlling terminal and isn't part
+/// of our process group; stdio is redirected to `/dev/null` since there's no
+/// terminal left for it to inherit anyway. On Windows, `DETACHED_PROCESS`
+/// combined with `CREATE_NEW_PROCESS_GROUP` achieves the same thing - no
+/// console, and ^C sent to us doesn't propagate to it.
+///
+/// Call this on a `Command` built by [`build_launch_command`] right before
+/// `.spawn()`, only when the caller's `OpenOptions.detached` is set - most
+/// spawns (version probes, the `doctor` self-test) want the ordinary
+/// parent/child relationship instead.
+pub fn detach_command(cmd: &mut Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        use std::process::Stdio;
+
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+
+        // Safety: `libc::setsid()` is async-signal-safe and is the only
+        // thing this hook calls between `fork` and `exec`.
+        unsafe {
+            cmd.pre_exec(|| {
+                if libc::setsid() == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        // winapi::um::winbase::{DETACHED_PROCESS, CREATE_NEW_PROCESS_GROUP}
+        const DETACHED_PROCESS: u32 = 0x0000_0008;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+    }
+}
+
+/// Builds the `Command` to launch `binary` with `args`, rewriting it to
+/// `flatpak run <app-id>` / `snap run <name>` when `find_binary` resolved a
+/// flatpak/snap-exported wrapper rather than a real executable, and with
+/// [`normalize_launch_env`] already applied.
+pub fn build_launch_command<S: AsRef<OsStr>>(binary: &Path, args: &[S]) -> Command {
+    let mut cmd = if let Some(app_id) = flatpak_app_id(binary) {
+        let mut c = Command::new("flatpak");
+        c.arg("run").arg(app_id);
+        c
+    } else if let Some(name) = snap_app_name(binary) {
+        let mut c = Command::new("snap");
+        c.arg("run").arg(name);
+        c
+    } else {
+        Command::new(binary)
+    };
+
+    cmd.args(args);
+    normalize_launch_env(&mut cmd);
+    cmd
+}
+
+/// mozrunner-style builder over [`build_launch_command`]: chain
+/// `.arg()`/`.args()`/`.env()`/`.envs()` to build up the process to launch
+/// incrementally, then `.start()` to spawn it - for a caller (like
+/// `ExternalEditorManager::open`, which assembles a shell invocation piece
+/// by piece) that would otherwise have to reach past `build_launch_command`
+/// to a raw `std::process::Command` just to add one more argument.
+pub struct Launch {
+    cmd: Command,
+    detached: bool,
+}
+
+impl Launch {
+    /// Starts building a launch of `binary`, already rewritten to
+    /// `flatpak run`/`snap run` (if `binary` resolved to one of those
+    /// exported wrappers) and environment-normalized via
+    /// [`build_launch_command`].
+    pub fn new(binary: &Path) -> Self {
+        Self {
+            cmd: build_launch_command(binary, &[] as &[&str]),
+            detached: false,
+        }
+    }
+
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.cmd.arg(arg);
+        self
+    }
+
+    pub fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.cmd.args(args);
+        self
+    }
+
+    pub fn env<K, V>(&mut self, key: K, val: V) -> &mut Self
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.cmd.env(key, val);
+        self
+    }
+
+    pub fn envs<I, K, V>(&mut self, vars: I) -> &mut Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.cmd.envs(vars);
+        self
+    }
+
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Self {
+        self.cmd.current_dir(dir);
+        self
+    }
+
+    /// Decouples the spawned process from ours once started - see
+    /// [`detach_command`]. Off by default, matching `OpenOptions::detached`.
+    pub fn detached(&mut self, detached: bool) -> &mut Self {
+        self.detached = detached;
+        self
+    }
+
+    /// Applies [`detach_command`] (if `.detached(true)` was set) and spawns
+    /// the process.
+    pub fn start(&mut self) -> io::Result<Child> {
+        if self.detached {
+            detach_command(&mut self.cmd);
+        }
+        self.cmd.spawn()
+    }
+}
+
+/// Quotes `value` as a single POSIX `sh` word: wrapped in single quotes,
+/// with each embedded single quote ended, escaped (`\'`), and reopened -
+/// the standard `'\''` trick, since single quotes admit no escape sequences
+/// of their own. Unlike `shell_escape::escape` (used for the macOS launch
+/// script in `terminal_detector`), this always quotes rather than only when
+/// it judges it necessary, so a caller building a command line piece by
+/// piece doesn't need to reason about which arguments happened to need it.
+pub fn posix_shell_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    quoted.push_str(&value.replace('\'', "'\\''"));
+    quoted.push('\'');
+    quoted
+}
+
+/// Quotes `value` per the Windows C runtime's argv-parsing rules (the same
+/// ones `cmd.exe` and `CommandLineToArgvW` use): wraps in double quotes,
+/// escaping embedded quotes with a backslash and doubling any run of
+/// backslashes that's immediately followed by a quote (or by the closing
+/// quote) so it isn't misread as escaping that quote.
+///
+/// Also doubles every `%` to `%%`. `CommandLineToArgvW`'s own quoting rules
+/// say nothing about `%` - it's `cmd.exe`'s separate pass over the command
+/// line that expands `%VAR%` to an environment variable's value, and it
+/// does this *before* argv splitting, so it isn't suppressed by the double
+/// quotes above. Without this, a path containing e.g. `%APPDATA%` would get
+/// silently expanded into someone else's environment variable before the
+/// spawned editor ever saw it. `%%` is `cmd.exe`'s own escape for a literal
+/// `%`, so this round-trips correctly.
+pub fn windows_shell_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let mut run = 1;
+            while chars.peek() == Some(&'\\') {
+                run += 1;
+                chars.next();
+            }
+            let factor = if matches!(chars.peek(), Some('"') | None) { 2 } else { 1 };
+            quoted.extend(std::iter::repeat('\\').take(run * factor));
+        } else if c == '"' {
+            quoted.push('\\');
+            quoted.push('"');
+        } else if c == '%' {
+            quoted.push('%');
+            quoted.push('%');
+        } else {
+            quoted.push(c);
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_pathlist_drops_empty_entries() {
+        assert_eq!(
+            normalize_pathlist("/usr/bin::/opt/app/bin:", None),
+            "/usr/bin:/opt/app/bin"
+        );
+    }
+
+    #[test]
+    fn normalize_pathlist_keeps_last_occurrence_of_a_duplicate() {
+        assert_eq!(
+            normalize_pathlist("/usr/bin:/opt/app/bin:/usr/bin", None),
+            "/opt/app/bin:/usr/bin"
+        );
+    }
+
+    #[test]
+    fn normalize_pathlist_strips_entries_under_bundle_prefix() {
+        assert_eq!(
+            normalize_pathlist(
+                "/app/bin:/usr/bin:/app/lib/gst",
+                Some(Path::new("/app"))
+            ),
+            "/usr/bin"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn normalize_pathlist_strips_an_entry_reached_through_a_symlinked_bundle_root() {
+        use std::os::unix::fs::symlink;
+
+        let temp = tempfile::TempDir::new().expect("temp dir");
+        let versioned = temp.path().join("1.0.0");
+        std::fs::create_dir_all(versioned.join("bin")).expect("mkdir");
+        let current = temp.path().join("current");
+        symlink(&versioned, &current).expect("symlink");
+
+        // `bundle_prefix` is the `current` symlink (what `$SNAP` points at),
+        // but the PATH entry names the resolved revision directory directly
+        // - textually unrelated to `current`, only equal once canonicalized.
+        let entry = versioned.join("bin");
+        let value = format!("{}:/usr/bin", entry.display());
+
+        assert_eq!(normalize_pathlist(&value, Some(&current)), "/usr/bin");
+    }
+
+    #[test]
+    fn normalize_pathlist_of_only_bundle_entries_is_empty() {
+        assert_eq!(
+            normalize_pathlist("/snap/sorcery/current/bin", Some(Path::new("/snap/sorcery/current"))),
+            ""
+        );
+    }
+
+    #[test]
+    fn flatpak_app_id_extracts_basename_from_export_path() {
+        assert_eq!(
+            flatpak_app_id(Path::new(
+                "/var/lib/flatpak/exports/bin/com.visualstudio.code"
+            )),
+            Some("com.visualstudio.code".to_string())
+        );
+        assert_eq!(flatpak_app_id(Path::new("/usr/bin/code")), None);
+    }
+
+    #[test]
+    fn snap_app_name_extracts_basename_from_snap_bin() {
+        assert_eq!(
+            snap_app_name(Path::new("/snap/bin/code")),
+            Some("code".to_string())
+        );
+        assert_eq!(snap_app_name(Path::new("/usr/bin/code")), None);
+    }
+
+    #[test]
+    fn posix_shell_quote_wraps_a_plain_path() {
+        assert_eq!(posix_shell_quote("/tmp/repo/README.md"), "'/tmp/repo/README.md'");
+    }
+
+    #[test]
+    fn posix_shell_quote_escapes_embedded_single_quotes() {
+        assert_eq!(
+            posix_shell_quote("/tmp/it's a file.rs"),
+            "'/tmp/it'\\''s a file.rs'"
+        );
+    }
+
+    #[test]
+    fn windows_shell_quote_wraps_a_plain_path() {
+        assert_eq!(
+            windows_shell_quote("C:\\repo\\README.md"),
+            "\"C:\\repo\\README.md\""
+        );
+    }
+
+    #[test]
+    fn windows_shell_quote_escapes_embedded_quotes() {
+        assert_eq!(
+            windows_shell_quote("C:\\it's \"quoted\".rs"),
+            "\"C:\\it's \\\"quoted\\\".rs\""
+        );
+    }
+
+    #[test]
+    fn windows_shell_quote_doubles_backslashes_before_closing_quote() {
+        assert_eq!(windows_shell_quote("C:\\trailing\\"), "\"C:\\trailing\\\\\"");
+    }
+
+    #[test]
+    fn windows_shell_quote_escapes_percent_to_block_env_expansion() {
+        assert_eq!(
+            windows_shell_quote("C:\\Users\\me\\%APPDATA%\\file.rs"),
+            "\"C:\\Users\\me\\%%APPDATA%%\\file.rs\""
+        );
+    }
+
+    #[test]
+    fn launch_builds_up_args_incrementally() {
+        let mut launch = Launch::new(Path::new("/bin/sh"));
+        launch.arg("-c").args(["echo", "hi"]);
+
+        let args: Vec<&OsStr> = launch.cmd.get_args().collect();
+        assert_eq!(args, vec![OsStr::new("-c"), OsStr::new("echo"), OsStr::new("hi")]);
+    }
+
+    #[test]
+    fn launch_env_overrides_the_normalized_environment() {
+        let mut launch = Launch::new(Path::new("/bin/sh"));
+        launch.env("MY_VAR", "1").envs([("A", "a"), ("B", "b")]);
+
+        let envs: Vec<_> = launch.cmd.get_envs().collect();
+        assert!(envs.contains(&(OsStr::new("MY_VAR"), Some(OsStr::new("1")))));
+        assert!(envs.contains(&(OsStr::new("A"), Some(OsStr::new("a")))));
+        assert!(envs.contains(&(OsStr::new("B"), Some(OsStr::new("b")))));
+    }
+}
@@ -0,0 +1,127 @@
+//! Best-effort MIME type detection for a resolved workspace file, feeding
+//! `EditorRegistry::suggest_editors`'s "Open With" ranking. Mirrors
+//! `file_types::classify`'s extension-table approach rather than shelling
+//! out to `xdg-mime`/`file` - this needs to produce an answer on every
+//! platform, not just the Linux path `external_editors::list_openers`
+//! already covers via `xdg-mime query filetype`.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// How many leading bytes of an extensionless file to read before
+/// concluding it's text or binary - enough to catch a NUL byte early in a
+/// binary file without paying for a full read on a large one.
+const SNIFF_LIMIT: usize = 8192;
+
+static EXTENSION_MIME_TYPES: &[(&str, &str)] = &[
+    (".txt", "text/plain"),
+    (".md", "text/markdown"),
+    (".rs", "text/x-rust"),
+    (".py", "text/x-python"),
+    (".rb", "text/x-ruby"),
+    (".go", "text/x-go"),
+    (".java", "text/x-java"),
+    (".kt", "text/x-kotlin"),
+    (".kts", "text/x-kotlin"),
+    (".swift", "text/x-swift"),
+    (".c", "text/x-csrc"),
+    (".h", "text/x-chdr"),
+    (".cc", "text/x-c++src"),
+    (".cpp", "text/x-c++src"),
+    (".cxx", "text/x-c++src"),
+    (".hpp", "text/x-c++hdr"),
+    (".hxx", "text/x-c++hdr"),
+    (".cs", "text/x-csharp"),
+    (".php", "text/x-php"),
+    (".scala", "text/x-scala"),
+    (".sh", "text/x-shellscript"),
+    (".bash", "text/x-shellscript"),
+    (".zsh", "text/x-shellscript"),
+    (".lua", "text/x-lua"),
+    (".pl", "text/x-perl"),
+    (".sql", "text/x-sql"),
+    (".html", "text/html"),
+    (".htm", "text/html"),
+    (".css", "text/css"),
+    (".scss", "text/x-scss"),
+    (".less", "text/x-less"),
+    // Registered as "application/*" by IANA, but declared as "text/*" here
+    // on purpose: every one of these is plain text any editor can open, and
+    // keeping them under the "text/" prefix lets a lightweight editor's
+    // blanket `supported_mime_types` match them the way it should.
+    (".json", "text/json"),
+    (".yaml", "text/yaml"),
+    (".yml", "text/yaml"),
+    (".toml", "text/toml"),
+    (".xml", "text/xml"),
+    (".js", "text/javascript"),
+    (".mjs", "text/javascript"),
+    (".cjs", "text/javascript"),
+    (".jsx", "text/javascript"),
+    (".ts", "text/typescript"),
+    (".tsx", "text/typescript"),
+];
+
+/// Resolves `path`'s MIME type: an exact extension-table lookup first,
+/// falling back to sniffing the file's leading bytes for a NUL byte when
+/// the extension is missing or unrecognized - good enough to tell "some
+/// kind of text" from "binary" without a full `file`-style magic-number
+/// database.
+pub fn detect_mime_type(path: &Path) -> String {
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        let ext = format!(".{}", ext.to_lowercase());
+        if let Some((_, mime)) = EXTENSION_MIME_TYPES.iter().find(|(known, _)| *known == ext) {
+            return mime.to_string();
+        }
+    }
+
+    if looks_like_text(path) {
+        "text/plain".to_string()
+    } else {
+        "application/octet-stream".to_string()
+    }
+}
+
+/// A missing or unreadable file sniffs as text: we'd rather over-suggest
+/// editors for a path that doesn't exist yet than silently return no
+/// suggestions at all.
+fn looks_like_text(path: &Path) -> bool {
+    let Ok(mut file) = File::open(path) else {
+        return true;
+    };
+    let mut buf = [0u8; SNIFF_LIMIT];
+    let Ok(n) = file.read(&mut buf) else {
+        return true;
+    };
+    !buf[..n].contains(&0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn detects_mime_type_by_extension() {
+        assert_eq!(detect_mime_type(Path::new("main.rs")), "text/x-rust");
+        assert_eq!(detect_mime_type(Path::new("package.json")), "text/json");
+    }
+
+    #[test]
+    fn sniffs_extensionless_text_file() {
+        let mut file = NamedTempFile::new().expect("tempfile");
+        write!(file, "#!/bin/sh\necho hi\n").unwrap();
+
+        assert_eq!(detect_mime_type(file.path()), "text/plain");
+    }
+
+    #[test]
+    fn sniffs_extensionless_binary_file() {
+        let mut file = NamedTempFile::new().expect("tempfile");
+        file.write_all(&[0x7f, b'E', b'L', b'F', 0, 1, 2, 3]).unwrap();
+
+        assert_eq!(detect_mime_type(file.path()), "application/octet-stream");
+    }
+}
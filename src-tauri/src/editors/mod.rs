@@ -1,10 +1,30 @@
+mod app_discovery;
+mod binary_locator;
+mod compilation_db;
+mod external;
 mod jetbrains;
 mod kate;
+mod launch;
+mod mime;
 mod others;
+mod process;
+mod process_async;
+mod process_scan;
 mod registry;
 mod terminal;
 mod traits;
 mod vscode;
 
-pub use registry::EditorRegistry;
-pub use traits::OpenOptions;
+pub use app_discovery::DiscoveredApp;
+pub use launch::packaging_mode;
+pub use mime::detect_mime_type;
+pub use registry::{EditorRegistry, EditorSuggestion};
+pub use traits::{EditorManager, EditorVariant, OpenMode, OpenOptions, SelfTestReport};
+
+/// Terminal names `defaults.preferred_terminal` can name explicitly besides
+/// `"auto"`, exposed so `settings::validation` can check a loaded config
+/// against them without the `settings` module depending on
+/// `editors::terminal` directly.
+pub fn known_terminal_preference_names() -> &'static [&'static str] {
+    terminal::TerminalApp::known_preference_names()
+}
@@ -1,4 +1,6 @@
-use super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
+use super::binary_locator::BinaryLocator;
+use super::launch::{build_launch_command, detach_command, normalize_launch_env};
+use super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenMode, OpenOptions};
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -29,6 +31,10 @@ impl EditorManager for XcodeManager {
         true
     }
 
+    fn supported_extensions(&self) -> &[&str] {
+        crate::file_types::source_code_extensions()
+    }
+
     async fn find_binary(&self) -> Option<PathBuf> {
         let xcode_path = PathBuf::from("/Applications/Xcode.app/Contents/MacOS/Xcode");
         if xcode_path.exists() {
@@ -45,18 +51,44 @@ impl EditorManager for XcodeManager {
 
         debug!("Opening in Xcode: {:?}", path);
 
-        Command::new("open")
-            .arg("-a")
-            .arg("Xcode")
-            .arg(path)
-            .spawn()
+        let mut cmd = Command::new("open");
+        cmd.arg("-a").arg("Xcode").arg(path);
+        normalize_launch_env(&mut cmd);
+        cmd.spawn()
             .map_err(|e| EditorError::LaunchFailed(e.to_string()))?;
 
         Ok(())
     }
 
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
-        Ok(Vec::new())
+        Ok(super::process_scan::find_running("Xcode")
+            .await?
+            .into_iter()
+            .map(|process| EditorInstance {
+                pid: process.pid,
+                workspace: super::process_scan::last_positional_arg(&process.command_line),
+                window_title: None,
+            })
+            .collect())
+    }
+
+    async fn prepare_compilation_db(&self, path: &Path) -> EditorResult<()> {
+        let project_arg = super::compilation_db::xcode_project_arg(path).ok_or_else(|| {
+            EditorError::Other(format!("No .xcworkspace/.xcodeproj found in {:?}", path))
+        })?;
+
+        debug!("Generating compile_commands.json for {:?}", path);
+
+        let output = Command::new("xcodebuild")
+            .args(project_arg)
+            .arg("build")
+            .current_dir(path)
+            .output()
+            .map_err(|e| EditorError::Other(e.to_string()))?;
+
+        let log = String::from_utf8_lossy(&output.stdout);
+        let commands = super::compilation_db::parse_xcodebuild_log(&log);
+        super::compilation_db::write_compile_commands(path, &commands)
     }
 }
 
@@ -82,33 +114,21 @@ impl EditorManager for ZedManager {
         true
     }
 
+    fn supported_extensions(&self) -> &[&str] {
+        crate::file_types::source_code_extensions()
+    }
+
     async fn find_binary(&self) -> Option<PathBuf> {
         #[cfg(target_os = "macos")]
         {
-            let candidates = vec![
-                PathBuf::from("/Applications/Zed.app/Contents/MacOS/cli"),
-                PathBuf::from("/usr/local/bin/zed"),
-                PathBuf::from("/opt/homebrew/bin/zed"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found Zed at {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
-
-        if let Ok(output) = Command::new("which").arg("zed").output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    return Some(PathBuf::from(path_str));
-                }
+            let app_bundle = PathBuf::from("/Applications/Zed.app/Contents/MacOS/cli");
+            if app_bundle.exists() {
+                debug!("Found Zed at {:?}", app_bundle);
+                return Some(app_bundle);
             }
         }
 
-        None
+        BinaryLocator::find("zed").await
     }
 
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
@@ -119,6 +139,13 @@ impl EditorManager for ZedManager {
 
         let mut args = vec![];
 
+        // Zed's own CLI doesn't distinguish "reuse" from "add to workspace" -
+        // either way it opens the file as a new tab in the last active
+        // window - so only `NewWindow` needs an explicit flag.
+        if options.mode == OpenMode::NewWindow {
+            args.push("-n".to_string());
+        }
+
         let file_arg = if let Some(line) = options.line {
             format!("{}:{}", path.display(), line)
         } else {
@@ -129,16 +156,28 @@ impl EditorManager for ZedManager {
 
         debug!("Launching Zed with args: {:?}", args);
 
-        Command::new(&binary)
-            .args(&args)
-            .spawn()
+        let mut cmd = build_launch_command(&binary, &args);
+        if let Some(dir) = &options.working_directory {
+            cmd.current_dir(dir);
+        }
+        if options.detached {
+            detach_command(&mut cmd);
+        }
+        cmd.spawn()
             .map_err(|e| EditorError::LaunchFailed(e.to_string()))?;
 
         Ok(())
     }
 
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
-        Ok(Vec::new())
+        Ok(super::process_scan::find_running("zed").await?
+            .into_iter()
+            .map(|process| EditorInstance {
+                pid: process.pid,
+                workspace: super::process_scan::last_positional_arg(&process.command_line),
+                window_title: None,
+            })
+            .collect())
     }
 }
 
@@ -150,11 +189,15 @@ impl SublimeManager {
     }
 }
 
-pub struct GeditManager;
+pub struct GeditManager {
+    processes: super::process::EditorProcessRegistry,
+}
 
 impl GeditManager {
     pub fn new() -> Self {
-        Self
+        Self {
+            processes: super::process::EditorProcessRegistry::new(),
+        }
     }
 }
 
@@ -172,31 +215,12 @@ impl EditorManager for GeditManager {
         false
     }
 
-    async fn find_binary(&self) -> Option<PathBuf> {
-        #[cfg(target_os = "linux")]
-        {
-            let candidates = vec![
-                PathBuf::from("/usr/bin/gedit"),
-                PathBuf::from("/usr/local/bin/gedit"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    return Some(path);
-                }
-            }
-        }
-
-        if let Ok(output) = Command::new("which").arg("gedit").output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    return Some(PathBuf::from(path_str));
-                }
-            }
-        }
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain", "text/"]
+    }
 
-        None
+    async fn find_binary(&self) -> Option<PathBuf> {
+        BinaryLocator::find("gedit").await
     }
 
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
@@ -215,16 +239,29 @@ impl EditorManager for GeditManager {
 
         debug!("Launching Gedit with args: {:?}", args);
 
-        Command::new(&binary)
-            .args(&args)
+        let mut cmd = build_launch_command(&binary, &args);
+        if let Some(dir) = &options.working_directory {
+            cmd.current_dir(dir);
+        }
+        if options.detached {
+            detach_command(&mut cmd);
+        }
+        let child = cmd
             .spawn()
             .map_err(|e| EditorError::LaunchFailed(e.to_string()))?;
 
+        self.processes.register(
+            super::process::EditorProcess::owned(child),
+            path.to_path_buf(),
+            options.line,
+            options.column,
+        );
+
         Ok(())
     }
 
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
-        Ok(Vec::new())
+        Ok(self.processes.running_instances())
     }
 }
 
@@ -242,18 +279,17 @@ impl EditorManager for SublimeManager {
         true
     }
 
+    fn supported_extensions(&self) -> &[&str] {
+        crate::file_types::source_code_extensions()
+    }
+
     async fn find_binary(&self) -> Option<PathBuf> {
         #[cfg(target_os = "macos")]
         {
-            let subl =
+            let app_bundle =
                 PathBuf::from("/Applications/Sublime Text.app/Contents/SharedSupport/bin/subl");
-            if subl.exists() {
-                return Some(subl);
-            }
-
-            let subl_usr = PathBuf::from("/usr/local/bin/subl");
-            if subl_usr.exists() {
-                return Some(subl_usr);
+            if app_bundle.exists() {
+                return Some(app_bundle);
             }
         }
 
@@ -265,16 +301,7 @@ impl EditorManager for SublimeManager {
             }
         }
 
-        if let Ok(output) = Command::new("which").arg("subl").output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    return Some(PathBuf::from(path_str));
-                }
-            }
-        }
-
-        None
+        BinaryLocator::find("subl").await
     }
 
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
@@ -285,6 +312,12 @@ impl EditorManager for SublimeManager {
 
         let mut args = vec![];
 
+        match options.mode {
+            OpenMode::Reuse => {}
+            OpenMode::AddToWorkspace => args.push("-a".to_string()),
+            OpenMode::NewWindow => args.push("-n".to_string()),
+        }
+
         let file_arg = match (options.line, options.column) {
             (Some(line), Some(column)) => format!("{}:{}:{}", path.display(), line, column),
             (Some(line), None) => format!("{}:{}", path.display(), line),
@@ -295,15 +328,27 @@ impl EditorManager for SublimeManager {
 
         debug!("Launching Sublime Text with args: {:?}", args);
 
-        Command::new(&binary)
-            .args(&args)
-            .spawn()
+        let mut cmd = build_launch_command(&binary, &args);
+        if let Some(dir) = &options.working_directory {
+            cmd.current_dir(dir);
+        }
+        if options.detached {
+            detach_command(&mut cmd);
+        }
+        cmd.spawn()
             .map_err(|e| EditorError::LaunchFailed(e.to_string()))?;
 
         Ok(())
     }
 
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
-        Ok(Vec::new())
+        Ok(super::process_scan::find_running("subl").await?
+            .into_iter()
+            .map(|process| EditorInstance {
+                pid: process.pid,
+                workspace: super::process_scan::last_positional_arg(&process.command_line),
+                window_title: None,
+            })
+            .collect())
     }
 }
@@ -0,0 +1,294 @@
+//! A handle to a spawned editor process, modeled on mozrunner's
+//! `Runner`/`RunnerProcess` split: a launcher gets back something it can
+//! poll or tear down later instead of a `Command::spawn()` result it
+//! immediately drops.
+//!
+//! Two flavors, depending on what the launch mechanism actually hands back:
+//! - [`EditorProcess::owned`] wraps a `Child` we spawned directly and still
+//!   hold - the common case.
+//! - [`EditorProcess::detached`] only knows a PID resolved after the fact.
+//! `open -n -a` on macOS and `cmd /c start` on Windows both hand back
+//! control of a short-lived launcher process rather than the editor
+//! itself, so there's no `Child` left to wait on once it exits.
+
+use super::traits::EditorInstance;
+use parking_lot::RwLock;
+use std::io;
+use std::path::PathBuf;
+use std::process::{Child, ExitStatus};
+use std::time::{Duration, SystemTime};
+use sysinfo::{Pid, System};
+use tracing::warn;
+
+/// How long [`EditorProcess::terminate`] waits for a graceful exit before
+/// escalating to [`EditorProcess::kill`].
+const TERMINATE_GRACE: Duration = Duration::from_secs(3);
+
+enum Inner {
+    Owned(Child),
+    Detached(u32),
+}
+
+pub struct EditorProcess {
+    pid: u32,
+    inner: Inner,
+}
+
+impl EditorProcess {
+    pub fn owned(child: Child) -> Self {
+        let pid = child.id();
+        Self {
+            pid,
+            inner: Inner::Owned(child),
+        }
+    }
+
+    pub fn detached(pid: u32) -> Self {
+        Self {
+            pid,
+            inner: Inner::Detached(pid),
+        }
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Non-blocking: `Some` once the process has exited, `None` while it's
+    /// still running. A detached handle can't recover a real exit code, so
+    /// it reports a synthetic success status the instant the pid
+    /// disappears - enough to know the slot is free, not what happened.
+    pub fn try_status(&mut self) -> Option<ExitStatus> {
+        match &mut self.inner {
+            Inner::Owned(child) => child.try_wait().unwrap_or(None),
+            Inner::Detached(pid) => {
+                if pid_alive(*pid) {
+                    None
+                } else {
+                    Some(synthetic_exit_status())
+                }
+            }
+        }
+    }
+
+    /// Whether the process is still alive, per [`Self::try_status`].
+    pub fn running(&mut self) -> bool {
+        self.try_status().is_none()
+    }
+
+    /// Blocks (off the async runtime) until the process exits.
+    pub async fn wait(self) -> io::Result<ExitStatus> {
+        match self.inner {
+            Inner::Owned(mut child) => tokio::task::spawn_blocking(move || child.wait())
+                .await
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?,
+            Inner::Detached(pid) => {
+                tokio::task::spawn_blocking(move || {
+                    while pid_alive(pid) {
+                        std::thread::sleep(Duration::from_millis(500));
+                    }
+                })
+                .await
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+                Ok(synthetic_exit_status())
+            }
+        }
+    }
+
+    /// Asks the process to exit - `SIGTERM` on Unix, escalating to
+    /// [`Self::kill`] if it's still alive after a grace period. Windows has
+    /// no gentler signal than termination, so this is just `kill` there.
+    pub async fn terminate(&mut self) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            send_signal(self.pid, "TERM")?;
+            tokio::time::sleep(TERMINATE_GRACE).await;
+            if pid_alive(self.pid) {
+                warn!(
+                    "pid {} still alive {}s after SIGTERM, escalating to SIGKILL",
+                    self.pid,
+                    TERMINATE_GRACE.as_secs()
+                );
+                self.kill().await?;
+            }
+            Ok(())
+        }
+
+        #[cfg(windows)]
+        self.kill().await
+    }
+
+    /// Kills the process outright - `SIGKILL` on Unix, `taskkill /F` on
+    /// Windows.
+    pub async fn kill(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            Inner::Owned(child) => child.kill(),
+            Inner::Detached(pid) => {
+                let pid = *pid;
+                #[cfg(unix)]
+                {
+                    send_signal(pid, "KILL")
+                }
+                #[cfg(windows)]
+                {
+                    kill_windows(pid, true)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn send_signal(pid: u32, signal: &str) -> io::Result<()> {
+    let status = std::process::Command::new("kill")
+        .arg(format!("-{}", signal))
+        .arg(pid.to_string())
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("kill -{} {} failed", signal, pid),
+        ))
+    }
+}
+
+#[cfg(unix)]
+fn pid_alive(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .arg("-0")
+        .arg(pid.to_string())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn pid_alive(pid: u32) -> bool {
+    let output = std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid)])
+        .output();
+
+    match output {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()),
+        Err(_) => false,
+    }
+}
+
+#[cfg(windows)]
+fn kill_windows(pid: u32, force: bool) -> io::Result<()> {
+    let mut cmd = std::process::Command::new("taskkill");
+    cmd.args(["/PID", &pid.to_string()]);
+    if force {
+        cmd.arg("/F");
+    }
+
+    let status = cmd.status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("taskkill /PID {} failed", pid),
+        ))
+    }
+}
+
+#[cfg(unix)]
+fn synthetic_exit_status() -> ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+    ExitStatus::from_raw(0)
+}
+
+#[cfg(windows)]
+fn synthetic_exit_status() -> ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+    ExitStatus::from_raw(0)
+}
+
+/// One `open()` call's [`EditorProcess`], plus what it was opened with -
+/// mirrors `JetBrainsManager`'s previously one-off `TrackedInstance`, pulled
+/// up here so any manager can keep the same bookkeeping instead of hand-rolling
+/// it.
+struct TrackedInstance {
+    process: EditorProcess,
+    path: PathBuf,
+    #[allow(dead_code)]
+    line: Option<usize>,
+    #[allow(dead_code)]
+    column: Option<usize>,
+    #[allow(dead_code)]
+    launched_at: SystemTime,
+}
+
+/// Registry of the processes an `EditorManager` has spawned via `open()`, so
+/// `get_running_instances` has something to report instead of a stubbed
+/// `Vec::new()`. One registry belongs to one manager - there's no cross-manager
+/// key because each `EditorManager` impl already owns its own instance of this.
+///
+/// An editor already running before Sorcery started (or left over from a
+/// prior session) is invisible here by design - nothing spawned it - the same
+/// gap `JetBrainsManager`'s process-matching fallback and `process_scan::find_running`
+/// fill for the editors that need it.
+pub struct EditorProcessRegistry {
+    instances: RwLock<Vec<TrackedInstance>>,
+}
+
+impl EditorProcessRegistry {
+    pub fn new() -> Self {
+        Self {
+            instances: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Records a process `open()` just spawned, so it shows up in
+    /// `running_instances` until it exits.
+    pub fn register(&self, process: EditorProcess, path: PathBuf, line: Option<usize>, column: Option<usize>) {
+        self.instances.write().push(TrackedInstance {
+            process,
+            path,
+            line,
+            column,
+            launched_at: SystemTime::now(),
+        });
+    }
+
+    /// Drops any tracked instance whose process has already exited via
+    /// `EditorProcess::running`'s advisory `try_wait`/pid-probe, so the
+    /// registry doesn't grow without bound across repeated opens.
+    fn prune_exited(&self) {
+        self.instances.write().retain_mut(|tracked| tracked.process.running());
+    }
+
+    /// Live instances this registry is tracking, as `EditorInstance`s for
+    /// `EditorManager::get_running_instances`. Prunes via `EditorProcess::running`
+    /// first, then cross-checks the survivors against a fresh `sysinfo` process
+    /// snapshot - belt-and-suspenders against a pid our side didn't notice exit
+    /// getting recycled by the OS before the next poll.
+    pub fn running_instances(&self) -> Vec<EditorInstance> {
+        self.prune_exited();
+
+        let sys = System::new_all();
+        self.instances
+            .write()
+            .retain(|tracked| sys.process(Pid::from_u32(tracked.process.pid())).is_some());
+
+        self.instances
+            .read()
+            .iter()
+            .map(|tracked| EditorInstance {
+                pid: tracked.process.pid(),
+                workspace: Some(tracked.path.display().to_string()),
+                window_title: None,
+            })
+            .collect()
+    }
+}
+
+impl Default for EditorProcessRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,33 @@
+//! Shared timeout bound for the short-lived probe processes editor
+//! detection shells out to (`which`, `ps aux`, `--serverlist`,
+//! `--eval`, ...). `ActiveEditorTracker` fires these every poll interval
+//! alongside unrelated async work, so a single unresponsive terminal or
+//! server socket must fail fast instead of stalling the tracker loop.
+
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+/// How long any single probe gets before we give up on it.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Runs `cmd` to completion, bounded by [`PROBE_TIMEOUT`]. `None` on
+/// timeout, spawn failure, or I/O error - callers already treat a failed
+/// probe as "not found"/"not running" rather than a hard error.
+pub async fn output_with_timeout(mut cmd: Command) -> Option<std::process::Output> {
+    timeout(PROBE_TIMEOUT, cmd.output()).await.ok()?.ok()
+}
+
+/// Same bound as [`output_with_timeout`], but for callers still building a
+/// `std::process::Command` (e.g. through [`super::launch::build_launch_command`])
+/// instead of `tokio::process::Command` - runs it on the blocking pool so the
+/// wait for completion doesn't tie up a tokio worker thread either.
+pub async fn blocking_output_with_timeout(
+    run: impl FnOnce() -> std::io::Result<std::process::Output> + Send + 'static,
+) -> Option<std::process::Output> {
+    timeout(PROBE_TIMEOUT, tokio::task::spawn_blocking(run))
+        .await
+        .ok()?
+        .ok()?
+        .ok()
+}
@@ -0,0 +1,147 @@
+use super::process_async::output_with_timeout;
+use super::traits::EditorResult;
+use tokio::process::Command;
+
+/// One running process's pid and full command line, as reported by the OS.
+pub struct RunningProcess {
+    pub pid: u32,
+    pub command_line: String,
+}
+
+/// Lists every running process whose command line contains `pattern`
+/// (case-insensitive), via `ps -ax -o pid=,command=` on macOS/Linux or
+/// `wmic process get ProcessId,CommandLine /format:list` on Windows -
+/// factored out of `VSCodeManager::get_running_instances` so other managers
+/// that need to find their own running windows don't reimplement the same
+/// OS-native process scan.
+pub async fn find_running(pattern: &str) -> EditorResult<Vec<RunningProcess>> {
+    let pattern = pattern.to_lowercase();
+
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    {
+        let mut cmd = Command::new("ps");
+        cmd.args(["-ax", "-o", "pid=,command="]);
+        let Some(output) = output_with_timeout(cmd).await else {
+            return Ok(Vec::new());
+        };
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim_start();
+                let (pid_str, command) = line.split_once(char::is_whitespace)?;
+                let pid: u32 = pid_str.parse().ok()?;
+                if !command.to_lowercase().contains(&pattern) {
+                    return None;
+                }
+                Some(RunningProcess {
+                    pid,
+                    command_line: command.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new("wmic");
+        cmd.args(["process", "get", "ProcessId,CommandLine", "/format:list"]);
+        let Some(output) = output_with_timeout(cmd).await else {
+            return Ok(Vec::new());
+        };
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout
+            .split("\r\n\r\n")
+            .flat_map(|block| block.split("\n\n"))
+            .filter_map(|block| {
+                let command = block
+                    .lines()
+                    .find_map(|line| line.trim().strip_prefix("CommandLine="))?;
+                if !command.to_lowercase().contains(&pattern) {
+                    return None;
+                }
+                let pid: u32 = block
+                    .lines()
+                    .find_map(|line| line.trim().strip_prefix("ProcessId="))?
+                    .trim()
+                    .parse()
+                    .ok()?;
+                Some(RunningProcess {
+                    pid,
+                    command_line: command.to_string(),
+                })
+            })
+            .collect())
+    }
+}
+
+/// Minimal whitespace tokenizer that keeps a double-quoted argument (as
+/// Chromium/Electron's command-line formatting emits for anything containing
+/// a space) together as one token.
+pub fn split_command_line(command_line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for ch in command_line.chars() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// The last non-flag token in `command_line` (skipping the binary itself) -
+/// the workspace/file path for an editor invoked as plainly as
+/// `<binary> [flags...] <path>`, which covers Zed and Sublime Text but not
+/// VS Code's `--folder-uri`/`--file-uri` forms (see
+/// `vscode::workspace_from_command_line` for those).
+pub fn last_positional_arg(command_line: &str) -> Option<String> {
+    split_command_line(command_line)
+        .into_iter()
+        .skip(1)
+        .filter(|token| !token.starts_with('-'))
+        .last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_command_line_keeps_quoted_argument_together() {
+        let tokens = split_command_line(r#"/usr/bin/subl "/home/user/My Project""#);
+        assert_eq!(tokens, vec!["/usr/bin/subl", "/home/user/My Project"]);
+    }
+
+    #[test]
+    fn last_positional_arg_skips_flags() {
+        assert_eq!(
+            last_positional_arg("/usr/bin/zed -n /home/user/repo"),
+            Some("/home/user/repo".to_string())
+        );
+    }
+
+    #[test]
+    fn last_positional_arg_is_none_when_only_flags() {
+        assert_eq!(last_positional_arg("/usr/bin/zed -n"), None);
+    }
+}
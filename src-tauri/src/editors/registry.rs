@@ -1,32 +1,46 @@
-use super::traits::EditorManager;
+use super::external::ExternalEditorManager;
+use super::traits::{EditorError, EditorManager, EditorResult, OpenOptions};
 use super::vscode::VSCodeManager;
 use super::jetbrains::JetBrainsManager;
-use super::terminal::{VimManager, NeovimManager, EmacsManager};
+use super::terminal::{EmacsManager, HelixManager, NanoManager, NeovimManager, TerminalEditorManager, VimManager};
 use super::others::{XcodeManager, ZedManager, SublimeManager};
+use crate::settings::SettingsManager;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use parking_lot::RwLock;
 
+/// One installed editor's ranked fit for a resolved file, returned by
+/// `EditorRegistry::suggest_editors` for an "Open With" menu - the frontend
+/// merges this with whatever `external_editors::list_openers` surfaces
+/// from the OS's own `.desktop`/LaunchServices registrations.
+#[derive(Clone, Serialize)]
+pub struct EditorSuggestion {
+    pub id: String,
+    pub display_name: String,
+}
+
 pub struct EditorRegistry {
     managers: RwLock<HashMap<String, Arc<dyn EditorManager>>>,
 }
 
 impl EditorRegistry {
-    pub fn new() -> Self {
+    pub fn new(settings: Arc<SettingsManager>) -> Self {
         let registry = Self {
             managers: RwLock::new(HashMap::new()),
         };
 
-        registry.register_all();
+        registry.register_all(&settings);
         registry
     }
 
-    fn register_all(&self) {
-        self.register(Arc::new(VSCodeManager::new("vscode", "Visual Studio Code", "code", "Visual Studio Code", "Code")));
-        self.register(Arc::new(VSCodeManager::new("cursor", "Cursor", "cursor", "Cursor", "Cursor")));
-        self.register(Arc::new(VSCodeManager::new("vscodium", "VSCodium", "codium", "VSCodium", "VSCodium")));
-        self.register(Arc::new(VSCodeManager::new("roo", "Roo Cline", "roo", "Roo Code", "Roo Code")));
-        self.register(Arc::new(VSCodeManager::new("windsurf", "Windsurf", "windsurf", "Windsurf", "Windsurf")));
+    fn register_all(&self, settings: &Arc<SettingsManager>) {
+        self.register(Arc::new(VSCodeManager::new("vscode", "Visual Studio Code", "code", "Visual Studio Code", "Code", settings.clone())));
+        self.register(Arc::new(VSCodeManager::new("cursor", "Cursor", "cursor", "Cursor", "Cursor", settings.clone())));
+        self.register(Arc::new(VSCodeManager::new("vscodium", "VSCodium", "codium", "VSCodium", "VSCodium", settings.clone())));
+        self.register(Arc::new(VSCodeManager::new("roo", "Roo Cline", "roo", "Roo Code", "Roo Code", settings.clone())));
+        self.register(Arc::new(VSCodeManager::new("windsurf", "Windsurf", "windsurf", "Windsurf", "Windsurf", settings.clone())));
 
         self.register(Arc::new(JetBrainsManager::new("idea", "IntelliJ IDEA", "idea")));
         self.register(Arc::new(JetBrainsManager::new("webstorm", "WebStorm", "webstorm")));
@@ -42,13 +56,19 @@ impl EditorRegistry {
 
         self.register(Arc::new(VimManager::new()));
         self.register(Arc::new(NeovimManager::new()));
+        self.register(Arc::new(NanoManager::new()));
         self.register(Arc::new(EmacsManager::new()));
+        self.register(Arc::new(HelixManager::new()));
 
         #[cfg(target_os = "macos")]
         self.register(Arc::new(XcodeManager::new()));
 
         self.register(Arc::new(ZedManager::new()));
         self.register(Arc::new(SublimeManager::new()));
+
+        // Last-resort fallback so `dispatcher.open` still succeeds on a
+        // headless/SSH box with no GUI editor and none of the above found.
+        self.register(Arc::new(TerminalEditorManager::new()));
     }
 
     pub fn register(&self, manager: Arc<dyn EditorManager>) {
@@ -56,17 +76,126 @@ impl EditorRegistry {
         self.managers.write().insert(id, manager);
     }
 
+    /// Registers an editor discovered via `external_editors::discover` - an
+    /// OS-level "open with" entry rather than one of the editors above -
+    /// under its own id, so `dispatcher.open` can dispatch to it like any
+    /// built-in editor once it's been picked from the clone dialog's editor
+    /// list. `terminal` carries through the source `.desktop` entry's
+    /// `Terminal` key (always `false` off Linux), so `ExternalEditorManager`
+    /// knows to run it through a `TerminalApp` instead of spawning it bare.
+    pub fn register_external(&self, id: &str, display_name: &str, exec_template: &str, terminal: bool) {
+        self.register(Arc::new(ExternalEditorManager::new(id, display_name, exec_template, terminal)));
+    }
+
+    /// Looks up a registered editor by id. `"default"` isn't a real
+    /// registered id - it resolves to `TerminalEditorManager` (registered
+    /// as `"terminal"`), so a caller with no specific editor preference
+    /// configured always gets back a working `EditorManager` rather than
+    /// `None`.
     pub fn get(&self, id: &str) -> Option<Arc<dyn EditorManager>> {
-        self.managers.read().get(id).cloned()
+        let managers = self.managers.read();
+        managers
+            .get(id)
+            .or_else(|| if id == "default" { managers.get("terminal") } else { None })
+            .cloned()
     }
 
     pub fn list_editors(&self) -> Vec<String> {
         self.managers.read().keys().cloned().collect()
     }
-}
 
-impl Default for EditorRegistry {
-    fn default() -> Self {
-        Self::new()
+    /// Opens `path` in editor `id`, attaching to an already-running
+    /// instance (`EditorManager::find_reusable_instance`/`open_in`) instead
+    /// of spawning a fresh one when one is found and `open_in` succeeds -
+    /// falling back to a plain `open()` otherwise, so `EditorDispatcher`
+    /// gets reuse-then-spawn behavior for any editor that opts in rather
+    /// than it being wired into one manager's `open()` by hand.
+    pub async fn reuse_then_open(&self, id: &str, path: &Path, options: &OpenOptions) -> EditorResult<()> {
+        let manager = self
+            .get(id)
+            .ok_or_else(|| EditorError::Other(format!("Editor '{}' not found in registry", id)))?;
+
+        if let Some(handle) = manager.find_reusable_instance(path).await {
+            if manager.open_in(handle, path, options).await.is_ok() {
+                return Ok(());
+            }
+        }
+
+        manager.open(path, options).await
+    }
+
+    /// Whether the registered editor `id` self-declares that it needs a
+    /// `defaults.editor_paths` entry before it can be dispatched. Returns
+    /// `false` for an unknown id, same as an editor with no such
+    /// requirement.
+    pub fn requires_configuration(&self, id: &str) -> bool {
+        self.managers
+            .read()
+            .get(id)
+            .map(|manager| manager.requires_configuration())
+            .unwrap_or(false)
+    }
+
+    /// Ranks every installed editor's fit for `path` by MIME type (see
+    /// `editors::detect_mime_type`), for an "Open With" menu: an editor
+    /// whose `supported_extensions` names `path`'s extension outranks one
+    /// that only matches on `supported_mime_types`, so IDEs surface ahead
+    /// of lightweight editors for source files while the latter still show
+    /// up - via the universal `"text/"` default - for everything else.
+    pub async fn suggest_editors(&self, path: &Path) -> Vec<EditorSuggestion> {
+        let mime = super::mime::detect_mime_type(path);
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| format!(".{}", ext.to_lowercase()));
+
+        let managers: Vec<Arc<dyn EditorManager>> = self.managers.read().values().cloned().collect();
+
+        let mut scored = Vec::new();
+        for manager in managers {
+            if !manager.is_installed().await {
+                continue;
+            }
+            let score = Self::score_manager(&*manager, extension.as_deref(), &mime);
+            if score > 0 {
+                scored.push((
+                    score,
+                    EditorSuggestion {
+                        id: manager.id().to_string(),
+                        display_name: manager.display_name().to_string(),
+                    },
+                ));
+            }
+        }
+
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.display_name.cmp(&b.1.display_name)));
+        scored.into_iter().map(|(_, suggestion)| suggestion).collect()
+    }
+
+    /// An extension match outranks an exact MIME match, which outranks a
+    /// declared MIME-family prefix (e.g. `"text/"` covering `"text/plain"`) -
+    /// the ordering `suggest_editors` relies on to put IDEs ahead of
+    /// lightweight editors for source files without hiding either from
+    /// plain text. Zero means "doesn't claim this file at all".
+    fn score_manager(manager: &dyn EditorManager, extension: Option<&str>, mime: &str) -> u32 {
+        if let Some(extension) = extension {
+            if manager.supported_extensions().contains(&extension) {
+                return 100;
+            }
+        }
+
+        if manager.supported_mime_types().contains(&mime) {
+            return 50;
+        }
+
+        if manager
+            .supported_mime_types()
+            .iter()
+            .any(|declared| declared.ends_with('/') && mime.starts_with(declared))
+        {
+            return 10;
+        }
+
+        0
     }
 }
@@ -1,3 +1,6 @@
+use super::super::binary_locator::BinaryLocator;
+use super::super::launch::normalize_launch_env;
+use super::super::process_async::output_with_timeout;
 use super::super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
@@ -10,6 +13,135 @@ impl EmacsManager {
     pub fn new() -> Self {
         Self
     }
+
+    /// Directories that may hold a running `emacs --daemon`'s `emacsclient`
+    /// socket - `$XDG_RUNTIME_DIR/emacs/` on a modern Linux desktop,
+    /// `/tmp/emacs<uid>/` everywhere else (including macOS). We don't know
+    /// our uid-suffixed directory name up front, so `/tmp` itself is
+    /// scanned below for anything matching `emacs*`.
+    fn socket_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![PathBuf::from("/tmp")];
+
+        if let Ok(runtime_dir) = std::env::var("XDG_RUNTIME_DIR") {
+            dirs.push(PathBuf::from(runtime_dir).join("emacs"));
+        }
+
+        dirs
+    }
+
+    /// The actual directory walk, run off the async runtime via
+    /// `spawn_blocking` in [`Self::gather_emacs_sockets`] since
+    /// `std::fs::read_dir` and friends block the calling thread.
+    fn scan_for_sockets_blocking(dirs: Vec<PathBuf>) -> Vec<PathBuf> {
+        let mut sockets = Vec::new();
+
+        for dir in dirs {
+            debug!("Checking directory: {:?}", dir);
+            if let Ok(entries) = std::fs::read_dir(&dir) {
+                for entry in entries.filter_map(|e| e.ok()) {
+                    let path = entry.path();
+                    let Ok(metadata) = std::fs::metadata(&path) else {
+                        continue;
+                    };
+
+                    #[cfg(unix)]
+                    {
+                        use std::os::unix::fs::FileTypeExt;
+                        if metadata.file_type().is_socket() {
+                            debug!("Found emacs server socket: {:?}", path);
+                            sockets.push(path);
+                        } else if metadata.is_dir() {
+                            let is_emacs_dir = path
+                                .file_name()
+                                .and_then(|n| n.to_str())
+                                .is_some_and(|n| n.starts_with("emacs"));
+                            if is_emacs_dir {
+                                if let Ok(inner) = std::fs::read_dir(&path) {
+                                    for inner_entry in inner.filter_map(|e| e.ok()) {
+                                        let inner_path = inner_entry.path();
+                                        if let Ok(inner_metadata) = std::fs::metadata(&inner_path)
+                                        {
+                                            if inner_metadata.file_type().is_socket() {
+                                                debug!(
+                                                    "Found emacs server socket: {:?}",
+                                                    inner_path
+                                                );
+                                                sockets.push(inner_path);
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        debug!("Total emacs sockets found: {}", sockets.len());
+        sockets
+    }
+
+    async fn gather_emacs_sockets(&self) -> Vec<PathBuf> {
+        let dirs = self.socket_dirs();
+        tokio::task::spawn_blocking(move || Self::scan_for_sockets_blocking(dirs))
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Evaluates an elisp expression against a specific daemon via
+    /// `emacsclient --socket-name`, mirroring how the Neovim RPC client
+    /// evaluates `getcwd()`/`bufname('%')` against a socket.
+    async fn eval_on_server(&self, socket: &Path, expr: &str) -> Option<String> {
+        let mut cmd = tokio::process::Command::new("emacsclient");
+        cmd.arg("--socket-name").arg(socket).arg("--eval").arg(expr);
+        let output = output_with_timeout(cmd).await?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if result.is_empty() {
+            None
+        } else {
+            // `--eval` prints the elisp printed representation, so a string
+            // result comes back double-quoted.
+            Some(result.trim_matches('"').to_string())
+        }
+    }
+
+    async fn find_emacs_server(&self, target_path: &Path) -> Option<PathBuf> {
+        let sockets = self.gather_emacs_sockets().await;
+        if sockets.is_empty() {
+            return None;
+        }
+
+        let target = target_path.canonicalize().ok()?;
+
+        // Longest-prefix match against each daemon's `default-directory`, so
+        // a path under a nested workspace prefers the daemon actually
+        // running there over one rooted further up the tree.
+        let mut best: Option<(&PathBuf, usize)> = None;
+        for socket in &sockets {
+            if let Some(cwd) = self.eval_on_server(socket, "default-directory").await {
+                let cwd = PathBuf::from(cwd);
+                if target.starts_with(&cwd) {
+                    let len = cwd.as_os_str().len();
+                    let is_better = match best {
+                        Some((_, best_len)) => len > best_len,
+                        None => true,
+                    };
+                    if is_better {
+                        best = Some((socket, len));
+                    }
+                }
+            }
+        }
+
+        best.map(|(socket, _)| socket.clone())
+            .or_else(|| sockets.first().cloned())
+    }
 }
 
 #[async_trait]
@@ -26,53 +158,21 @@ impl EditorManager for EmacsManager {
         true
     }
 
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain", "text/"]
+    }
+
     async fn find_binary(&self) -> Option<PathBuf> {
         #[cfg(target_os = "macos")]
         {
-            let candidates = vec![
-                PathBuf::from("/Applications/Emacs.app/Contents/MacOS/bin/emacsclient"),
-                PathBuf::from("/opt/homebrew/bin/emacsclient"),
-                PathBuf::from("/usr/local/bin/emacsclient"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found emacsclient at {:?}", path);
-                    return Some(path);
-                }
+            let app_bundle = PathBuf::from("/Applications/Emacs.app/Contents/MacOS/bin/emacsclient");
+            if app_bundle.exists() {
+                debug!("Found emacsclient at {:?}", app_bundle);
+                return Some(app_bundle);
             }
         }
 
-        #[cfg(target_os = "linux")]
-        {
-            let candidates = vec![
-                PathBuf::from("/usr/bin/emacsclient"),
-                PathBuf::from("/usr/local/bin/emacsclient"),
-                PathBuf::from("/snap/bin/emacsclient"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found emacsclient at {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
-
-        if let Ok(output) = Command::new("which").arg("emacsclient").output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    let path = PathBuf::from(&path_str);
-                    if path.exists() {
-                        debug!("Found emacsclient via which: {:?}", path);
-                        return Some(path);
-                    }
-                }
-            }
-        }
-
-        None
+        BinaryLocator::find("emacsclient").await
     }
 
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
@@ -92,6 +192,27 @@ impl EditorManager for EmacsManager {
 
         args.push(path.display().to_string());
 
+        if let Some(socket) = self.find_emacs_server(path).await {
+            debug!("Found emacs daemon socket {:?}, reusing it", socket);
+
+            let mut cmd = Command::new("emacsclient");
+            cmd.arg("--socket-name").arg(&socket).arg("-n");
+            cmd.args(&args);
+            normalize_launch_env(&mut cmd);
+            let result = cmd
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+
+            if result.is_ok() {
+                debug!("Successfully opened file in existing emacs daemon");
+                return Ok(());
+            }
+
+            debug!("Failed to reuse emacs daemon, falling back to the normal launch ladder");
+        }
+
         #[cfg(target_os = "macos")]
         {
             debug!("Trying to open Emacs.app on macOS");
@@ -99,8 +220,10 @@ impl EditorManager for EmacsManager {
             let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
             cmd_args.extend(args_str);
 
-            let result = Command::new("open")
-                .args(&cmd_args)
+            let mut cmd = Command::new("open");
+            cmd.args(&cmd_args);
+            normalize_launch_env(&mut cmd);
+            let result = cmd
                 .stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
@@ -118,8 +241,10 @@ impl EditorManager for EmacsManager {
                 .chain(args.iter().map(|s| s.as_str()))
                 .collect();
 
-            let result = Command::new("emacsclient")
-                .args(&emacsclient_args)
+            let mut cmd = Command::new("emacsclient");
+            cmd.args(&emacsclient_args);
+            normalize_launch_env(&mut cmd);
+            let result = cmd
                 .stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
@@ -134,9 +259,10 @@ impl EditorManager for EmacsManager {
 
             let emacs_args: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
 
-            Command::new("emacs")
-                .args(&emacs_args)
-                .stdin(Stdio::null())
+            let mut cmd = Command::new("emacs");
+            cmd.args(&emacs_args);
+            normalize_launch_env(&mut cmd);
+            cmd.stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .spawn()
@@ -152,8 +278,10 @@ impl EditorManager for EmacsManager {
             debug!("Trying runemacs on Windows");
             let runemacs_args: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
 
-            let result = Command::new("runemacs")
-                .args(&runemacs_args)
+            let mut cmd = Command::new("runemacs");
+            cmd.args(&runemacs_args);
+            normalize_launch_env(&mut cmd);
+            let result = cmd
                 .stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
@@ -168,9 +296,10 @@ impl EditorManager for EmacsManager {
 
             let emacs_args: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
 
-            Command::new("emacs")
-                .args(&emacs_args)
-                .stdin(Stdio::null())
+            let mut cmd = Command::new("emacs");
+            cmd.args(&emacs_args);
+            normalize_launch_env(&mut cmd);
+            cmd.stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .spawn()
@@ -188,8 +317,10 @@ impl EditorManager for EmacsManager {
             let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
             client_args.extend(args_str);
 
-            let result = Command::new("emacsclient")
-                .args(&client_args)
+            let mut cmd = Command::new("emacsclient");
+            cmd.args(&client_args);
+            normalize_launch_env(&mut cmd);
+            let result = cmd
                 .stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
@@ -204,8 +335,10 @@ impl EditorManager for EmacsManager {
 
             let emacs_args: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
 
-            let result = Command::new("emacs")
-                .args(&emacs_args)
+            let mut cmd = Command::new("emacs");
+            cmd.args(&emacs_args);
+            normalize_launch_env(&mut cmd);
+            let result = cmd
                 .stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
@@ -222,9 +355,10 @@ impl EditorManager for EmacsManager {
             let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
             terminal_args.extend(args_str);
 
-            Command::new("gnome-terminal")
-                .args(&terminal_args)
-                .stdin(Stdio::null())
+            let mut cmd = Command::new("gnome-terminal");
+            cmd.args(&terminal_args);
+            normalize_launch_env(&mut cmd);
+            cmd.stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .spawn()
@@ -237,12 +371,37 @@ impl EditorManager for EmacsManager {
     }
 
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
+        let sockets = self.gather_emacs_sockets().await;
+        if !sockets.is_empty() {
+            let mut instances = Vec::new();
+            for socket in &sockets {
+                let pid = self
+                    .eval_on_server(socket, "(emacs-pid)")
+                    .await
+                    .and_then(|pid| pid.parse::<u32>().ok());
+                let Some(pid) = pid else { continue };
+
+                let workspace = self.eval_on_server(socket, "default-directory").await;
+                let window_title = self.eval_on_server(socket, "(buffer-name)").await;
+
+                instances.push(EditorInstance {
+                    pid,
+                    workspace,
+                    window_title,
+                });
+            }
+
+            if !instances.is_empty() {
+                return Ok(instances);
+            }
+        }
+
         #[cfg(target_os = "windows")]
         {
-            let output = Command::new("tasklist")
-                .output()
-                .map_err(|e| EditorError::Other(e.to_string()))?;
-
+            let cmd = tokio::process::Command::new("tasklist");
+            let Some(output) = output_with_timeout(cmd).await else {
+                return Ok(Vec::new());
+            };
             if !output.status.success() {
                 return Ok(Vec::new());
             }
@@ -261,11 +420,11 @@ impl EditorManager for EmacsManager {
 
         #[cfg(target_os = "macos")]
         {
-            let output = Command::new("ps")
-                .arg("aux")
-                .output()
-                .map_err(|e| EditorError::Other(e.to_string()))?;
-
+            let mut cmd = tokio::process::Command::new("ps");
+            cmd.arg("aux");
+            let Some(output) = output_with_timeout(cmd).await else {
+                return Ok(Vec::new());
+            };
             if !output.status.success() {
                 return Ok(Vec::new());
             }
@@ -287,11 +446,11 @@ impl EditorManager for EmacsManager {
 
         #[cfg(target_os = "linux")]
         {
-            let output = Command::new("ps")
-                .arg("aux")
-                .output()
-                .map_err(|e| EditorError::Other(e.to_string()))?;
-
+            let mut cmd = tokio::process::Command::new("ps");
+            cmd.arg("aux");
+            let Some(output) = output_with_timeout(cmd).await else {
+                return Ok(Vec::new());
+            };
             if !output.status.success() {
                 return Ok(Vec::new());
             }
@@ -0,0 +1,182 @@
+use super::super::launch::build_launch_command;
+use super::super::process_async::output_with_timeout;
+use super::super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Fallback used when neither `$VISUAL` nor `$EDITOR` is set, matching the
+/// default most POSIX shells assume.
+const DEFAULT_EDITOR: &str = "vi";
+
+/// Last-resort `EditorManager` for headless/SSH sessions with no GUI editor
+/// installed. Resolves `$VISUAL`, then `$EDITOR`, then `vi`, and runs it
+/// attached to the controlling TTY (rather than spawning a detached window
+/// the way the other terminal managers do), so `dispatcher.open` still
+/// succeeds when nothing else is registered.
+pub struct TerminalEditorManager;
+
+impl TerminalEditorManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The configured editor command plus any arguments the user baked into
+    /// `$VISUAL`/`$EDITOR` (e.g. `EDITOR="emacsclient -t"`).
+    fn configured_command() -> (String, Vec<String>) {
+        let raw = std::env::var("VISUAL")
+            .or_else(|_| std::env::var("EDITOR"))
+            .unwrap_or_else(|_| DEFAULT_EDITOR.to_string());
+
+        let mut parts = raw.split_whitespace();
+        let command = parts.next().unwrap_or(DEFAULT_EDITOR).to_string();
+        let args = parts.map(String::from).collect();
+        (command, args)
+    }
+
+    /// Translates `options.line`/`options.column` into the resolved editor's
+    /// jump syntax. Editors not recognized here just get the bare path.
+    fn jump_args(command: &str, path: &Path, options: &OpenOptions) -> Vec<String> {
+        let Some(line) = options.line else {
+            return vec![path.display().to_string()];
+        };
+
+        let name = Path::new(command)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(command);
+
+        match name {
+            "code" | "code-insiders" => vec![
+                "-g".to_string(),
+                format!("{}:{}:{}", path.display(), line, options.column.unwrap_or(1)),
+            ],
+            "vim" | "nvim" | "emacs" | "emacsclient" => vec![
+                format!("+{}:{}", line, options.column.unwrap_or(1)),
+                path.display().to_string(),
+            ],
+            _ => vec![format!("+{}", line), path.display().to_string()],
+        }
+    }
+}
+
+#[async_trait]
+impl EditorManager for TerminalEditorManager {
+    fn id(&self) -> &str {
+        "terminal"
+    }
+
+    fn display_name(&self) -> &str {
+        "Terminal ($EDITOR)"
+    }
+
+    fn supports_folders(&self) -> bool {
+        false
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain", "text/"]
+    }
+
+    async fn find_binary(&self) -> Option<PathBuf> {
+        let (command, _) = Self::configured_command();
+
+        let candidate = PathBuf::from(&command);
+        if candidate.is_absolute() && candidate.exists() {
+            return Some(candidate);
+        }
+
+        let mut cmd = tokio::process::Command::new("which");
+        cmd.arg(&command);
+        let output = output_with_timeout(cmd).await?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if path_str.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(path_str))
+        }
+    }
+
+    async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
+        let binary = self
+            .find_binary()
+            .await
+            .ok_or(EditorError::BinaryNotFound)?;
+
+        let (command, mut args) = Self::configured_command();
+        args.extend(Self::jump_args(&command, path, options));
+
+        debug!(
+            "Launching {} attached to the controlling TTY: {:?}",
+            binary.display(),
+            args
+        );
+
+        // The user may sit in this editor for as long as they like, so the
+        // blocking wait for it to exit runs on a blocking-pool thread
+        // rather than tying up a tokio worker for the whole session.
+        let status = tokio::task::spawn_blocking(move || {
+            build_launch_command(&binary, &args).status()
+        })
+        .await
+        .map_err(|e| EditorError::LaunchFailed(e.to_string()))?
+        .map_err(|e| EditorError::LaunchFailed(e.to_string()))?;
+
+        if !status.success() {
+            return Err(EditorError::LaunchFailed(format!(
+                "{} exited with {}",
+                command, status
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vim_jump_args_include_line_and_column() {
+        let options = OpenOptions {
+            line: Some(42),
+            column: Some(7),
+            ..Default::default()
+        };
+        assert_eq!(
+            TerminalEditorManager::jump_args("vim", Path::new("/tmp/file.rs"), &options),
+            vec!["+42:7".to_string(), "/tmp/file.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn code_jump_args_use_goto_flag() {
+        let options = OpenOptions {
+            line: Some(10),
+            column: None,
+            ..Default::default()
+        };
+        assert_eq!(
+            TerminalEditorManager::jump_args("code", Path::new("/tmp/file.rs"), &options),
+            vec!["-g".to_string(), "/tmp/file.rs:10:1".to_string()]
+        );
+    }
+
+    #[test]
+    fn unknown_editor_without_line_just_gets_the_path() {
+        let options = OpenOptions::default();
+        assert_eq!(
+            TerminalEditorManager::jump_args("nano", Path::new("/tmp/file.rs"), &options),
+            vec!["/tmp/file.rs".to_string()]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use super::super::binary_locator::BinaryLocator;
+use super::super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
+use super::terminal_detector::TerminalApp;
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+pub struct HelixManager;
+
+impl HelixManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl EditorManager for HelixManager {
+    fn id(&self) -> &str {
+        "helix"
+    }
+
+    fn display_name(&self) -> &str {
+        "Helix"
+    }
+
+    fn supports_folders(&self) -> bool {
+        true
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain", "text/"]
+    }
+
+    async fn find_binary(&self) -> Option<PathBuf> {
+        BinaryLocator::find("hx").await
+    }
+
+    async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
+        self.find_binary()
+            .await
+            .ok_or(EditorError::BinaryNotFound)?;
+
+        // Helix takes the jump target as part of the path argument itself
+        // (`hx file:line:col`) rather than a separate `+line` flag like
+        // vim/kakoune.
+        let file_arg = match (options.line, options.column) {
+            (Some(line), Some(column)) => format!("{}:{}:{}", path.display(), line, column),
+            (Some(line), None) => format!("{}:{}", path.display(), line),
+            _ => path.display().to_string(),
+        };
+
+        debug!("Opening helix with arg: {}", file_arg);
+
+        let terminal_pref = options.terminal_preference.as_deref();
+        if let Some(terminal) = TerminalApp::detect_installed_with_preference(terminal_pref) {
+            debug!("Using terminal: {:?}", terminal);
+            terminal
+                .launch_editor(
+                    "hx",
+                    &[file_arg],
+                    options.working_directory.as_deref(),
+                    &options.env,
+                    options.detached,
+                )
+                .map_err(|e| EditorError::LaunchFailed(e))?;
+        } else {
+            return Err(EditorError::Other(
+                "No terminal emulator found. Please install iTerm2, Alacritty, or another terminal.".to_string()
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
+        Ok(Vec::new())
+    }
+}
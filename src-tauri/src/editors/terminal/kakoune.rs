@@ -1,8 +1,8 @@
+use super::super::binary_locator::BinaryLocator;
 use super::super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
 use super::terminal_detector::TerminalApp;
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use tracing::debug;
 
 pub struct KakouneManager;
@@ -23,50 +23,12 @@ impl EditorManager for KakouneManager {
         "Kakoune"
     }
 
-    async fn find_binary(&self) -> Option<PathBuf> {
-        #[cfg(target_os = "macos")]
-        {
-            let candidates = vec![
-                PathBuf::from("/opt/homebrew/bin/kak"),
-                PathBuf::from("/usr/local/bin/kak"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found kak at {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
-
-        #[cfg(target_os = "linux")]
-        {
-            let candidates = vec![
-                PathBuf::from("/usr/bin/kak"),
-                PathBuf::from("/usr/local/bin/kak"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found kak at {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain", "text/"]
+    }
 
-        if let Ok(output) = Command::new("which").arg("kak").output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    let path = PathBuf::from(&path_str);
-                    if path.exists() {
-                        debug!("Found kak via which: {:?}", path);
-                        return Some(path);
-                    }
-                }
-            }
-        }
-        None
+    async fn find_binary(&self) -> Option<PathBuf> {
+        BinaryLocator::find("kak").await
     }
 
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
@@ -92,7 +54,13 @@ impl EditorManager for KakouneManager {
         if let Some(terminal) = TerminalApp::detect_installed_with_preference(terminal_pref) {
             debug!("Using terminal: {:?}", terminal);
             terminal
-                .launch_editor("kak", &kak_args)
+                .launch_editor(
+                    "kak",
+                    &kak_args,
+                    options.working_directory.as_deref(),
+                    &options.env,
+                    options.detached,
+                )
                 .map_err(|e| EditorError::LaunchFailed(e))?;
         } else {
             return Err(EditorError::Other(
@@ -1,8 +1,8 @@
+use super::super::binary_locator::BinaryLocator;
 use super::super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
 use super::terminal_detector::TerminalApp;
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use tracing::debug;
 
 pub struct MicroManager;
@@ -23,51 +23,12 @@ impl EditorManager for MicroManager {
         "Micro"
     }
 
-    async fn find_binary(&self) -> Option<PathBuf> {
-        #[cfg(target_os = "macos")]
-        {
-            let candidates = vec![
-                PathBuf::from("/opt/homebrew/bin/micro"),
-                PathBuf::from("/usr/local/bin/micro"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found micro at {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
-
-        #[cfg(target_os = "linux")]
-        {
-            let candidates = vec![
-                PathBuf::from("/usr/bin/micro"),
-                PathBuf::from("/usr/local/bin/micro"),
-                PathBuf::from("/snap/bin/micro"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found micro at {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain", "text/"]
+    }
 
-        if let Ok(output) = Command::new("which").arg("micro").output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    let path = PathBuf::from(&path_str);
-                    if path.exists() {
-                        debug!("Found micro via which: {:?}", path);
-                        return Some(path);
-                    }
-                }
-            }
-        }
-        None
+    async fn find_binary(&self) -> Option<PathBuf> {
+        BinaryLocator::find("micro").await
     }
 
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
@@ -93,7 +54,13 @@ impl EditorManager for MicroManager {
         if let Some(terminal) = TerminalApp::detect_installed_with_preference(terminal_pref) {
             debug!("Using terminal: {:?}", terminal);
             terminal
-                .launch_editor("micro", &micro_args)
+                .launch_editor(
+                    "micro",
+                    &micro_args,
+                    options.working_directory.as_deref(),
+                    &options.env,
+                    options.detached,
+                )
                 .map_err(|e| EditorError::LaunchFailed(e))?;
         } else {
             return Err(EditorError::Other(
@@ -1,14 +1,22 @@
 mod emacs;
+mod env_editor;
+mod helix;
 mod kakoune;
 mod micro;
 mod nano;
 mod neovim;
+mod nvim_rpc;
 mod terminal_detector;
+mod variant;
 mod vim;
+mod xdg_terminal;
 
 pub use emacs::EmacsManager;
+pub use env_editor::TerminalEditorManager;
+pub use helix::HelixManager;
 pub use kakoune::KakouneManager;
 pub use micro::MicroManager;
 pub use nano::NanoManager;
 pub use neovim::NeovimManager;
+pub use terminal_detector::TerminalApp;
 pub use vim::VimManager;
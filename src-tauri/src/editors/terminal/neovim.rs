@@ -1,15 +1,42 @@
-use super::super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
+use super::super::launch::{build_launch_command, detach_command};
+use super::super::traits::{
+    EditorError, EditorInstance, EditorManager, EditorResult, EditorVariant, OpenOptions,
+    ReuseHandle,
+};
+use super::nvim_rpc::{self, ConnectionPool};
 use super::terminal_detector::TerminalApp;
+use super::variant::{VariantCandidate, VariantResolver};
 use async_trait::async_trait;
+use futures::future::join_all;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use tracing::debug;
 
-pub struct NeovimManager;
+/// Priority order: `nvim-qt` is a standalone GUI application, falling back
+/// to plain terminal `nvim`.
+const VARIANT_CANDIDATES: &[VariantCandidate] = &[
+    VariantCandidate {
+        id: "nvim-qt",
+        display_name: "Neovim Qt",
+        binary_names: &["nvim-qt"],
+    },
+    VariantCandidate {
+        id: "nvim",
+        display_name: "Neovim",
+        binary_names: &["nvim"],
+    },
+];
+
+pub struct NeovimManager {
+    resolver: VariantResolver,
+    pool: ConnectionPool,
+}
 
 impl NeovimManager {
     pub fn new() -> Self {
-        Self
+        Self {
+            resolver: VariantResolver::new(VARIANT_CANDIDATES.to_vec()),
+            pool: ConnectionPool::new(),
+        }
     }
 
     async fn find_nvim_socket(&self, target_path: &Path) -> Option<PathBuf> {
@@ -20,49 +47,42 @@ impl NeovimManager {
 
         let target = target_path.canonicalize().ok()?;
 
-        for socket in &sockets {
-            if let Some(cwd) = self.get_nvim_cwd(socket).await {
+        // One concurrent `getcwd` round-trip per socket instead of a serial
+        // scan, so selecting the right instance among several running ones
+        // costs a single await rather than N queries back-to-back.
+        let queries = sockets
+            .iter()
+            .map(|socket| async move { (socket, nvim_rpc::query_socket(&self.pool, socket).await) });
+        let results = join_all(queries).await;
+
+        // Longest-prefix match against each instance's cwd, so a path under
+        // a nested workspace prefers the nvim actually running there over
+        // one rooted further up the tree.
+        let mut best: Option<(&PathBuf, usize)> = None;
+        for (socket, metadata) in results {
+            if let Some(cwd) = metadata.and_then(|m| m.cwd) {
                 if target.starts_with(&cwd) {
-                    return Some(socket.clone());
-                }
-            }
-        }
-
-        sockets.first().cloned()
-    }
-
-    fn search_dir_for_sockets(
-        &self,
-        dir: &Path,
-        sockets: &mut Vec<PathBuf>,
-        depth: usize,
-        max_depth: usize,
-    ) {
-        if depth >= max_depth {
-            return;
-        }
-
-        if let Ok(entries) = std::fs::read_dir(dir) {
-            for entry in entries.filter_map(|e| e.ok()) {
-                let path = entry.path();
-                if let Ok(metadata) = std::fs::metadata(&path) {
-                    #[cfg(unix)]
-                    {
-                        use std::os::unix::fs::FileTypeExt;
-                        if metadata.file_type().is_socket() {
-                            debug!("Found nvim socket at depth {}: {:?}", depth, path);
-                            sockets.push(path);
-                        } else if metadata.is_dir() {
-                            debug!("Searching subdirectory at depth {}: {:?}", depth, path);
-                            self.search_dir_for_sockets(&path, sockets, depth + 1, max_depth);
-                        }
+                    let len = cwd.as_os_str().len();
+                    let is_better = match best {
+                        Some((_, best_len)) => len > best_len,
+                        None => true,
+                    };
+                    if is_better {
+                        best = Some((socket, len));
                     }
                 }
             }
         }
+
+        best.map(|(socket, _)| socket.clone())
+            .or_else(|| sockets.first().cloned())
     }
 
-    async fn gather_nvim_sockets(&self) -> Vec<PathBuf> {
+    /// The actual directory walk, run off the async runtime via
+    /// `spawn_blocking` in [`Self::gather_nvim_sockets`] since
+    /// `std::fs::read_dir` and friends block the calling thread.
+    #[cfg(unix)]
+    fn scan_for_sockets_blocking() -> Vec<PathBuf> {
         use std::env;
 
         let mut sockets = Vec::new();
@@ -83,19 +103,16 @@ impl NeovimManager {
                         if name.contains("nvim") {
                             debug!("Found nvim-related item: {:?}", path);
                             if let Ok(metadata) = std::fs::metadata(&path) {
-                                #[cfg(unix)]
-                                {
-                                    use std::os::unix::fs::FileTypeExt;
-                                    if metadata.file_type().is_socket() {
-                                        debug!("Found nvim socket: {:?}", path);
-                                        sockets.push(path);
-                                    } else if metadata.is_dir() {
-                                        debug!(
-                                            "Found nvim directory, searching inside: {:?}",
-                                            path
-                                        );
-                                        self.search_dir_for_sockets(&path, &mut sockets, 0, 2);
-                                    }
+                                use std::os::unix::fs::FileTypeExt;
+                                if metadata.file_type().is_socket() {
+                                    debug!("Found nvim socket: {:?}", path);
+                                    sockets.push(path);
+                                } else if metadata.is_dir() {
+                                    debug!(
+                                        "Found nvim directory, searching inside: {:?}",
+                                        path
+                                    );
+                                    Self::search_dir_for_sockets(&path, &mut sockets, 0, 2);
                                 }
                             }
                         }
@@ -108,25 +125,66 @@ impl NeovimManager {
         sockets
     }
 
-    async fn get_nvim_cwd(&self, socket: &Path) -> Option<PathBuf> {
-        let binary = self.find_binary().await?;
-
-        let output = Command::new(&binary)
-            .arg("--server")
-            .arg(socket)
-            .arg("--remote-expr")
-            .arg("getcwd()")
-            .output()
-            .ok()?;
-
-        if output.status.success() {
-            let cwd_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !cwd_str.is_empty() {
-                return Some(PathBuf::from(cwd_str));
+    /// Neovim's RPC server has no filesystem socket to scan for on Windows -
+    /// it listens on a named pipe under the `\\.\pipe\` namespace instead.
+    /// `std::fs::read_dir` happens to work against that namespace the same
+    /// way it would a directory (it goes through `FindFirstFileW`/
+    /// `FindNextFileW` under the hood), so no extra crate is needed to
+    /// enumerate it - just a different root than the Unix tmp-dir scan and a
+    /// name-prefix filter instead of an `is_socket()` check.
+    #[cfg(windows)]
+    fn scan_for_sockets_blocking() -> Vec<PathBuf> {
+        let mut sockets = Vec::new();
+
+        debug!(r"Enumerating nvim named pipes in \\.\pipe\");
+        if let Ok(entries) = std::fs::read_dir(r"\\.\pipe\") {
+            for entry in entries.filter_map(|e| e.ok()) {
+                if let Some(name) = entry.file_name().to_str().map(str::to_string) {
+                    if name.starts_with("nvim") {
+                        let pipe_path = PathBuf::from(format!(r"\\.\pipe\{}", name));
+                        debug!("Found nvim named pipe: {:?}", pipe_path);
+                        sockets.push(pipe_path);
+                    }
+                }
+            }
+        }
+
+        debug!("Total nvim named pipes found: {}", sockets.len());
+        sockets
+    }
+
+    #[cfg(unix)]
+    fn search_dir_for_sockets(
+        dir: &Path,
+        sockets: &mut Vec<PathBuf>,
+        depth: usize,
+        max_depth: usize,
+    ) {
+        if depth >= max_depth {
+            return;
+        }
+
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if let Ok(metadata) = std::fs::metadata(&path) {
+                    use std::os::unix::fs::FileTypeExt;
+                    if metadata.file_type().is_socket() {
+                        debug!("Found nvim socket at depth {}: {:?}", depth, path);
+                        sockets.push(path);
+                    } else if metadata.is_dir() {
+                        debug!("Searching subdirectory at depth {}: {:?}", depth, path);
+                        Self::search_dir_for_sockets(&path, sockets, depth + 1, max_depth);
+                    }
+                }
             }
         }
+    }
 
-        None
+    async fn gather_nvim_sockets(&self) -> Vec<PathBuf> {
+        tokio::task::spawn_blocking(Self::scan_for_sockets_blocking)
+            .await
+            .unwrap_or_default()
     }
 }
 
@@ -144,106 +202,45 @@ impl EditorManager for NeovimManager {
         true
     }
 
-    async fn find_binary(&self) -> Option<PathBuf> {
-        #[cfg(target_os = "macos")]
-        {
-            let candidates = vec![
-                PathBuf::from("/opt/homebrew/bin/nvim"),
-                PathBuf::from("/usr/local/bin/nvim"),
-                PathBuf::from("/usr/bin/nvim"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found nvim at {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain", "text/"]
+    }
 
-        #[cfg(target_os = "linux")]
-        {
-            let candidates = vec![
-                PathBuf::from("/usr/bin/nvim"),
-                PathBuf::from("/usr/local/bin/nvim"),
-                PathBuf::from("/snap/bin/nvim"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found nvim at {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
+    async fn find_binary(&self) -> Option<PathBuf> {
+        self.resolver.resolve(None).await.map(|v| v.binary_path)
+    }
 
-        if let Ok(output) = Command::new("which").arg("nvim").output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    let path = PathBuf::from(&path_str);
-                    if path.exists() {
-                        debug!("Found nvim via which: {:?}", path);
-                        return Some(path);
-                    }
-                }
-            }
-        }
-        None
+    async fn variants(&self) -> Vec<EditorVariant> {
+        self.resolver
+            .variants()
+            .await
+            .into_iter()
+            .map(EditorVariant::from)
+            .collect()
     }
 
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
         tracing::info!("[NVIM-DEBUG] open() called for path: {:?}", path);
-        let binary = self
-            .find_binary()
+        let variant = self
+            .resolver
+            .resolve(options.preferred_variant.as_deref())
             .await
             .ok_or(EditorError::BinaryNotFound)?;
-        tracing::info!("[NVIM-DEBUG] Found binary: {:?}", binary);
+        tracing::info!("[NVIM-DEBUG] Found binary: {:?}", variant.binary_path);
 
-        if let Some(socket) = self.find_nvim_socket(path).await {
+        if let Some(handle) = self.find_reusable_instance(path).await {
             tracing::info!(
-                "[NVIM-DEBUG] Found nvim socket: {:?}, trying to reuse",
-                socket
+                "[NVIM-DEBUG] Found reusable nvim instance, trying to reuse via RPC"
             );
 
-            let path_str = path.display().to_string();
-            let escaped_path = path_str.replace('\\', "\\\\").replace(' ', "\\ ");
-            let keys = match (options.line, options.column) {
-                (Some(line), Some(column)) => {
-                    format!(
-                        ":e {}<CR>:call cursor({},{})<CR>",
-                        escaped_path, line, column
-                    )
-                }
-                (Some(line), None) => {
-                    format!(":{}<CR>:e {}<CR>", line, escaped_path)
-                }
-                _ => {
-                    format!(":e {}<CR>", escaped_path)
-                }
-            };
-
-            tracing::info!("[NVIM-DEBUG] Sending keys to socket: {}", keys);
-            let result = Command::new(&binary)
-                .arg("--server")
-                .arg(&socket)
-                .arg("--remote-send")
-                .arg(&keys)
-                .output();
-
-            match result {
-                Ok(output) if output.status.success() => {
-                    tracing::info!("[NVIM-DEBUG] Successfully sent file to existing nvim instance");
+            match self.open_in(handle, path, options).await {
+                Ok(()) => {
+                    tracing::info!("[NVIM-DEBUG] Successfully opened file in existing nvim instance via RPC");
                     return Ok(());
                 }
-                Ok(output) => {
-                    tracing::info!(
-                        "[NVIM-DEBUG] Failed to send to nvim socket, status: {:?}, stderr: {:?}",
-                        output.status,
-                        String::from_utf8_lossy(&output.stderr)
-                    );
+                Err(e) => {
+                    tracing::info!("[NVIM-DEBUG] Failed to open via nvim RPC, falling back to spawning a new instance: {}", e);
                 }
-                Err(e) => tracing::info!("[NVIM-DEBUG] Error sending to nvim socket: {}", e),
             }
         } else {
             tracing::info!("[NVIM-DEBUG] No nvim socket found");
@@ -264,28 +261,73 @@ impl EditorManager for NeovimManager {
         }
         nvim_args.push(path.display().to_string());
 
-        debug!("Spawning nvim with args: {:?}", nvim_args);
+        debug!(
+            "Spawning {} ({}) with args: {:?}",
+            variant.display_name, variant.id, nvim_args
+        );
 
-        let terminal_pref = options.terminal_preference.as_deref();
-        if let Some(terminal) = TerminalApp::detect_installed_with_preference(terminal_pref) {
-            tracing::info!("[NVIM-DEBUG] Using terminal: {:?}", terminal);
-            terminal.launch_editor("nvim", &nvim_args).map_err(|e| {
-                tracing::error!("[NVIM-DEBUG] Terminal launch failed: {}", e);
-                EditorError::LaunchFailed(e)
-            })?;
-            tracing::info!("[NVIM-DEBUG] Terminal launch succeeded");
+        if variant.id == "nvim-qt" {
+            let mut cmd = build_launch_command(&variant.binary_path, &nvim_args);
+            if options.detached {
+                detach_command(&mut cmd);
+            }
+            cmd.spawn()
+                .map_err(|e| {
+                    tracing::error!("[NVIM-DEBUG] nvim-qt launch failed: {}", e);
+                    EditorError::LaunchFailed(e.to_string())
+                })?;
+            tracing::info!("[NVIM-DEBUG] nvim-qt launch succeeded");
         } else {
-            tracing::error!("[NVIM-DEBUG] No terminal emulator found");
-            return Err(EditorError::Other(
-                "No terminal emulator found. Please install iTerm2, Alacritty, or another terminal.".to_string()
-            ));
+            let terminal_pref = options.terminal_preference.as_deref();
+            if let Some(terminal) = TerminalApp::detect_installed_with_preference(terminal_pref) {
+                tracing::info!("[NVIM-DEBUG] Using terminal: {:?}", terminal);
+                terminal
+                    .launch_editor(
+                        "nvim",
+                        &nvim_args,
+                        options.working_directory.as_deref(),
+                        &options.env,
+                        options.detached,
+                    )
+                    .map_err(|e| {
+                        tracing::error!("[NVIM-DEBUG] Terminal launch failed: {}", e);
+                        EditorError::LaunchFailed(e)
+                    })?;
+                tracing::info!("[NVIM-DEBUG] Terminal launch succeeded");
+            } else {
+                tracing::error!("[NVIM-DEBUG] No terminal emulator found");
+                return Err(EditorError::Other(
+                    "No terminal emulator found. Please install iTerm2, Alacritty, or another terminal.".to_string()
+                ));
+            }
         }
 
         tracing::info!("[NVIM-DEBUG] open() completed successfully");
         Ok(())
     }
 
+    async fn find_reusable_instance(&self, path: &Path) -> Option<ReuseHandle> {
+        self.find_nvim_socket(path).await.map(ReuseHandle::NvimSocket)
+    }
+
+    async fn open_in(&self, handle: ReuseHandle, path: &Path, options: &OpenOptions) -> EditorResult<()> {
+        let ReuseHandle::NvimSocket(socket) = handle;
+        nvim_rpc::open_via_rpc(&self.pool, &socket, path, options.line).await
+    }
+
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
-        Ok(Vec::new())
+        let mut instances = Vec::new();
+
+        for socket in self.gather_nvim_sockets().await {
+            if let Some(metadata) = nvim_rpc::query_socket(&self.pool, &socket).await {
+                instances.push(EditorInstance {
+                    pid: metadata.pid,
+                    workspace: metadata.cwd.map(|cwd| cwd.to_string_lossy().into_owned()),
+                    window_title: (!metadata.buffers.is_empty()).then(|| metadata.buffers.join(", ")),
+                });
+            }
+        }
+
+        Ok(instances)
     }
 }
@@ -0,0 +1,227 @@
+//! A thin MessagePack-RPC client for driving an already-running Neovim
+//! instance through its real API, in place of the `--remote-send` keystroke
+//! injection `NeovimManager::open` used to rely on (which silently
+//! corrupted when the target buffer was in insert/visual mode, a modal
+//! prompt was open, or the path contained quotes or unicode).
+//!
+//! Built on `nvim-rs`, which connects the socket discovered by
+//! `NeovimManager::find_nvim_socket` - a Unix domain socket, or on Windows
+//! a named pipe under `\\.\pipe\` - as a `Compat`-wrapped writer and hands
+//! back a typed `Neovim` handle; responses are matched to requests by msgid
+//! over the `[0, msgid, method, params]` RPC framing internally, so callers
+//! just await typed futures. [`ConnectionPool`] keeps one of these handles
+//! alive per socket so a scan across several running instances reuses the
+//! same connections instead of dialing fresh ones for every query.
+
+use super::super::traits::{EditorError, EditorResult};
+use async_trait::async_trait;
+use nvim_rs::compat::tokio::Compat;
+use nvim_rs::{create::tokio as create, Handler, Neovim};
+use parking_lot::RwLock;
+use rmpv::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+#[cfg(unix)]
+use tokio::net::UnixStream;
+#[cfg(windows)]
+use tokio::net::windows::named_pipe::NamedPipeClient;
+
+/// The RPC transport a socket path is dialed over - a Unix domain socket
+/// everywhere but Windows, where Neovim instead listens on a named pipe
+/// under `\\.\pipe\` (see `NeovimManager::scan_for_sockets_blocking`).
+#[cfg(unix)]
+type Transport = Compat<UnixStream>;
+#[cfg(windows)]
+type Transport = Compat<NamedPipeClient>;
+
+/// `nvim-rs` requires a `Handler` for notifications/requests Neovim sends
+/// back to us - Sorcery only ever drives Neovim one-way, so this just
+/// satisfies the trait with no-ops.
+#[derive(Clone)]
+struct NoopHandler;
+
+#[async_trait]
+impl Handler for NoopHandler {
+    type Writer = Transport;
+
+    async fn handle_request(
+        &self,
+        _name: String,
+        _args: Vec<Value>,
+        _neovim: Neovim<Self::Writer>,
+    ) -> Result<Value, Value> {
+        Err(Value::from("Sorcery does not handle nvim requests"))
+    }
+}
+
+type NvimHandle = Neovim<Transport>;
+
+/// Caches one live msgpack-RPC connection per socket path, keyed the same
+/// way `EditorRegistry` keys its manager map. `find_nvim_socket` and
+/// `get_running_instances` both query every discovered socket in a single
+/// pass, so reusing the connection turns that into one dial per socket for
+/// the lifetime of the pool instead of one per query.
+pub struct ConnectionPool {
+    connections: RwLock<HashMap<PathBuf, NvimHandle>>,
+}
+
+impl ConnectionPool {
+    pub fn new() -> Self {
+        Self {
+            connections: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cached connection for `socket`, dialing a new one if
+    /// there isn't one yet. The reader task `nvim-rs` spawns alongside the
+    /// handle is left detached - it keeps running for as long as the
+    /// connection stays in the pool, which is the point of pooling it.
+    async fn connect(&self, socket: &Path) -> Option<NvimHandle> {
+        if let Some(nvim) = self.connections.read().get(socket) {
+            return Some(nvim.clone());
+        }
+
+        #[cfg(unix)]
+        let dialed = create::new_unix_socket(socket, NoopHandler).await;
+        #[cfg(windows)]
+        let dialed = create::new_named_pipe(socket, NoopHandler).await;
+
+        let (nvim, _io_handle) = dialed.ok()?;
+        self.connections.write().insert(socket.to_path_buf(), nvim.clone());
+        Some(nvim)
+    }
+
+    /// Drops a cached connection so the next `connect` call dials fresh -
+    /// used once a request against it comes back as a transport-level
+    /// failure rather than an RPC error, meaning the socket itself (not
+    /// just the last call) has gone stale.
+    fn evict(&self, socket: &Path) {
+        self.connections.write().remove(socket);
+    }
+}
+
+/// Opens `path` in the Neovim instance listening on `socket` via
+/// `nvim_command`/`nvim_win_set_cursor` instead of synthetic keystrokes,
+/// then raises and centers the window - deterministic regardless of
+/// whatever mode the buffer was already in.
+pub async fn open_via_rpc(
+    pool: &ConnectionPool,
+    socket: &Path,
+    path: &Path,
+    line: Option<usize>,
+) -> EditorResult<()> {
+    let Some(nvim) = pool.connect(socket).await else {
+        return Err(EditorError::Other(format!(
+            "Failed to connect to nvim socket: {:?}",
+            socket
+        )));
+    };
+
+    // `edit` is still a colon-command, so a bare path containing a space,
+    // `%`, or `#` would get misparsed as a second argument or an
+    // alternate-file reference - run it through Neovim's own
+    // `fnameescape()` first rather than hand-rolling the escaping rules.
+    let escaped_path = match nvim
+        .call_function("fnameescape", vec![Value::from(path.display().to_string())])
+        .await
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+    {
+        Some(escaped) => escaped,
+        None => {
+            pool.evict(socket);
+            return Err(EditorError::Other(
+                "fnameescape() failed while opening path in nvim".to_string(),
+            ));
+        }
+    };
+
+    if let Err(e) = nvim.command(&format!("edit {escaped_path}")).await {
+        pool.evict(socket);
+        return Err(EditorError::Other(format!("nvim_command(edit) failed: {e}")));
+    }
+
+    if let Some(line) = line {
+        match nvim.get_current_win().await {
+            Ok(window) => {
+                if let Err(e) = window.set_cursor((line as i64, 0)).await {
+                    tracing::warn!("nvim_win_set_cursor failed: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("nvim_get_current_win failed: {}", e),
+        }
+    }
+
+    // Raise and center the window the edit landed in, the same way the
+    // keystroke-based implementation's `<CR>` sequence used to leave the
+    // cursor line mid-screen.
+    if let Err(e) = nvim.command("normal! zz").await {
+        tracing::warn!("Failed to center window after opening in nvim: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Everything `find_nvim_socket`/`NeovimManager::get_running_instances` need
+/// to know about a live Neovim instance, gathered in one RPC round-trip per
+/// socket instead of the old shell-out-per-query `--remote-expr` approach.
+pub struct SocketMetadata {
+    pub pid: u32,
+    pub cwd: Option<PathBuf>,
+    /// Every listed buffer with a non-empty name, in `nvim_list_bufs` order -
+    /// not just the current one, so a socket editing several files reports
+    /// all of them rather than whichever happened to have focus.
+    pub buffers: Vec<String>,
+}
+
+/// How long a single socket gets to answer before it's treated as
+/// unreachable - a stale or wedged nvim must not stall the rest of the scan.
+const QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Connects to `socket` (reusing `pool`'s cached connection when there is
+/// one) and reads `getpid()`/`getcwd()`/the open buffer list through real
+/// API calls, returning `None` if the socket isn't a reachable Neovim
+/// instance (stale, another process squatting the path, or it didn't
+/// answer within [`QUERY_TIMEOUT`]).
+pub async fn query_socket(pool: &ConnectionPool, socket: &Path) -> Option<SocketMetadata> {
+    match tokio::time::timeout(QUERY_TIMEOUT, query_socket_inner(pool, socket)).await {
+        Ok(Some(metadata)) => Some(metadata),
+        Ok(None) => {
+            pool.evict(socket);
+            None
+        }
+        Err(_) => {
+            pool.evict(socket);
+            None
+        }
+    }
+}
+
+async fn query_socket_inner(pool: &ConnectionPool, socket: &Path) -> Option<SocketMetadata> {
+    let nvim = pool.connect(socket).await?;
+
+    let pid = nvim
+        .call_function("getpid", vec![])
+        .await
+        .ok()?
+        .as_u64()? as u32;
+
+    let cwd = nvim
+        .call_function("getcwd", vec![])
+        .await
+        .ok()
+        .and_then(|v| v.as_str().map(PathBuf::from));
+
+    let mut buffers = Vec::new();
+    if let Ok(bufs) = nvim.list_bufs().await {
+        for buf in bufs {
+            if let Ok(name) = buf.get_name().await {
+                if !name.is_empty() {
+                    buffers.push(name);
+                }
+            }
+        }
+    }
+
+    Some(SocketMetadata { pid, cwd, buffers })
+}
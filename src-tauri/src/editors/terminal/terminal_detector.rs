@@ -1,7 +1,12 @@
+use super::super::launch::{detach_command, normalize_launch_env};
+#[cfg(target_os = "linux")]
+use super::xdg_terminal;
+use super::xdg_terminal::XdgTerminal;
+use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
 use std::os::unix::fs::PermissionsExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::debug;
 
@@ -16,6 +21,11 @@ pub enum TerminalApp {
     GnomeTerminal, // Linux
     Konsole,       // Linux KDE
     Xterm,         // Linux fallback
+    /// A terminal discovered from an XDG `.desktop` entry (or the Debian
+    /// `x-terminal-emulator` alternative) rather than one of the variants
+    /// above - covers a user's installed terminal even when it isn't one we
+    /// hardcode.
+    Xdg(XdgTerminal),
 }
 
 impl TerminalApp {
@@ -34,6 +44,27 @@ impl TerminalApp {
         }
     }
 
+    /// Every name `from_string` recognizes (besides `"auto"`/`""`, which
+    /// mean "detect one"), for validating a configured
+    /// `defaults.preferred_terminal` against - see
+    /// `settings::validation::warn_unknown_references`. A name outside this
+    /// list isn't necessarily wrong: it may still match an XDG-discovered
+    /// terminal at runtime, which can't be enumerated statically.
+    pub fn known_preference_names() -> &'static [&'static str] {
+        &[
+            "iterm2",
+            "iterm",
+            "alacritty",
+            "kitty",
+            "wezterm",
+            "terminal",
+            "gnome-terminal",
+            "gnome",
+            "konsole",
+            "xterm",
+        ]
+    }
+
     #[cfg(target_os = "macos")]
     pub fn detect_installed_with_preference(preferred: Option<&str>) -> Option<Self> {
         // If user has a preference, check if it's installed first
@@ -103,6 +134,20 @@ impl TerminalApp {
                             terminal
                         );
                     }
+                } else if let Some(xdg_terminal) = xdg_terminal::scan_installed_terminals()
+                    .into_iter()
+                    .find(|t| t.name.eq_ignore_ascii_case(pref))
+                {
+                    debug!(
+                        "Using preferred terminal from XDG desktop entry: {}",
+                        xdg_terminal.name
+                    );
+                    return Some(Self::Xdg(xdg_terminal));
+                } else {
+                    debug!(
+                        "Preferred terminal '{}' not found among known or XDG-discovered terminals, falling back to auto-detect",
+                        pref
+                    );
                 }
             }
         }
@@ -123,6 +168,19 @@ impl TerminalApp {
             }
         }
 
+        if let Some(xdg_terminal) = xdg_terminal::debian_alternatives_terminal() {
+            debug!(
+                "Using Debian x-terminal-emulator alternative: {}",
+                xdg_terminal.name
+            );
+            return Some(Self::Xdg(xdg_terminal));
+        }
+
+        if let Some(xdg_terminal) = xdg_terminal::scan_installed_terminals().into_iter().next() {
+            debug!("Using XDG-discovered terminal: {}", xdg_terminal.name);
+            return Some(Self::Xdg(xdg_terminal));
+        }
+
         None
     }
 
@@ -135,6 +193,7 @@ impl TerminalApp {
             Self::GnomeTerminal => "gnome-terminal",
             Self::Konsole => "konsole",
             Self::Xterm => "xterm",
+            Self::Xdg(_) => return true,
             _ => return false,
         };
         Self::is_command_available(cmd)
@@ -149,41 +208,52 @@ impl TerminalApp {
             .unwrap_or(false)
     }
 
-    pub fn launch_editor(&self, editor: &str, args: &[String]) -> Result<(), String> {
+    pub fn launch_editor(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         debug!("Launching editor '{}' with args: {:?}", editor, args);
         match self {
             #[cfg(target_os = "macos")]
-            Self::ITerm2 => self.launch_via_script("iTerm", editor, args),
+            Self::ITerm2 => self.launch_via_script("iTerm", editor, args, working_directory, env, detached),
 
             #[cfg(target_os = "macos")]
-            Self::Terminal => self.launch_via_script("Terminal", editor, args),
+            Self::Terminal => self.launch_via_script("Terminal", editor, args, working_directory, env, detached),
 
             #[cfg(target_os = "macos")]
-            Self::Alacritty => self.launch_alacritty_macos_direct(editor, args),
+            Self::Alacritty => self.launch_alacritty_macos_direct(editor, args, working_directory, env, detached),
 
             #[cfg(target_os = "macos")]
-            Self::Kitty => self.launch_kitty_macos_direct(editor, args),
+            Self::Kitty => self.launch_kitty_macos_direct(editor, args, working_directory, env, detached),
 
             #[cfg(target_os = "macos")]
-            Self::WezTerm => self.launch_wezterm_macos_direct(editor, args),
+            Self::WezTerm => self.launch_wezterm_macos_direct(editor, args, working_directory, env, detached),
 
             #[cfg(target_os = "linux")]
-            Self::Alacritty => self.launch_alacritty_linux_direct(editor, args),
+            Self::Alacritty => self.launch_alacritty_linux_direct(editor, args, working_directory, env, detached),
 
             #[cfg(target_os = "linux")]
-            Self::Kitty => self.launch_kitty_linux_direct(editor, args),
+            Self::Kitty => self.launch_kitty_linux_direct(editor, args, working_directory, env, detached),
 
             #[cfg(target_os = "linux")]
-            Self::WezTerm => self.launch_wezterm_linux_direct(editor, args),
+            Self::WezTerm => self.launch_wezterm_linux_direct(editor, args, working_directory, env, detached),
 
             #[cfg(target_os = "linux")]
-            Self::GnomeTerminal => self.launch_gnome_terminal_direct(editor, args),
+            Self::GnomeTerminal => self.launch_gnome_terminal_direct(editor, args, working_directory, env, detached),
 
             #[cfg(target_os = "linux")]
-            Self::Konsole => self.launch_konsole_direct(editor, args),
+            Self::Konsole => self.launch_konsole_direct(editor, args, working_directory, env, detached),
 
             #[cfg(target_os = "linux")]
-            Self::Xterm => self.launch_xterm_direct(editor, args),
+            Self::Xterm => self.launch_xterm_direct(editor, args, working_directory, env, detached),
+
+            Self::Xdg(xdg_terminal) => {
+                xdg_terminal.launch_editor(editor, args, working_directory, env, detached)
+            }
 
             #[allow(unreachable_patterns)]
             _ => Err("Terminal not supported on this platform".to_string()),
@@ -191,7 +261,15 @@ impl TerminalApp {
     }
 
     #[cfg(target_os = "macos")]
-    fn launch_via_script(&self, app_name: &str, editor: &str, args: &[String]) -> Result<(), String> {
+    fn launch_via_script(
+        &self,
+        app_name: &str,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         use std::process::Stdio;
         use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -202,6 +280,18 @@ impl TerminalApp {
         let script_path = format!("/tmp/sorcery_launch_{}.sh", timestamp);
 
         let mut script_content = String::from("#!/bin/bash\n");
+        if let Some(dir) = working_directory {
+            script_content.push_str("cd ");
+            script_content.push_str(&shell_escape::escape(dir.to_string_lossy()));
+            script_content.push('\n');
+        }
+        for (key, value) in env {
+            script_content.push_str("export ");
+            script_content.push_str(key);
+            script_content.push('=');
+            script_content.push_str(&shell_escape::escape(value.into()));
+            script_content.push('\n');
+        }
         script_content.push_str(&shell_escape::escape(editor.into()));
         for arg in args {
             script_content.push(' ');
@@ -216,11 +306,19 @@ impl TerminalApp {
         fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755))
             .map_err(|e| format!("Failed to set script permissions: {}", e))?;
 
-        Command::new("open")
-            .arg("-a")
-            .arg(app_name)
-            .arg(&script_path)
-            .stdin(Stdio::null())
+        let mut cmd = Command::new("open");
+        cmd.arg("-a").arg(app_name).arg(&script_path);
+
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
+        cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .spawn()
@@ -230,7 +328,14 @@ impl TerminalApp {
     }
 
     #[cfg(target_os = "macos")]
-    fn launch_alacritty_macos_direct(&self, editor: &str, args: &[String]) -> Result<(), String> {
+    fn launch_alacritty_macos_direct(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         use std::process::Stdio;
 
         let mut cmd = Command::new("open");
@@ -245,6 +350,15 @@ impl TerminalApp {
             cmd.arg(arg);
         }
 
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
         cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -255,7 +369,14 @@ impl TerminalApp {
     }
 
     #[cfg(target_os = "macos")]
-    fn launch_kitty_macos_direct(&self, editor: &str, args: &[String]) -> Result<(), String> {
+    fn launch_kitty_macos_direct(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         use std::process::Stdio;
 
         let mut cmd = Command::new("open");
@@ -269,6 +390,15 @@ impl TerminalApp {
             cmd.arg(arg);
         }
 
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
         cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -279,7 +409,14 @@ impl TerminalApp {
     }
 
     #[cfg(target_os = "macos")]
-    fn launch_wezterm_macos_direct(&self, editor: &str, args: &[String]) -> Result<(), String> {
+    fn launch_wezterm_macos_direct(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         use std::process::Stdio;
 
         let mut cmd = Command::new("open");
@@ -295,6 +432,15 @@ impl TerminalApp {
             cmd.arg(arg);
         }
 
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
         cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -305,7 +451,14 @@ impl TerminalApp {
     }
 
     #[cfg(target_os = "linux")]
-    fn launch_alacritty_linux_direct(&self, editor: &str, args: &[String]) -> Result<(), String> {
+    fn launch_alacritty_linux_direct(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         use std::process::Stdio;
 
         let mut cmd = Command::new("alacritty");
@@ -315,6 +468,15 @@ impl TerminalApp {
             cmd.arg(arg);
         }
 
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
         cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -325,7 +487,14 @@ impl TerminalApp {
     }
 
     #[cfg(target_os = "linux")]
-    fn launch_kitty_linux_direct(&self, editor: &str, args: &[String]) -> Result<(), String> {
+    fn launch_kitty_linux_direct(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         use std::process::Stdio;
 
         let mut cmd = Command::new("kitty");
@@ -335,6 +504,15 @@ impl TerminalApp {
             cmd.arg(arg);
         }
 
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
         cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -345,7 +523,14 @@ impl TerminalApp {
     }
 
     #[cfg(target_os = "linux")]
-    fn launch_wezterm_linux_direct(&self, editor: &str, args: &[String]) -> Result<(), String> {
+    fn launch_wezterm_linux_direct(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         use std::process::Stdio;
 
         let mut cmd = Command::new("wezterm");
@@ -355,6 +540,15 @@ impl TerminalApp {
             cmd.arg(arg);
         }
 
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
         cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -365,7 +559,14 @@ impl TerminalApp {
     }
 
     #[cfg(target_os = "linux")]
-    fn launch_gnome_terminal_direct(&self, editor: &str, args: &[String]) -> Result<(), String> {
+    fn launch_gnome_terminal_direct(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         use std::process::Stdio;
 
         let mut cmd = Command::new("gnome-terminal");
@@ -375,6 +576,15 @@ impl TerminalApp {
             cmd.arg(arg);
         }
 
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
         cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -385,7 +595,14 @@ impl TerminalApp {
     }
 
     #[cfg(target_os = "linux")]
-    fn launch_konsole_direct(&self, editor: &str, args: &[String]) -> Result<(), String> {
+    fn launch_konsole_direct(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         use std::process::Stdio;
 
         let mut cmd = Command::new("konsole");
@@ -395,6 +612,15 @@ impl TerminalApp {
             cmd.arg(arg);
         }
 
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
         cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -405,7 +631,14 @@ impl TerminalApp {
     }
 
     #[cfg(target_os = "linux")]
-    fn launch_xterm_direct(&self, editor: &str, args: &[String]) -> Result<(), String> {
+    fn launch_xterm_direct(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
         use std::process::Stdio;
 
         let mut cmd = Command::new("xterm");
@@ -415,6 +648,15 @@ impl TerminalApp {
             cmd.arg(arg);
         }
 
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
         cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -0,0 +1,94 @@
+//! Variant resolution for the Vim family: `VimManager` (vim/gvim/MacVim) and
+//! `NeovimManager` (nvim/nvim-qt) each hardcoded a single binary name, so a
+//! user running a GUI flavor or a non-`PATH` build had no way to be found.
+//! `VariantResolver` probes an ordered list of candidates instead and caches
+//! which ones actually resolved, so a manager can expose every installed
+//! flavor and let `OpenOptions.preferred_variant` pick among them.
+
+use super::super::binary_locator::BinaryLocator;
+use super::super::traits::EditorVariant;
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+
+/// One candidate flavor within a vim-family editor, together with the
+/// binary names that resolve to it, tried in priority order.
+#[derive(Clone, Copy)]
+pub struct VariantCandidate {
+    pub id: &'static str,
+    pub display_name: &'static str,
+    pub binary_names: &'static [&'static str],
+}
+
+/// A variant that was actually found installed.
+#[derive(Debug, Clone)]
+pub struct ResolvedVariant {
+    pub id: String,
+    pub display_name: String,
+    pub binary_path: PathBuf,
+}
+
+impl From<ResolvedVariant> for EditorVariant {
+    fn from(variant: ResolvedVariant) -> Self {
+        Self {
+            id: variant.id,
+            display_name: variant.display_name,
+            binary_path: variant.binary_path,
+        }
+    }
+}
+
+/// Probes `candidates` in priority order and caches which ones are
+/// installed, so repeated opens don't re-shell-out to re-discover the same
+/// binaries.
+pub struct VariantResolver {
+    candidates: Vec<VariantCandidate>,
+    cache: RwLock<Option<Vec<ResolvedVariant>>>,
+}
+
+impl VariantResolver {
+    pub fn new(candidates: Vec<VariantCandidate>) -> Self {
+        Self {
+            candidates,
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Every candidate that resolved to an installed binary, priority order
+    /// preserved, probing (and caching) on first call.
+    pub async fn variants(&self) -> Vec<ResolvedVariant> {
+        if let Some(cached) = self.cache.read().await.as_ref() {
+            return cached.clone();
+        }
+
+        let mut resolved = Vec::new();
+        for candidate in &self.candidates {
+            for name in candidate.binary_names {
+                if let Some(path) = BinaryLocator::find(name).await {
+                    resolved.push(ResolvedVariant {
+                        id: candidate.id.to_string(),
+                        display_name: candidate.display_name.to_string(),
+                        binary_path: path,
+                    });
+                    break;
+                }
+            }
+        }
+
+        *self.cache.write().await = Some(resolved.clone());
+        resolved
+    }
+
+    /// The variant to launch: `preferred`'s match if it's installed,
+    /// otherwise the highest-priority installed candidate.
+    pub async fn resolve(&self, preferred: Option<&str>) -> Option<ResolvedVariant> {
+        let variants = self.variants().await;
+
+        if let Some(preferred) = preferred {
+            if let Some(found) = variants.iter().find(|v| v.id == preferred) {
+                return Some(found.clone());
+            }
+        }
+
+        variants.into_iter().next()
+    }
+}
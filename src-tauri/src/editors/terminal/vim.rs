@@ -1,15 +1,85 @@
-use super::super::traits::{EditorError, EditorInstance, EditorManager, EditorResult, OpenOptions};
+use super::super::launch::build_launch_command;
+use super::super::process_async::output_with_timeout;
+use super::super::traits::{
+    EditorError, EditorInstance, EditorManager, EditorResult, EditorVariant, OpenOptions,
+    SelfTestReport,
+};
 use super::terminal_detector::TerminalApp;
+use super::variant::{VariantCandidate, VariantResolver};
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use tokio::process::Command;
 use tracing::debug;
 
-pub struct VimManager;
+/// Priority order: GUI flavors first (they need no terminal emulator and
+/// are what most desktop installs actually run), falling back to plain
+/// terminal vim.
+const VARIANT_CANDIDATES: &[VariantCandidate] = &[
+    VariantCandidate {
+        id: "macvim",
+        display_name: "MacVim",
+        binary_names: &["mvim"],
+    },
+    VariantCandidate {
+        id: "gvim",
+        display_name: "GVim",
+        binary_names: &["gvim"],
+    },
+    VariantCandidate {
+        id: "vim",
+        display_name: "Vim",
+        binary_names: &["vim"],
+    },
+];
+
+pub struct VimManager {
+    resolver: VariantResolver,
+}
 
 impl VimManager {
     pub fn new() -> Self {
-        Self
+        Self {
+            resolver: VariantResolver::new(VARIANT_CANDIDATES.to_vec()),
+        }
+    }
+
+    /// Servers registered with vim's `+clientserver` feature (X11's
+    /// selection registry on Linux, a named pipe on Windows) - unlike
+    /// Neovim's msgpack-RPC sockets, there's no filesystem directory to
+    /// scan, so this shells out to vim's own registry listing instead.
+    async fn list_servers(&self, binary: &Path) -> Vec<String> {
+        let mut cmd = Command::new(binary);
+        cmd.arg("--serverlist");
+
+        match output_with_timeout(cmd).await {
+            Some(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Evaluates a vimscript expression against a named server via
+    /// `--remote-expr`, mirroring how Neovim's RPC client evaluates
+    /// `getpid()`/`getcwd()`/`bufname('%')` against a socket.
+    async fn remote_expr(&self, binary: &Path, server: &str, expr: &str) -> Option<String> {
+        let mut cmd = Command::new(binary);
+        cmd.arg("--servername").arg(server).arg("--remote-expr").arg(expr);
+        let output = output_with_timeout(cmd).await?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
     }
 }
 
@@ -27,55 +97,27 @@ impl EditorManager for VimManager {
         true
     }
 
-    async fn find_binary(&self) -> Option<PathBuf> {
-        #[cfg(target_os = "macos")]
-        {
-            let candidates = vec![
-                PathBuf::from("/opt/homebrew/bin/vim"),
-                PathBuf::from("/usr/local/bin/vim"),
-                PathBuf::from("/usr/bin/vim"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found vim at {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain", "text/"]
+    }
 
-        #[cfg(target_os = "linux")]
-        {
-            let candidates = vec![
-                PathBuf::from("/usr/bin/vim"),
-                PathBuf::from("/usr/local/bin/vim"),
-            ];
-
-            for path in candidates {
-                if path.exists() {
-                    debug!("Found vim at {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
+    async fn find_binary(&self) -> Option<PathBuf> {
+        self.resolver.resolve(None).await.map(|v| v.binary_path)
+    }
 
-        if let Ok(output) = Command::new("which").arg("vim").output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    let path = PathBuf::from(&path_str);
-                    if path.exists() {
-                        debug!("Found vim via which: {:?}", path);
-                        return Some(path);
-                    }
-                }
-            }
-        }
-        None
+    async fn variants(&self) -> Vec<EditorVariant> {
+        self.resolver
+            .variants()
+            .await
+            .into_iter()
+            .map(EditorVariant::from)
+            .collect()
     }
 
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()> {
-        self.find_binary()
+        let variant = self
+            .resolver
+            .resolve(options.preferred_variant.as_deref())
             .await
             .ok_or(EditorError::BinaryNotFound)?;
 
@@ -92,24 +134,112 @@ impl EditorManager for VimManager {
         }
         vim_args.push(path.display().to_string());
 
-        debug!("Opening vim with args: {:?}", vim_args);
-
-        let terminal_pref = options.terminal_preference.as_deref();
-        if let Some(terminal) = TerminalApp::detect_installed_with_preference(terminal_pref) {
-            debug!("Using terminal: {:?}", terminal);
-            terminal
-                .launch_editor("vim", &vim_args)
-                .map_err(|e| EditorError::LaunchFailed(e))?;
+        debug!(
+            "Opening {} ({}) with args: {:?}",
+            variant.display_name, variant.id, vim_args
+        );
+
+        if variant.id == "vim" {
+            // Plain terminal vim needs a terminal emulator to run inside;
+            // the GUI flavors below are standalone applications.
+            let terminal_pref = options.terminal_preference.as_deref();
+            if let Some(terminal) = TerminalApp::detect_installed_with_preference(terminal_pref) {
+                debug!("Using terminal: {:?}", terminal);
+                terminal
+                    .launch_editor(
+                        "vim",
+                        &vim_args,
+                        options.working_directory.as_deref(),
+                        &options.env,
+                        options.detached,
+                    )
+                    .map_err(EditorError::LaunchFailed)?;
+            } else {
+                return Err(EditorError::Other(
+                    "No terminal emulator found. Please install iTerm2, Alacritty, or another terminal.".to_string()
+                ));
+            }
         } else {
-            return Err(EditorError::Other(
-                "No terminal emulator found. Please install iTerm2, Alacritty, or another terminal.".to_string()
-            ));
+            build_launch_command(&variant.binary_path, &vim_args)
+                .spawn()
+                .map_err(|e| EditorError::LaunchFailed(e.to_string()))?;
         }
 
         Ok(())
     }
 
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
-        Ok(Vec::new())
+        let Some(binary) = self.find_binary().await else {
+            return Ok(Vec::new());
+        };
+
+        let mut instances = Vec::new();
+        for server in self.list_servers(&binary).await {
+            let pid = self
+                .remote_expr(&binary, &server, "getpid()")
+                .await
+                .and_then(|pid| pid.parse::<u32>().ok());
+            let Some(pid) = pid else {
+                continue;
+            };
+
+            let workspace = self.remote_expr(&binary, &server, "getcwd()").await;
+            let window_title = self.remote_expr(&binary, &server, "bufname('%')").await;
+
+            instances.push(EditorInstance {
+                pid,
+                workspace,
+                window_title,
+            });
+        }
+
+        Ok(instances)
+    }
+
+    async fn self_test(&self) -> SelfTestReport {
+        let binary_path = self.find_binary().await;
+        if binary_path.is_none() {
+            return SelfTestReport {
+                editor_id: self.id().to_string(),
+                display_name: self.display_name().to_string(),
+                binary_path: None,
+                terminal: None,
+                success: false,
+                error: Some("vim binary not found".to_string()),
+            };
+        }
+
+        let terminal = TerminalApp::detect_installed_with_preference(None);
+        let Some(terminal) = terminal else {
+            return SelfTestReport {
+                editor_id: self.id().to_string(),
+                display_name: self.display_name().to_string(),
+                binary_path,
+                terminal: None,
+                success: false,
+                error: Some(
+                    "No terminal emulator found. Please install iTerm2, Alacritty, or another terminal."
+                        .to_string(),
+                ),
+            };
+        };
+
+        debug!("Self-test: launching vim no-op through {:?}", terminal);
+        let launch_result = terminal.launch_editor(
+            "vim",
+            &["-c".to_string(), "q".to_string()],
+            None,
+            &std::collections::HashMap::new(),
+            false,
+        );
+
+        SelfTestReport {
+            editor_id: self.id().to_string(),
+            display_name: self.display_name().to_string(),
+            binary_path,
+            terminal: Some(format!("{:?}", terminal)),
+            success: launch_result.is_ok(),
+            error: launch_result.err(),
+        }
     }
 }
@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use super::super::launch::{detach_command, normalize_launch_env};
+
+/// A terminal emulator discovered from an installed XDG `.desktop` entry
+/// rather than one of the emulators [`super::terminal_detector::TerminalApp`]
+/// hardcodes - covers distro-specific or user-installed terminals (tilix,
+/// terminator, foot, ...) our fixed enum doesn't know about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XdgTerminal {
+    pub name: String,
+    pub binary: PathBuf,
+    /// The desktop entry's own `Exec` flags (field codes stripped, binary
+    /// token dropped) to pass ahead of our `-e <editor>` argument.
+    pub exec_flags: Vec<String>,
+}
+
+impl XdgTerminal {
+    /// Launches `editor` (with `args`) inside this terminal the same way
+    /// `TerminalApp::launch_*_direct` do: the desktop entry's own flags
+    /// first, then `-e <editor> <args...>`.
+    pub fn launch_editor(
+        &self,
+        editor: &str,
+        args: &[String],
+        working_directory: Option<&Path>,
+        env: &HashMap<String, String>,
+        detached: bool,
+    ) -> Result<(), String> {
+        let mut cmd = Command::new(&self.binary);
+        cmd.args(&self.exec_flags).arg("-e").arg(editor);
+        for arg in args {
+            cmd.arg(arg);
+        }
+
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(env);
+
+        normalize_launch_env(&mut cmd);
+        if detached {
+            detach_command(&mut cmd);
+        }
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to launch {}: {}", self.name, e))?;
+
+        Ok(())
+    }
+}
+
+/// Directories to scan for `.desktop` files, per the XDG Base Directory
+/// Specification: `$XDG_DATA_HOME/applications` followed by `applications`
+/// under each `$XDG_DATA_DIRS` entry.
+fn xdg_application_dirs() -> Vec<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local/share")));
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .unwrap_or_else(|_| "/usr/local/share:/usr/share".to_string());
+
+    data_home
+        .into_iter()
+        .chain(data_dirs.split(':').filter(|d| !d.is_empty()).map(PathBuf::from))
+        .map(|dir| dir.join("applications"))
+        .collect()
+}
+
+/// The `Exec` value with field codes (`%f`, `%F`, `%u`, `%U`, `%i`, `%c`,
+/// `%k`, `%%`, ...) stripped, mirroring `tracker::desktop_entries`'s helper
+/// of the same purpose.
+fn exec_without_field_codes(exec: &str) -> String {
+    let mut result = String::with_capacity(exec.len());
+    let mut chars = exec.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            if let Some(&code) = chars.peek() {
+                chars.next();
+                if code == '%' {
+                    result.push('%');
+                }
+                continue;
+            }
+        }
+        result.push(c);
+    }
+
+    result.trim().to_string()
+}
+
+struct TerminalCandidate {
+    name: String,
+    exec: String,
+}
+
+/// Parses the `[Desktop Entry]` group of a `.desktop` file, returning it
+/// only if it declares itself a terminal emulator: either via the standard
+/// `Categories=...;TerminalEmulator;...` or the `X-Terminal-Emulator=true`
+/// key some distros set instead.
+fn parse_terminal_candidate(contents: &str) -> Option<TerminalCandidate> {
+    let mut in_desktop_entry = false;
+    let mut name = None;
+    let mut exec = None;
+    let mut categories = None;
+    let mut x_terminal_emulator = false;
+
+    for line in contents.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            in_desktop_entry = line == "[Desktop Entry]";
+            continue;
+        }
+
+        if !in_desktop_entry {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "Name" => name = Some(value.trim().to_string()),
+            "Exec" => exec = Some(value.trim().to_string()),
+            "Categories" => categories = Some(value.trim().to_string()),
+            "X-Terminal-Emulator" => x_terminal_emulator = value.trim().eq_ignore_ascii_case("true"),
+            _ => {}
+        }
+    }
+
+    let is_terminal_emulator = x_terminal_emulator
+        || categories
+            .as_deref()
+            .is_some_and(|cats| cats.split(';').any(|c| c == "TerminalEmulator"));
+
+    if !is_terminal_emulator {
+        return None;
+    }
+
+    Some(TerminalCandidate {
+        name: name?,
+        exec: exec?,
+    })
+}
+
+/// Resolves the first whitespace-separated token of a field-code-stripped
+/// `Exec` value to an actual binary on disk: as an absolute path if given
+/// as one, otherwise via a `PATH` lookup.
+fn resolve_exec_binary(exec_stripped: &str) -> Option<(PathBuf, Vec<String>)> {
+    let mut tokens = exec_stripped.split_whitespace();
+    let first = tokens.next()?;
+    let flags: Vec<String> = tokens.map(|t| t.to_string()).collect();
+
+    let as_path = PathBuf::from(first);
+    if as_path.is_absolute() {
+        return as_path.exists().then_some((as_path, flags));
+    }
+
+    let output = Command::new("which").arg(first).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let resolved = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()?
+        .trim()
+        .to_string();
+    if resolved.is_empty() {
+        return None;
+    }
+    let resolved = PathBuf::from(resolved);
+    resolved.exists().then_some((resolved, flags))
+}
+
+/// Scans `$XDG_DATA_DIRS/applications` and `~/.local/share/applications`
+/// for `.desktop` entries declaring themselves a terminal emulator,
+/// resolving each one's `Exec` binary. Entries whose binary can't be
+/// resolved, or that repeat a binary already found, are skipped.
+pub fn scan_installed_terminals() -> Vec<XdgTerminal> {
+    let mut seen = std::collections::HashSet::new();
+    let mut terminals = Vec::new();
+
+    for dir in xdg_application_dirs() {
+        let Ok(read_dir) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("desktop") {
+                continue;
+            }
+
+            let Ok(contents) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let Some(candidate) = parse_terminal_candidate(&contents) else {
+                continue;
+            };
+            let stripped = exec_without_field_codes(&candidate.exec);
+            let Some((binary, exec_flags)) = resolve_exec_binary(&stripped) else {
+                continue;
+            };
+
+            if seen.insert(binary.clone()) {
+                terminals.push(XdgTerminal {
+                    name: candidate.name,
+                    binary,
+                    exec_flags,
+                });
+            }
+        }
+    }
+
+    terminals
+}
+
+/// Debian and derivatives expose the user/admin-chosen terminal via the
+/// `update-alternatives` symlink at `/usr/bin/x-terminal-emulator`, which
+/// resolving via our fixed enum or the `.desktop` scan alone would miss if
+/// the chosen alternative has no matching `Categories=TerminalEmulator`
+/// entry of its own.
+pub fn debian_alternatives_terminal() -> Option<XdgTerminal> {
+    let link = PathBuf::from("/usr/bin/x-terminal-emulator");
+    if !link.exists() {
+        return None;
+    }
+
+    let name = std::fs::canonicalize(&link)
+        .ok()
+        .and_then(|resolved| resolved.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "x-terminal-emulator".to_string());
+
+    Some(XdgTerminal {
+        name,
+        binary: link,
+        exec_flags: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_terminal_candidate_accepts_categories_marker() {
+        let contents = "[Desktop Entry]\n\
+                         Name=Tilix\n\
+                         Exec=tilix\n\
+                         Categories=GTK;System;TerminalEmulator;\n";
+        let candidate = parse_terminal_candidate(contents).unwrap();
+        assert_eq!(candidate.name, "Tilix");
+        assert_eq!(candidate.exec, "tilix");
+    }
+
+    #[test]
+    fn parse_terminal_candidate_accepts_x_terminal_emulator_marker() {
+        let contents = "[Desktop Entry]\n\
+                         Name=Foot\n\
+                         Exec=foot\n\
+                         X-Terminal-Emulator=true\n";
+        assert!(parse_terminal_candidate(contents).is_some());
+    }
+
+    #[test]
+    fn parse_terminal_candidate_rejects_non_terminal_apps() {
+        let contents = "[Desktop Entry]\n\
+                         Name=Visual Studio Code\n\
+                         Exec=/usr/bin/code %F\n\
+                         Categories=Development;TextEditor;\n";
+        assert!(parse_terminal_candidate(contents).is_none());
+    }
+
+    #[test]
+    fn exec_without_field_codes_strips_codes() {
+        assert_eq!(
+            exec_without_field_codes("/usr/bin/tilix --working-directory=%u"),
+            "/usr/bin/tilix --working-directory="
+        );
+    }
+
+    #[test]
+    fn resolve_exec_binary_accepts_existing_absolute_path() {
+        let (binary, flags) = resolve_exec_binary("/bin/sh -l").unwrap();
+        assert_eq!(binary, PathBuf::from("/bin/sh"));
+        assert_eq!(flags, vec!["-l".to_string()]);
+    }
+
+    #[test]
+    fn resolve_exec_binary_rejects_missing_absolute_path() {
+        assert!(resolve_exec_binary("/no/such/terminal-binary").is_none());
+    }
+}
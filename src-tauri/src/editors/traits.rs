@@ -1,5 +1,7 @@
+use super::app_discovery::DiscoveredApp;
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
@@ -20,11 +22,63 @@ pub enum EditorError {
 
 pub type EditorResult<T> = Result<T, EditorError>;
 
+/// How an editor should place an opened file relative to whatever window(s)
+/// it already has open. Editors that don't distinguish these (most of the
+/// terminal-based ones) are free to treat every variant the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpenMode {
+    /// Reuse the currently focused window, replacing what it's showing.
+    Reuse,
+    /// Add the file to the workspace of an already-running instance that
+    /// already has it open, instead of spawning a new window. `EditorDispatcher`
+    /// downgrades this to `NewWindow` if no such instance is found.
+    AddToWorkspace,
+    /// Always spawn a brand-new window.
+    NewWindow,
+}
+
+impl Default for OpenMode {
+    fn default() -> Self {
+        Self::NewWindow
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct OpenOptions {
     pub line: Option<usize>,
     pub column: Option<usize>,
-    pub new_window: bool,
+    pub mode: OpenMode,
+    pub terminal_preference: Option<String>,
+    /// Set when `path` lives inside a WSL distro, so an editor that
+    /// understands WSL remoting (currently VS Code) can open it through the
+    /// distro instead of treating the Windows-side UNC path as a local file.
+    pub wsl_target: Option<crate::wsl::WslTarget>,
+    /// Directory the editor/terminal process should start in, mozrunner's
+    /// `Runner`-style - typically the workspace root, so a terminal editor
+    /// opens `cd`'d into the repo rather than wherever Sorcery itself was
+    /// launched from (usually `$HOME`).
+    pub working_directory: Option<PathBuf>,
+    /// Extra environment variables to set on the spawned process, layered
+    /// on top of (and able to override) whatever `normalize_launch_env`
+    /// leaves in place - e.g. a workspace-specific `EDITOR` or `PATH`
+    /// addition.
+    pub env: HashMap<String, String>,
+    /// Ask the editor to (re)generate a `compile_commands.json` for the
+    /// opened folder after launching, via `EditorManager::prepare_compilation_db`.
+    /// Off by default since it's an extra build invocation most opens don't
+    /// want to pay for; only editors that can mine a build log (currently
+    /// `XcodeManager`) act on it.
+    pub generate_compilation_db: bool,
+    /// Which variant a manager with more than one installed flavor
+    /// (`EditorManager::variants`) should launch, e.g. `"macvim"` over
+    /// plain `"vim"`. `None` means "use the highest-priority one found".
+    pub preferred_variant: Option<String>,
+    /// Fully decouple the spawned process from Sorcery instead of leaving it
+    /// as a direct child - see `launch::detach_command`. Off by default since
+    /// most callers (e.g. the `doctor` self-test) want the ordinary
+    /// parent/child relationship; the clone-dialog "open in editor" action
+    /// turns it on so a GUI editor outlives the launcher that opened it.
+    pub detached: bool,
 }
 
 impl Default for OpenOptions {
@@ -32,7 +86,14 @@ impl Default for OpenOptions {
         Self {
             line: None,
             column: None,
-            new_window: false,
+            mode: OpenMode::default(),
+            terminal_preference: None,
+            wsl_target: None,
+            working_directory: None,
+            env: HashMap::new(),
+            generate_compilation_db: false,
+            preferred_variant: None,
+            detached: false,
         }
     }
 }
@@ -44,19 +105,189 @@ pub struct EditorInstance {
     pub window_title: Option<String>,
 }
 
+/// One installed flavor of an editor that supports more than one (e.g.
+/// Vim's `vim`/`gvim`/MacVim, Neovim's `nvim`/`nvim-qt`), as resolved by a
+/// `VariantResolver`. Surfaced so the UI can show what actually launched
+/// and let a user with several installed pick one via
+/// `OpenOptions.preferred_variant`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorVariant {
+    pub id: String,
+    pub display_name: String,
+    pub binary_path: PathBuf,
+}
+
+/// An already-running instance of an editor that `find_reusable_instance`
+/// located and can hand to `open_in`, instead of `open` spawning a fresh
+/// process - Neovim's RPC socket today; a VS Code/JetBrains remote-window
+/// handle would be a future variant rather than its own trait method.
+#[derive(Debug, Clone)]
+pub enum ReuseHandle {
+    /// Unix socket (or, on Windows, a named pipe) speaking Neovim's
+    /// msgpack-RPC protocol.
+    NvimSocket(PathBuf),
+}
+
+/// Result of exercising an editor's launch path end-to-end, for the
+/// `doctor` command - lets a user on a fresh machine tell whether their
+/// configured editor/terminal combination actually works before they rely
+/// on it instead of discovering it the first time they try to open a file.
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    pub editor_id: String,
+    pub display_name: String,
+    pub binary_path: Option<PathBuf>,
+    pub terminal: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
 #[async_trait]
 pub trait EditorManager: Send + Sync {
     fn id(&self) -> &str;
 
     fn display_name(&self) -> &str;
 
+    /// Whether this editor can be pointed at a directory rather than a
+    /// single file. Terminal line editors that only make sense on one file
+    /// at a time should override this to `false`.
+    fn supports_folders(&self) -> bool {
+        true
+    }
+
+    /// Whether this editor needs an explicit binary path or launch command
+    /// configured under `defaults.editor_paths` before it can be dispatched,
+    /// rather than relying on the usual install-location probing in
+    /// `find_binary`. `EditorDispatcher` fails fast with an actionable error
+    /// if this is `true` and no such path is configured, instead of falling
+    /// through to a `PATH` lookup that could silently resolve the wrong
+    /// binary.
+    fn requires_configuration(&self) -> bool {
+        false
+    }
+
+    /// Extensions (dot-prefixed, e.g. `".rs"`) this editor is a good fit
+    /// for, used by `EditorRegistry::suggest_editors` to rank IDE-class
+    /// editors ahead of lightweight ones for source files. Empty by
+    /// default - most managers here are terminal/lightweight editors
+    /// equally at home in any text file, with nothing specific to claim.
+    fn supported_extensions(&self) -> &[&str] {
+        &[]
+    }
+
+    /// MIME types (or `"<type>/"` prefixes) this editor declares support
+    /// for, merged with `supported_extensions` by
+    /// `EditorRegistry::suggest_editors` into a ranked "Open With" list.
+    /// Every registered editor can at least open plain text, so the
+    /// default covers that baseline without every manager repeating it.
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/"]
+    }
+
     async fn is_installed(&self) -> bool {
         self.find_binary().await.is_some()
     }
 
     async fn find_binary(&self) -> Option<PathBuf>;
 
+    /// Every installed flavor of this editor, in priority order, for
+    /// editors backed by a `VariantResolver` (currently `VimManager`,
+    /// `NeovimManager`). Single-binary editors leave this empty - there's
+    /// nothing to choose between.
+    async fn variants(&self) -> Vec<EditorVariant> {
+        Vec::new()
+    }
+
+    /// Every installed copy of this editor found by scanning the OS's own
+    /// application registry (see `app_discovery`) rather than the fixed
+    /// candidate list `find_binary` checks, so an install in a
+    /// non-standard location is still surfaced to the UI. The default
+    /// falls back to wrapping whatever `find_binary` resolves, which is
+    /// enough for editors that don't yet have a dedicated match predicate;
+    /// override it once an editor knows its own bundle id/`.desktop` exec
+    /// name to report every install instead of just the first one found.
+    async fn discover(&self) -> Vec<DiscoveredApp> {
+        #[cfg(target_os = "linux")]
+        if let Some(binary_path) = super::app_discovery::linux_find_binary(self.id()) {
+            return vec![self.as_discovered_app(binary_path)];
+        }
+
+        #[cfg(target_os = "macos")]
+        if let Some(binary_path) = super::app_discovery::macos_find_binary(self.id()) {
+            return vec![self.as_discovered_app(binary_path)];
+        }
+
+        #[cfg(target_os = "windows")]
+        if let Some(binary_path) = super::app_discovery::windows_find_binary(self.id()).await {
+            return vec![self.as_discovered_app(binary_path)];
+        }
+
+        match self.find_binary().await {
+            Some(binary_path) => vec![self.as_discovered_app(binary_path)],
+            None => Vec::new(),
+        }
+    }
+
+    fn as_discovered_app(&self, binary_path: PathBuf) -> DiscoveredApp {
+        DiscoveredApp {
+            id: self.id().to_string(),
+            display_name: self.display_name().to_string(),
+            binary_path,
+        }
+    }
+
     async fn open(&self, path: &Path, options: &OpenOptions) -> EditorResult<()>;
 
+    /// Looks for an already-running instance of this editor that `path`
+    /// could be handed to instead of spawning a new window - `NeovimManager`
+    /// overrides this with its existing socket scan. The default is `None`,
+    /// i.e. "this editor has no concept of attaching to a running instance",
+    /// which is true of every editor that just shells out to a fresh launch.
+    async fn find_reusable_instance(&self, _path: &Path) -> Option<ReuseHandle> {
+        None
+    }
+
+    /// Opens `path` in the already-running instance `handle` points at. The
+    /// default falls back to a fresh `open()`, so overriding
+    /// `find_reusable_instance` without overriding this is still correct,
+    /// just pointless - only an editor whose `find_reusable_instance` can
+    /// return `Some` needs to override both.
+    async fn open_in(&self, _handle: ReuseHandle, path: &Path, options: &OpenOptions) -> EditorResult<()> {
+        self.open(path, options).await
+    }
+
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>>;
+
+    /// Produces (or refreshes) a clang compilation database at
+    /// `<path>/compile_commands.json`, when `open()` was called with
+    /// `OpenOptions.generate_compilation_db` set. The default is a no-op -
+    /// most editors have no build log to mine; `XcodeManager` overrides this
+    /// to capture `xcodebuild`'s `CompileC`/`CompileSwift` invocations.
+    async fn prepare_compilation_db(&self, _path: &Path) -> EditorResult<()> {
+        Ok(())
+    }
+
+    /// Confirms this editor's launch path actually works, for the `doctor`
+    /// command. The default just resolves `find_binary()` - enough for
+    /// editors `open` launches directly. Terminal-dispatched editors (see
+    /// `VimManager`) override this to also spawn a trivial no-op command
+    /// through the detected `TerminalApp`, since a resolved binary alone
+    /// doesn't confirm the terminal half of that launch path works.
+    async fn self_test(&self) -> SelfTestReport {
+        let binary_path = self.find_binary().await;
+        let success = binary_path.is_some();
+        let display_name = self.display_name().to_string();
+        SelfTestReport {
+            editor_id: self.id().to_string(),
+            display_name: display_name.clone(),
+            binary_path,
+            terminal: None,
+            success,
+            error: if success {
+                None
+            } else {
+                Some(format!("{} binary not found", display_name))
+            },
+        }
+    }
 }
@@ -1,7 +1,11 @@
-use super::traits::{EditorManager, OpenOptions, EditorInstance, EditorResult, EditorError};
+use super::binary_locator::BinaryLocator;
+use super::launch::build_launch_command;
+use super::process_async::{blocking_output_with_timeout, output_with_timeout};
+use super::traits::{EditorManager, OpenMode, OpenOptions, EditorInstance, EditorResult, EditorError};
+use crate::settings::SettingsManager;
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::sync::Arc;
 use tracing::{debug, warn};
 
 pub struct VSCodeManager {
@@ -11,10 +15,18 @@ pub struct VSCodeManager {
     macos_app_name: String,
     #[cfg(target_os = "windows")]
     windows_exe_name: String,
+    settings: Arc<SettingsManager>,
 }
 
 impl VSCodeManager {
-    pub fn new(id: &str, display_name: &str, cli_name: &str, macos_app_name: &str, _windows_exe_name: &str) -> Self {
+    pub fn new(
+        id: &str,
+        display_name: &str,
+        cli_name: &str,
+        macos_app_name: &str,
+        _windows_exe_name: &str,
+        settings: Arc<SettingsManager>,
+    ) -> Self {
         Self {
             id: id.to_string(),
             display_name: display_name.to_string(),
@@ -22,35 +34,79 @@ impl VSCodeManager {
             macos_app_name: macos_app_name.to_string(),
             #[cfg(target_os = "windows")]
             windows_exe_name: _windows_exe_name.to_string(),
+            settings,
+        }
+    }
+
+    /// `defaults.editor_paths.<id>`, if the user has pinned this editor to a
+    /// path themselves - short-circuits the OS-native discovery below
+    /// entirely, the same way a manually configured path always wins for an
+    /// editor install in a non-standard location.
+    async fn find_configured_binary(&self) -> Option<PathBuf> {
+        let configured = self.settings.get_editor_path(&self.id).await?;
+        let path = PathBuf::from(configured);
+        if path.exists() {
+            Some(path)
+        } else {
+            warn!(
+                "Configured path for {} does not exist: {:?}",
+                self.display_name, path
+            );
+            None
         }
     }
 
     #[cfg(target_os = "macos")]
     async fn find_binary_macos(&self) -> Option<PathBuf> {
-        let candidates = vec![
-            PathBuf::from(format!("/Applications/{}.app/Contents/Resources/app/bin/{}",
-                self.macos_app_name, self.cli_name)),
-            PathBuf::from(format!("/usr/local/bin/{}", self.cli_name)),
-            PathBuf::from(format!("/opt/homebrew/bin/{}", self.cli_name)),
-        ];
+        let app_bundle = PathBuf::from(format!(
+            "/Applications/{}.app/Contents/Resources/app/bin/{}",
+            self.macos_app_name, self.cli_name
+        ));
+        if app_bundle.exists() {
+            debug!("Found {} at {:?}", self.display_name, app_bundle);
+            return Some(app_bundle);
+        }
 
-        for path in candidates {
-            if path.exists() {
-                debug!("Found {} at {:?}", self.display_name, path);
-                return Some(path);
-            }
+        if let Some(path) = self.find_via_system_profiler_macos().await {
+            debug!("Found {} via system_profiler: {:?}", self.display_name, path);
+            return Some(path);
         }
 
-        if let Ok(output) = Command::new("which").arg(&self.cli_name).output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    let path = PathBuf::from(path_str);
-                    if path.exists() {
-                        debug!("Found {} via which: {:?}", self.display_name, path);
-                        return Some(path);
-                    }
-                }
+        BinaryLocator::find(&self.cli_name).await
+    }
+
+    /// Falls back to `system_profiler SPApplicationsDataType` when the
+    /// bundle isn't under `/Applications` - it enumerates every installed
+    /// app regardless of where it actually lives (a user-chosen install
+    /// folder, an external volume), which a fixed candidate list can't.
+    #[cfg(target_os = "macos")]
+    async fn find_via_system_profiler_macos(&self) -> Option<PathBuf> {
+        let mut cmd = tokio::process::Command::new("system_profiler");
+        cmd.args(["SPApplicationsDataType", "-detailLevel", "mini"]);
+        let output = output_with_timeout(cmd).await?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let app_name = format!("{}:", self.macos_app_name);
+        let mut lines = stdout.lines();
+
+        while let Some(line) = lines.next() {
+            if line.trim() != app_name {
+                continue;
+            }
+
+            let location = lines
+                .by_ref()
+                .take_while(|l| !l.trim().is_empty())
+                .find_map(|l| l.trim().strip_prefix("Location: "))?;
+
+            let cli_path = PathBuf::from(location)
+                .join("Contents/Resources/app/bin")
+                .join(&self.cli_name);
+            if cli_path.exists() {
+                return Some(cli_path);
             }
         }
 
@@ -73,7 +129,14 @@ impl VSCodeManager {
             }
         }
 
-        if let Ok(output) = Command::new("where").arg(&format!("{}.cmd", self.cli_name)).output() {
+        if let Some(path) = self.find_via_registry_windows().await {
+            debug!("Found {} via registry: {:?}", self.display_name, path);
+            return Some(path);
+        }
+
+        let mut cmd = tokio::process::Command::new("where");
+        cmd.arg(&format!("{}.cmd", self.cli_name));
+        if let Some(output) = output_with_timeout(cmd).await {
             if output.status.success() {
                 let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
                 if !path_str.is_empty() {
@@ -89,29 +152,38 @@ impl VSCodeManager {
         None
     }
 
-    #[cfg(target_os = "linux")]
-    async fn find_binary_linux(&self) -> Option<PathBuf> {
-        let candidates = vec![
-            PathBuf::from(format!("/usr/local/bin/{}", self.cli_name)),
-            PathBuf::from(format!("/usr/bin/{}", self.cli_name)),
-            PathBuf::from(format!("/snap/bin/{}", self.cli_name)),
+    /// Resolves the install directory by app name out of the per-machine
+    /// and per-user `Uninstall` registry keys (both the native view and the
+    /// `Wow6432Node` one a 64-bit Windows puts 32-bit installers under),
+    /// via `reg query` rather than linking a registry crate - same approach
+    /// `external_editors::discover` uses for `HKEY_CLASSES_ROOT`.
+    #[cfg(target_os = "windows")]
+    async fn find_via_registry_windows(&self) -> Option<PathBuf> {
+        const UNINSTALL_KEYS: &[&str] = &[
+            "HKLM\\Software\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+            "HKLM\\Software\\Wow6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+            "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
         ];
 
-        for path in candidates {
-            if path.exists() {
-                debug!("Found {} at {:?}", self.display_name, path);
-                return Some(path);
+        for uninstall_key in UNINSTALL_KEYS {
+            let mut cmd = tokio::process::Command::new("reg");
+            cmd.args(["query", uninstall_key]);
+            let Some(output) = output_with_timeout(cmd).await else {
+                continue;
+            };
+            if !output.status.success() {
+                continue;
             }
-        }
 
-        if let Ok(output) = Command::new("which").arg(&self.cli_name).output() {
-            if output.status.success() {
-                let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path_str.is_empty() {
-                    let path = PathBuf::from(path_str);
-                    if path.exists() {
-                        debug!("Found {} via which: {:?}", self.display_name, path);
-                        return Some(path);
+            for subkey in String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|line| line.trim_start().starts_with("HKEY_"))
+                .map(|line| line.trim().to_string())
+            {
+                if let Some(install_dir) = self.registry_subkey_install_location(&subkey).await {
+                    let cli_path = install_dir.join("bin").join(format!("{}.cmd", self.cli_name));
+                    if cli_path.exists() {
+                        return Some(cli_path);
                     }
                 }
             }
@@ -119,6 +191,61 @@ impl VSCodeManager {
 
         None
     }
+
+    /// Reads `DisplayName`/`InstallLocation` out of one `Uninstall` subkey,
+    /// returning the install directory if `DisplayName` matches this
+    /// editor.
+    #[cfg(target_os = "windows")]
+    async fn registry_subkey_install_location(&self, subkey: &str) -> Option<PathBuf> {
+        let mut cmd = tokio::process::Command::new("reg");
+        cmd.args(["query", subkey, "/v", "DisplayName"]);
+        let output = output_with_timeout(cmd).await?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let display_name = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.trim_start().starts_with("DisplayName"))
+            .and_then(|line| line.split("REG_SZ").nth(1))
+            .map(|value| value.trim().to_string())?;
+
+        if !display_name.contains(&self.windows_exe_name) {
+            return None;
+        }
+
+        let mut cmd = tokio::process::Command::new("reg");
+        cmd.args(["query", subkey, "/v", "InstallLocation"]);
+        let output = output_with_timeout(cmd).await?;
+        if !output.status.success() {
+            return None;
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.trim_start().starts_with("InstallLocation"))
+            .and_then(|line| line.split("REG_SZ").nth(1))
+            .map(|value| PathBuf::from(value.trim()))
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn find_binary_linux(&self) -> Option<PathBuf> {
+        if let Some(path) = BinaryLocator::find(&self.cli_name).await {
+            debug!("Found {} at {:?}", self.display_name, path);
+            return Some(path);
+        }
+
+        // Catches installs under arbitrary prefixes (and flatpak-exported
+        // apps) that the shared locator's fixed prefixes don't cover.
+        if let Some(path) = crate::tracker::DesktopEntryRegistry::scan().find_binary(&self.id) {
+            if path.exists() {
+                debug!("Found {} via desktop entry: {:?}", self.display_name, path);
+                return Some(path);
+            }
+        }
+
+        None
+    }
 }
 
 #[async_trait]
@@ -131,7 +258,15 @@ impl EditorManager for VSCodeManager {
         &self.display_name
     }
 
+    fn supported_extensions(&self) -> &[&str] {
+        crate::file_types::source_code_extensions()
+    }
+
     async fn find_binary(&self) -> Option<PathBuf> {
+        if let Some(path) = self.find_configured_binary().await {
+            return Some(path);
+        }
+
         #[cfg(target_os = "macos")]
         return self.find_binary_macos().await;
 
@@ -146,39 +281,59 @@ impl EditorManager for VSCodeManager {
         let binary = self.find_binary().await
             .ok_or(EditorError::BinaryNotFound)?;
 
-        let mut args = vec![];
+        let mut args: Vec<String> = vec![];
 
-        if !options.new_window {
-            args.push("--reuse-window");
-        } else {
-            args.push("--new-window");
+        match options.mode {
+            OpenMode::Reuse => args.push("--reuse-window".to_string()),
+            OpenMode::AddToWorkspace => args.push("--add".to_string()),
+            OpenMode::NewWindow => args.push("--new-window".to_string()),
         }
 
+        // A WSL-backed path can't be opened as a local file from the Windows
+        // side - point the CLI at the distro instead so it opens through its
+        // Remote - WSL server, the same thing `code .` does when run from a
+        // WSL shell.
+        let target = if let Some(wsl_target) = &options.wsl_target {
+            args.push("--remote".to_string());
+            args.push(format!("wsl+{}", wsl_target.distro));
+            wsl_target.linux_path.clone()
+        } else {
+            path.display().to_string()
+        };
+
         let goto_arg = if let Some(line) = options.line {
             let col = options.column.unwrap_or(1);
-            format!("--goto {}:{}:{}", path.display(), line, col)
+            format!("--goto {}:{}:{}", target, line, col)
         } else {
-            path.display().to_string()
+            target
         };
 
-        args.push(&goto_arg);
+        args.push(goto_arg);
 
         debug!("Launching {} with args: {:?}", self.display_name, args);
 
-        let result = Command::new(&binary)
-            .args(&args)
-            .output();
+        let launch_args = args.clone();
+        let launch_binary = binary.clone();
+        let working_directory = options.working_directory.clone();
+        let result = blocking_output_with_timeout(move || {
+            let mut cmd = build_launch_command(&launch_binary, &launch_args);
+            if let Some(dir) = &working_directory {
+                cmd.current_dir(dir);
+            }
+            cmd.output()
+        })
+        .await;
 
         match result {
-            Ok(output) if output.status.success() => {
+            Some(output) if output.status.success() => {
                 return Ok(());
             }
-            Ok(output) => {
+            Some(output) => {
                 let stderr = String::from_utf8_lossy(&output.stderr);
                 debug!("Failed to launch {} with primary binary: {}", self.display_name, stderr);
             }
-            Err(e) => {
-                debug!("Failed to exec {} with primary binary: {}", self.display_name, e);
+            None => {
+                debug!("Failed to exec {} with primary binary", self.display_name);
             }
         }
 
@@ -191,21 +346,28 @@ impl EditorManager for VSCodeManager {
                 debug!("Trying app bundle fallback at {:?}", cli_path);
 
                 if cli_path.exists() {
-                    let fallback_result = Command::new(&cli_path)
-                        .args(&args)
-                        .output();
+                    let fallback_args = args.clone();
+                    let fallback_working_directory = options.working_directory.clone();
+                    let fallback_result = blocking_output_with_timeout(move || {
+                        let mut cmd = build_launch_command(&cli_path, &fallback_args);
+                        if let Some(dir) = &fallback_working_directory {
+                            cmd.current_dir(dir);
+                        }
+                        cmd.output()
+                    })
+                    .await;
 
                     match fallback_result {
-                        Ok(output) if output.status.success() => {
+                        Some(output) if output.status.success() => {
                             debug!("Successfully launched {} via app bundle fallback", self.display_name);
                             return Ok(());
                         }
-                        Ok(output) => {
+                        Some(output) => {
                             let stderr = String::from_utf8_lossy(&output.stderr);
                             warn!("App bundle fallback also failed: {}", stderr);
                         }
-                        Err(e) => {
-                            warn!("Failed to exec via app bundle: {}", e);
+                        None => {
+                            warn!("Failed to exec via app bundle");
                         }
                     }
                 }
@@ -217,75 +379,79 @@ impl EditorManager for VSCodeManager {
 
     async fn get_running_instances(&self) -> EditorResult<Vec<EditorInstance>> {
         #[cfg(target_os = "macos")]
-        {
-            let pattern = format!("/Applications/{}.app", self.macos_app_name);
-
-            let output = Command::new("ps")
-                .arg("aux")
-                .output()
-                .map_err(|e| EditorError::Other(e.to_string()))?;
-
-            if !output.status.success() {
-                return Ok(Vec::new());
-            }
-
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if stdout.to_lowercase().contains(&pattern.to_lowercase()) {
-                return Ok(vec![EditorInstance {
-                    pid: 0,
-                    workspace: Some("detected (workspace unknown)".to_string()),
-                    window_title: None,
-                }]);
-            }
-
-            Ok(Vec::new())
-        }
-
+        let pattern = format!("/Applications/{}.app", self.macos_app_name);
         #[cfg(target_os = "windows")]
-        {
-            let pattern = format!("{}.exe", self.windows_exe_name);
-
-            let output = Command::new("tasklist")
-                .output()
-                .map_err(|e| EditorError::Other(e.to_string()))?;
+        let pattern = self.windows_exe_name.clone();
+        #[cfg(target_os = "linux")]
+        let pattern = self.cli_name.clone();
+
+        Ok(super::process_scan::find_running(&pattern).await?
+            .into_iter()
+            .map(|process| EditorInstance {
+                pid: process.pid,
+                workspace: workspace_from_command_line(&process.command_line),
+                window_title: None,
+            })
+            .collect())
+    }
+}
 
-            if !output.status.success() {
-                return Ok(Vec::new());
+/// Extracts the workspace/file a running `code`-family process was launched
+/// with from its full command line. `--folder-uri`/`--file-uri` (what VS
+/// Code itself passes when restoring or handed a `file://` URI) win out over
+/// a bare positional path argument.
+fn workspace_from_command_line(command_line: &str) -> Option<String> {
+    let tokens = super::process_scan::split_command_line(command_line);
+    let mut positional = None;
+
+    let mut iter = tokens.iter().skip(1).peekable();
+    while let Some(token) = iter.next() {
+        if let Some(value) = token.strip_prefix("--folder-uri=") {
+            return Some(uri_to_path(value));
+        }
+        if token == "--folder-uri" {
+            if let Some(value) = iter.next() {
+                return Some(uri_to_path(value));
             }
-
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if stdout.to_lowercase().contains(&pattern.to_lowercase()) {
-                return Ok(vec![EditorInstance {
-                    pid: 0,
-                    workspace: Some("detected (workspace unknown)".to_string()),
-                    window_title: None,
-                }]);
+        }
+        if let Some(value) = token.strip_prefix("--file-uri=") {
+            return Some(uri_to_path(value));
+        }
+        if token == "--file-uri" {
+            if let Some(value) = iter.next() {
+                return Some(uri_to_path(value));
             }
-
-            Ok(Vec::new())
         }
+        if !token.starts_with('-') {
+            positional = Some(token.clone());
+        }
+    }
 
-        #[cfg(target_os = "linux")]
-        {
-            let output = Command::new("ps")
-                .arg("aux")
-                .output()
-                .map_err(|e| EditorError::Other(e.to_string()))?;
-
-            if !output.status.success() {
-                return Ok(Vec::new());
-            }
+    positional
+}
 
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if stdout.to_lowercase().contains(&self.cli_name.to_lowercase()) {
-                return Ok(vec![EditorInstance {
-                    pid: 0,
-                    workspace: Some("detected (workspace unknown)".to_string()),
-                    window_title: None,
-                }]);
+/// Decodes a `file://` URI into a plain filesystem path. Falls back to
+/// returning the URI as-is if it isn't a `file://` URI.
+fn uri_to_path(uri: &str) -> String {
+    let Some(path) = uri.strip_prefix("file://") else {
+        return uri.to_string();
+    };
+
+    let mut decoded = String::with_capacity(path.len());
+    let mut chars = path.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '%' {
+            let hex: String = chars.by_ref().take(2).collect();
+            if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                decoded.push(byte as char);
+                continue;
             }
-
-            Ok(Vec::new())
+            decoded.push('%');
+            decoded.push_str(&hex);
+        } else {
+            decoded.push(ch);
         }
     }
+
+    decoded
 }
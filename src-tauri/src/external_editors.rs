@@ -0,0 +1,276 @@
+//! Discovery of "Open With"-style external editors: apps installed on the
+//! machine that can open a text/source file but aren't one of the editors
+//! `editors::EditorRegistry` has first-class dispatch support for. Used by
+//! the clone dialog to let the user pick an arbitrary installed editor
+//! instead of being limited to our hardcoded roster.
+//!
+//! [`discover`] surfaces the fixed, always-available text-editor roster;
+//! [`list_openers`] does the same thing scoped to one specific file's
+//! actual type, for an "Open With" menu on that file.
+//!
+//! Each discovered editor gets registered into the `EditorRegistry` under
+//! its own id (see `EditorRegistry::register_external`) so picking one and
+//! saving it into `WorkspaceConfig.editor` works the same way picking
+//! "vscode" or "idea" would.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AvailableEditor {
+    pub id: String,
+    pub name: String,
+    /// Raw launch command, field codes and all - only used internally to
+    /// register an `ExternalEditorManager`, not meant for display.
+    #[serde(skip)]
+    pub exec_template: String,
+    /// Whether `exec_template` expects to run attached to a terminal
+    /// emulator rather than spawn as a standalone GUI process - carried
+    /// through from the source `.desktop` entry's `Terminal` key on Linux;
+    /// always `false` on platforms with no such concept.
+    pub terminal: bool,
+}
+
+/// Enumerates editors installed on the machine that declare themselves
+/// capable of opening plain text/source files, via whatever "open with"
+/// mechanism the platform exposes.
+#[cfg(target_os = "linux")]
+pub fn discover() -> Vec<AvailableEditor> {
+    crate::tracker::desktop_entries::list_text_editors()
+        .into_iter()
+        .map(|entry| AvailableEditor {
+            id: entry.id,
+            name: entry.name,
+            exec_template: entry.exec,
+            terminal: entry.terminal,
+        })
+        .collect()
+}
+
+/// macOS has no central registry of per-MIME-type handlers we can read
+/// without linking against `LaunchServices` directly, so this takes the
+/// same best-effort approach as `VSCodeManager::get_running_instances`:
+/// scan the well-known app directories and read just enough of each
+/// bundle's `Info.plist` to tell whether it declares handling a text-ish
+/// `UTTypeConformsTo`/`LSItemContentTypes` entry.
+#[cfg(target_os = "macos")]
+pub fn discover() -> Vec<AvailableEditor> {
+    let app_dirs = [
+        std::path::PathBuf::from("/Applications"),
+        dirs::home_dir()
+            .map(|home| home.join("Applications"))
+            .unwrap_or_default(),
+    ];
+
+    let mut editors = Vec::new();
+
+    for dir in app_dirs {
+        let Ok(read_dir) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in read_dir.flatten() {
+            let bundle_path = entry.path();
+            if bundle_path.extension().and_then(|ext| ext.to_str()) != Some("app") {
+                continue;
+            }
+
+            let plist_path = bundle_path.join("Contents/Info.plist");
+            let Ok(plist) = std::fs::read_to_string(&plist_path) else {
+                continue;
+            };
+
+            let declares_text_handling = plist.contains("public.text")
+                || plist.contains("public.source-code")
+                || plist.contains("public.plain-text");
+            if !declares_text_handling {
+                continue;
+            }
+
+            let name = bundle_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Unknown".to_string());
+
+            editors.push(AvailableEditor {
+                id: format!("external:{}", name.to_lowercase().replace(' ', "-")),
+                name,
+                exec_template: format!("open -a \"{}\" %f", bundle_path.display()),
+                terminal: false,
+            });
+        }
+    }
+
+    editors
+}
+
+/// Windows registers per-extension "Open With" handlers under
+/// `HKEY_CLASSES_ROOT\.<ext>\OpenWithProgids`; resolve the handful of
+/// extensions we actually care about (plain text and a few common source
+/// extensions) through `reg query` rather than linking a registry crate,
+/// matching how `VSCodeManager::find_binary` shells out to `where` instead.
+#[cfg(target_os = "windows")]
+pub fn discover() -> Vec<AvailableEditor> {
+    use std::collections::HashSet;
+
+    let extensions = [".txt", ".md", ".rs", ".py", ".js", ".ts"];
+    let mut seen_prog_ids = HashSet::new();
+    let mut editors = Vec::new();
+
+    for ext in extensions {
+        editors.extend(openers_for_extension(ext, &mut seen_prog_ids));
+    }
+
+    editors
+}
+
+/// Every `OpenWithProgids` handler registered for `ext` (e.g. `".txt"`),
+/// skipping any `ProgId` already in `seen` - shared between `discover`'s
+/// fixed extension list and `list_openers`'s single, path-derived one.
+#[cfg(target_os = "windows")]
+fn openers_for_extension(
+    ext: &str,
+    seen: &mut std::collections::HashSet<String>,
+) -> Vec<AvailableEditor> {
+    use std::process::Command;
+
+    let mut editors = Vec::new();
+
+    let Ok(output) = Command::new("reg")
+        .args(["query", &format!("HKEY_CLASSES_ROOT\\{}\\OpenWithProgids", ext)])
+        .output()
+    else {
+        return editors;
+    };
+
+    if !output.status.success() {
+        return editors;
+    }
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some(prog_id) = line.trim().split_whitespace().next() else {
+            continue;
+        };
+        if prog_id.is_empty() || !seen.insert(prog_id.to_string()) {
+            continue;
+        }
+
+        let command_key = format!("HKEY_CLASSES_ROOT\\{}\\shell\\open\\command", prog_id);
+        let Ok(command_output) = Command::new("reg").args(["query", &command_key, "/ve"]).output() else {
+            continue;
+        };
+        if !command_output.status.success() {
+            continue;
+        }
+
+        let stdout = String::from_utf8_lossy(&command_output.stdout);
+        let Some(exec_template) = stdout
+            .lines()
+            .find(|line| line.trim_start().starts_with("(Default)"))
+            .and_then(|line| line.split("REG_SZ").nth(1))
+            .map(|value| value.trim().to_string())
+        else {
+            continue;
+        };
+
+        editors.push(AvailableEditor {
+            id: format!("external:{}", prog_id.to_lowercase()),
+            name: prog_id.to_string(),
+            exec_template,
+            terminal: false,
+        });
+    }
+
+    editors
+}
+
+/// Applications registered to open `path`'s specific extension, for an
+/// "Open With" menu on that one file rather than `discover`'s fixed
+/// always-available roster.
+#[cfg(target_os = "windows")]
+pub fn list_openers(path: &std::path::Path) -> Vec<AvailableEditor> {
+    use std::collections::HashSet;
+
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return Vec::new();
+    };
+
+    let mut seen_prog_ids = HashSet::new();
+    openers_for_extension(&format!(".{}", ext), &mut seen_prog_ids)
+}
+
+/// Linux resolves a file's MIME type the same way a file manager would
+/// (`xdg-mime query filetype`), then surfaces every `.desktop` entry that
+/// declares handling it - the per-file-type counterpart to `discover`'s
+/// fixed `text/*` roster.
+#[cfg(target_os = "linux")]
+pub fn list_openers(path: &std::path::Path) -> Vec<AvailableEditor> {
+    use std::process::Command;
+
+    let Ok(output) = Command::new("xdg-mime").args(["query", "filetype"]).arg(path).output() else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    let mime = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if mime.is_empty() {
+        return Vec::new();
+    }
+
+    crate::tracker::desktop_entries::list_openers_for_mime(&mime)
+        .into_iter()
+        .map(|entry| AvailableEditor {
+            id: entry.id,
+            name: entry.name,
+            exec_template: entry.exec,
+            terminal: entry.terminal,
+        })
+        .collect()
+}
+
+/// macOS has no `xdg-mime`-style CLI for "what can open this file", so this
+/// asks Launch Services directly via `NSWorkspace.URLsForApplicationsToOpenURL`
+/// through an ASObjC `osascript` snippet - the same Cocoa bridging approach,
+/// rather than a fixed app-directory scan like `discover`'s.
+#[cfg(target_os = "macos")]
+pub fn list_openers(path: &std::path::Path) -> Vec<AvailableEditor> {
+    use std::process::Command;
+
+    let script = format!(
+        r#"use framework "Foundation"
+use framework "AppKit"
+set theURL to current application's NSURL's fileURLWithPath:"{}"
+set theApps to current application's NSWorkspace's sharedWorkspace()'s URLsForApplicationsToOpenURL:theURL
+set output to ""
+repeat with appURL in theApps
+    set output to output & (appURL's |path|() as text) & linefeed
+end repeat
+return output"#,
+        path.display()
+    );
+
+    let Ok(output) = Command::new("osascript").arg("-e").arg(&script).output() else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|app_path| {
+            let name = std::path::Path::new(app_path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| app_path.to_string());
+            AvailableEditor {
+                id: format!("external:{}", name.to_lowercase().replace(' ', "-")),
+                exec_template: format!("open -a \"{}\" %f", app_path),
+                name,
+                terminal: false,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,125 @@
+use std::path::Path;
+
+/// Broad category a file's extension falls into. Shared by `PathValidator`
+/// (to decide what's safe to open) and `workspace_mru::probe` (to decide what
+/// counts as a meaningful recency signal), so the two stay in sync instead of
+/// each maintaining its own ad-hoc extension list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileCategory {
+    SourceCode,
+    Image,
+    RawCameraFormat,
+    Archive,
+    Executable,
+    Unknown,
+}
+
+impl FileCategory {
+    /// Binary/generated artifacts whose mtime churn shouldn't be read as
+    /// "the user is actively working here" (a rebuilt `.zip` or a re-exported
+    /// `.png` doesn't mean someone touched the workspace by hand).
+    pub fn is_ignored_for_activity(self) -> bool {
+        matches!(
+            self,
+            FileCategory::Image | FileCategory::RawCameraFormat | FileCategory::Archive | FileCategory::Executable
+        )
+    }
+}
+
+static SOURCE_CODE_EXTENSIONS: &[&str] = &[
+    ".rs", ".ts", ".tsx", ".js", ".jsx", ".mjs", ".cjs", ".py", ".rb", ".go", ".java", ".kt",
+    ".kts", ".swift", ".c", ".h", ".cc", ".cpp", ".cxx", ".hpp", ".hxx", ".cs", ".php", ".scala",
+    ".sh", ".bash", ".zsh", ".ps1", ".lua", ".pl", ".r", ".sql", ".html", ".htm", ".css", ".scss",
+    ".sass", ".less", ".vue", ".svelte", ".json", ".yaml", ".yml", ".toml", ".xml", ".md",
+    ".proto", ".graphql", ".dart", ".ex", ".exs", ".erl", ".hs", ".clj", ".zig", ".vim",
+];
+
+static IMAGE_EXTENSIONS: &[&str] = &[
+    ".png", ".jpg", ".jpeg", ".gif", ".bmp", ".webp", ".tiff", ".tif", ".ico", ".heic", ".heif",
+    ".avif", ".svg",
+];
+
+/// Raw camera formats, the same exhaustive kind of table czkawka keeps for
+/// its duplicate-image scanner.
+static RAW_CAMERA_EXTENSIONS: &[&str] = &[
+    ".cr2", ".cr3", ".nef", ".nrw", ".arw", ".srf", ".sr2", ".orf", ".rw2", ".raf", ".dng",
+    ".pef", ".raw", ".rwl", ".3fr", ".erf", ".kdc", ".mrw", ".x3f",
+];
+
+static ARCHIVE_EXTENSIONS: &[&str] = &[
+    ".zip", ".tar", ".gz", ".tgz", ".bz2", ".xz", ".7z", ".rar", ".zst", ".iso", ".jar", ".war",
+];
+
+static EXECUTABLE_EXTENSIONS: &[&str] = &[
+    ".exe", ".bat", ".cmd", ".vbs", ".app", ".dmg", ".msi", ".com", ".scr",
+];
+
+/// The extension table behind `FileCategory::SourceCode`, shared so a
+/// consumer that wants "does this look like source code" without the rest
+/// of `classify`'s categories (`editors::mime`'s IDE-vs-lightweight-editor
+/// ranking) doesn't have to keep its own copy in sync.
+pub fn source_code_extensions() -> &'static [&'static str] {
+    SOURCE_CODE_EXTENSIONS
+}
+
+/// Classifies a path by its extension alone (case-insensitive); the file
+/// need not exist. Extensionless paths, or extensions not present in any
+/// table above, are `FileCategory::Unknown`.
+pub fn classify(path: &Path) -> FileCategory {
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return FileCategory::Unknown;
+    };
+    let ext = format!(".{}", ext.to_lowercase());
+
+    if EXECUTABLE_EXTENSIONS.contains(&ext.as_str()) {
+        FileCategory::Executable
+    } else if SOURCE_CODE_EXTENSIONS.contains(&ext.as_str()) {
+        FileCategory::SourceCode
+    } else if IMAGE_EXTENSIONS.contains(&ext.as_str()) {
+        FileCategory::Image
+    } else if RAW_CAMERA_EXTENSIONS.contains(&ext.as_str()) {
+        FileCategory::RawCameraFormat
+    } else if ARCHIVE_EXTENSIONS.contains(&ext.as_str()) {
+        FileCategory::Archive
+    } else {
+        FileCategory::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn classifies_source_code() {
+        assert_eq!(classify(Path::new("main.rs")), FileCategory::SourceCode);
+        assert_eq!(classify(Path::new("App.tsx")), FileCategory::SourceCode);
+    }
+
+    #[test]
+    fn classifies_images_and_raw_formats() {
+        assert_eq!(classify(Path::new("photo.PNG")), FileCategory::Image);
+        assert_eq!(classify(Path::new("shot.CR2")), FileCategory::RawCameraFormat);
+    }
+
+    #[test]
+    fn classifies_archives_and_executables() {
+        assert_eq!(classify(Path::new("bundle.tar.gz")), FileCategory::Archive);
+        assert_eq!(classify(Path::new("installer.exe")), FileCategory::Executable);
+    }
+
+    #[test]
+    fn unknown_for_extensionless_or_unlisted() {
+        assert_eq!(classify(Path::new("Makefile")), FileCategory::Unknown);
+        assert_eq!(classify(Path::new("notes.xyz")), FileCategory::Unknown);
+    }
+
+    #[test]
+    fn only_binary_categories_are_ignored_for_activity() {
+        assert!(FileCategory::Image.is_ignored_for_activity());
+        assert!(FileCategory::Executable.is_ignored_for_activity());
+        assert!(!FileCategory::SourceCode.is_ignored_for_activity());
+        assert!(!FileCategory::Unknown.is_ignored_for_activity());
+    }
+}
@@ -0,0 +1,383 @@
+use super::GIT_COMMAND_LOG;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Uniform result shape for a git operation regardless of which backend
+/// produced it, so callers and the command log don't need to know whether
+/// the operation shelled out to `git` or went through libgit2.
+#[derive(Debug, Clone)]
+pub struct GitBackendOutput {
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl GitBackendOutput {
+    fn ok(stdout: String) -> Self {
+        Self {
+            success: true,
+            exit_code: Some(0),
+            stdout,
+            stderr: String::new(),
+        }
+    }
+
+    fn err(stderr: String) -> Self {
+        Self {
+            success: false,
+            exit_code: Some(1),
+            stdout: String::new(),
+            stderr,
+        }
+    }
+}
+
+/// Which implementation satisfies `GitBackend` operations. Selectable at
+/// runtime so machines without a `git` binary on PATH can fall back to the
+/// vendored libgit2 bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitBackendKind {
+    Process,
+    Git2,
+}
+
+/// Common git operations, implemented once per backend. Every method logs a
+/// `GitCommandLogEntry` before returning, the same way `run_git_command`
+/// does, so the in-app log doesn't need to care which backend ran.
+pub trait GitBackend: Send + Sync {
+    fn kind(&self) -> GitBackendKind;
+    fn status(&self, working_dir: &str) -> GitBackendOutput;
+    fn fetch(&self, working_dir: &str, remote: &str) -> GitBackendOutput;
+    fn clone_repo(&self, url: &str, dest: &str) -> GitBackendOutput;
+    fn log(&self, working_dir: &str, max_count: usize) -> GitBackendOutput;
+    fn diff(&self, working_dir: &str) -> GitBackendOutput;
+}
+
+/// Returns the backend requested (callers that need libgit2 specifically
+/// should request `GitBackendKind::Git2`).
+pub fn backend_for(kind: GitBackendKind) -> Box<dyn GitBackend> {
+    match kind {
+        GitBackendKind::Process => Box::new(ProcessBackend),
+        GitBackendKind::Git2 => Box::new(Git2Backend),
+    }
+}
+
+static ACTIVE_BACKEND_KIND: OnceLock<GitBackendKind> = OnceLock::new();
+
+/// Which `GitBackendKind` operations should actually use, probed once (on
+/// first call) and cached for the life of the process: `Process` if a
+/// working `git` binary is on PATH, `Git2` otherwise - the fallback this
+/// module exists for, so a locked-down machine with no `git` CLI still
+/// gets a working status/fetch/clone/log/diff instead of every git
+/// operation failing with "No such file or directory".
+pub fn active_backend_kind() -> GitBackendKind {
+    *ACTIVE_BACKEND_KIND.get_or_init(probe_backend_kind)
+}
+
+fn probe_backend_kind() -> GitBackendKind {
+    let git_available = std::process::Command::new("git")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false);
+
+    if git_available {
+        GitBackendKind::Process
+    } else {
+        GitBackendKind::Git2
+    }
+}
+
+fn log_entry(
+    command: &str,
+    args: &[String],
+    working_dir: &str,
+    output: &GitBackendOutput,
+    duration: Duration,
+) {
+    if output.success {
+        let fake_output = std::process::Output {
+            status: exit_status(output.exit_code),
+            stdout: output.stdout.clone().into_bytes(),
+            stderr: output.stderr.clone().into_bytes(),
+        };
+        let args_ref: Vec<&str> = args.iter().map(String::as_str).collect();
+        GIT_COMMAND_LOG.log_command_with_type(
+            command,
+            &args_ref,
+            working_dir,
+            &fake_output,
+            duration,
+            "git2",
+        );
+    } else {
+        let args_ref: Vec<&str> = args.iter().map(String::as_str).collect();
+        GIT_COMMAND_LOG.log_error_with_type(
+            command,
+            &args_ref,
+            working_dir,
+            &output.stderr,
+            duration,
+            "git2",
+        );
+    }
+}
+
+#[cfg(unix)]
+fn exit_status(code: Option<i32>) -> std::process::ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+    std::process::ExitStatus::from_raw(code.unwrap_or(1) << 8)
+}
+
+#[cfg(windows)]
+fn exit_status(code: Option<i32>) -> std::process::ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+    std::process::ExitStatus::from_raw(code.unwrap_or(1) as u32)
+}
+
+/// Shells out to the `git` binary, as `run_git_command` already did. Kept as
+/// the default backend since it requires no behavior changes for users with
+/// a working `git` install.
+pub struct ProcessBackend;
+
+impl ProcessBackend {
+    fn run(&self, working_dir: &str, args: &[&str]) -> GitBackendOutput {
+        match std::process::Command::new("git")
+            .current_dir(working_dir)
+            .args(args)
+            .output()
+        {
+            Ok(output) => GitBackendOutput {
+                success: output.status.success(),
+                exit_code: output.status.code(),
+                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            },
+            Err(e) => GitBackendOutput::err(e.to_string()),
+        }
+    }
+}
+
+impl GitBackend for ProcessBackend {
+    fn kind(&self) -> GitBackendKind {
+        GitBackendKind::Process
+    }
+
+    fn status(&self, working_dir: &str) -> GitBackendOutput {
+        let start = Instant::now();
+        let output = self.run(working_dir, &["status", "--porcelain"]);
+        log_entry(
+            "status",
+            &["--porcelain".to_string()],
+            working_dir,
+            &output,
+            start.elapsed(),
+        );
+        output
+    }
+
+    fn fetch(&self, working_dir: &str, remote: &str) -> GitBackendOutput {
+        let start = Instant::now();
+        let output = self.run(working_dir, &["fetch", remote]);
+        log_entry(
+            "fetch",
+            &[remote.to_string()],
+            working_dir,
+            &output,
+            start.elapsed(),
+        );
+        output
+    }
+
+    fn clone_repo(&self, url: &str, dest: &str) -> GitBackendOutput {
+        let start = Instant::now();
+        let output = self.run(".", &["clone", url, dest]);
+        log_entry(
+            "clone",
+            &[url.to_string(), dest.to_string()],
+            ".",
+            &output,
+            start.elapsed(),
+        );
+        output
+    }
+
+    fn log(&self, working_dir: &str, max_count: usize) -> GitBackendOutput {
+        let start = Instant::now();
+        let max_count_arg = format!("-{}", max_count);
+        let output = self.run(working_dir, &["log", &max_count_arg, "--oneline"]);
+        log_entry(
+            "log",
+            &[max_count_arg],
+            working_dir,
+            &output,
+            start.elapsed(),
+        );
+        output
+    }
+
+    fn diff(&self, working_dir: &str) -> GitBackendOutput {
+        let start = Instant::now();
+        let output = self.run(working_dir, &["diff"]);
+        log_entry("diff", &[], working_dir, &output, start.elapsed());
+        output
+    }
+}
+
+/// Runs git operations through libgit2 rather than spawning a `git`
+/// subprocess. Useful on machines that are locked down or simply don't have
+/// a `git` binary on PATH.
+pub struct Git2Backend;
+
+impl Git2Backend {
+    fn open(working_dir: &str) -> Result<git2::Repository, git2::Error> {
+        git2::Repository::discover(working_dir)
+    }
+}
+
+impl GitBackend for Git2Backend {
+    fn kind(&self) -> GitBackendKind {
+        GitBackendKind::Git2
+    }
+
+    /// Emits `git status --porcelain`-shaped lines (`"?? path"` for
+    /// untracked, `" M path"` for anything else) rather than `Status`'s own
+    /// `Debug` format, so `GitHandler::get_working_tree_status` can parse
+    /// this output with the exact same code it already uses for
+    /// `ProcessBackend`'s real `git status --porcelain`.
+    fn status(&self, working_dir: &str) -> GitBackendOutput {
+        let start = Instant::now();
+        let output = (|| -> Result<String, git2::Error> {
+            let repo = Self::open(working_dir)?;
+            let mut opts = git2::StatusOptions::new();
+            opts.include_untracked(true);
+            let statuses = repo.statuses(Some(&mut opts))?;
+
+            let mut lines = String::new();
+            for entry in statuses.iter() {
+                if let Some(path) = entry.path() {
+                    let code = if entry.status().contains(git2::Status::WT_NEW) {
+                        "??"
+                    } else {
+                        " M"
+                    };
+                    lines.push_str(&format!("{} {}\n", code, path));
+                }
+            }
+            Ok(lines)
+        })()
+        .map_or_else(
+            |e| GitBackendOutput::err(e.to_string()),
+            GitBackendOutput::ok,
+        );
+
+        log_entry(
+            "status",
+            &["--porcelain".to_string()],
+            working_dir,
+            &output,
+            start.elapsed(),
+        );
+        output
+    }
+
+    fn fetch(&self, working_dir: &str, remote: &str) -> GitBackendOutput {
+        let start = Instant::now();
+        let output = (|| -> Result<String, git2::Error> {
+            let repo = Self::open(working_dir)?;
+            let mut remote_handle = repo.find_remote(remote)?;
+            remote_handle.fetch(&[] as &[&str], None, None)?;
+            Ok(format!("Fetched from {}", remote))
+        })()
+        .map_or_else(
+            |e| GitBackendOutput::err(e.to_string()),
+            GitBackendOutput::ok,
+        );
+
+        log_entry(
+            "fetch",
+            &[remote.to_string()],
+            working_dir,
+            &output,
+            start.elapsed(),
+        );
+        output
+    }
+
+    fn clone_repo(&self, url: &str, dest: &str) -> GitBackendOutput {
+        let start = Instant::now();
+        let output = git2::Repository::clone(url, Path::new(dest)).map_or_else(
+            |e| GitBackendOutput::err(e.to_string()),
+            |_| GitBackendOutput::ok(format!("Cloned {} into {}", url, dest)),
+        );
+
+        log_entry(
+            "clone",
+            &[url.to_string(), dest.to_string()],
+            ".",
+            &output,
+            start.elapsed(),
+        );
+        output
+    }
+
+    fn log(&self, working_dir: &str, max_count: usize) -> GitBackendOutput {
+        let start = Instant::now();
+        let output = (|| -> Result<String, git2::Error> {
+            let repo = Self::open(working_dir)?;
+            let mut revwalk = repo.revwalk()?;
+            revwalk.push_head()?;
+
+            let mut lines = String::new();
+            for oid in revwalk.take(max_count) {
+                let oid = oid?;
+                let commit = repo.find_commit(oid)?;
+                let summary = commit.summary().unwrap_or("").to_string();
+                lines.push_str(&format!("{:.7} {}\n", oid, summary));
+            }
+            Ok(lines)
+        })()
+        .map_or_else(
+            |e| GitBackendOutput::err(e.to_string()),
+            GitBackendOutput::ok,
+        );
+
+        log_entry(
+            "log",
+            &[format!("-{}", max_count)],
+            working_dir,
+            &output,
+            start.elapsed(),
+        );
+        output
+    }
+
+    fn diff(&self, working_dir: &str) -> GitBackendOutput {
+        let start = Instant::now();
+        let output = (|| -> Result<String, git2::Error> {
+            let repo = Self::open(working_dir)?;
+            let diff = repo.diff_index_to_workdir(None, None)?;
+
+            let mut text = String::new();
+            diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
+                if let Ok(content) = std::str::from_utf8(line.content()) {
+                    text.push_str(content);
+                }
+                true
+            })?;
+            Ok(text)
+        })()
+        .map_or_else(
+            |e| GitBackendOutput::err(e.to_string()),
+            GitBackendOutput::ok,
+        );
+
+        log_entry("diff", &[], working_dir, &output, start.elapsed());
+        output
+    }
+}
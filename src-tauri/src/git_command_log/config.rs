@@ -0,0 +1,131 @@
+use serde::Deserialize;
+use std::path::PathBuf;
+use tracing::warn;
+
+use super::MAX_HISTORY_ENTRIES;
+
+const CONFIG_FILE_NAME: &str = "sorcery.toml";
+
+/// Tunables for `GitCommandLog`, loaded from a project-local `sorcery.toml`
+/// if one exists. Every field falls back to today's hard-coded defaults so
+/// an absent file changes nothing.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LogConfig {
+    /// Capacity of the in-memory ring buffer (`MAX_LOG_ENTRIES` today).
+    pub capacity: usize,
+    /// `command_type`s to keep; entries of any other type are dropped before
+    /// being stored. Empty means "allow everything".
+    pub allow_command_types: Vec<String>,
+    /// `command_type`s to suppress, e.g. noisy `"request"` or `"editor"`
+    /// entries. Applied after `allow_command_types`.
+    pub deny_command_types: Vec<String>,
+    /// Whether credential redaction runs before entries are stored.
+    pub redact: bool,
+    /// Rolling window kept in the on-disk history file.
+    pub history_max_entries: usize,
+    /// Byte cap that triggers history file rotation.
+    pub history_max_bytes: u64,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            capacity: super::MAX_LOG_ENTRIES,
+            allow_command_types: Vec::new(),
+            deny_command_types: Vec::new(),
+            redact: true,
+            history_max_entries: MAX_HISTORY_ENTRIES,
+            history_max_bytes: super::MAX_HISTORY_FILE_BYTES,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct LogConfigFile {
+    #[serde(default)]
+    log: LogConfigSection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct LogConfigSection {
+    capacity: usize,
+    allow_command_types: Vec<String>,
+    deny_command_types: Vec<String>,
+    redact: bool,
+    history_max_entries: usize,
+    history_max_bytes: u64,
+}
+
+impl Default for LogConfigSection {
+    fn default() -> Self {
+        let defaults = LogConfig::default();
+        Self {
+            capacity: defaults.capacity,
+            allow_command_types: defaults.allow_command_types,
+            deny_command_types: defaults.deny_command_types,
+            redact: defaults.redact,
+            history_max_entries: defaults.history_max_entries,
+            history_max_bytes: defaults.history_max_bytes,
+        }
+    }
+}
+
+impl LogConfig {
+    /// Looks for `sorcery.toml` in the platform config directory and parses
+    /// its `[log]` section. Falls back to `LogConfig::default()` when the
+    /// file is absent or fails to parse.
+    pub fn load() -> Self {
+        let Some(path) = Self::config_path() else {
+            return Self::default();
+        };
+
+        Self::load_from(&path)
+    }
+
+    fn config_path() -> Option<PathBuf> {
+        let config_dir = dirs::config_dir()?;
+        Some(config_dir.join("sorcery-desktop").join(CONFIG_FILE_NAME))
+    }
+
+    fn load_from(path: &std::path::Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+
+        let contents = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) => {
+                warn!("Failed to read {}: {}", path.display(), e);
+                return Self::default();
+            }
+        };
+
+        match toml::from_str::<LogConfigFile>(&contents) {
+            Ok(file) => LogConfig {
+                capacity: file.log.capacity,
+                allow_command_types: file.log.allow_command_types,
+                deny_command_types: file.log.deny_command_types,
+                redact: file.log.redact,
+                history_max_entries: file.log.history_max_entries,
+                history_max_bytes: file.log.history_max_bytes,
+            },
+            Err(e) => {
+                warn!("Failed to parse {}: {}, using defaults", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Whether an entry of `command_type` should be kept under this config.
+    pub fn allows_command_type(&self, command_type: &str) -> bool {
+        if !self.allow_command_types.is_empty()
+            && !self.allow_command_types.iter().any(|t| t == command_type)
+        {
+            return false;
+        }
+
+        !self.deny_command_types.iter().any(|t| t == command_type)
+    }
+}
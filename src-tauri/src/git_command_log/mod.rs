@@ -1,14 +1,36 @@
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 use std::process::Output;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tracing::warn;
+
+mod backend;
+mod config;
+mod redaction;
+mod search;
+mod streaming;
+pub use backend::{active_backend_kind, backend_for, GitBackend, GitBackendKind, GitBackendOutput};
+pub use config::LogConfig;
+pub use redaction::redact;
+pub use streaming::{run_git_command_streaming, CancelToken, GitProgressEvent};
 
 const MAX_LOG_ENTRIES: usize = 30;
 
-#[derive(Debug, Clone, Serialize)]
+/// Rolling window kept in the on-disk history file, separate from the smaller
+/// in-memory ring buffer used for the live "recent activity" view.
+const MAX_HISTORY_ENTRIES: usize = 5000;
+
+/// Once the history file exceeds this size, it is rewritten with only the
+/// most recent `MAX_HISTORY_ENTRIES` entries.
+const MAX_HISTORY_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitCommandLogEntry {
     pub timestamp: DateTime<Utc>,
     pub command: String,
@@ -25,12 +47,112 @@ pub struct GitCommandLogEntry {
 
 pub struct GitCommandLog {
     entries: Mutex<VecDeque<GitCommandLogEntry>>,
+    history_path: Option<PathBuf>,
+    config: LogConfig,
 }
 
 impl GitCommandLog {
     pub fn new() -> Self {
+        Self::with_config(LogConfig::load())
+    }
+
+    pub fn with_config(config: LogConfig) -> Self {
+        let history_path = Self::history_file_path();
+
+        let seeded = history_path
+            .as_deref()
+            .map(|path| Self::load_tail_capped(path, config.capacity))
+            .unwrap_or_default();
+
         Self {
-            entries: Mutex::new(VecDeque::with_capacity(MAX_LOG_ENTRIES)),
+            entries: Mutex::new(seeded),
+            history_path,
+            config,
+        }
+    }
+
+    fn history_file_path() -> Option<PathBuf> {
+        let config_dir = dirs::config_dir()?;
+        let sorcery_dir = config_dir.join("sorcery-desktop");
+        if let Err(e) = std::fs::create_dir_all(&sorcery_dir) {
+            warn!("Failed to create sorcery-desktop config directory: {}", e);
+            return None;
+        }
+        Some(sorcery_dir.join("git_command_history.jsonl"))
+    }
+
+    /// Reads the trailing `cap` entries from the history file to seed the
+    /// in-memory ring buffer, skipping any malformed lines rather than
+    /// failing the whole load.
+    fn load_tail_capped(path: &Path, cap: usize) -> VecDeque<GitCommandLogEntry> {
+        let file = match std::fs::File::open(path) {
+            Ok(f) => f,
+            Err(_) => return VecDeque::with_capacity(cap),
+        };
+
+        let mut entries: VecDeque<GitCommandLogEntry> = VecDeque::with_capacity(cap);
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<GitCommandLogEntry>(&line) {
+                Ok(entry) => {
+                    if entries.len() >= cap {
+                        entries.pop_front();
+                    }
+                    entries.push_back(entry);
+                }
+                Err(e) => warn!("Skipping malformed git command history line: {}", e),
+            }
+        }
+
+        entries
+    }
+
+    /// Appends one entry to the history file as a single JSON line, rotating
+    /// the file when it grows past `MAX_HISTORY_FILE_BYTES` by keeping only
+    /// the most recent `MAX_HISTORY_ENTRIES` lines. Must be called with
+    /// `entries` already locked so writes can't interleave.
+    fn append_to_history(&self, entry: &GitCommandLogEntry) {
+        let Some(path) = self.history_path.as_ref() else {
+            return;
+        };
+
+        if let Ok(metadata) = std::fs::metadata(path) {
+            if metadata.len() > self.config.history_max_bytes {
+                self.rotate_history(path);
+            }
+        }
+
+        let line = match serde_json::to_string(entry) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("Failed to serialize git command log entry: {}", e);
+                return;
+            }
+        };
+
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", line));
+
+        if let Err(e) = result {
+            warn!("Failed to append to git command history file: {}", e);
+        }
+    }
+
+    fn rotate_history(&self, path: &Path) {
+        let tail = Self::load_tail_capped(path, self.config.history_max_entries);
+        let rewritten: String = tail
+            .iter()
+            .filter_map(|e| serde_json::to_string(e).ok())
+            .map(|line| line + "\n")
+            .collect();
+
+        if let Err(e) = std::fs::write(path, rewritten) {
+            warn!("Failed to rotate git command history file: {}", e);
         }
     }
 
@@ -54,13 +176,17 @@ impl GitCommandLog {
         duration: Duration,
         command_type: &str,
     ) {
+        if !self.config.allows_command_type(command_type) {
+            return;
+        }
+
         let mut entries = self.entries.lock();
 
-        if entries.len() >= MAX_LOG_ENTRIES {
+        if entries.len() >= self.config.capacity {
             entries.pop_front();
         }
 
-        let entry = GitCommandLogEntry {
+        let mut entry = GitCommandLogEntry {
             timestamp: Utc::now(),
             command: command.to_string(),
             args: args.iter().map(|s| s.to_string()).collect(),
@@ -73,7 +199,13 @@ impl GitCommandLog {
             command_type: command_type.to_string(),
         };
 
-        entries.push_back(entry);
+        if self.config.redact {
+            redaction::redact_entry(&mut entry);
+        }
+
+        entries.push_back(entry.clone());
+        drop(entries);
+        self.append_to_history(&entry);
     }
 
     pub fn log_error(
@@ -96,13 +228,17 @@ impl GitCommandLog {
         duration: Duration,
         command_type: &str,
     ) {
+        if !self.config.allows_command_type(command_type) {
+            return;
+        }
+
         let mut entries = self.entries.lock();
 
-        if entries.len() >= MAX_LOG_ENTRIES {
+        if entries.len() >= self.config.capacity {
             entries.pop_front();
         }
 
-        let entry = GitCommandLogEntry {
+        let mut entry = GitCommandLogEntry {
             timestamp: Utc::now(),
             command: command.to_string(),
             args: args.iter().map(|s| s.to_string()).collect(),
@@ -115,7 +251,13 @@ impl GitCommandLog {
             command_type: command_type.to_string(),
         };
 
-        entries.push_back(entry);
+        if self.config.redact {
+            redaction::redact_entry(&mut entry);
+        }
+
+        entries.push_back(entry.clone());
+        drop(entries);
+        self.append_to_history(&entry);
     }
 
     pub fn log_editor_launch(
@@ -127,9 +269,13 @@ impl GitCommandLog {
         error: Option<&str>,
         duration: Duration,
     ) {
+        if !self.config.allows_command_type("editor") {
+            return;
+        }
+
         let mut entries = self.entries.lock();
 
-        if entries.len() >= MAX_LOG_ENTRIES {
+        if entries.len() >= self.config.capacity {
             entries.pop_front();
         }
 
@@ -138,7 +284,7 @@ impl GitCommandLog {
             args.push(format!("--line {}", l));
         }
 
-        let entry = GitCommandLogEntry {
+        let mut entry = GitCommandLogEntry {
             timestamp: Utc::now(),
             command: format!("open-{}", editor),
             args,
@@ -155,7 +301,13 @@ impl GitCommandLog {
             command_type: "editor".to_string(),
         };
 
-        entries.push_back(entry);
+        if self.config.redact {
+            redaction::redact_entry(&mut entry);
+        }
+
+        entries.push_back(entry.clone());
+        drop(entries);
+        self.append_to_history(&entry);
     }
 
     pub fn log_request(
@@ -166,13 +318,17 @@ impl GitCommandLog {
         details: &str,
         duration: Duration,
     ) {
+        if !self.config.allows_command_type("request") {
+            return;
+        }
+
         let mut entries = self.entries.lock();
 
-        if entries.len() >= MAX_LOG_ENTRIES {
+        if entries.len() >= self.config.capacity {
             entries.pop_front();
         }
 
-        let entry = GitCommandLogEntry {
+        let mut entry = GitCommandLogEntry {
             timestamp: Utc::now(),
             command: url.to_string(),
             args: vec![result.to_string()],
@@ -193,12 +349,39 @@ impl GitCommandLog {
             command_type: "request".to_string(),
         };
 
-        entries.push_back(entry);
+        if self.config.redact {
+            redaction::redact_entry(&mut entry);
+        }
+
+        entries.push_back(entry.clone());
+        drop(entries);
+        self.append_to_history(&entry);
     }
 
     pub fn get_entries(&self) -> Vec<GitCommandLogEntry> {
         self.entries.lock().iter().cloned().collect()
     }
+
+    /// Fuzzy-ranks buffered entries against `query` over their `command`,
+    /// joined `args`, and `working_dir`, fzf-style. Only matching entries are
+    /// returned, sorted by descending score with ties broken by most-recent
+    /// timestamp first.
+    pub fn search(&self, query: &str) -> Vec<(GitCommandLogEntry, i64)> {
+        let mut scored: Vec<(GitCommandLogEntry, i64)> = self
+            .entries
+            .lock()
+            .iter()
+            .filter_map(|entry| search::score_entry(entry, query).map(|score| (entry.clone(), score)))
+            .collect();
+
+        scored.sort_by(|(entry_a, score_a), (entry_b, score_b)| {
+            score_b
+                .cmp(score_a)
+                .then_with(|| entry_b.timestamp.cmp(&entry_a.timestamp))
+        });
+
+        scored
+    }
 }
 
 lazy_static::lazy_static! {
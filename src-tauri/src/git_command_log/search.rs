@@ -0,0 +1,92 @@
+const CONSECUTIVE_BONUS: i64 = 5;
+const WORD_BOUNDARY_BONUS: i64 = 10;
+const BASE_SCORE: i64 = 1;
+
+/// fzf-style subsequence match: walks `candidate` trying to consume the
+/// characters of `query`, in order, case-insensitively. Returns `None` if
+/// not every query character could be matched, otherwise the accumulated
+/// score (higher is a better match).
+fn fuzzy_score(candidate: &str, query: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let query_chars: Vec<char> = query.chars().collect();
+
+    let mut score = 0i64;
+    let mut query_idx = 0;
+    let mut prev_matched = false;
+
+    for (i, &c) in candidate_chars.iter().enumerate() {
+        if query_idx >= query_chars.len() {
+            break;
+        }
+
+        if c.to_ascii_lowercase() == query_chars[query_idx].to_ascii_lowercase() {
+            score += BASE_SCORE;
+
+            if prev_matched {
+                score += CONSECUTIVE_BONUS;
+            }
+
+            let at_word_boundary = i == 0
+                || matches!(candidate_chars[i - 1], '/' | '-' | ' ' | '_');
+            if at_word_boundary {
+                score += WORD_BOUNDARY_BONUS;
+            }
+
+            query_idx += 1;
+            prev_matched = true;
+        } else {
+            prev_matched = false;
+        }
+    }
+
+    if query_idx == query_chars.len() {
+        Some(score)
+    } else {
+        None
+    }
+}
+
+pub(super) fn score_entry(entry: &super::GitCommandLogEntry, query: &str) -> Option<i64> {
+    let haystack = format!(
+        "{} {} {}",
+        entry.command,
+        entry.args.join(" "),
+        entry.working_dir
+    );
+
+    fuzzy_score(&haystack, query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_in_order_subsequence() {
+        assert!(fuzzy_score("rebase", "rbs").is_some());
+        assert!(fuzzy_score("rebase", "ser").is_none());
+    }
+
+    #[test]
+    fn rewards_word_boundary_matches() {
+        let boundary = fuzzy_score("my-repo/rebase", "r").unwrap();
+        let mid_word = fuzzy_score("xrebase", "r").unwrap();
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn rewards_consecutive_matches() {
+        let consecutive = fuzzy_score("rebase", "re").unwrap();
+        let scattered = fuzzy_score("r_e", "re").unwrap();
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn empty_query_matches_everything_with_zero_score() {
+        assert_eq!(fuzzy_score("anything", ""), Some(0));
+    }
+}
@@ -0,0 +1,142 @@
+use super::{redaction, GIT_COMMAND_LOG};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Shared by caller and the streaming task to request early termination of
+/// the child process, e.g. when the user closes the clone dialog.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// One line of incremental output from a streaming git command, along with
+/// the percentage git reports for "Receiving objects" / "Resolving deltas"
+/// phases when present.
+#[derive(Debug, Clone, Serialize)]
+pub struct GitProgressEvent {
+    pub line: String,
+    pub percent: Option<u8>,
+}
+
+static PROGRESS_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(Receiving objects|Resolving deltas):\s+(\d{1,3})%").unwrap());
+
+fn parse_progress(line: &str) -> Option<u8> {
+    PROGRESS_RE
+        .captures(line)
+        .and_then(|caps| caps.get(2))
+        .and_then(|m| m.as_str().parse::<u8>().ok())
+}
+
+async fn forward_lines<R: tokio::io::AsyncRead + Unpin>(
+    reader: R,
+    progress_tx: mpsc::UnboundedSender<GitProgressEvent>,
+    collected: Arc<parking_lot::Mutex<String>>,
+) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        collected.lock().push_str(&line);
+        collected.lock().push('\n');
+        // Forwarded immediately to the UI, well before the aggregated
+        // `GitCommandLogEntry` this line feeds into gets redacted below -
+        // redact here too so a credential embedded in the clone URL (e.g.
+        // git's own "Cloning into ... user:token@host" progress line)
+        // never reaches a subscriber unredacted.
+        let redacted_line = redaction::redact(&line);
+        let _ = progress_tx.send(GitProgressEvent {
+            percent: parse_progress(&line),
+            line: redacted_line,
+        });
+    }
+}
+
+/// Runs a git command with piped stdout/stderr, forwarding each line (plus
+/// any parsed clone/fetch progress percentage) over `progress_tx` as it
+/// arrives, instead of blocking until the process exits like
+/// `run_git_command` does. Honors `cancel` by killing the child as soon as
+/// it's observed, and still records one final `GitCommandLogEntry` with the
+/// aggregated output and duration when the process ends.
+pub async fn run_git_command_streaming(
+    working_dir: &str,
+    args: &[&str],
+    progress_tx: mpsc::UnboundedSender<GitProgressEvent>,
+    cancel: CancelToken,
+) -> std::io::Result<std::process::ExitStatus> {
+    let start = Instant::now();
+
+    let mut child = Command::new("git")
+        .current_dir(working_dir)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = child.stdout.take().expect("stdout was piped");
+    let stderr = child.stderr.take().expect("stderr was piped");
+
+    let collected_stdout = Arc::new(parking_lot::Mutex::new(String::new()));
+    let collected_stderr = Arc::new(parking_lot::Mutex::new(String::new()));
+
+    let stdout_task = tokio::spawn(forward_lines(
+        stdout,
+        progress_tx.clone(),
+        collected_stdout.clone(),
+    ));
+    let stderr_task = tokio::spawn(forward_lines(
+        stderr,
+        progress_tx.clone(),
+        collected_stderr.clone(),
+    ));
+
+    let status = loop {
+        tokio::select! {
+            status = child.wait() => {
+                break status?;
+            }
+            _ = tokio::time::sleep(CANCEL_POLL_INTERVAL) => {
+                if cancel.is_cancelled() {
+                    debug!("Cancellation requested, killing git child process");
+                    let _ = child.kill().await;
+                    break child.wait().await?;
+                }
+            }
+        }
+    };
+
+    let _ = stdout_task.await;
+    let _ = stderr_task.await;
+
+    let duration = start.elapsed();
+    let fake_output = std::process::Output {
+        status,
+        stdout: collected_stdout.lock().clone().into_bytes(),
+        stderr: collected_stderr.lock().clone().into_bytes(),
+    };
+
+    GIT_COMMAND_LOG.log_command("git", args, working_dir, &fake_output, duration);
+
+    Ok(status)
+}
@@ -0,0 +1,219 @@
+//! Bulk-clone an entire GitHub org/user into the configured source folder.
+//!
+//! This is the "populate my source folder from a whole organization" path
+//! that sits behind `HandleResult::ShowBulkCloneDialog`: page the host's
+//! REST API to enumerate `owner`'s repositories, then fan `GitHandler::clone`
+//! out across them and register each success as an explicit `WorkspaceConfig`,
+//! the same way the single-repo `clone_and_open` command does for one repo.
+
+use crate::git_command_log::{CancelToken, GIT_COMMAND_LOG};
+use crate::protocol_handler::GitHandler;
+use crate::settings::{NormalizedPath, SettingsManager, WorkspaceConfig};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+const PER_PAGE: u32 = 100;
+
+/// Clones run this many at a time so bulk-cloning a large org doesn't spawn
+/// hundreds of simultaneous git processes.
+const CLONE_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BulkCloneOptions {
+    #[serde(default)]
+    pub include_forks: bool,
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+/// Mirrors `WorkspaceSync`'s `SyncResult` shape: the UI renders the same
+/// "N added, M skipped" summary either way.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BulkCloneSummary {
+    pub cloned: Vec<String>,
+    pub skipped: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteRepo {
+    name: String,
+    clone_url: String,
+    fork: bool,
+    archived: bool,
+}
+
+/// Enumerates `owner`'s repositories on `host`, paging until the API
+/// returns a short page. Tries `orgs` before `users` since GitHub 404s
+/// (rather than redirecting) when an org-shaped request hits a plain user
+/// account.
+async fn list_owner_repos(host: &str, owner: &str, token: Option<&str>) -> Result<Vec<RemoteRepo>> {
+    let client = reqwest::Client::builder()
+        .user_agent("sorcery-desktop")
+        .build()
+        .context("Failed to build HTTP client")?;
+
+    let api_base = if host == "github.com" {
+        GITHUB_API_BASE.to_string()
+    } else {
+        format!("https://{}/api/v3", host)
+    };
+
+    for kind in ["orgs", "users"] {
+        let mut repos = Vec::new();
+        let mut page = 1u32;
+        let mut saw_page = false;
+
+        loop {
+            let url = format!(
+                "{}/{}/{}/repos?per_page={}&page={}",
+                api_base, kind, owner, PER_PAGE, page
+            );
+
+            let mut request = client.get(&url).header("Accept", "application/vnd.github+json");
+            if let Some(token) = token {
+                request = request.bearer_auth(token);
+            }
+
+            let response = request.send().await.context("GitHub API request failed")?;
+            if !response.status().is_success() {
+                break;
+            }
+
+            let batch: Vec<RemoteRepo> = response
+                .json()
+                .await
+                .context("Failed to parse GitHub API response")?;
+            saw_page = true;
+            let is_last_page = batch.len() < PER_PAGE as usize;
+            repos.extend(batch);
+
+            if is_last_page {
+                break;
+            }
+            page += 1;
+        }
+
+        if saw_page {
+            return Ok(repos);
+        }
+    }
+
+    bail!("'{}' was not found as an org or user on {}", owner, host)
+}
+
+/// Clones every eligible repo returned for `owner` on `host` into
+/// `settings.defaults.default_workspaces_folder`, skipping forks/archived
+/// repos per `options` and any repo that already has a workspace
+/// registered under that name. Registers each clone as a new
+/// `WorkspaceConfig` once all clones have settled.
+pub async fn bulk_clone(
+    settings_manager: &SettingsManager,
+    host: &str,
+    owner: &str,
+    options: &BulkCloneOptions,
+) -> Result<BulkCloneSummary> {
+    let token = settings_manager.get_git_host_token(host).await;
+    let repos = list_owner_repos(host, owner, token.as_deref()).await?;
+
+    let settings = settings_manager.get().await;
+    let repo_base = shellexpand::tilde(&settings.defaults.default_workspaces_folder);
+    let base_dir = PathBuf::from(repo_base.as_ref());
+    let existing_names: HashSet<String> = settings
+        .workspaces
+        .iter()
+        .filter_map(|ws| ws.name.clone())
+        .collect();
+    drop(settings);
+
+    let mut summary = BulkCloneSummary::default();
+    let mut pending = Vec::new();
+
+    for repo in repos {
+        if repo.fork && !options.include_forks {
+            summary.skipped.push(repo.name);
+        } else if repo.archived && !options.include_archived {
+            summary.skipped.push(repo.name);
+        } else if existing_names.contains(&repo.name) {
+            summary.skipped.push(repo.name);
+        } else {
+            pending.push(repo);
+        }
+    }
+
+    let semaphore = Arc::new(Semaphore::new(CLONE_CONCURRENCY));
+    let mut tasks = Vec::with_capacity(pending.len());
+
+    for repo in pending {
+        let semaphore = semaphore.clone();
+        let base_dir = base_dir.clone();
+        tasks.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            let result = clone_one(&repo, &base_dir).await;
+            (repo.name, result)
+        }));
+    }
+
+    for task in tasks {
+        match task.await {
+            Ok((name, Ok(()))) => summary.cloned.push(name),
+            Ok((name, Err(_))) => summary.failed.push(name),
+            Err(e) => tracing::error!("Bulk clone task panicked: {}", e),
+        }
+    }
+
+    if !summary.cloned.is_empty() {
+        let mut settings = settings_manager.get().await;
+        for name in &summary.cloned {
+            let target_path = base_dir.join(name);
+            settings.workspaces.push(WorkspaceConfig {
+                path: target_path.to_string_lossy().to_string(),
+                name: Some(name.clone()),
+                editor: String::new(),
+                auto_discovered: false,
+                enable_paths: None,
+                disable_paths: None,
+                tags: Vec::new(),
+                normalized_path: Some(NormalizedPath::from_existing(target_path)),
+            });
+        }
+        settings_manager.save(settings).await?;
+    }
+
+    Ok(summary)
+}
+
+async fn clone_one(repo: &RemoteRepo, base_dir: &Path) -> Result<()> {
+    let target_path = base_dir.join(&repo.name);
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(async move { while progress_rx.recv().await.is_some() {} });
+
+    let start = std::time::Instant::now();
+    let result = GitHandler::clone(
+        &repo.clone_url,
+        &target_path,
+        None,
+        progress_tx,
+        CancelToken::new(),
+    )
+    .await;
+    let duration = start.elapsed();
+
+    GIT_COMMAND_LOG.log_request(
+        &repo.clone_url,
+        result.is_ok(),
+        "bulk_clone",
+        &match &result {
+            Ok(()) => format!("Cloned {} to {}", repo.name, target_path.display()),
+            Err(e) => e.to_string(),
+        },
+        duration,
+    );
+
+    result
+}
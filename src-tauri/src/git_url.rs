@@ -0,0 +1,171 @@
+//! Shorthand expansion for the deep-link clone path: lets a `srcuri://`
+//! link or a pasted-in clone box entry be `owner/repo` or `gh:owner/repo`
+//! instead of a full clone URL, the same convenience `go/`-style shortlinks
+//! give engineers for internal tools.
+
+use crate::settings::GitVendorConfig;
+use std::collections::HashMap;
+
+/// Prefix -> (host, prefer SSH) for the providers everyone already expects
+/// a shortlink for. `custom_git_vendors` in settings extends this list
+/// (and can override an entry, e.g. a self-hosted `gh:`).
+const BUILTIN_VENDORS: &[(&str, &str, bool)] = &[
+    ("gh", "github.com", false),
+    ("gl", "gitlab.com", false),
+    ("bb", "bitbucket.org", false),
+];
+
+fn lookup_vendor(prefix: &str, custom_vendors: &HashMap<String, GitVendorConfig>) -> Option<(String, bool)> {
+    if let Some(config) = custom_vendors.get(prefix) {
+        return Some((config.host.clone(), config.use_ssh));
+    }
+
+    BUILTIN_VENDORS
+        .iter()
+        .find(|(p, _, _)| *p == prefix)
+        .map(|(_, host, use_ssh)| (host.to_string(), *use_ssh))
+}
+
+fn is_complete_url(remote_url: &str) -> bool {
+    remote_url.starts_with("https://")
+        || remote_url.starts_with("http://")
+        || remote_url.starts_with("git@")
+        || remote_url.starts_with("ssh://")
+        || remote_url.starts_with("git://")
+        || remote_url.starts_with("file://")
+        || remote_url.starts_with('/')
+}
+
+fn build_url(host: &str, path: &str, use_ssh: bool) -> String {
+    if use_ssh {
+        format!("git@{}:{}.git", host, path)
+    } else {
+        format!("https://{}/{}", host, path)
+    }
+}
+
+/// Looks like a bare `owner/repo` (or `group/subgroup/repo`) shorthand
+/// rather than a host name: no scheme, no `.` before the first `/` (which
+/// would make it look like a domain), and at least one `/`.
+fn looks_like_owner_repo(remote_url: &str) -> bool {
+    match remote_url.split_once('/') {
+        Some((first_segment, rest)) => {
+            !first_segment.contains('.') && !first_segment.contains(':') && !rest.is_empty()
+        }
+        None => false,
+    }
+}
+
+/// Expands `remote_url` into a full clone URL, leaving anything that's
+/// already a complete URL (or that doesn't match a recognized shorthand)
+/// unchanged. Recognizes `owner/repo` (defaults to GitHub), `gh:`/`gl:`/`bb:`
+/// prefixes, and any prefix registered in `custom_git_vendors`.
+pub fn expand(remote_url: &str, custom_vendors: &HashMap<String, GitVendorConfig>) -> String {
+    let remote_url = remote_url.trim();
+
+    if is_complete_url(remote_url) {
+        return remote_url.to_string();
+    }
+
+    if let Some((prefix, path)) = remote_url.split_once(':') {
+        if let Some((host, use_ssh)) = lookup_vendor(prefix, custom_vendors) {
+            return build_url(&host, path, use_ssh);
+        }
+    }
+
+    if looks_like_owner_repo(remote_url) {
+        return build_url("github.com", remote_url, false);
+    }
+
+    // Not a recognized shorthand and not already a complete URL - assume
+    // it's a bare host (e.g. an internal git server) and let `GitHandler`'s
+    // own fallback add the scheme.
+    remote_url.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vendors(pairs: &[(&str, &str, bool)]) -> HashMap<String, GitVendorConfig> {
+        pairs
+            .iter()
+            .map(|(prefix, host, use_ssh)| {
+                (
+                    prefix.to_string(),
+                    GitVendorConfig {
+                        host: host.to_string(),
+                        use_ssh: *use_ssh,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn expands_owner_repo_shorthand_to_github() {
+        assert_eq!(
+            expand("owner/repo", &HashMap::new()),
+            "https://github.com/owner/repo"
+        );
+    }
+
+    #[test]
+    fn expands_gh_prefix() {
+        assert_eq!(
+            expand("gh:owner/repo", &HashMap::new()),
+            "https://github.com/owner/repo"
+        );
+    }
+
+    #[test]
+    fn expands_gl_and_bb_prefixes() {
+        assert_eq!(
+            expand("gl:group/repo", &HashMap::new()),
+            "https://gitlab.com/group/repo"
+        );
+        assert_eq!(
+            expand("bb:owner/repo", &HashMap::new()),
+            "https://bitbucket.org/owner/repo"
+        );
+    }
+
+    #[test]
+    fn expands_custom_vendor_over_ssh() {
+        let custom = vendors(&[("work", "git.corp.example.com", true)]);
+        assert_eq!(
+            expand("work:team/repo", &custom),
+            "git@git.corp.example.com:team/repo.git"
+        );
+    }
+
+    #[test]
+    fn custom_vendor_overrides_builtin_prefix() {
+        let custom = vendors(&[("gh", "github.internal.example.com", false)]);
+        assert_eq!(
+            expand("gh:owner/repo", &custom),
+            "https://github.internal.example.com/owner/repo"
+        );
+    }
+
+    #[test]
+    fn leaves_complete_urls_unchanged() {
+        assert_eq!(
+            expand("https://github.com/owner/repo.git", &HashMap::new()),
+            "https://github.com/owner/repo.git"
+        );
+        assert_eq!(
+            expand("git@github.com:owner/repo.git", &HashMap::new()),
+            "git@github.com:owner/repo.git"
+        );
+    }
+
+    #[test]
+    fn leaves_unrecognized_prefix_and_bare_host_unchanged() {
+        assert_eq!(expand("svn:owner/repo", &HashMap::new()), "svn:owner/repo");
+        assert_eq!(
+            expand("git.example.com/owner/repo", &HashMap::new()),
+            "git.example.com/owner/repo"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::{debug, info, warn};
+
+/// How long a forward attempt waits for a connection and acknowledgement
+/// before giving up and letting the caller handle the URL itself.
+const FORWARD_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Sanity cap on a forwarded URL's length, so a corrupt or hostile peer on
+/// the socket can't make us allocate an unbounded read buffer.
+const MAX_URL_LEN: u32 = 8192;
+
+/// Per-user IPC socket used to forward a `srcuri://` URL from a
+/// freshly-launched process to an already-running instance, so the running
+/// instance's `ActiveEditorTracker`/`ActiveWorkspaceTracker` MRU state stays
+/// authoritative instead of every launch starting fresh with its own.
+#[cfg(unix)]
+fn socket_path() -> PathBuf {
+    dirs::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("sorcery-desktop.sock")
+}
+
+#[cfg(windows)]
+fn pipe_path() -> String {
+    r"\\.\pipe\sorcery-desktop".to_string()
+}
+
+/// Tries to hand `url` to an already-running instance over the local
+/// socket/pipe. Returns `true` if it was written and acknowledged within
+/// `FORWARD_TIMEOUT`; `false` if nothing is listening, the socket is stale,
+/// or the running instance didn't respond in time — in which case the
+/// caller should fall back to handling the URL itself.
+pub async fn try_forward(url: &str) -> bool {
+    match tokio::time::timeout(FORWARD_TIMEOUT, forward(url)).await {
+        Ok(Ok(())) => {
+            info!("Forwarded URL to running instance over IPC");
+            true
+        }
+        Ok(Err(e)) => {
+            debug!("No running instance to forward to ({}), handling locally", e);
+            false
+        }
+        Err(_) => {
+            warn!("IPC forward timed out, handling URL locally");
+            false
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn forward(url: &str) -> Result<()> {
+    let mut stream = tokio::net::UnixStream::connect(socket_path()).await?;
+    write_framed(&mut stream, url).await?;
+    read_ack(&mut stream).await
+}
+
+#[cfg(windows)]
+async fn forward(url: &str) -> Result<()> {
+    let mut stream = tokio::net::windows::named_pipe::ClientOptions::new().open(pipe_path())?;
+    write_framed(&mut stream, url).await?;
+    read_ack(&mut stream).await
+}
+
+/// Runs the server side for as long as the process lives: binds the
+/// socket/pipe (clearing a stale file left behind by a crashed prior
+/// instance) and calls `on_url` with each received URL, acknowledging it
+/// once that returns so the sender can tell it was actually accepted.
+/// No-ops (logging a warning) if another instance is already listening.
+#[cfg(unix)]
+pub fn serve(on_url: impl Fn(String) + Send + Sync + 'static) {
+    let on_url = Arc::new(on_url);
+
+    tauri::async_runtime::spawn(async move {
+        let path = socket_path();
+
+        if tokio::net::UnixStream::connect(&path).await.is_ok() {
+            warn!("IPC socket {:?} already has a live listener, not rebinding", path);
+            return;
+        }
+        let _ = std::fs::remove_file(&path);
+
+        let listener = match tokio::net::UnixListener::bind(&path) {
+            Ok(listener) => listener,
+            Err(e) => {
+                warn!("Failed to bind IPC socket {:?}: {}", path, e);
+                return;
+            }
+        };
+
+        // `bind` doesn't restrict who can connect - without this, a socket
+        // that fell back to the shared, world-writable temp dir (no
+        // `XDG_RUNTIME_DIR`) would let any local user forward arbitrary
+        // srcuri:// URLs into this instance.
+        use std::os::unix::fs::PermissionsExt;
+        if let Err(e) = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)) {
+            warn!("Failed to restrict IPC socket {:?} permissions: {}", path, e);
+        }
+
+        info!("Listening for forwarded URLs on {:?}", path);
+        accept_loop(listener, on_url).await;
+    });
+}
+
+#[cfg(unix)]
+async fn accept_loop(listener: tokio::net::UnixListener, on_url: Arc<impl Fn(String) + Send + Sync + 'static>) {
+    loop {
+        let (stream, _addr) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!("IPC accept failed: {}", e);
+                continue;
+            }
+        };
+        spawn_connection(stream, on_url.clone());
+    }
+}
+
+#[cfg(windows)]
+pub fn serve(on_url: impl Fn(String) + Send + Sync + 'static) {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let on_url = Arc::new(on_url);
+
+    tauri::async_runtime::spawn(async move {
+        let path = pipe_path();
+
+        loop {
+            let server = match ServerOptions::new().first_pipe_instance(true).create(&path) {
+                Ok(server) => server,
+                Err(e) => {
+                    warn!("Failed to create named pipe {}: {}", path, e);
+                    return;
+                }
+            };
+
+            if server.connect().await.is_err() {
+                continue;
+            }
+            spawn_connection(server, on_url.clone());
+
+            // Each accepted connection consumes this pipe instance, so the
+            // next iteration creates a fresh one to accept the next client.
+            match ServerOptions::new().create(&path) {
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("Failed to re-create named pipe {}: {}", path, e);
+                    return;
+                }
+            }
+        }
+    });
+
+    info!("Listening for forwarded URLs on {}", pipe_path());
+}
+
+fn spawn_connection<S>(mut stream: S, on_url: Arc<impl Fn(String) + Send + Sync + 'static>)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        match read_framed(&mut stream).await {
+            Ok(url) => {
+                on_url(url);
+                let _ = stream.write_all(&[1u8]).await;
+            }
+            Err(e) => debug!("Failed to read forwarded URL: {}", e),
+        }
+    });
+}
+
+async fn write_framed<W: AsyncWrite + Unpin>(stream: &mut W, payload: &str) -> Result<()> {
+    let bytes = payload.as_bytes();
+    stream.write_u32(bytes.len() as u32).await?;
+    stream.write_all(bytes).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+async fn read_framed<R: AsyncRead + Unpin>(stream: &mut R) -> Result<String> {
+    let len = stream.read_u32().await?;
+    if len > MAX_URL_LEN {
+        bail!("Forwarded URL length {} exceeds max of {}", len, MAX_URL_LEN);
+    }
+
+    let mut buf = vec![0u8; len as usize];
+    stream.read_exact(&mut buf).await?;
+    Ok(String::from_utf8(buf)?)
+}
+
+async fn read_ack<R: AsyncRead + Unpin>(stream: &mut R) -> Result<()> {
+    let mut ack = [0u8; 1];
+    stream.read_exact(&mut ack).await?;
+    Ok(())
+}
@@ -3,9 +3,18 @@
 
 pub mod dispatcher;
 pub mod editors;
+pub mod external_editors;
+pub mod file_types;
 pub mod git_command_log;
+pub mod git_host;
+pub mod git_url;
 pub mod path_validator;
+pub mod project_kind;
 pub mod protocol_handler;
+pub mod repo_discovery;
+pub mod sandbox_env;
 pub mod settings;
+pub mod settings_sync;
 pub mod tracker;
 pub mod workspace_mru;
+pub mod wsl;
@@ -0,0 +1,179 @@
+//! In-process log capture: a `tracing` layer that mirrors every event into a
+//! bounded ring buffer and, once the Tauri app has started, broadcasts it to
+//! the frontend - so diagnosing a failed clone or protocol registration
+//! doesn't require hunting through the OS's own crash-dump folders.
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::VecDeque;
+use tauri::{AppHandle, Emitter};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Entries beyond this are dropped oldest-first - enough to cover a typical
+/// troubleshooting session without growing unbounded for a long-running app.
+const CAPACITY: usize = 2000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogRecord {
+    pub ts: DateTime<Utc>,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// Pulls just the `message` field out of an event's fields, discarding
+/// structured extras (`key = value`) the same way the plain-text fmt layer's
+/// default format prints them separately - we only need the message text.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        }
+    }
+}
+
+struct LogBuffer {
+    entries: Mutex<VecDeque<LogRecord>>,
+    app: Mutex<Option<AppHandle>>,
+}
+
+impl LogBuffer {
+    const fn new() -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+            app: Mutex::new(None),
+        }
+    }
+
+    fn push(&self, record: LogRecord) {
+        if let Some(app) = self.app.lock().as_ref() {
+            let _ = app.emit("logs://entry", &record);
+        }
+
+        let mut entries = self.entries.lock();
+        if entries.len() >= CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(record);
+    }
+
+    fn recent(&self, max_lines: usize, min_level: Level) -> Vec<LogRecord> {
+        let entries = self.entries.lock();
+        let mut matched: Vec<LogRecord> = entries
+            .iter()
+            .rev()
+            .filter(|entry| {
+                entry
+                    .level
+                    .parse::<Level>()
+                    .is_ok_and(|level| level <= min_level)
+            })
+            .take(max_lines)
+            .cloned()
+            .collect();
+        matched.reverse();
+        matched
+    }
+}
+
+static LOG_BUFFER: LogBuffer = LogBuffer::new();
+
+/// Lets buffered entries start broadcasting live once the Tauri event loop
+/// exists; call once from `main`'s `.setup()`.
+pub fn set_app_handle(app: AppHandle) {
+    *LOG_BUFFER.app.lock() = Some(app);
+}
+
+/// `tracing_subscriber` layer that feeds the ring buffer, installed
+/// alongside the existing `fmt` layer via `tracing_subscriber::registry()`.
+pub struct LogBufferLayer;
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        LOG_BUFFER.push(LogRecord {
+            ts: Utc::now(),
+            level: event.metadata().level().to_string(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+        });
+    }
+}
+
+/// Returns up to `max_lines` of the most recent buffered entries at or above
+/// `min_level` severity (oldest first), for `get_recent_logs`.
+pub fn recent(max_lines: usize, min_level: Level) -> Vec<LogRecord> {
+    LOG_BUFFER.recent(max_lines, min_level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_recent_round_trips_in_order() {
+        let buffer = LogBuffer::new();
+        for i in 0..3 {
+            buffer.push(LogRecord {
+                ts: Utc::now(),
+                level: "INFO".to_string(),
+                target: "test".to_string(),
+                message: format!("line {i}"),
+            });
+        }
+
+        let got = buffer.recent(10, Level::INFO);
+        assert_eq!(got.len(), 3);
+        assert_eq!(got[0].message, "line 0");
+        assert_eq!(got[2].message, "line 2");
+    }
+
+    #[test]
+    fn recent_filters_by_min_level() {
+        let buffer = LogBuffer::new();
+        buffer.push(LogRecord {
+            ts: Utc::now(),
+            level: "DEBUG".to_string(),
+            target: "test".to_string(),
+            message: "debug line".to_string(),
+        });
+        buffer.push(LogRecord {
+            ts: Utc::now(),
+            level: "ERROR".to_string(),
+            target: "test".to_string(),
+            message: "error line".to_string(),
+        });
+
+        let got = buffer.recent(10, Level::INFO);
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].message, "error line");
+    }
+
+    #[test]
+    fn recent_caps_to_max_lines() {
+        let buffer = LogBuffer::new();
+        for i in 0..5 {
+            buffer.push(LogRecord {
+                ts: Utc::now(),
+                level: "INFO".to_string(),
+                target: "test".to_string(),
+                message: format!("line {i}"),
+            });
+        }
+
+        let got = buffer.recent(2, Level::INFO);
+        assert_eq!(got.len(), 2);
+        assert_eq!(got[0].message, "line 3");
+        assert_eq!(got[1].message, "line 4");
+    }
+}
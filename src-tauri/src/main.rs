@@ -4,13 +4,24 @@
 mod commands;
 mod dispatcher;
 mod editors;
+mod external_editors;
 mod git_command_log;
+mod git_host;
+mod git_url;
+mod ipc_forward;
+mod log_buffer;
 mod path_validator;
+mod project_kind;
 mod protocol_handler;
 mod protocol_registration;
+mod repo_discovery;
+mod sandbox_env;
 mod settings;
+mod settings_sync;
 mod tracker;
 mod workspace_mru;
+mod workspace_watcher;
+mod wsl;
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -20,7 +31,7 @@ use tauri::{
     AppHandle, Emitter, Listener, Manager,
 };
 use tauri_plugin_deep_link::DeepLinkExt;
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 use crate::git_command_log::GIT_COMMAND_LOG;
 
@@ -186,6 +197,7 @@ async fn handle_protocol_result(
             line,
             column,
             git_ref,
+            clone_strategy,
         }) => {
             tracing::info!(
                 "Request: showing clone dialog for {} from {}",
@@ -212,6 +224,7 @@ async fn handle_protocol_result(
                 column,
                 git_ref: git_ref_display,
                 git_ref_kind: git_ref.clone(),
+                clone_strategy,
             });
             match tauri::WebviewWindowBuilder::new(
                 app_handle,
@@ -233,6 +246,46 @@ async fn handle_protocol_result(
                 Err(e) => tracing::error!("Failed to open clone dialog: {}", e),
             }
         }
+        Ok(protocol_handler::HandleResult::ShowBulkCloneDialog {
+            host,
+            owner,
+            include_forks,
+            include_archived,
+        }) => {
+            tracing::info!("Request: showing bulk clone dialog for {}/{}", host, owner);
+            GIT_COMMAND_LOG.log_request(
+                url,
+                true,
+                "bulk_clone_dialog",
+                &format!("Offering to bulk-clone {} from {}", owner, host),
+                duration,
+            );
+            commands::set_bulk_clone_dialog_data(commands::BulkCloneDialogData {
+                host,
+                owner,
+                include_forks,
+                include_archived,
+            });
+            match tauri::WebviewWindowBuilder::new(
+                app_handle,
+                "bulk-clone-dialog",
+                tauri::WebviewUrl::App("bulk-clone-dialog.html".into()),
+            )
+            .title("Clone Organization")
+            .inner_size(520.0, 420.0)
+            .center()
+            .resizable(false)
+            .always_on_top(true)
+            .focused(true)
+            .build()
+            {
+                Ok(window) => {
+                    #[cfg(target_os = "macos")]
+                    set_dark_titlebar(&window);
+                }
+                Err(e) => tracing::error!("Failed to open bulk clone dialog: {}", e),
+            }
+        }
         Ok(protocol_handler::HandleResult::OpenInBrowser { url: browser_url }) => {
             tracing::info!("Request: opening in browser: {}", browser_url);
             GIT_COMMAND_LOG.log_request(
@@ -256,35 +309,68 @@ async fn handle_protocol_result(
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env())
+    tracing_subscriber::registry()
+        .with(EnvFilter::from_default_env())
+        .with(tracing_subscriber::fmt::layer())
+        .with(log_buffer::LogBufferLayer)
         .init();
 
     tracing::info!("Starting Sorcery Desktop...");
 
     let settings_manager = Arc::new(settings::SettingsManager::new().await?);
     let path_validator = Arc::new(path_validator::PathValidator::new(settings_manager.clone()));
-    let editor_registry = Arc::new(editors::EditorRegistry::new());
+    let editor_registry = Arc::new(editors::EditorRegistry::new(settings_manager.clone()));
     let tracker = Arc::new(tracker::ActiveEditorTracker::new(editor_registry.clone()));
     let workspace_tracker = Arc::new(workspace_mru::ActiveWorkspaceTracker::new(
         settings_manager.clone(),
     ));
     let workspace_sync = Arc::new(settings::WorkspaceSync::new(settings_manager.clone()));
+    let workspace_watcher = Arc::new(workspace_watcher::WorkspaceWatcher::new(
+        settings_manager.clone(),
+        workspace_sync.clone(),
+    ));
     let dispatcher = Arc::new(dispatcher::EditorDispatcher::new(
         settings_manager.clone(),
         path_validator.clone(),
         editor_registry.clone(),
         tracker.clone(),
+        workspace_tracker.clone(),
     ));
     let protocol_handler = Arc::new(protocol_handler::ProtocolHandler::new(
         settings_manager.clone(),
         dispatcher.clone(),
         workspace_tracker.clone(),
+        path_validator.clone(),
     ));
-
-    settings_manager.load().await?;
+    let sync_manager = Arc::new(settings_sync::SyncManager::new(settings_manager.clone()));
+
+    settings_manager
+        .load(
+            &editor_registry.list_editors(),
+            editors::known_terminal_preference_names(),
+        )
+        .await?;
     tracing::info!("Settings loaded");
 
+    for entry in &settings_manager.get().await.defaults.self_hosted_providers {
+        if entry.match_subdomains {
+            protocol_handler::SrcuriParser::register_provider_host_suffix(
+                entry.host.clone(),
+                entry.provider.to_provider(),
+            );
+        } else {
+            protocol_handler::SrcuriParser::register_provider_host(
+                entry.host.clone(),
+                entry.provider.to_provider(),
+            );
+        }
+    }
+
+    settings_manager.clone().start_watching(
+        editor_registry.list_editors(),
+        editors::known_terminal_preference_names(),
+    );
+
     // Sync workspaces from default_workspaces_folder
     if let Err(e) = workspace_sync.sync().await {
         tracing::warn!("Failed to sync workspaces: {}", e);
@@ -336,7 +422,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = std::env::args().collect();
     if args.len() > 1 {
         let url = &args[1];
+        if url == "--print-config" {
+            print!("{}", settings::generate_example_config());
+            return Ok(());
+        }
         if url.starts_with("srcuri://") {
+            if ipc_forward::try_forward(url).await {
+                tracing::info!("URL handled by already-running instance");
+                return Ok(());
+            }
+
             tracing::info!("Processing command-line URL: {}", url);
             match protocol_handler.handle_url(url).await {
                 Ok(protocol_handler::HandleResult::Opened) => {
@@ -358,6 +453,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     let protocol_handler_clone = protocol_handler.clone();
+    let workspace_watcher_clone = workspace_watcher.clone();
 
     tauri::Builder::default()
         .setup(move |app| {
@@ -377,6 +473,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             let ph = protocol_handler_clone.clone();
             let ph_cold_start = protocol_handler_clone.clone();
 
+            // Let buffered log entries start broadcasting to the frontend
+            // now that the event loop exists.
+            log_buffer::set_app_handle(app_handle.clone());
+
+            let watcher_app_handle = app_handle.clone();
+            let watcher_handle = workspace_watcher_clone.clone();
+            tauri::async_runtime::spawn(async move {
+                tracing::info!("Starting workspace filesystem watcher...");
+                watcher_handle.run(watcher_app_handle).await;
+            });
+
+            // Accept URLs forwarded by a freshly-launched second process
+            // (see `ipc_forward::try_forward`) and route them through the
+            // same handling path as a live deep-link event, so this
+            // already-running instance's trackers stay authoritative.
+            let ipc_app_handle = app.handle().clone();
+            let ipc_ph = protocol_handler_clone.clone();
+            ipc_forward::serve(move |url| {
+                let app_handle = ipc_app_handle.clone();
+                let ph = ipc_ph.clone();
+                tauri::async_runtime::spawn(async move {
+                    let start = std::time::Instant::now();
+                    let result = ph.handle_url(&url).await;
+                    handle_protocol_result(result, &app_handle, &url, start.elapsed()).await;
+                });
+            });
+
             app.handle().listen("deep-link://new-url", move |event| {
                 let payload = event.payload();
                 let event_time = std::time::SystemTime::now()
@@ -525,6 +648,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .manage(dispatcher)
         .manage(protocol_handler)
         .manage(workspace_sync)
+        .manage(sync_manager)
         .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
             let event_time = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
@@ -577,11 +701,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             commands::get_settings,
             commands::get_settings_path,
             commands::save_settings,
+            commands::sync_push_settings,
+            commands::sync_pull_settings,
             commands::get_all_workspaces,
             commands::promote_workspace,
             commands::sync_workspaces,
             commands::delete_workspace,
+            commands::set_workspace_tags,
+            commands::list_workspaces_by_tag,
             commands::get_editor_testbed_data,
+            commands::get_available_editors,
+            commands::get_openers_for_path,
+            commands::get_editor_suggestions,
+            commands::open_with,
+            commands::watch_workspace_file,
+            commands::unwatch_workspace,
+            commands::reveal_in_file_manager,
+            commands::doctor,
             commands::test_open_file,
             commands::open_in_editor,
             commands::detect_source_folder,
@@ -590,20 +726,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             commands::workspace_chooser_cancelled,
             commands::get_revision_dialog_data,
             commands::get_git_revision_info,
+            commands::get_blame_at_revision,
             commands::open_file_at_revision,
+            commands::preview_blob_at_revision,
             commands::revision_dialog_cancelled,
             commands::refresh_working_tree_status,
+            commands::stream_working_tree_status,
+            commands::working_tree_status_scan_cancelled,
+            commands::get_git_operation_state,
+            commands::abort_git_operation,
+            commands::continue_git_operation,
+            commands::checkout_revision_stashing,
+            commands::restore_autostash,
+            commands::list_git_worktrees,
+            commands::remove_worktree,
+            commands::set_worktree_pinned,
             commands::create_worktree_and_open,
             commands::get_git_command_history,
             commands::test_protocol_url,
             commands::get_clone_dialog_data,
             commands::clone_and_open,
             commands::update_clone_path,
+            commands::set_clone_editor,
             commands::clone_cancelled,
+            commands::get_bulk_clone_dialog_data,
+            commands::bulk_clone_from_host,
+            commands::bulk_clone_cancelled,
             commands::get_protocol_registration_status,
             commands::reregister_protocol,
             commands::get_logs_directory,
             commands::open_logs_directory,
+            commands::get_recent_logs,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
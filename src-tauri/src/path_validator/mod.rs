@@ -1,16 +1,33 @@
+mod pattern;
+
+use crate::file_types::{self, FileCategory};
 use crate::settings::SettingsManager;
 use anyhow::{bail, Context, Result};
 use once_cell::sync::Lazy;
+use pattern::PathPatternSet;
 use regex::Regex;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use thiserror::Error;
 
 static SUSPICIOUS_PATTERNS: Lazy<Regex> =
     Lazy::new(|| Regex::new(r#"(\.\./|\.\.\\|~|//|[\x00-\x1f]|[<>|?*;'`$&(){}\[\]"])"#).unwrap());
 
-static DANGEROUS_EXTENSIONS: &[&str] = &[
-    ".exe", ".bat", ".cmd", ".sh", ".ps1", ".vbs", ".app", ".dmg",
-];
+/// Error from [`PathValidator::validate_within_workspace`], kept distinct
+/// from the generic `anyhow::Error` the rest of `PathValidator` returns so
+/// callers (and the UI) can tell "doesn't exist" apart from "outside
+/// workspace" instead of matching on message text.
+#[derive(Debug, Error)]
+pub enum WorkspaceMembershipError {
+    #[error("Path does not exist: {0}")]
+    NotFound(PathBuf),
+
+    #[error("Path is outside any configured workspace: {0}")]
+    OutsideWorkspace(PathBuf),
+
+    #[error(transparent)]
+    Invalid(#[from] anyhow::Error),
+}
 
 pub struct PathValidator {
     settings_manager: Arc<SettingsManager>,
@@ -27,6 +44,8 @@ impl PathValidator {
         Self::sanitize(path_str).context("Sanitize failed")?;
         tracing::debug!("Path sanitized");
 
+        self.check_executable_allowed(path_str).await?;
+
         let normalized = self.normalize(path_str).context("Normalize failed")?;
         tracing::debug!("Path normalized to: {}", normalized.display());
 
@@ -88,13 +107,22 @@ impl PathValidator {
             }
         }
 
-        for ext in DANGEROUS_EXTENSIONS {
-            if path.to_lowercase().ends_with(ext) {
-                bail!("Opening executable files is not allowed");
-            }
+        Ok(())
+    }
+
+    /// Consults `file_types::classify` instead of a hardcoded suffix list:
+    /// paths that resolve to `FileCategory::Executable` are rejected unless
+    /// the user has opted in via `defaults.allow_executable_files`.
+    async fn check_executable_allowed(&self, path_str: &str) -> Result<()> {
+        if file_types::classify(Path::new(path_str)) != FileCategory::Executable {
+            return Ok(());
         }
 
-        Ok(())
+        if self.settings_manager.get().await.defaults.allow_executable_files {
+            return Ok(());
+        }
+
+        bail!("Opening executable files is not allowed");
     }
 
     fn normalize(&self, path: &str) -> Result<PathBuf> {
@@ -109,19 +137,237 @@ impl PathValidator {
             .canonicalize()
             .context("Failed to resolve path (file may not exist)")?;
 
-        #[cfg(target_os = "macos")]
-        {
-            let canonical_str = canonical.to_string_lossy();
-            if canonical_str.starts_with("/private/") {
-                if let Ok(stripped) = canonical.strip_prefix("/private") {
-                    let mut absolute = PathBuf::from("/");
-                    absolute.push(stripped);
-                    return Ok(absolute);
+        let canonical = Self::strip_windows_verbatim_prefix(canonical);
+        Ok(Self::strip_macos_private_prefix(canonical))
+    }
+
+    /// Strips the `\\?\` extended-length prefix Windows' `canonicalize()`
+    /// adds, the dunce approach: a verbatim UNC path (`\\?\UNC\server\share`)
+    /// becomes the ordinary `\\server\share`, and a verbatim drive path
+    /// (`\\?\C:\...`) becomes `C:\...`. This is the Windows analogue of the
+    /// macOS `/private` stripping above — both exist so a canonicalized path
+    /// round-trips through the sanitizer's drive-letter rules and prefix
+    /// comparisons like `check_workspace_membership` instead of silently
+    /// failing to match a user-entered path.
+    #[cfg(target_os = "windows")]
+    fn strip_windows_verbatim_prefix(path: PathBuf) -> PathBuf {
+        let raw = path.to_string_lossy();
+
+        if let Some(rest) = raw.strip_prefix(r"\\?\UNC\") {
+            return PathBuf::from(format!(r"\\{}", rest));
+        }
+
+        if let Some(rest) = raw.strip_prefix(r"\\?\") {
+            let looks_like_drive = rest.as_bytes().get(1) == Some(&b':')
+                && rest.as_bytes().first().is_some_and(u8::is_ascii_alphabetic);
+            if looks_like_drive {
+                return PathBuf::from(rest);
+            }
+        }
+
+        path
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn strip_windows_verbatim_prefix(path: PathBuf) -> PathBuf {
+        path
+    }
+
+    #[cfg(target_os = "macos")]
+    fn strip_macos_private_prefix(path: PathBuf) -> PathBuf {
+        let path_str = path.to_string_lossy();
+        if path_str.starts_with("/private/") {
+            if let Ok(stripped) = path.strip_prefix("/private") {
+                let mut absolute = PathBuf::from("/");
+                absolute.push(stripped);
+                return absolute;
+            }
+        }
+        path
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn strip_macos_private_prefix(path: PathBuf) -> PathBuf {
+        path
+    }
+
+    /// Resolves `.` and `..` components purely lexically, without touching
+    /// the filesystem, so a not-yet-existing path (e.g. a "save as" target)
+    /// can still be validated. `..` pops the last normal component off the
+    /// stack; a `..` that would pop past the root is rejected rather than
+    /// silently clamped, since that would otherwise let a crafted path climb
+    /// outside whatever directory the caller expected to stay under.
+    fn normalize_logical(path: &Path) -> Result<PathBuf> {
+        use std::path::Component;
+
+        let mut stack: Vec<Component> = Vec::new();
+
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => match stack.last() {
+                    Some(Component::Normal(_)) => {
+                        stack.pop();
+                    }
+                    _ => bail!("Path escapes root via '..'"),
+                },
+                other => stack.push(other),
+            }
+        }
+
+        let mut result = PathBuf::new();
+        for component in stack {
+            result.push(component.as_os_str());
+        }
+
+        Ok(result)
+    }
+
+    /// Validates a path that may not exist yet (e.g. a new file being
+    /// created): sanitizes, tilde-expands, logically normalizes `.`/`..`
+    /// without touching the filesystem, and requires the *parent* directory
+    /// to already exist. Unlike `normalize`, this never canonicalizes via
+    /// the filesystem, since the whole point is to tolerate a nonexistent
+    /// target.
+    pub async fn validate_for_creation(&self, path_str: &str) -> Result<PathBuf> {
+        tracing::debug!("Validating path for creation: {}", path_str);
+
+        Self::sanitize(path_str).context("Sanitize failed")?;
+
+        self.check_executable_allowed(path_str).await?;
+
+        let expanded = shellexpand::tilde(path_str);
+        let path = Path::new(expanded.as_ref());
+
+        if !path.is_absolute() {
+            bail!("Path must be absolute");
+        }
+
+        let normalized = Self::normalize_logical(path).context("Failed to normalize path")?;
+
+        let parent = normalized
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .context("Path has no parent directory")?;
+
+        if !parent.is_dir() {
+            bail!(
+                "Parent directory does not exist or is not a directory: {}",
+                parent.display()
+            );
+        }
+
+        Ok(Self::strip_macos_private_prefix(normalized))
+    }
+
+    /// Rejects a workspace-relative path whose `..` components would climb
+    /// above the workspace root before it's ever joined onto a real
+    /// filesystem path. Walks `Component`s with a depth counter seeded at
+    /// the workspace root (0): `Normal` pushes it deeper, `ParentDir` pops
+    /// one level, and a pop that would take it negative means the path
+    /// tries to escape the root (e.g. `../../etc/passwd`). This catches the
+    /// traversal before `PathBuf::join`/`exists()` get a chance to resolve
+    /// it against whatever happens to live outside the workspace.
+    pub fn reject_path_traversal(relative_path: &Path) -> Result<()> {
+        use std::path::Component;
+
+        let mut depth: i64 = 0;
+
+        for component in relative_path.components() {
+            match component {
+                Component::Normal(_) => depth += 1,
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    depth -= 1;
+                    if depth < 0 {
+                        bail!(
+                            "Path escapes workspace root via '..': {}",
+                            relative_path.display()
+                        );
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => {
+                    bail!(
+                        "Path must be relative to the workspace root: {}",
+                        relative_path.display()
+                    );
                 }
             }
         }
 
-        Ok(canonical)
+        Ok(())
+    }
+
+    /// Enforces a workspace's `enable_paths`/`disable_paths` glob policy
+    /// against a path relative to that workspace's root, so a `srcuri://`
+    /// link can only open files the workspace has chosen to expose (e.g.
+    /// `src/**`/`tests/**` but never `target/**` or `.env`). Workspaces
+    /// without either list configured are unrestricted, matching today's
+    /// behavior. Returns `Ok(())` if `workspace_name` doesn't match any
+    /// configured workspace, leaving that failure to whatever workspace
+    /// lookup the caller already did.
+    pub async fn check_workspace_path_policy(
+        &self,
+        workspace_name: &str,
+        relative_path: &Path,
+    ) -> Result<()> {
+        let settings = self.settings_manager.get().await;
+
+        for workspace in &settings.workspaces {
+            let ws_name = workspace.name.as_deref().unwrap_or_else(|| {
+                workspace
+                    .normalized_path
+                    .as_ref()
+                    .and_then(|p| p.file_name())
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("")
+            });
+
+            if ws_name.eq_ignore_ascii_case(workspace_name) {
+                let empty: Vec<String> = Vec::new();
+
+                // A workspace's own enable/disable_paths always win; only an
+                // untagged-for-this-purpose workspace (neither list set)
+                // falls back to its first tag with a configured policy.
+                let (tag_enable, tag_disable) =
+                    if workspace.enable_paths.is_none() && workspace.disable_paths.is_none() {
+                        workspace
+                            .tags
+                            .iter()
+                            .find_map(|tag| settings.defaults.tag_defaults.get(tag))
+                            .map(|defaults| {
+                                (defaults.enable_paths.clone(), defaults.disable_paths.clone())
+                            })
+                            .unwrap_or((None, None))
+                    } else {
+                        (None, None)
+                    };
+
+                let pattern_set = PathPatternSet::compile(
+                    workspace
+                        .enable_paths
+                        .as_ref()
+                        .or(tag_enable.as_ref())
+                        .unwrap_or(&empty),
+                    workspace
+                        .disable_paths
+                        .as_ref()
+                        .or(tag_disable.as_ref())
+                        .unwrap_or(&empty),
+                );
+
+                if !pattern_set.matches(relative_path) {
+                    bail!(
+                        "Path excluded by workspace policy: {}",
+                        relative_path.display()
+                    );
+                }
+
+                return Ok(());
+            }
+        }
+
+        Ok(())
     }
 
     fn verify_exists_any(&self, path: &Path) -> Result<()> {
@@ -136,9 +382,13 @@ impl PathValidator {
         Ok(())
     }
 
-    // TODO: Implement workspace-based security checks per ai/4-path-validation.md
-    #[allow(dead_code)]
-    async fn check_workspace_membership(&self, path: &Path) -> Result<()> {
+    /// Checks `path` (expected to already be canonicalized, e.g. via
+    /// `normalize`) against each configured workspace's `normalized_path`
+    /// (itself canonicalized by `SettingsManager`). Comparing two
+    /// symlink-resolved paths component-wise via `is_under` defeats both `..`
+    /// traversal and a symlink inside the workspace that points elsewhere -
+    /// a plain string-prefix check would miss the latter.
+    async fn check_workspace_membership(&self, path: &Path) -> Result<(), WorkspaceMembershipError> {
         let settings = self.settings_manager.get().await;
 
         if settings.workspaces.is_empty() {
@@ -153,18 +403,50 @@ impl PathValidator {
             }
         }
 
-        bail!(
-            "File is not within any configured workspace: {}",
-            path.display()
-        );
+        Err(WorkspaceMembershipError::OutsideWorkspace(
+            path.to_path_buf(),
+        ))
     }
 
-    #[allow(dead_code)]
     fn is_under(child: &Path, parent: &Path) -> bool {
         child.starts_with(parent)
     }
 
-    // TODO: Implement workspace-based security checks per ai/4-path-validation.md
+    /// Entry point for callers that must enforce workspace membership (e.g.
+    /// file-open commands), as opposed to `validate_any`/`validate_workspace_path`
+    /// which plain configuration flows use without that restriction.
+    /// Sanitizes, resolves executable policy, and canonicalizes exactly like
+    /// `validate_any`, then additionally requires the resolved path to fall
+    /// under a configured workspace.
+    pub async fn validate_within_workspace(
+        &self,
+        path_str: &str,
+    ) -> Result<PathBuf, WorkspaceMembershipError> {
+        Self::sanitize(path_str)?;
+        self.check_executable_allowed(path_str).await?;
+
+        let expanded = shellexpand::tilde(path_str);
+        let path = Path::new(expanded.as_ref());
+
+        if !path.is_absolute() {
+            return Err(anyhow::anyhow!("Path must be absolute").into());
+        }
+
+        let canonical = match path.canonicalize() {
+            Ok(canonical) => canonical,
+            Err(_) => return Err(WorkspaceMembershipError::NotFound(path.to_path_buf())),
+        };
+
+        let canonical = Self::strip_windows_verbatim_prefix(canonical);
+        let normalized = Self::strip_macos_private_prefix(canonical);
+
+        self.check_workspace_membership(&normalized).await?;
+
+        Ok(normalized)
+    }
+
+    /// Validates a path intended to *become* a configured workspace root, so
+    /// it deliberately does not check membership against existing workspaces.
     #[allow(dead_code)]
     pub async fn validate_workspace_path(&self, path_str: &str) -> Result<PathBuf> {
         let expanded = shellexpand::tilde(path_str);
@@ -235,4 +517,54 @@ mod tests {
         assert!(PathValidator::sanitize("/tmp/file+plus.txt").is_ok(), "plus allowed");
         assert!(PathValidator::sanitize("/tmp/file=equals.txt").is_ok(), "equals allowed");
     }
+
+    #[test]
+    fn normalize_logical_resolves_dot_and_dotdot() {
+        let result = PathValidator::normalize_logical(std::path::Path::new("/a/b/../c/./d"));
+        assert_eq!(result.unwrap(), std::path::PathBuf::from("/a/c/d"));
+    }
+
+    #[test]
+    fn normalize_logical_rejects_escaping_root() {
+        let result = PathValidator::normalize_logical(std::path::Path::new("/a/../../b"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn is_under_matches_component_wise() {
+        assert!(PathValidator::is_under(
+            std::path::Path::new("/workspace/src/main.rs"),
+            std::path::Path::new("/workspace")
+        ));
+        assert!(!PathValidator::is_under(
+            std::path::Path::new("/workspace-other/main.rs"),
+            std::path::Path::new("/workspace")
+        ));
+    }
+
+    #[test]
+    fn reject_path_traversal_allows_nested_relative_path() {
+        let result = PathValidator::reject_path_traversal(std::path::Path::new("src/main.rs"));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn reject_path_traversal_allows_dotdot_that_stays_within_root() {
+        let result =
+            PathValidator::reject_path_traversal(std::path::Path::new("src/../README.md"));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn reject_path_traversal_rejects_escaping_root() {
+        let result =
+            PathValidator::reject_path_traversal(std::path::Path::new("../../etc/passwd"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reject_path_traversal_rejects_absolute_path() {
+        let result = PathValidator::reject_path_traversal(std::path::Path::new("/etc/passwd"));
+        assert!(result.is_err());
+    }
 }
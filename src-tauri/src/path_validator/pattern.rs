@@ -0,0 +1,83 @@
+use glob::Pattern;
+use std::path::Path;
+use tracing::warn;
+
+/// Compiled allow/deny glob patterns scoping which paths inside a workspace
+/// `srcuri://` is allowed to open (e.g. only `src/**` and `tests/**`, never
+/// `target/**` or `.env`). A path is admissible if it matches at least one
+/// `enable` pattern (or the list is empty, meaning "no restriction") and
+/// matches none of the `disable` patterns; `disable` always wins.
+pub struct PathPatternSet {
+    enable: Vec<Pattern>,
+    disable: Vec<Pattern>,
+}
+
+impl PathPatternSet {
+    /// Compiles `enable_paths`/`disable_paths` once up front. A glob that
+    /// fails to parse is logged and dropped rather than rejecting the whole
+    /// workspace config over one bad pattern.
+    pub fn compile(enable_paths: &[String], disable_paths: &[String]) -> Self {
+        Self {
+            enable: Self::compile_patterns(enable_paths),
+            disable: Self::compile_patterns(disable_paths),
+        }
+    }
+
+    fn compile_patterns(patterns: &[String]) -> Vec<Pattern> {
+        patterns
+            .iter()
+            .filter_map(|raw| match Pattern::new(raw) {
+                Ok(pattern) => Some(pattern),
+                Err(e) => {
+                    warn!("Ignoring invalid workspace path pattern '{}': {}", raw, e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        let path_str = path.to_string_lossy();
+
+        let enabled = self.enable.is_empty() || self.enable.iter().any(|p| p.matches(&path_str));
+        if !enabled {
+            return false;
+        }
+
+        !self.disable.iter().any(|p| p.matches(&path_str))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_pattern_set_allows_everything() {
+        let set = PathPatternSet::compile(&[], &[]);
+        assert!(set.matches(Path::new("src/main.rs")));
+        assert!(set.matches(Path::new(".env")));
+    }
+
+    #[test]
+    fn enable_patterns_restrict_to_match() {
+        let set = PathPatternSet::compile(&["src/**".to_string(), "tests/**".to_string()], &[]);
+        assert!(set.matches(Path::new("src/main.rs")));
+        assert!(set.matches(Path::new("tests/it.rs")));
+        assert!(!set.matches(Path::new("target/debug/app")));
+    }
+
+    #[test]
+    fn disable_patterns_override_enable() {
+        let set = PathPatternSet::compile(&["**".to_string()], &[".env".to_string()]);
+        assert!(set.matches(Path::new("src/main.rs")));
+        assert!(!set.matches(Path::new(".env")));
+    }
+
+    #[test]
+    fn invalid_pattern_is_ignored_not_fatal() {
+        let set = PathPatternSet::compile(&["[".to_string()], &[]);
+        // No valid enable patterns compiled, so the set behaves as unrestricted.
+        assert!(set.matches(Path::new("anything.rs")));
+    }
+}
@@ -0,0 +1,131 @@
+//! Lightweight project-type classifier for workspace roots.
+//!
+//! Adapts the manifest-inspection approach tauri-cli's `info` command uses
+//! (reading `Cargo.lock`/`package.json` to report a project's stack): check
+//! a workspace root for a handful of well-known marker files and infer a
+//! primary language, plus a framework where one can be read straight out of
+//! the manifest. This is a best-effort hint for the UI, not a build-system
+//! integration - on any ambiguity (multiple markers, unreadable manifest) it
+//! just returns the first language match with no framework.
+
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectInfo {
+    pub kind: String,
+    pub framework: Option<String>,
+}
+
+/// Marker file checked in order; the first one present wins. Order matters
+/// when a workspace mixes ecosystems (e.g. a Rust/Tauri app with a
+/// `package.json` for its frontend tooling) - `Cargo.toml` is checked first
+/// since that's the primary manifest for this repo's own stack.
+const MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "rust"),
+    ("package.json", "node"),
+    ("go.mod", "go"),
+    ("pyproject.toml", "python"),
+    ("pom.xml", "java"),
+    ("build.gradle", "java"),
+    ("build.gradle.kts", "java"),
+];
+
+/// Inspects `root` for the marker files in [`MARKERS`] and returns the
+/// first match, with a framework name if one can be read out of the
+/// manifest. Returns `None` if `root` doesn't look like a recognized
+/// project.
+pub fn detect(root: &Path) -> Option<ProjectInfo> {
+    for (marker, kind) in MARKERS {
+        let manifest_path = root.join(marker);
+        if manifest_path.is_file() {
+            let framework = match *kind {
+                "rust" => detect_rust_framework(&manifest_path),
+                "node" => detect_node_framework(&manifest_path),
+                _ => None,
+            };
+            return Some(ProjectInfo {
+                kind: kind.to_string(),
+                framework,
+            });
+        }
+    }
+
+    None
+}
+
+fn detect_rust_framework(cargo_toml: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(cargo_toml).ok()?;
+
+    for (needle, framework) in [
+        ("tauri", "tauri"),
+        ("actix-web", "actix-web"),
+        ("axum", "axum"),
+        ("rocket", "rocket"),
+    ] {
+        if contents.contains(needle) {
+            return Some(framework.to_string());
+        }
+    }
+
+    None
+}
+
+fn detect_node_framework(package_json: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(package_json).ok()?;
+
+    for (needle, framework) in [
+        ("\"next\"", "next"),
+        ("\"react\"", "react"),
+        ("\"vue\"", "vue"),
+        ("\"svelte\"", "svelte"),
+        ("\"@angular/core\"", "angular"),
+    ] {
+        if contents.contains(needle) {
+            return Some(framework.to_string());
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn detects_rust_with_tauri_framework() {
+        let dir = std::env::temp_dir().join(format!("project_kind_test_rust_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[dependencies]\ntauri = \"2\"\n").unwrap();
+
+        let info = detect(&dir).unwrap();
+        assert_eq!(info.kind, "rust");
+        assert_eq!(info.framework.as_deref(), Some("tauri"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn detects_node_with_react_framework() {
+        let dir = std::env::temp_dir().join(format!("project_kind_test_node_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("package.json"), "{\"dependencies\": {\"react\": \"^18\"}}").unwrap();
+
+        let info = detect(&dir).unwrap();
+        assert_eq!(info.kind, "node");
+        assert_eq!(info.framework.as_deref(), Some("react"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn returns_none_for_unrecognized_directory() {
+        let dir = std::env::temp_dir().join(format!("project_kind_test_empty_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        assert!(detect(&dir).is_none());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
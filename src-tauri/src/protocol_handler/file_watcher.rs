@@ -0,0 +1,226 @@
+//! Watch-and-reopen mode for a resolved [`WorkspaceMatch`]: once a file has
+//! been opened, keep an eye on it so an external build step or generator
+//! that rewrites it is picked up without the user having to re-trigger the
+//! `srcuri://` link by hand.
+//!
+//! Watches are tracked per workspace path (see [`WorkspaceFileWatcher::stop`])
+//! so tearing one down when a workspace is removed from settings is a single
+//! lookup rather than hunting down every file that happened to be watched
+//! under it.
+
+use crate::dispatcher::EditorDispatcher;
+use crate::editors::OpenMode;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// How long to wait after the last raw filesystem event before reopening,
+/// so a save that touches the file more than once (write + chmod, or a
+/// build step's temp-file-then-rename) settles into a single reopen.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// A single watched file: the OS-level watcher (dropping it stops the
+/// watch) plus the debounce/reopen task driven by its events.
+struct ActiveWatch {
+    _watcher: RecommendedWatcher,
+    task: JoinHandle<()>,
+}
+
+impl Drop for ActiveWatch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Registers filesystem watches for resolved workspace files and re-issues
+/// the editor `open` when one changes on disk. Holds one [`ActiveWatch`]
+/// per watched file, grouped by the workspace path it belongs to.
+pub struct WorkspaceFileWatcher {
+    dispatcher: Arc<EditorDispatcher>,
+    watches: Mutex<HashMap<PathBuf, Vec<ActiveWatch>>>,
+}
+
+impl WorkspaceFileWatcher {
+    pub fn new(dispatcher: Arc<EditorDispatcher>) -> Self {
+        Self {
+            dispatcher,
+            watches: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Starts watching `full_file_path` for changes, reopening it (with the
+    /// same `line`/`column`) once events settle. Watches the file's parent
+    /// directory rather than the file itself: an editor save commonly
+    /// replaces the file atomically via rename rather than writing it in
+    /// place, which would otherwise orphan a watch held on the old inode.
+    pub fn start(
+        &self,
+        workspace_path: PathBuf,
+        full_file_path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+    ) -> notify::Result<()> {
+        let Some(parent) = full_file_path.parent() else {
+            return Ok(());
+        };
+        let Some(file_name) = full_file_path.file_name().map(|n| n.to_owned()) else {
+            return Ok(());
+        };
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                if event.paths.iter().any(|p| p.file_name() == Some(file_name.as_os_str())) {
+                    let _ = tx.send(());
+                }
+            }
+        })?;
+        watcher.watch(parent, RecursiveMode::NonRecursive)?;
+
+        let dispatcher = self.dispatcher.clone();
+        let watched_path = full_file_path.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                if rx.recv().await.is_none() {
+                    return;
+                }
+                Self::drain_and_settle(&mut rx).await;
+
+                if !watched_path.is_file() {
+                    debug!("Watched file {:?} no longer exists, skipping reopen", watched_path);
+                    continue;
+                }
+
+                info!("Reopening {:?} after a change on disk", watched_path);
+                if let Err(e) = dispatcher
+                    .open(
+                        &watched_path.to_string_lossy(),
+                        line,
+                        column,
+                        OpenMode::Reuse,
+                        None,
+                    )
+                    .await
+                {
+                    warn!("Failed to reopen {:?} after a change: {}", watched_path, e);
+                }
+            }
+        });
+
+        self.watches
+            .lock()
+            .entry(workspace_path)
+            .or_default()
+            .push(ActiveWatch {
+                _watcher: watcher,
+                task,
+            });
+
+        Ok(())
+    }
+
+    /// Swallows further events arriving within `DEBOUNCE`, so a burst of
+    /// writes (or a rename pair) collapses into one reopen.
+    async fn drain_and_settle(rx: &mut mpsc::UnboundedReceiver<()>) {
+        loop {
+            tokio::select! {
+                event = rx.recv() => {
+                    if event.is_none() {
+                        return;
+                    }
+                }
+                _ = tokio::time::sleep(DEBOUNCE) => return,
+            }
+        }
+    }
+
+    /// Tears down every watch registered under `workspace_path`, e.g. when
+    /// that workspace is removed from settings.
+    pub fn stop(&self, workspace_path: &Path) {
+        if let Some(stopped) = self.watches.lock().remove(workspace_path) {
+            debug!(
+                "Stopped {} file watch(es) for workspace {:?}",
+                stopped.len(),
+                workspace_path
+            );
+        }
+    }
+
+    /// Number of files currently watched under `workspace_path`, for tests
+    /// and diagnostics.
+    pub fn watch_count(&self, workspace_path: &Path) -> usize {
+        self.watches
+            .lock()
+            .get(workspace_path)
+            .map(Vec::len)
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editors::EditorRegistry;
+    use crate::path_validator::PathValidator;
+    use crate::settings::SettingsManager;
+    use crate::tracker::ActiveEditorTracker;
+    use crate::workspace_mru::ActiveWorkspaceTracker;
+    use std::fs;
+    use std::time::Duration as StdDuration;
+    use tempfile::TempDir;
+
+    async fn test_dispatcher() -> Arc<EditorDispatcher> {
+        let settings_manager = Arc::new(SettingsManager::new().await.expect("settings"));
+        let path_validator = Arc::new(PathValidator::new(settings_manager.clone()));
+        let editor_registry = Arc::new(EditorRegistry::new(settings_manager.clone()));
+        let tracker = Arc::new(ActiveEditorTracker::new(editor_registry.clone()));
+        let workspace_tracker = Arc::new(ActiveWorkspaceTracker::new(settings_manager.clone()));
+
+        Arc::new(EditorDispatcher::new(
+            settings_manager,
+            path_validator,
+            editor_registry,
+            tracker,
+            workspace_tracker,
+        ))
+    }
+
+    #[tokio::test]
+    async fn start_registers_a_watch_and_stop_tears_it_down() {
+        let dir = TempDir::new().expect("tempdir");
+        let file = dir.path().join("main.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let watcher = WorkspaceFileWatcher::new(test_dispatcher().await);
+        watcher
+            .start(dir.path().to_path_buf(), file.clone(), None, None)
+            .expect("start watch");
+
+        assert_eq!(watcher.watch_count(dir.path()), 1);
+
+        watcher.stop(dir.path());
+        assert_eq!(watcher.watch_count(dir.path()), 0);
+    }
+
+    #[tokio::test]
+    async fn starting_a_second_watch_in_the_same_workspace_adds_to_it() {
+        let dir = TempDir::new().expect("tempdir");
+        let a = dir.path().join("a.rs");
+        let b = dir.path().join("b.rs");
+        fs::write(&a, "fn a() {}").unwrap();
+        fs::write(&b, "fn b() {}").unwrap();
+
+        let watcher = WorkspaceFileWatcher::new(test_dispatcher().await);
+        watcher.start(dir.path().to_path_buf(), a, None, None).expect("start a");
+        watcher.start(dir.path().to_path_buf(), b, None, None).expect("start b");
+
+        assert_eq!(watcher.watch_count(dir.path()), 2);
+        tokio::time::sleep(StdDuration::from_millis(10)).await;
+    }
+}
@@ -1,9 +1,25 @@
 use super::GitRef;
-use crate::git_command_log::run_git_command;
-use anyhow::{bail, Context, Result};
+use crate::git_command_log::{
+    active_backend_kind, backend_for, redact, run_git_command, run_git_command_streaming,
+    CancelToken, GitBackendKind, GitProgressEvent,
+};
+use anyhow::{Context, Result};
 use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::sync::mpsc;
 const MAX_FILE_SIZE_BYTES: u64 = 10 * 1024 * 1024; // 10MB
+/// Default cap passed to `create_worktree` when a caller has no per-project
+/// override - worktrees beyond this are evicted LRU-style by
+/// `enforce_worktree_limit`, skipping any a caller has pinned via
+/// `set_worktree_pinned`.
+pub const DEFAULT_MAX_WORKTREES: usize = 3;
+/// Default number of entries `stream_working_tree_status` batches together
+/// before calling back, balancing UI responsiveness against callback
+/// overhead on a very large repo.
+pub const STATUS_BATCH_SIZE: usize = 512;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct WorkingTreeStatus {
@@ -12,21 +28,210 @@ pub struct WorkingTreeStatus {
     pub untracked_count: usize,
 }
 
+/// One `git status --porcelain=v2` entry, as streamed by
+/// [`GitHandler::stream_working_tree_status`].
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkingTreeEntry {
+    pub path: String,
+    pub is_untracked: bool,
+}
+
+/// A chunk of [`WorkingTreeEntry`]s from [`GitHandler::stream_working_tree_status`],
+/// carrying running tallies so a caller can update counts progressively
+/// instead of waiting for the final [`WorkingTreeStatus`].
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkingTreeStatusBatch {
+    pub entries: Vec<WorkingTreeEntry>,
+    pub modified_count: usize,
+    pub untracked_count: usize,
+}
+
+/// One line of [`GitHandler::get_blame_at_revision`] output: the commit that
+/// last touched it and that commit's author/summary metadata, collapsed from
+/// git's repeated-header porcelain format so every line carries its full
+/// provenance regardless of whether git bothered to repeat it.
+#[derive(Debug, Clone, Serialize)]
+pub struct BlameLine {
+    pub line_no: usize,
+    pub commit_short: String,
+    pub author: String,
+    pub author_time: i64,
+    pub summary: String,
+    pub content: String,
+}
+
+/// Which in-progress git operation is blocking other git commands, so the
+/// UI can render the matching abort/continue button rather than a generic
+/// "busy" message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitOperationKind {
+    Merge,
+    Rebase,
+    CherryPick,
+    Bisect,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct GitOperationState {
     pub is_blocked: bool,
     pub blocking_reason: Option<String>,
+    pub operation: Option<GitOperationKind>,
+}
+
+/// Whether `abort_operation` or `continue_operation` ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationAction {
+    Abort,
+    Continue,
+}
+
+/// Outcome of [`GitHandler::abort_operation`] / [`GitHandler::continue_operation`].
+/// `had_conflicts` tells the caller a `continue` left the tree mid-operation
+/// again (e.g. the next commit in a rebase also conflicted) rather than
+/// finishing cleanly.
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationActionResult {
+    pub operation: GitOperationKind,
+    pub action: OperationAction,
+    pub had_conflicts: bool,
+}
+
+/// Where a workspace's repository actually lives, discovered via `git
+/// rev-parse` rather than assumed from `.git`'s presence. A `.git` *entry*
+/// (file or directory) existing one level down isn't enough to locate the
+/// real git directory: it's a `gitdir:` pointer file for a linked worktree
+/// or submodule, and bare repos have no `.git` subdirectory at all - the
+/// repo directory itself is the git dir.
+#[derive(Debug, Clone)]
+pub struct RepoLocation {
+    /// The working tree root, or `None` for a bare repo.
+    pub toplevel: Option<PathBuf>,
+    /// The real git directory - for a linked worktree this is
+    /// `<main-repo>/.git/worktrees/<name>`, where sentinel files like
+    /// `MERGE_HEAD` actually live, not `<worktree>/.git` itself.
+    pub git_dir: PathBuf,
+    pub is_bare: bool,
+    pub is_linked_worktree: bool,
+}
+
+/// Outcome of [`GitHandler::checkout_revision_stashing`]: whether a dirty
+/// tree actually had to be set aside before the checkout could proceed, so
+/// the caller knows whether a later `restore_autostash` call is meaningful.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckoutStashResult {
+    pub stashed: bool,
+}
+
+/// Outcome of [`GitHandler::restore_autostash`]: whether there was anything
+/// to pop, and whether doing so left conflict markers behind for the user
+/// to resolve rather than cleanly restoring their working tree.
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreStashResult {
+    pub restored: bool,
+    pub had_conflicts: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorktreeInfo {
+    pub path: PathBuf,
+    pub head_oid: Option<String>,
+    pub branch: Option<String>,
+    pub is_detached: bool,
+    /// Set by `git worktree lock` (surfaced as a `locked` line in
+    /// `--porcelain` output) - `set_worktree_pinned` is the one place that
+    /// toggles it, and `enforce_worktree_limit` skips locked worktrees when
+    /// picking eviction candidates.
+    pub is_locked: bool,
+}
+
+/// A git operation's failure, classified by exit status and stderr instead
+/// of left as a free-text `anyhow` message, so a caller like
+/// `get_revision_dialog_state` can `match` on the kind of failure instead of
+/// grepping stderr for substrings like `"already checked out"`. Still
+/// implements `Display` (via thiserror), so existing `.to_string()` call
+/// sites at the Tauri command boundary don't need to change.
+#[derive(Debug, thiserror::Error)]
+pub enum GitError {
+    #[error("Workspace is not a git repository: {0}")]
+    NotARepository(PathBuf),
+
+    #[error("Invalid git revision '{rev}': {stderr}")]
+    InvalidRevision { rev: String, stderr: String },
+
+    #[error("Revision '{0}' not found")]
+    RevisionNotFound(String),
+
+    /// Distinct from [`Self::RevisionNotFound`]: the revision isn't in any
+    /// ref `RevisionResolver` already knows about, but the request named a
+    /// `remote` it could plausibly come from, so a caller can offer to fetch
+    /// rather than just reporting a flat miss.
+    #[error("Revision '{rev}' is not available locally; fetch from '{remote}' and try again")]
+    NeedsFetch { rev: String, remote: String },
+
+    #[error("File '{file_path}' does not exist at revision '{rev}'")]
+    FileNotFoundAtRevision { file_path: String, rev: String },
+
+    #[error("File '{path}' is too large ({size} bytes, max {max} bytes)")]
+    FileTooLarge { path: String, size: usize, max: u64 },
+
+    #[error("Working tree has {modified_count} modified file(s)")]
+    WorkingTreeDirty { modified_count: usize },
+
+    #[error("Git operation already in progress: {0:?}")]
+    OperationInProgress(GitOperationState),
+
+    #[error("Branch is already checked out in another worktree")]
+    BranchAlreadyCheckedOut,
+
+    #[error("git command failed: {stderr}")]
+    CommandFailed { stderr: String },
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl GitError {
+    fn not_a_repo(workspace_path: &Path) -> Self {
+        Self::NotARepository(workspace_path.to_path_buf())
+    }
+
+    /// Classifies a failed `git checkout`'s stderr: an unknown pathspec
+    /// means the revision itself doesn't exist, anything else is some other
+    /// checkout failure (conflicting local changes git itself refused to
+    /// clobber, detached-HEAD weirdness, etc).
+    fn classify_checkout_failure(rev: &str, stderr: &str) -> Self {
+        if stderr.contains("pathspec") && stderr.contains("did not match") {
+            Self::RevisionNotFound(rev.to_string())
+        } else {
+            Self::CommandFailed {
+                stderr: stderr.to_string(),
+            }
+        }
+    }
+
+    /// Classifies a failed `git worktree add`'s stderr: git refuses to check
+    /// the same branch out twice, which `create_worktree` normally recovers
+    /// from by retrying detached - this only fires if that retry *also*
+    /// fails, or a caller wants to report the root cause directly.
+    fn classify_worktree_add_failure(stderr: &str) -> Self {
+        if stderr.contains("already checked out") || stderr.contains("is already used") {
+            Self::BranchAlreadyCheckedOut
+        } else {
+            Self::CommandFailed {
+                stderr: stderr.to_string(),
+            }
+        }
+    }
 }
 
 pub struct GitHandler;
 
 impl GitHandler {
-    pub fn validate_revision(workspace_path: &Path, rev: &str) -> Result<()> {
+    pub fn validate_revision(workspace_path: &Path, rev: &str) -> Result<(), GitError> {
         if !Self::is_git_repo(workspace_path) {
-            bail!(
-                "Workspace is not a git repository: {}",
-                workspace_path.display()
-            );
+            return Err(GitError::not_a_repo(workspace_path));
         }
 
         let workspace_str = workspace_path.to_string_lossy();
@@ -34,11 +239,10 @@ impl GitHandler {
             .context("Failed to execute git rev-parse")?;
 
         if !output.status.success() {
-            bail!(
-                "Invalid git revision '{}': {}",
-                rev,
-                String::from_utf8_lossy(&output.stderr)
-            );
+            return Err(GitError::InvalidRevision {
+                rev: rev.to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
         }
 
         Ok(())
@@ -48,12 +252,9 @@ impl GitHandler {
         workspace_path: &Path,
         file_path: &str,
         rev: &str,
-    ) -> Result<String> {
+    ) -> Result<String, GitError> {
         if !Self::is_git_repo(workspace_path) {
-            bail!(
-                "Workspace is not a git repository: {}",
-                workspace_path.display()
-            );
+            return Err(GitError::not_a_repo(workspace_path));
         }
 
         let workspace_str = workspace_path.to_string_lossy();
@@ -63,37 +264,120 @@ impl GitHandler {
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             if stderr.contains("does not exist") || stderr.contains("exists on disk, but not in") {
-                bail!("File '{}' does not exist at revision '{}'", file_path, rev);
+                return Err(GitError::FileNotFoundAtRevision {
+                    file_path: file_path.to_string(),
+                    rev: rev.to_string(),
+                });
             }
-            bail!(
-                "Failed to get file '{}' at revision '{}': {}",
-                file_path,
-                rev,
-                stderr
-            );
+            return Err(GitError::CommandFailed {
+                stderr: stderr.to_string(),
+            });
         }
 
         let content =
             String::from_utf8(output.stdout).context("File content is not valid UTF-8")?;
 
         if content.len() > MAX_FILE_SIZE_BYTES as usize {
-            bail!(
-                "File '{}' is too large ({} bytes, max {} bytes)",
-                file_path,
-                content.len(),
-                MAX_FILE_SIZE_BYTES
-            );
+            return Err(GitError::FileTooLarge {
+                path: file_path.to_string(),
+                size: content.len(),
+                max: MAX_FILE_SIZE_BYTES,
+            });
         }
 
         Ok(content)
     }
 
-    pub fn get_revision_info(workspace_path: &Path, rev: &str) -> Result<String> {
+    /// Runs `git blame --porcelain <rev> -- <file_path>` and collapses it
+    /// into one [`BlameLine`] per line. Porcelain format only repeats a
+    /// commit's author/summary metadata the first time that commit appears
+    /// in the output - later lines blamed on the same commit carry just its
+    /// oid - so metadata is cached by oid as it's seen and reused for
+    /// repeats. Shares `get_file_at_revision`'s existence/size guard so the
+    /// UI gets the same `FileNotFoundAtRevision`/`FileTooLarge` errors for
+    /// both.
+    pub fn get_blame_at_revision(
+        workspace_path: &Path,
+        file_path: &str,
+        rev: &str,
+    ) -> Result<Vec<BlameLine>, GitError> {
+        Self::get_file_at_revision(workspace_path, file_path, rev)?;
+
+        let workspace_str = workspace_path.to_string_lossy();
+        let output = run_git_command(
+            &workspace_str,
+            &["blame", "--porcelain", rev, "--", file_path],
+        )
+        .context("Failed to execute git blame")?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        let stdout =
+            String::from_utf8(output.stdout).context("git blame output is not valid UTF-8")?;
+        Ok(Self::parse_blame_porcelain(&stdout))
+    }
+
+    /// Parses `git blame --porcelain` output. Each hunk starts with a header
+    /// line `<oid> <orig-line> <final-line> [<num-lines>]`; the first time an
+    /// oid appears it's followed by `author `/`author-time `/`summary `
+    /// metadata lines before the tab-prefixed content line, and later
+    /// appearances of the same oid skip straight to the content line.
+    fn parse_blame_porcelain(output: &str) -> Vec<BlameLine> {
+        let mut metadata_by_oid: HashMap<String, (String, i64, String)> = HashMap::new();
+        let mut lines_out = Vec::new();
+
+        let mut current_oid = String::new();
+        let mut current_final_line = 0usize;
+        let mut pending_author = String::new();
+        let mut pending_author_time = 0i64;
+        let mut pending_summary = String::new();
+
+        for line in output.lines() {
+            if let Some(content) = line.strip_prefix('\t') {
+                let (author, author_time, summary) = metadata_by_oid
+                    .entry(current_oid.clone())
+                    .or_insert_with(|| {
+                        (pending_author.clone(), pending_author_time, pending_summary.clone())
+                    })
+                    .clone();
+
+                lines_out.push(BlameLine {
+                    line_no: current_final_line,
+                    commit_short: current_oid.chars().take(7).collect(),
+                    author,
+                    author_time,
+                    summary,
+                    content: content.to_string(),
+                });
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix("author ") {
+                pending_author = rest.to_string();
+            } else if let Some(rest) = line.strip_prefix("author-time ") {
+                pending_author_time = rest.trim().parse().unwrap_or(0);
+            } else if let Some(rest) = line.strip_prefix("summary ") {
+                pending_summary = rest.to_string();
+            } else {
+                let mut fields = line.split_whitespace();
+                let Some(oid) = fields.next() else { continue };
+                if oid.len() == 40 && oid.chars().all(|c| c.is_ascii_hexdigit()) {
+                    current_oid = oid.to_string();
+                    current_final_line = fields.nth(1).and_then(|s| s.parse().ok()).unwrap_or(0);
+                }
+            }
+        }
+
+        lines_out
+    }
+
+    pub fn get_revision_info(workspace_path: &Path, rev: &str) -> Result<String, GitError> {
         if !Self::is_git_repo(workspace_path) {
-            bail!(
-                "Workspace is not a git repository: {}",
-                workspace_path.display()
-            );
+            return Err(GitError::not_a_repo(workspace_path));
         }
 
         let workspace_str = workspace_path.to_string_lossy();
@@ -104,21 +388,17 @@ impl GitHandler {
         .context("Failed to execute git log")?;
 
         if !output.status.success() {
-            bail!(
-                "Failed to get revision info: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
+            return Err(GitError::CommandFailed {
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
         }
 
         Ok(String::from_utf8(output.stdout).context("Git log output is not valid UTF-8")?)
     }
 
-    pub fn get_current_ref(workspace_path: &Path) -> Result<String> {
+    pub fn get_current_ref(workspace_path: &Path) -> Result<String, GitError> {
         if !Self::is_git_repo(workspace_path) {
-            bail!(
-                "Workspace is not a git repository: {}",
-                workspace_path.display()
-            );
+            return Err(GitError::not_a_repo(workspace_path));
         }
 
         let workspace_str = workspace_path.to_string_lossy();
@@ -126,42 +406,53 @@ impl GitHandler {
             .context("Failed to execute git symbolic-ref")?;
 
         if output.status.success() {
-            return Ok(String::from_utf8(output.stdout)?.trim().to_string());
+            let stdout = String::from_utf8(output.stdout).context("git output is not valid UTF-8")?;
+            return Ok(stdout.trim().to_string());
         }
 
         let output = run_git_command(&workspace_str, &["rev-parse", "--short", "HEAD"])
             .context("Failed to execute git rev-parse")?;
 
         if !output.status.success() {
-            bail!(
-                "Failed to get current ref: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
+            return Err(GitError::CommandFailed {
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
         }
 
-        Ok(String::from_utf8(output.stdout)?.trim().to_string())
+        let stdout = String::from_utf8(output.stdout).context("git output is not valid UTF-8")?;
+        Ok(stdout.trim().to_string())
     }
 
-    pub fn get_working_tree_status(workspace_path: &Path) -> Result<WorkingTreeStatus> {
+    /// Uses `GitBackendKind::Git2` instead of shelling out when
+    /// `active_backend_kind()` reports there's no `git` binary on PATH, so
+    /// this stays usable on a locked-down machine without one - see
+    /// `git_command_log::backend`.
+    pub fn get_working_tree_status(workspace_path: &Path) -> Result<WorkingTreeStatus, GitError> {
         if !Self::is_git_repo(workspace_path) {
-            bail!(
-                "Workspace is not a git repository: {}",
-                workspace_path.display()
-            );
+            return Err(GitError::not_a_repo(workspace_path));
         }
 
         let workspace_str = workspace_path.to_string_lossy();
-        let output = run_git_command(&workspace_str, &["status", "--porcelain"])
-            .context("Failed to execute git status")?;
 
-        if !output.status.success() {
-            bail!(
-                "Failed to get working tree status: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
-        }
+        let status_output = if active_backend_kind() == GitBackendKind::Git2 {
+            let output = backend_for(GitBackendKind::Git2).status(&workspace_str);
+            if !output.success {
+                return Err(GitError::CommandFailed { stderr: output.stderr });
+            }
+            output.stdout
+        } else {
+            let output = run_git_command(&workspace_str, &["status", "--porcelain"])
+                .context("Failed to execute git status")?;
+
+            if !output.status.success() {
+                return Err(GitError::CommandFailed {
+                    stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                });
+            }
+
+            String::from_utf8(output.stdout).context("git status output is not valid UTF-8")?
+        };
 
-        let status_output = String::from_utf8(output.stdout)?;
         let lines: Vec<&str> = status_output.lines().collect();
 
         let modified_count = lines.iter().filter(|line| !line.starts_with("??")).count();
@@ -175,20 +466,149 @@ impl GitHandler {
         })
     }
 
-    pub fn check_git_operation_state(workspace_path: &Path) -> Result<GitOperationState> {
+    /// Like `get_working_tree_status`, but for very large working trees:
+    /// streams `git status --porcelain=v2 -z` incrementally rather than
+    /// collecting and parsing the whole output at once, calling `on_batch`
+    /// every `batch_size` entries (plus once more for any remainder) so a
+    /// caller can update counts progressively instead of freezing until the
+    /// scan finishes. Checks `cancel` between records and kills the child as
+    /// soon as a superseded request is detected.
+    pub fn stream_working_tree_status(
+        workspace_path: &Path,
+        batch_size: usize,
+        cancel: &CancelToken,
+        mut on_batch: impl FnMut(WorkingTreeStatusBatch),
+    ) -> Result<WorkingTreeStatus, GitError> {
         if !Self::is_git_repo(workspace_path) {
-            bail!(
-                "Workspace is not a git repository: {}",
-                workspace_path.display()
-            );
+            return Err(GitError::not_a_repo(workspace_path));
+        }
+
+        let mut child = std::process::Command::new("git")
+            .current_dir(workspace_path)
+            .args(["status", "--porcelain=v2", "-z"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .context("Failed to spawn git status")?;
+
+        let stdout = child.stdout.take().expect("stdout was piped");
+        let mut reader = BufReader::new(stdout);
+
+        let mut modified_count = 0usize;
+        let mut untracked_count = 0usize;
+        let mut batch: Vec<WorkingTreeEntry> = Vec::with_capacity(batch_size);
+
+        loop {
+            if cancel.is_cancelled() {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(GitError::CommandFailed {
+                    stderr: "Working tree status scan cancelled".to_string(),
+                });
+            }
+
+            let mut record = Vec::new();
+            let read = reader
+                .read_until(0, &mut record)
+                .context("Failed to read git status output")?;
+            if read == 0 {
+                break;
+            }
+            if record.last() == Some(&0) {
+                record.pop();
+            }
+            if record.is_empty() {
+                continue;
+            }
+
+            let line = String::from_utf8_lossy(&record).into_owned();
+            let kind = line.as_bytes()[0];
+
+            // Renamed/copied entries carry an extra NUL-terminated origin
+            // path after the new path - consume it so it isn't mistaken for
+            // the start of the next record.
+            if kind == b'2' {
+                let mut origin = Vec::new();
+                reader
+                    .read_until(0, &mut origin)
+                    .context("Failed to read rename origin path")?;
+            }
+
+            if kind == b'!' {
+                continue;
+            }
+
+            let is_untracked = kind == b'?';
+            if is_untracked {
+                untracked_count += 1;
+            } else {
+                modified_count += 1;
+            }
+
+            batch.push(WorkingTreeEntry {
+                path: Self::parse_porcelain_v2_path(&line, kind),
+                is_untracked,
+            });
+
+            if batch.len() >= batch_size {
+                on_batch(WorkingTreeStatusBatch {
+                    entries: std::mem::replace(&mut batch, Vec::with_capacity(batch_size)),
+                    modified_count,
+                    untracked_count,
+                });
+            }
         }
 
-        let git_dir = workspace_path.join(".git");
+        if !batch.is_empty() {
+            on_batch(WorkingTreeStatusBatch {
+                entries: batch,
+                modified_count,
+                untracked_count,
+            });
+        }
+
+        let status = child.wait().context("Failed to wait on git status")?;
+        if !status.success() {
+            return Err(GitError::CommandFailed {
+                stderr: "git status exited with a failure".to_string(),
+            });
+        }
+
+        Ok(WorkingTreeStatus {
+            is_clean: modified_count == 0 && untracked_count == 0,
+            modified_count,
+            untracked_count,
+        })
+    }
+
+    /// Extracts the path field from one `git status --porcelain=v2` record
+    /// given its leading byte (`1`/`2`/`u`/`?`/`!`). Ordinary, renamed/copied,
+    /// and unmerged entries each have a different number of fixed fields
+    /// before the path; untracked/ignored entries have just the one.
+    fn parse_porcelain_v2_path(line: &str, kind: u8) -> String {
+        let field_count = match kind {
+            b'1' => 9,
+            b'2' => 10,
+            b'u' => 11,
+            _ => 2,
+        };
+        line.splitn(field_count, ' ')
+            .last()
+            .unwrap_or(line)
+            .to_string()
+    }
+
+    pub fn check_git_operation_state(workspace_path: &Path) -> Result<GitOperationState, GitError> {
+        let Some(location) = Self::discover_repo(workspace_path) else {
+            return Err(GitError::not_a_repo(workspace_path));
+        };
+
+        let git_dir = location.git_dir;
 
         if git_dir.join("MERGE_HEAD").exists() {
             return Ok(GitOperationState {
                 is_blocked: true,
                 blocking_reason: Some("Merge in progress".to_string()),
+                operation: Some(GitOperationKind::Merge),
             });
         }
 
@@ -199,6 +619,7 @@ impl GitHandler {
             return Ok(GitOperationState {
                 is_blocked: true,
                 blocking_reason: Some("Rebase in progress".to_string()),
+                operation: Some(GitOperationKind::Rebase),
             });
         }
 
@@ -206,6 +627,7 @@ impl GitHandler {
             return Ok(GitOperationState {
                 is_blocked: true,
                 blocking_reason: Some("Cherry-pick in progress".to_string()),
+                operation: Some(GitOperationKind::CherryPick),
             });
         }
 
@@ -213,37 +635,116 @@ impl GitHandler {
             return Ok(GitOperationState {
                 is_blocked: true,
                 blocking_reason: Some("Bisect in progress".to_string()),
+                operation: Some(GitOperationKind::Bisect),
             });
         }
 
         Ok(GitOperationState {
             is_blocked: false,
             blocking_reason: None,
+            operation: None,
         })
     }
 
-    pub fn checkout_revision(workspace_path: &Path, rev: &str) -> Result<()> {
+    /// Runs the abort command for whichever operation `check_git_operation_state`
+    /// reports is in progress (`git merge --abort`, `rebase --abort`,
+    /// `cherry-pick --abort`, or `bisect reset`).
+    pub fn abort_operation(workspace_path: &Path) -> Result<OperationActionResult, GitError> {
         if !Self::is_git_repo(workspace_path) {
-            bail!(
-                "Workspace is not a git repository: {}",
-                workspace_path.display()
-            );
+            return Err(GitError::not_a_repo(workspace_path));
+        }
+
+        let state = Self::check_git_operation_state(workspace_path)?;
+        let Some(operation) = state.operation else {
+            return Err(GitError::CommandFailed {
+                stderr: "No in-progress git operation to abort".to_string(),
+            });
+        };
+
+        let workspace_str = workspace_path.to_string_lossy();
+        let args: &[&str] = match operation {
+            GitOperationKind::Merge => &["merge", "--abort"],
+            GitOperationKind::Rebase => &["rebase", "--abort"],
+            GitOperationKind::CherryPick => &["cherry-pick", "--abort"],
+            GitOperationKind::Bisect => &["bisect", "reset"],
+        };
+
+        let output = run_git_command(&workspace_str, args)
+            .context("Failed to execute git abort command")?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        Ok(OperationActionResult {
+            operation,
+            action: OperationAction::Abort,
+            had_conflicts: false,
+        })
+    }
+
+    /// Runs the continue command for whichever operation
+    /// `check_git_operation_state` reports is in progress (`git merge
+    /// --continue`, `rebase --continue`, `cherry-pick --continue`). Bisect
+    /// has no separate continue step - `good`/`bad` advance it and `reset`
+    /// is the only way out - so it maps to the same `bisect reset` as
+    /// `abort_operation`.
+    pub fn continue_operation(workspace_path: &Path) -> Result<OperationActionResult, GitError> {
+        if !Self::is_git_repo(workspace_path) {
+            return Err(GitError::not_a_repo(workspace_path));
+        }
+
+        let state = Self::check_git_operation_state(workspace_path)?;
+        let Some(operation) = state.operation else {
+            return Err(GitError::CommandFailed {
+                stderr: "No in-progress git operation to continue".to_string(),
+            });
+        };
+
+        let workspace_str = workspace_path.to_string_lossy();
+        let args: &[&str] = match operation {
+            GitOperationKind::Merge => &["merge", "--continue"],
+            GitOperationKind::Rebase => &["rebase", "--continue"],
+            GitOperationKind::CherryPick => &["cherry-pick", "--continue"],
+            GitOperationKind::Bisect => &["bisect", "reset"],
+        };
+
+        let output = run_git_command(&workspace_str, args)
+            .context("Failed to execute git continue command")?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let had_conflicts = stderr.to_lowercase().contains("conflict");
+
+        if !output.status.success() && !had_conflicts {
+            return Err(GitError::CommandFailed {
+                stderr: stderr.to_string(),
+            });
+        }
+
+        Ok(OperationActionResult {
+            operation,
+            action: OperationAction::Continue,
+            had_conflicts,
+        })
+    }
+
+    pub fn checkout_revision(workspace_path: &Path, rev: &str) -> Result<(), GitError> {
+        if !Self::is_git_repo(workspace_path) {
+            return Err(GitError::not_a_repo(workspace_path));
         }
 
         let state = Self::check_git_operation_state(workspace_path)?;
         if state.is_blocked {
-            bail!(
-                "Cannot checkout: {}",
-                state.blocking_reason.unwrap_or_default()
-            );
+            return Err(GitError::OperationInProgress(state));
         }
 
         let status = Self::get_working_tree_status(workspace_path)?;
         if !status.is_clean {
-            bail!(
-                "Cannot checkout: working tree has {} modified file(s)",
-                status.modified_count
-            );
+            return Err(GitError::WorkingTreeDirty {
+                modified_count: status.modified_count,
+            });
         }
 
         let workspace_str = workspace_path.to_string_lossy();
@@ -252,25 +753,114 @@ impl GitHandler {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            if stderr.contains("pathspec") && stderr.contains("did not match") {
-                bail!("Revision '{}' not found", rev);
-            }
-            bail!("Failed to checkout '{}': {}", rev, stderr);
+            return Err(GitError::classify_checkout_failure(rev, &stderr));
         }
 
         Ok(())
     }
 
+    /// Like `checkout_revision`, but instead of hard-failing on a dirty
+    /// working tree, autostashes it first (`git stash push
+    /// --include-untracked`) so the checkout can proceed, mirroring the
+    /// `autostash` option git itself offers for `rebase`/`pull`. Pair with
+    /// `restore_autostash` once the caller is done with the checked-out
+    /// revision - this deliberately doesn't pop the stash itself, so e.g. a
+    /// revision preview can leave the tree clean for as long as it's open.
+    pub fn checkout_revision_stashing(
+        workspace_path: &Path,
+        rev: &str,
+    ) -> Result<CheckoutStashResult, GitError> {
+        if !Self::is_git_repo(workspace_path) {
+            return Err(GitError::not_a_repo(workspace_path));
+        }
+
+        let state = Self::check_git_operation_state(workspace_path)?;
+        if state.is_blocked {
+            return Err(GitError::OperationInProgress(state));
+        }
+
+        let workspace_str = workspace_path.to_string_lossy();
+        let status = Self::get_working_tree_status(workspace_path)?;
+
+        let stashed = if status.is_clean {
+            false
+        } else {
+            let message = format!("sorcery-autostash {}", rev);
+            let output = run_git_command(
+                &workspace_str,
+                &["stash", "push", "--include-untracked", "-m", &message],
+            )
+            .context("Failed to execute git stash push")?;
+
+            if !output.status.success() {
+                return Err(GitError::CommandFailed {
+                    stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                });
+            }
+
+            true
+        };
+
+        let output = run_git_command(&workspace_str, &["checkout", rev])
+            .context("Failed to execute git checkout")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+            if stashed {
+                // Don't strand the user's changes in the stash over a
+                // checkout that didn't even succeed.
+                let _ = run_git_command(&workspace_str, &["stash", "pop"]);
+            }
+
+            return Err(GitError::classify_checkout_failure(rev, &stderr));
+        }
+
+        Ok(CheckoutStashResult { stashed })
+    }
+
+    /// Pops the stash `checkout_revision_stashing` created, if any. Reports
+    /// `had_conflicts` (rather than erroring) when the pop applied with
+    /// conflict markers, since in that case the stash is still meaningfully
+    /// "restored" - just not cleanly - and the caller needs to tell the user
+    /// to resolve them instead of assuming a silent success.
+    pub fn restore_autostash(workspace_path: &Path) -> Result<RestoreStashResult, GitError> {
+        if !Self::is_git_repo(workspace_path) {
+            return Err(GitError::not_a_repo(workspace_path));
+        }
+
+        let workspace_str = workspace_path.to_string_lossy();
+        let output = run_git_command(&workspace_str, &["stash", "pop"])
+            .context("Failed to execute git stash pop")?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let had_conflicts = stderr.to_lowercase().contains("conflict");
+
+        if !output.status.success() && !had_conflicts {
+            if stderr.contains("No stash entries found") {
+                return Ok(RestoreStashResult {
+                    restored: false,
+                    had_conflicts: false,
+                });
+            }
+            return Err(GitError::CommandFailed {
+                stderr: stderr.to_string(),
+            });
+        }
+
+        Ok(RestoreStashResult {
+            restored: true,
+            had_conflicts,
+        })
+    }
+
     pub fn file_exists_at_revision(
         workspace_path: &Path,
         file_path: &str,
         rev: &str,
-    ) -> Result<bool> {
+    ) -> Result<bool, GitError> {
         if !Self::is_git_repo(workspace_path) {
-            bail!(
-                "Workspace is not a git repository: {}",
-                workspace_path.display()
-            );
+            return Err(GitError::not_a_repo(workspace_path));
         }
 
         let workspace_str = workspace_path.to_string_lossy();
@@ -284,16 +874,11 @@ impl GitHandler {
     }
 
     pub fn find_git_root(start_path: &Path) -> Option<PathBuf> {
-        let mut current = start_path;
-        loop {
-            if Self::is_git_repo(current) {
-                return Some(current.to_path_buf());
-            }
-            current = current.parent()?;
-        }
+        let location = Self::discover_repo(start_path)?;
+        Some(location.toplevel.unwrap_or(location.git_dir))
     }
 
-    pub fn should_skip_revision_dialog(workspace_path: &Path, rev: &str) -> Result<bool> {
+    pub fn should_skip_revision_dialog(workspace_path: &Path, rev: &str) -> Result<bool, GitError> {
         if !Self::is_git_repo(workspace_path) {
             return Ok(false);
         }
@@ -307,17 +892,17 @@ impl GitHandler {
         workspace_path: &Path,
         file_path: &str,
         rev: &str,
-    ) -> Result<(bool, Option<String>, WorkingTreeStatus)> {
+    ) -> Result<(bool, Option<String>, WorkingTreeStatus), GitError> {
         if !Self::is_git_repo(workspace_path) {
-            bail!(
-                "Workspace is not a git repository: {}",
-                workspace_path.display()
-            );
+            return Err(GitError::not_a_repo(workspace_path));
         }
 
         let file_exists = Self::file_exists_at_revision(workspace_path, file_path, rev)?;
         if !file_exists {
-            bail!("File '{}' does not exist at revision '{}'", file_path, rev);
+            return Err(GitError::FileNotFoundAtRevision {
+                file_path: file_path.to_string(),
+                rev: rev.to_string(),
+            });
         }
 
         let status = Self::get_working_tree_status(workspace_path)?;
@@ -341,80 +926,251 @@ impl GitHandler {
     }
 
     fn is_git_repo(path: &Path) -> bool {
-        path.join(".git").exists()
+        Self::discover_repo(path).is_some()
     }
 
-    pub fn clone_repo(
+    /// Locates the repository containing `path` by asking git itself
+    /// (`git rev-parse --is-bare-repository --absolute-git-dir
+    /// --git-common-dir`, plus `--show-toplevel` for non-bare repos)
+    /// instead of assuming `.git` is a directory one level down. This makes
+    /// linked worktrees (`create_worktree`'s own output), submodules, and
+    /// bare repos all resolve correctly, where the naive check silently
+    /// treated bare repos as "not a repo" and read sentinel files from the
+    /// wrong place inside worktrees.
+    pub fn discover_repo(path: &Path) -> Option<RepoLocation> {
+        let path_str = path.to_string_lossy();
+        let output = run_git_command(
+            &path_str,
+            &[
+                "rev-parse",
+                "--is-bare-repository",
+                "--absolute-git-dir",
+                "--git-common-dir",
+            ],
+        )
+        .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        let mut lines = stdout.lines();
+        let is_bare = lines.next()? == "true";
+        let git_dir = PathBuf::from(lines.next()?);
+        let common_dir_raw = lines.next()?;
+        let common_dir = if Path::new(common_dir_raw).is_absolute() {
+            PathBuf::from(common_dir_raw)
+        } else {
+            path.join(common_dir_raw)
+        };
+
+        let toplevel = if is_bare {
+            None
+        } else {
+            let output = run_git_command(&path_str, &["rev-parse", "--show-toplevel"]).ok()?;
+            if output.status.success() {
+                Some(PathBuf::from(
+                    String::from_utf8(output.stdout).ok()?.trim(),
+                ))
+            } else {
+                None
+            }
+        };
+
+        Some(RepoLocation {
+            toplevel,
+            is_linked_worktree: git_dir != common_dir,
+            is_bare,
+            git_dir,
+        })
+    }
+
+    /// True if `workspace_path` is a git repository colocated with Jujutsu,
+    /// i.e. has both `.git` and `.jj` — the configuration under which `jj`
+    /// operates directly on the git repo's refs and objects.
+    pub fn is_colocated_jj_repo(workspace_path: &Path) -> bool {
+        Self::is_git_repo(workspace_path) && workspace_path.join(".jj").exists()
+    }
+
+    /// Resolves a jj change ID, bookmark, or revset (e.g. `@-`) to the
+    /// underlying git commit SHA, for a repo colocated with `.jj`. Bails if
+    /// the revset resolves to zero or more than one commit.
+    pub fn resolve_jj_revision(workspace_path: &Path, revset: &str) -> Result<String, GitError> {
+        if !Self::is_colocated_jj_repo(workspace_path) {
+            return Err(GitError::not_a_repo(workspace_path));
+        }
+
+        let output = std::process::Command::new("jj")
+            .current_dir(workspace_path)
+            .args(["log", "--no-graph", "-r", revset, "-T", "commit_id ++ \"\\n\""])
+            .output()
+            .context("Failed to execute jj log")?;
+
+        if !output.status.success() {
+            return Err(GitError::InvalidRevision {
+                rev: revset.to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        let commits: Vec<String> = String::from_utf8(output.stdout)
+            .context("jj log output is not valid UTF-8")?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        match commits.as_slice() {
+            [] => Err(GitError::RevisionNotFound(revset.to_string())),
+            [commit] => Ok(commit.clone()),
+            _ => Err(GitError::InvalidRevision {
+                rev: revset.to_string(),
+                stderr: format!("ambiguous, resolved to {} commits", commits.len()),
+            }),
+        }
+    }
+
+    /// Clones `remote_url` into `target_path`, preferring a partial clone
+    /// (`--filter=blob:none`) so opening one file out of a large monorepo
+    /// doesn't require every blob up front. When `git_ref` names a branch or
+    /// tag, also goes shallow (`--depth 1 --branch <ref>`); a bare commit SHA
+    /// can't be passed to `--branch`, so that case clones normally and then
+    /// fetches just that one commit. Streams progress lines over
+    /// `progress_tx` as the clone runs and stops early if `cancel` fires.
+    /// Falls back to a plain full clone if the server rejects the
+    /// partial/shallow request, and validates the requested revision exists
+    /// once cloning is done.
+    pub async fn clone(
         remote_url: &str,
         target_path: &Path,
         git_ref: Option<&GitRef>,
-    ) -> Result<()> {
-        use std::process::Command;
-
+        progress_tx: mpsc::UnboundedSender<GitProgressEvent>,
+        cancel: CancelToken,
+    ) -> Result<(), GitError> {
         if target_path.exists() {
-            bail!("Target path already exists: {}", target_path.display());
+            return Err(GitError::CommandFailed {
+                stderr: format!("Target path already exists: {}", target_path.display()),
+            });
         }
 
         if let Some(parent) = target_path.parent() {
             std::fs::create_dir_all(parent).context("Failed to create parent directory")?;
         }
 
-        // Ensure https:// prefix for git clone compatibility when needed
-        let url = if remote_url.starts_with("https://")
-            || remote_url.starts_with("http://")
-            || remote_url.starts_with("git@")
-            || remote_url.starts_with("ssh://")
-            || remote_url.starts_with("file://")
-            || remote_url.starts_with('/')
-        {
-            remote_url.to_string()
-        } else {
-            format!("https://{}", remote_url)
-        };
-
-        let mut cmd = Command::new("git");
-        cmd.arg("clone");
+        let url = Self::normalize_clone_url(remote_url);
+        let target_str = target_path.to_string_lossy().to_string();
 
-        if let Some(GitRef::Commit(_)) = git_ref {
-            cmd.arg("--no-checkout");
-        }
+        let branch_ref = match git_ref {
+            Some(GitRef::Branch(name)) | Some(GitRef::Tag(name)) => Some(name.as_str()),
+            _ => None,
+        };
 
-        if let Some(reference) = git_ref {
-            if let GitRef::Branch(name) | GitRef::Tag(name) = reference {
-                cmd.args(["--branch", name]);
-            }
+        let mut partial_args: Vec<&str> = vec!["clone", "--progress", "--filter=blob:none"];
+        if let Some(branch) = branch_ref {
+            partial_args.extend(["--depth", "1", "--branch", branch]);
         }
+        partial_args.extend([url.as_str(), target_str.as_str()]);
 
-        cmd.arg(&url);
-        cmd.arg(target_path);
+        let status =
+            run_git_command_streaming(".", &partial_args, progress_tx.clone(), cancel.clone())
+                .await
+                .context("Failed to execute git clone")?;
 
-        let output = cmd.output().context("Failed to execute git clone")?;
-
-        if !output.status.success() {
-            bail!(
-                "Failed to clone repository: {}",
-                String::from_utf8_lossy(&output.stderr)
+        if !status.success() {
+            tracing::warn!(
+                "Partial/shallow clone of '{}' failed, falling back to a full clone",
+                redact(&url)
             );
+            if target_path.exists() {
+                std::fs::remove_dir_all(target_path)
+                    .context("Failed to clean up failed partial clone")?;
+            }
+
+            let full_args = ["clone", "--progress", url.as_str(), target_str.as_str()];
+            let status =
+                run_git_command_streaming(".", &full_args, progress_tx.clone(), cancel.clone())
+                    .await
+                    .context("Failed to execute fallback git clone")?;
+
+            if !status.success() {
+                return Err(GitError::CommandFailed {
+                    stderr: format!(
+                        "Failed to clone repository '{}'",
+                        redact(&url)
+                    ),
+                });
+            }
         }
 
         if let Some(GitRef::Commit(commit)) = git_ref {
-            tracing::info!("Checking out commit {} after clone", commit);
-            let target_str = target_path.to_string_lossy();
-            let checkout = run_git_command(&target_str, &["checkout", commit])
+            tracing::info!("Fetching single commit {} after partial clone", commit);
+            let fetch_args = ["fetch", "--depth", "1", "origin", commit.as_str()];
+            let status = run_git_command_streaming(
+                &target_str,
+                &fetch_args,
+                progress_tx.clone(),
+                cancel.clone(),
+            )
+            .await
+            .context("Failed to execute git fetch for commit")?;
+
+            if !status.success() {
+                return Err(GitError::CommandFailed {
+                    stderr: format!("Failed to fetch commit '{}'", commit),
+                });
+            }
+
+            let checkout = run_git_command(&target_str, &["checkout", "FETCH_HEAD"])
                 .context("Failed to execute git checkout for commit")?;
             if !checkout.status.success() {
-                bail!(
-                    "Failed to checkout commit '{}': {}",
+                return Err(GitError::classify_checkout_failure(
                     commit,
-                    String::from_utf8_lossy(&checkout.stderr)
-                );
+                    &String::from_utf8_lossy(&checkout.stderr),
+                ));
             }
         }
 
-        tracing::info!("Cloned {} to {}", url, target_path.display());
+        if let Some(rev) = git_ref.and_then(Self::bare_rev) {
+            Self::validate_revision(target_path, rev)?;
+        }
+
+        tracing::info!(
+            "Cloned {} to {}",
+            redact(&url),
+            target_path.display()
+        );
         Ok(())
     }
 
+    /// The plain rev string for refs that `git rev-parse` understands
+    /// directly; a jj revset only resolves through a colocated jj repo
+    /// (which a fresh clone never has), so it has nothing to validate here.
+    fn bare_rev(git_ref: &GitRef) -> Option<&str> {
+        match git_ref {
+            GitRef::Commit(s) | GitRef::Branch(s) | GitRef::Tag(s) => Some(s.as_str()),
+            GitRef::Jj(_) => None,
+            // Not a literal rev string until it's resolved against the
+            // repo's tags - nothing to validate with a bare rev-parse yet.
+            GitRef::Version(_) => None,
+        }
+    }
+
+    fn normalize_clone_url(remote_url: &str) -> String {
+        if remote_url.starts_with("https://")
+            || remote_url.starts_with("http://")
+            || remote_url.starts_with("git@")
+            || remote_url.starts_with("ssh://")
+            || remote_url.starts_with("file://")
+            || remote_url.starts_with('/')
+        {
+            remote_url.to_string()
+        } else {
+            format!("https://{}", remote_url)
+        }
+    }
+
     /// Get the base directory for worktrees: ~/.sorcery/worktrees
     fn get_worktrees_base_dir() -> Result<PathBuf> {
         let home = dirs::home_dir().context("Could not find home directory")?;
@@ -438,29 +1194,47 @@ impl GitHandler {
     }
 
     /// Resolve a ref to its commit hash
-    fn resolve_commit_hash(workspace_path: &Path, rev: &str) -> Result<String> {
+    fn resolve_commit_hash(workspace_path: &Path, rev: &str) -> Result<String, GitError> {
         let workspace_str = workspace_path.to_string_lossy();
         let output = run_git_command(&workspace_str, &["rev-parse", rev])
             .context("Failed to resolve commit hash")?;
 
         if !output.status.success() {
-            bail!("Failed to resolve '{}' to commit hash", rev);
+            return Err(GitError::RevisionNotFound(rev.to_string()));
         }
 
-        Ok(String::from_utf8(output.stdout)?.trim().to_string())
+        let stdout = String::from_utf8(output.stdout).context("git output is not valid UTF-8")?;
+        Ok(stdout.trim().to_string())
     }
 
-    /// Enforce LRU limit: keep max 3 worktrees per project, remove oldest if needed
-    fn enforce_worktree_limit(workspace_path: &Path, project_dir: &Path) -> Result<()> {
-        const MAX_WORKTREES: usize = 3;
-
+    /// Enforces an LRU cap on worktrees under `project_dir`, evicting the
+    /// oldest by directory mtime until there's room for one more. Consults
+    /// `list_worktrees` alongside mtime so a worktree `set_worktree_pinned`
+    /// has locked is never picked as an eviction candidate, no matter how
+    /// stale its mtime looks.
+    fn enforce_worktree_limit(
+        workspace_path: &Path,
+        project_dir: &Path,
+        max_worktrees: usize,
+    ) -> Result<()> {
         if !project_dir.exists() {
             return Ok(());
         }
 
+        let locked_paths: std::collections::HashSet<PathBuf> = Self::list_worktrees(workspace_path)
+            .map(|worktrees| {
+                worktrees
+                    .into_iter()
+                    .filter(|wt| wt.is_locked)
+                    .map(|wt| wt.path)
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let mut entries: Vec<_> = std::fs::read_dir(project_dir)?
             .filter_map(|e| e.ok())
             .filter(|e| e.path().is_dir())
+            .filter(|e| !locked_paths.contains(&e.path()))
             .filter_map(|e| {
                 let metadata = e.metadata().ok()?;
                 let mtime = metadata.modified().ok()?;
@@ -468,15 +1242,16 @@ impl GitHandler {
             })
             .collect();
 
-        if entries.len() < MAX_WORKTREES {
+        if entries.len() < max_worktrees {
             return Ok(());
         }
 
         // Sort by mtime, oldest first
         entries.sort_by(|a, b| a.1.cmp(&b.1));
 
-        // Remove oldest entries until we're under the limit
-        let to_remove = entries.len() - (MAX_WORKTREES - 1); // -1 to make room for new one
+        // Remove oldest entries until we're under the limit, leaving room
+        // for the one about to be created.
+        let to_remove = entries.len() - max_worktrees.saturating_sub(1);
         for (path, _) in entries.into_iter().take(to_remove) {
             tracing::info!("Removing old worktree: {}", path.display());
 
@@ -501,24 +1276,178 @@ impl GitHandler {
         }
 
         // Prune stale worktree entries
+        if let Err(e) = Self::prune_worktrees(workspace_path) {
+            tracing::warn!("Failed to prune stale worktree entries: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Parses `git worktree list --porcelain`, the ground truth for what git
+    /// itself considers a live worktree (as opposed to checking the
+    /// filesystem, which can't tell a real worktree from a directory that
+    /// merely looks like one). Records are blank-line separated; within a
+    /// record each line is `key value`, except the bare `detached`/`locked`
+    /// flags (`locked` can also carry a trailing reason, which this ignores).
+    pub fn list_worktrees(workspace_path: &Path) -> Result<Vec<WorktreeInfo>, GitError> {
+        if !Self::is_git_repo(workspace_path) {
+            return Err(GitError::not_a_repo(workspace_path));
+        }
+
+        let workspace_str = workspace_path.to_string_lossy();
+        let output = run_git_command(&workspace_str, &["worktree", "list", "--porcelain"])
+            .context("Failed to execute git worktree list")?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut worktrees = Vec::new();
+        let mut current: Option<WorktreeInfo> = None;
+
+        for line in stdout.lines() {
+            if line.is_empty() {
+                if let Some(worktree) = current.take() {
+                    worktrees.push(worktree);
+                }
+            } else if let Some(path) = line.strip_prefix("worktree ") {
+                if let Some(worktree) = current.take() {
+                    worktrees.push(worktree);
+                }
+                current = Some(WorktreeInfo {
+                    path: PathBuf::from(path),
+                    head_oid: None,
+                    branch: None,
+                    is_detached: false,
+                    is_locked: false,
+                });
+            } else if let Some(head) = line.strip_prefix("HEAD ") {
+                if let Some(worktree) = current.as_mut() {
+                    worktree.head_oid = Some(head.to_string());
+                }
+            } else if let Some(branch) = line.strip_prefix("branch ") {
+                if let Some(worktree) = current.as_mut() {
+                    worktree.branch = Some(branch.trim_start_matches("refs/heads/").to_string());
+                }
+            } else if line == "detached" {
+                if let Some(worktree) = current.as_mut() {
+                    worktree.is_detached = true;
+                }
+            } else if line == "locked" || line.starts_with("locked ") {
+                if let Some(worktree) = current.as_mut() {
+                    worktree.is_locked = true;
+                }
+            }
+        }
+
+        if let Some(worktree) = current.take() {
+            worktrees.push(worktree);
+        }
+
+        Ok(worktrees)
+    }
+
+    /// Removes administrative state left behind for worktrees whose
+    /// directory disappeared out from under git (e.g. `rm -rf` instead of
+    /// `git worktree remove`), so they stop showing up in `list_worktrees`.
+    pub fn prune_worktrees(workspace_path: &Path) -> Result<(), GitError> {
+        if !Self::is_git_repo(workspace_path) {
+            return Err(GitError::not_a_repo(workspace_path));
+        }
+
         let workspace_str = workspace_path.to_string_lossy();
-        let _ = run_git_command(&workspace_str, &["worktree", "prune"]);
+        let output = run_git_command(&workspace_str, &["worktree", "prune"])
+            .context("Failed to execute git worktree prune")?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Removes a worktree via `git worktree remove [--force]`. If git
+    /// refuses (e.g. the worktree's directory already vanished out from
+    /// under it) but the directory itself is gone too, falls back to
+    /// pruning the stale administrative entry instead of erroring.
+    pub fn remove_worktree(
+        workspace_path: &Path,
+        worktree_path: &Path,
+        force: bool,
+    ) -> Result<(), GitError> {
+        if !Self::is_git_repo(workspace_path) {
+            return Err(GitError::not_a_repo(workspace_path));
+        }
+
+        let workspace_str = workspace_path.to_string_lossy();
+        let path_str = worktree_path.to_string_lossy();
+        let mut args: Vec<&str> = vec!["worktree", "remove"];
+        if force {
+            args.push("--force");
+        }
+        args.push(&path_str);
+
+        let output =
+            run_git_command(&workspace_str, &args).context("Failed to execute git worktree remove")?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+
+        if !worktree_path.exists() {
+            return Self::prune_worktrees(workspace_path);
+        }
+
+        Err(GitError::CommandFailed {
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        })
+    }
+
+    /// Locks or unlocks a worktree via `git worktree lock`/`unlock`, so
+    /// `enforce_worktree_limit` treats it as pinned and skips it when
+    /// picking eviction candidates.
+    pub fn set_worktree_pinned(
+        workspace_path: &Path,
+        worktree_path: &Path,
+        pinned: bool,
+    ) -> Result<(), GitError> {
+        if !Self::is_git_repo(workspace_path) {
+            return Err(GitError::not_a_repo(workspace_path));
+        }
+
+        let workspace_str = workspace_path.to_string_lossy();
+        let path_str = worktree_path.to_string_lossy();
+        let subcommand = if pinned { "lock" } else { "unlock" };
+        let output = run_git_command(&workspace_str, &["worktree", subcommand, &path_str])
+            .context("Failed to execute git worktree lock/unlock")?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
 
         Ok(())
     }
 
     /// Create a worktree for the given branch/commit, or reuse existing one.
-    /// Returns the path to the worktree.
+    /// Returns the path to the worktree. `max_worktrees` caps how many
+    /// worktrees `enforce_worktree_limit` keeps per project before evicting
+    /// the oldest unlocked one - pass `DEFAULT_MAX_WORKTREES` absent a
+    /// per-project override.
     pub fn create_worktree(
         workspace_path: &Path,
         project_name: &str,
         branch_or_commit: &str,
-    ) -> Result<PathBuf> {
+        max_worktrees: usize,
+    ) -> Result<PathBuf, GitError> {
         if !Self::is_git_repo(workspace_path) {
-            bail!(
-                "Workspace is not a git repository: {}",
-                workspace_path.display()
-            );
+            return Err(GitError::not_a_repo(workspace_path));
         }
 
         // Calculate worktree path
@@ -528,8 +1457,14 @@ impl GitHandler {
         let project_dir = base_dir.join(&safe_project);
         let worktree_path = project_dir.join(&safe_ref);
 
-        // Check if worktree already exists and is valid
-        if worktree_path.exists() && worktree_path.join(".git").exists() {
+        // Check if git itself already considers this path a live worktree,
+        // rather than trusting the filesystem, which can't distinguish a
+        // real worktree from a directory that merely looks like one.
+        let already_tracked = Self::list_worktrees(workspace_path)
+            .map(|worktrees| worktrees.iter().any(|wt| wt.path == worktree_path))
+            .unwrap_or(false);
+
+        if already_tracked && worktree_path.exists() {
             tracing::info!("Reusing existing worktree: {}", worktree_path.display());
             // Touch the directory to update mtime for LRU
             let _ = std::fs::File::create(worktree_path.join(".sorcery_accessed"));
@@ -544,7 +1479,7 @@ impl GitHandler {
         }
 
         // Enforce LRU limit before creating new worktree
-        Self::enforce_worktree_limit(workspace_path, &project_dir)?;
+        Self::enforce_worktree_limit(workspace_path, &project_dir, max_worktrees)?;
 
         // Ensure project directory exists
         std::fs::create_dir_all(&project_dir)
@@ -582,13 +1517,12 @@ impl GitHandler {
                 return Ok(worktree_path);
             }
 
-            bail!(
-                "Failed to create worktree (detached): {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
+            return Err(GitError::CommandFailed {
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
         }
 
-        bail!("Failed to create worktree: {}", stderr);
+        Err(GitError::classify_worktree_add_failure(&stderr))
     }
 }
 
@@ -634,18 +1568,422 @@ mod tests {
         String::from_utf8_lossy(&output.stdout).trim().to_string()
     }
 
-    #[test]
-    fn clone_repo_supports_commit_refs() {
+    #[tokio::test]
+    async fn clone_fetches_and_checks_out_bare_commit() {
         let (temp, origin, commit) = create_remote_repo();
         let target = temp.path().join("clone");
-        GitHandler::clone_repo(
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        GitHandler::clone(
             origin.to_str().unwrap(),
             &target,
             Some(&GitRef::Commit(commit.clone())),
+            tx,
+            CancelToken::new(),
         )
+        .await
         .expect("clone commit");
 
         let head = capture(Command::new("git").args(["rev-parse", "HEAD"]), &target);
         assert_eq!(head, commit);
+
+        let mut saw_progress = false;
+        while rx.try_recv().is_ok() {
+            saw_progress = true;
+        }
+        assert!(saw_progress, "expected at least one progress event");
+    }
+
+    #[tokio::test]
+    async fn clone_validates_requested_branch_exists() {
+        let (temp, origin, _commit) = create_remote_repo();
+        let target = temp.path().join("clone");
+        let (tx, _rx) = mpsc::unbounded_channel();
+
+        let result = GitHandler::clone(
+            origin.to_str().unwrap(),
+            &target,
+            Some(&GitRef::Branch("does-not-exist".to_string())),
+            tx,
+            CancelToken::new(),
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn get_blame_at_revision_attributes_each_line_to_its_commit() {
+        let temp = init_repo_with_feature_branch();
+        let feature_head =
+            capture(Command::new("git").args(["rev-parse", "feature"]), temp.path());
+
+        let lines = GitHandler::get_blame_at_revision(temp.path(), "b.txt", &feature_head)
+            .expect("blame");
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].content, "2");
+        assert_eq!(lines[0].summary, "feature change");
+        assert_eq!(&lines[0].commit_short, &feature_head[..7]);
+    }
+
+    #[test]
+    fn get_blame_at_revision_errors_when_file_missing_at_revision() {
+        let temp = init_repo_with_feature_branch();
+        let head = capture(Command::new("git").args(["rev-parse", "main"]), temp.path());
+
+        let result = GitHandler::get_blame_at_revision(temp.path(), "does-not-exist.txt", &head);
+        assert!(matches!(result, Err(GitError::FileNotFoundAtRevision { .. })));
+    }
+
+    #[test]
+    fn is_colocated_jj_repo_requires_both_dot_dirs() {
+        let temp = TempDir::new().expect("temp dir");
+        run(Command::new("git").arg("init"), temp.path());
+        assert!(!GitHandler::is_colocated_jj_repo(temp.path()));
+
+        std::fs::create_dir(temp.path().join(".jj")).unwrap();
+        assert!(GitHandler::is_colocated_jj_repo(temp.path()));
+    }
+
+    #[test]
+    fn resolve_jj_revision_rejects_non_colocated_repo() {
+        let temp = TempDir::new().expect("temp dir");
+        run(Command::new("git").arg("init"), temp.path());
+
+        let result = GitHandler::resolve_jj_revision(temp.path(), "@-");
+        assert!(result.is_err());
+    }
+
+    /// A repo with `a.txt`/`b.txt` committed on `main`, and a `feature`
+    /// branch that only changes `b.txt` - so dirtying `a.txt` on `main`
+    /// stashes and restores cleanly across a checkout to `feature`.
+    fn init_repo_with_feature_branch() -> TempDir {
+        let temp = TempDir::new().expect("temp dir");
+        let work = temp.path();
+        run(Command::new("git").arg("init"), work);
+        run(Command::new("git").args(["branch", "-M", "main"]), work);
+        std::fs::write(work.join("a.txt"), "1").unwrap();
+        std::fs::write(work.join("b.txt"), "1").unwrap();
+        run(Command::new("git").args(["add", "."]), work);
+        run(Command::new("git").args(["commit", "-m", "init"]), work);
+
+        run(Command::new("git").args(["checkout", "-b", "feature"]), work);
+        std::fs::write(work.join("b.txt"), "2").unwrap();
+        run(Command::new("git").args(["commit", "-am", "feature change"]), work);
+        run(Command::new("git").args(["checkout", "main"]), work);
+
+        temp
+    }
+
+    #[test]
+    fn checkout_revision_stashing_stashes_dirty_tree_and_checks_out() {
+        let temp = init_repo_with_feature_branch();
+        std::fs::write(temp.path().join("a.txt"), "dirty edit").unwrap();
+
+        let result = GitHandler::checkout_revision_stashing(temp.path(), "feature")
+            .expect("checkout with autostash");
+        assert!(result.stashed);
+
+        let head = GitHandler::get_current_ref(temp.path()).expect("current ref");
+        assert_eq!(head, "feature");
+        assert_eq!(std::fs::read_to_string(temp.path().join("b.txt")).unwrap(), "2");
+    }
+
+    #[test]
+    fn checkout_revision_stashing_skips_stash_on_clean_tree() {
+        let temp = init_repo_with_feature_branch();
+
+        let result = GitHandler::checkout_revision_stashing(temp.path(), "feature")
+            .expect("checkout without autostash");
+        assert!(!result.stashed);
+    }
+
+    #[test]
+    fn restore_autostash_reapplies_stashed_changes() {
+        let temp = init_repo_with_feature_branch();
+        std::fs::write(temp.path().join("a.txt"), "dirty edit").unwrap();
+
+        GitHandler::checkout_revision_stashing(temp.path(), "feature").expect("checkout");
+        let result = GitHandler::restore_autostash(temp.path()).expect("restore autostash");
+
+        assert!(result.restored);
+        assert!(!result.had_conflicts);
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("a.txt")).unwrap(),
+            "dirty edit"
+        );
+    }
+
+    #[test]
+    fn restore_autostash_reports_nothing_to_restore() {
+        let temp = init_repo_with_feature_branch();
+
+        let result = GitHandler::restore_autostash(temp.path()).expect("restore autostash");
+        assert!(!result.restored);
+        assert!(!result.had_conflicts);
+    }
+
+    #[test]
+    fn checkout_revision_stashing_refuses_during_merge() {
+        let temp = init_repo_with_feature_branch();
+        std::fs::write(temp.path().join(".git").join("MERGE_HEAD"), "deadbeef").unwrap();
+        std::fs::write(temp.path().join("a.txt"), "dirty edit").unwrap();
+
+        let result = GitHandler::checkout_revision_stashing(temp.path(), "feature");
+        assert!(result.is_err());
+
+        let status = GitHandler::get_working_tree_status(temp.path()).expect("status");
+        assert!(!status.is_clean, "dirty change should not have been stashed away");
+    }
+
+    #[test]
+    fn abort_operation_aborts_conflicting_merge() {
+        let temp = init_repo_with_feature_branch();
+        // feature and main both touch b.txt, so merging feature into main
+        // conflicts.
+        std::fs::write(temp.path().join("b.txt"), "main edit").unwrap();
+        run(Command::new("git").args(["commit", "-am", "main edit"]), temp.path());
+        let merge_status = Command::new("git")
+            .args(["merge", "feature"])
+            .current_dir(temp.path())
+            .status()
+            .expect("merge attempt");
+        assert!(!merge_status.success(), "expected merge conflict");
+
+        let state = GitHandler::check_git_operation_state(temp.path()).expect("state");
+        assert_eq!(state.operation, Some(GitOperationKind::Merge));
+
+        let result = GitHandler::abort_operation(temp.path()).expect("abort merge");
+        assert_eq!(result.operation, GitOperationKind::Merge);
+        assert_eq!(result.action, OperationAction::Abort);
+
+        let state = GitHandler::check_git_operation_state(temp.path()).expect("state after abort");
+        assert!(!state.is_blocked);
+    }
+
+    #[test]
+    fn abort_operation_errors_when_nothing_in_progress() {
+        let temp = init_repo_with_feature_branch();
+        let result = GitHandler::abort_operation(temp.path());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn continue_operation_finishes_clean_cherry_pick() {
+        let temp = init_repo_with_feature_branch();
+        let feature_head =
+            capture(Command::new("git").args(["rev-parse", "feature"]), temp.path());
+
+        // Start a cherry-pick that applies cleanly (no edit conflicts) but
+        // leave it open by asking git not to commit automatically.
+        Command::new("git")
+            .args(["cherry-pick", "--no-commit", &feature_head])
+            .current_dir(temp.path())
+            .status()
+            .expect("cherry-pick attempt");
+
+        let state = GitHandler::check_git_operation_state(temp.path()).expect("state");
+        assert_eq!(state.operation, Some(GitOperationKind::CherryPick));
+
+        let result = GitHandler::continue_operation(temp.path()).expect("continue cherry-pick");
+        assert_eq!(result.operation, GitOperationKind::CherryPick);
+        assert_eq!(result.action, OperationAction::Continue);
+        assert!(!result.had_conflicts);
+    }
+
+    #[test]
+    fn discover_repo_finds_ordinary_repo() {
+        let temp = init_repo_with_feature_branch();
+        let location = GitHandler::discover_repo(temp.path()).expect("location");
+        assert!(!location.is_bare);
+        assert!(!location.is_linked_worktree);
+        assert_eq!(
+            location.toplevel.unwrap().canonicalize().unwrap(),
+            temp.path().canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn discover_repo_identifies_bare_repo() {
+        let temp = TempDir::new().expect("temp dir");
+        let bare = temp.path().join("repo.git");
+        run(
+            Command::new("git").args(["init", "--bare", bare.to_str().unwrap()]),
+            temp.path(),
+        );
+
+        let location = GitHandler::discover_repo(&bare).expect("location");
+        assert!(location.is_bare);
+        assert!(location.toplevel.is_none());
+    }
+
+    #[test]
+    fn discover_repo_reads_linked_worktree_sentinel_files() {
+        let temp = init_repo_with_feature_branch();
+        let worktree = temp.path().join("wt");
+        run(
+            Command::new("git").args([
+                "worktree",
+                "add",
+                worktree.to_str().unwrap(),
+                "feature",
+            ]),
+            temp.path(),
+        );
+
+        // `.git` inside a linked worktree is a `gitdir:` pointer file, not a
+        // directory - confirm discovery still finds the real git dir.
+        assert!(worktree.join(".git").is_file());
+
+        let location = GitHandler::discover_repo(&worktree).expect("location");
+        assert!(location.is_linked_worktree);
+        assert!(!location.is_bare);
+
+        // main and feature both already touch b.txt relative to their common
+        // ancestor (feature from `init_repo_with_feature_branch`, main from
+        // the edit below), so merging main into feature - run from inside the
+        // worktree - conflicts. That lets us confirm the in-progress state is
+        // detected from the real (shared) git dir, not the `.git` pointer
+        // file that lives in the worktree itself.
+        std::fs::write(temp.path().join("b.txt"), "main edit").unwrap();
+        run(Command::new("git").args(["commit", "-am", "main edit"]), temp.path());
+
+        let merge_status = Command::new("git")
+            .args(["merge", "main"])
+            .current_dir(&worktree)
+            .status()
+            .expect("merge attempt");
+        assert!(!merge_status.success(), "expected merge conflict");
+
+        let state = GitHandler::check_git_operation_state(&worktree).expect("state");
+        assert_eq!(state.operation, Some(GitOperationKind::Merge));
+
+        let result = GitHandler::abort_operation(&worktree).expect("abort merge in worktree");
+        assert_eq!(result.operation, GitOperationKind::Merge);
+    }
+
+    #[test]
+    fn set_worktree_pinned_is_reflected_in_list_worktrees() {
+        let temp = init_repo_with_feature_branch();
+        let worktree = temp.path().join("wt");
+        run(
+            Command::new("git").args(["worktree", "add", worktree.to_str().unwrap(), "feature"]),
+            temp.path(),
+        );
+
+        let before = GitHandler::list_worktrees(temp.path()).expect("list worktrees");
+        let entry = before.iter().find(|wt| wt.path == worktree).expect("worktree entry");
+        assert!(!entry.is_locked);
+
+        GitHandler::set_worktree_pinned(temp.path(), &worktree, true).expect("lock worktree");
+
+        let after = GitHandler::list_worktrees(temp.path()).expect("list worktrees");
+        let entry = after.iter().find(|wt| wt.path == worktree).expect("worktree entry");
+        assert!(entry.is_locked);
+
+        GitHandler::set_worktree_pinned(temp.path(), &worktree, false).expect("unlock worktree");
+        let after = GitHandler::list_worktrees(temp.path()).expect("list worktrees");
+        let entry = after.iter().find(|wt| wt.path == worktree).expect("worktree entry");
+        assert!(!entry.is_locked);
+    }
+
+    #[test]
+    fn remove_worktree_drops_it_from_list_worktrees() {
+        let temp = init_repo_with_feature_branch();
+        let worktree = temp.path().join("wt");
+        run(
+            Command::new("git").args(["worktree", "add", worktree.to_str().unwrap(), "feature"]),
+            temp.path(),
+        );
+
+        GitHandler::remove_worktree(temp.path(), &worktree, false).expect("remove worktree");
+
+        let worktrees = GitHandler::list_worktrees(temp.path()).expect("list worktrees");
+        assert!(!worktrees.iter().any(|wt| wt.path == worktree));
+    }
+
+    #[test]
+    fn enforce_worktree_limit_skips_locked_worktrees_when_evicting() {
+        let temp = init_repo_with_feature_branch();
+        let project_dir = temp.path().join("worktrees-project");
+        std::fs::create_dir_all(&project_dir).unwrap();
+
+        let mut worktrees = Vec::new();
+        for i in 0..2 {
+            let wt = project_dir.join(format!("wt{i}"));
+            run(
+                Command::new("git").args(["worktree", "add", wt.to_str().unwrap(), "feature"]),
+                temp.path(),
+            );
+            worktrees.push(wt);
+        }
+        GitHandler::set_worktree_pinned(temp.path(), &worktrees[0], true).expect("lock worktree");
+
+        // Cap of 1: without the lock this would evict wt0 (the oldest), but
+        // it's pinned, so wt1 should be evicted instead.
+        GitHandler::enforce_worktree_limit(temp.path(), &project_dir, 1).expect("enforce limit");
+
+        assert!(worktrees[0].exists(), "locked worktree should survive eviction");
+        assert!(!worktrees[1].exists(), "unlocked worktree should be evicted");
+    }
+
+    #[test]
+    fn stream_working_tree_status_batches_entries_and_tallies_counts() {
+        let temp = init_repo_with_feature_branch();
+        std::fs::write(temp.path().join("a.txt"), "dirty edit").unwrap();
+        std::fs::write(temp.path().join("new.txt"), "untracked").unwrap();
+
+        let mut batches = Vec::new();
+        let cancel = CancelToken::new();
+        let result = GitHandler::stream_working_tree_status(temp.path(), 1, &cancel, |batch| {
+            batches.push(batch);
+        })
+        .expect("stream status");
+
+        assert_eq!(result.modified_count, 1);
+        assert_eq!(result.untracked_count, 1);
+        assert!(!result.is_clean);
+
+        // batch_size of 1 means every entry gets its own callback.
+        assert_eq!(batches.len(), 2);
+        let last = batches.last().unwrap();
+        assert_eq!(last.modified_count, 1);
+        assert_eq!(last.untracked_count, 1);
+
+        let all_paths: Vec<&str> = batches
+            .iter()
+            .flat_map(|b| b.entries.iter().map(|e| e.path.as_str()))
+            .collect();
+        assert!(all_paths.contains(&"a.txt"));
+        assert!(all_paths.contains(&"new.txt"));
+    }
+
+    #[test]
+    fn stream_working_tree_status_reports_clean_tree() {
+        let temp = init_repo_with_feature_branch();
+        let cancel = CancelToken::new();
+        let mut batch_count = 0;
+        let result =
+            GitHandler::stream_working_tree_status(temp.path(), STATUS_BATCH_SIZE, &cancel, |_| {
+                batch_count += 1;
+            })
+            .expect("stream status");
+
+        assert!(result.is_clean);
+        assert_eq!(batch_count, 0);
+    }
+
+    #[test]
+    fn stream_working_tree_status_honors_cancellation() {
+        let temp = init_repo_with_feature_branch();
+        std::fs::write(temp.path().join("a.txt"), "dirty edit").unwrap();
+
+        let cancel = CancelToken::new();
+        cancel.cancel();
+
+        let result =
+            GitHandler::stream_working_tree_status(temp.path(), STATUS_BATCH_SIZE, &cancel, |_| {});
+        assert!(result.is_err());
     }
 }
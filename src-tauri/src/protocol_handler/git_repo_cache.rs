@@ -0,0 +1,151 @@
+use super::git::{GitHandler, WorkingTreeStatus};
+use crate::git_command_log::run_git_command;
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long a cached entry is trusted without re-checking `.git/HEAD`'s mtime.
+const CACHE_TTL: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+struct CachedRepo {
+    current_ref: Option<String>,
+    remote_origin_url: Option<String>,
+    working_tree_status: Option<WorkingTreeStatus>,
+    head_mtime: Option<SystemTime>,
+    cached_at: Instant,
+}
+
+/// Memoizes per-repository git metadata that `ProtocolHandler` would
+/// otherwise re-derive (and re-spawn `git` processes for) on every srcuri
+/// open of an already-known repository. Keyed by canonicalized git root.
+///
+/// An entry is reused only while it's within `CACHE_TTL` *and* `.git/HEAD`'s
+/// mtime still matches what was cached, so a checkout or branch switch is
+/// picked up immediately rather than waiting out the TTL.
+pub struct GitRepoCache {
+    /// Maps a child path to its discovered git root, so repeat opens under
+    /// the same workspace skip re-walking parent directories.
+    roots_by_child: Mutex<HashMap<PathBuf, PathBuf>>,
+    repos: Mutex<HashMap<PathBuf, CachedRepo>>,
+    /// Per-repo lock used only around mutations (checkout, worktree add).
+    /// Read-only lookups never acquire it, so concurrent reads of the same
+    /// repository don't serialize behind each other.
+    repo_locks: Mutex<HashMap<PathBuf, Arc<tokio::sync::RwLock<()>>>>,
+}
+
+impl GitRepoCache {
+    pub fn new() -> Self {
+        Self {
+            roots_by_child: Mutex::new(HashMap::new()),
+            repos: Mutex::new(HashMap::new()),
+            repo_locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the lock a mutation against `git_root` (checkout, worktree
+    /// add) should hold for its duration. Read-only lookups in this cache
+    /// deliberately don't take it.
+    pub fn mutation_lock(&self, git_root: &Path) -> Arc<tokio::sync::RwLock<()>> {
+        self.repo_locks
+            .lock()
+            .entry(git_root.to_path_buf())
+            .or_insert_with(|| Arc::new(tokio::sync::RwLock::new(())))
+            .clone()
+    }
+
+    /// Cached equivalent of `GitHandler::find_git_root`.
+    pub fn find_git_root(&self, start_path: &Path) -> Option<PathBuf> {
+        if let Some(root) = self.roots_by_child.lock().get(start_path) {
+            return Some(root.clone());
+        }
+
+        let root = GitHandler::find_git_root(start_path)?;
+        self.roots_by_child
+            .lock()
+            .insert(start_path.to_path_buf(), root.clone());
+        Some(root)
+    }
+
+    /// Cached equivalent of `GitHandler::get_current_ref`.
+    pub fn get_current_ref(&self, git_root: &Path) -> Result<String> {
+        self.refresh(git_root)
+            .current_ref
+            .context("Failed to determine current ref")
+    }
+
+    /// Cached `remote.origin.url`, or `None` if the repo has no such remote.
+    pub fn remote_origin_url(&self, git_root: &Path) -> Option<String> {
+        self.refresh(git_root).remote_origin_url
+    }
+
+    /// Cached equivalent of `GitHandler::get_working_tree_status`.
+    pub fn working_tree_status(&self, git_root: &Path) -> Result<WorkingTreeStatus> {
+        self.refresh(git_root)
+            .working_tree_status
+            .context("Failed to determine working tree status")
+    }
+
+    /// Drops cached state for `git_root`, forcing the next lookup to
+    /// re-derive everything. Call after a mutation (checkout, worktree add)
+    /// that this cache wouldn't otherwise observe via `.git/HEAD`'s mtime.
+    pub fn invalidate(&self, git_root: &Path) {
+        self.repos.lock().remove(git_root);
+        self.roots_by_child
+            .lock()
+            .retain(|_, root| root != git_root);
+    }
+
+    fn refresh(&self, git_root: &Path) -> CachedRepo {
+        let head_mtime = Self::head_mtime(git_root);
+
+        {
+            let repos = self.repos.lock();
+            if let Some(cached) = repos.get(git_root) {
+                if cached.cached_at.elapsed() < CACHE_TTL && cached.head_mtime == head_mtime {
+                    return cached.clone();
+                }
+            }
+        }
+
+        let entry = CachedRepo {
+            current_ref: GitHandler::get_current_ref(git_root).ok(),
+            remote_origin_url: Self::read_remote_origin_url(git_root),
+            working_tree_status: GitHandler::get_working_tree_status(git_root).ok(),
+            head_mtime,
+            cached_at: Instant::now(),
+        };
+
+        self.repos.lock().insert(git_root.to_path_buf(), entry.clone());
+        entry
+    }
+
+    fn head_mtime(git_root: &Path) -> Option<SystemTime> {
+        git_root.join(".git").join("HEAD").metadata().ok()?.modified().ok()
+    }
+
+    fn read_remote_origin_url(git_root: &Path) -> Option<String> {
+        let workspace_str = git_root.to_string_lossy();
+        let output =
+            run_git_command(&workspace_str, &["config", "--get", "remote.origin.url"]).ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if url.is_empty() {
+            None
+        } else {
+            Some(url)
+        }
+    }
+}
+
+impl Default for GitRepoCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
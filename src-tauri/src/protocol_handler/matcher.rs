@@ -1,8 +1,11 @@
+use super::workspace_index::WorkspaceIndex;
+use crate::git_command_log::run_git_command;
+use crate::path_validator::PathValidator;
 use crate::settings::SettingsManager;
 use crate::workspace_mru::ActiveWorkspaceTracker;
 use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::SystemTime;
 use tracing::{debug, info};
@@ -15,11 +18,17 @@ pub struct WorkspaceMatch {
     pub last_seen: Option<i64>,
     #[serde(skip)]
     pub last_active: Option<SystemTime>,
+    /// Levenshtein distance from the query to this match's workspace-relative
+    /// path, when it came from `WorkspaceIndex::resolve_fuzzy` rather than an
+    /// exact hit - lets a chooser surface "closest guess first" and explain
+    /// why an inexact match was offered.
+    pub fuzzy_score: Option<usize>,
 }
 
 pub struct PathMatcher {
     settings_manager: Arc<SettingsManager>,
     workspace_tracker: Arc<ActiveWorkspaceTracker>,
+    workspace_index: WorkspaceIndex,
 }
 
 impl PathMatcher {
@@ -30,35 +39,61 @@ impl PathMatcher {
         Self {
             settings_manager,
             workspace_tracker,
+            workspace_index: WorkspaceIndex::new(),
         }
     }
 
     pub async fn find_partial_matches(&self, partial_path: &str) -> Result<Vec<WorkspaceMatch>> {
+        PathValidator::reject_path_traversal(Path::new(partial_path))?;
+
         let settings = self.settings_manager.get().await;
         let mut matches = Vec::new();
 
         for workspace in &settings.workspaces {
             if let Some(workspace_root) = &workspace.normalized_path {
-                let candidate = workspace_root.join(partial_path);
+                let workspace_name = workspace.name.clone().unwrap_or_else(|| {
+                    workspace_root
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("unknown")
+                        .to_string()
+                });
+
+                let exact = self.workspace_index.resolve(workspace_root, partial_path);
+                if !exact.is_empty() {
+                    for candidate in exact {
+                        matches.push(WorkspaceMatch {
+                            workspace_name: workspace_name.clone(),
+                            workspace_path: workspace_root.clone().into_path_buf(),
+                            full_file_path: candidate,
+                            last_seen: None,
+                            last_active: None,
+                            fuzzy_score: None,
+                        });
+                    }
+                    continue;
+                }
 
-                if candidate.exists() && (candidate.is_file() || candidate.is_dir()) {
+                // No exact hit in this workspace - fall back to the closest
+                // typo-tolerant guesses rather than leaving it out entirely.
+                for (candidate, distance) in self.workspace_index.resolve_fuzzy(workspace_root, partial_path) {
                     matches.push(WorkspaceMatch {
-                        workspace_name: workspace.name.clone().unwrap_or_else(|| {
-                            workspace_root
-                                .file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("unknown")
-                                .to_string()
-                        }),
-                        workspace_path: workspace_root.clone(),
+                        workspace_name: workspace_name.clone(),
+                        workspace_path: workspace_root.clone().into_path_buf(),
                         full_file_path: candidate,
                         last_seen: None,
                         last_active: None,
+                        fuzzy_score: Some(distance),
                     });
                 }
             }
         }
 
+        // Exact hits (score 0) sort first; fuzzy fallback from different
+        // workspaces is interleaved by closeness. `sort_by_recent_usage`
+        // breaks ties by MRU once the caller applies it downstream.
+        matches.sort_by_key(|m| m.fuzzy_score.unwrap_or(0));
+
         debug!(
             "Found {} matches for partial path '{}'",
             matches.len(),
@@ -72,6 +107,8 @@ impl PathMatcher {
         workspace_name: &str,
         relative_path: &str,
     ) -> Result<PathBuf> {
+        PathValidator::reject_path_traversal(Path::new(relative_path))?;
+
         let settings = self.settings_manager.get().await;
 
         for workspace in &settings.workspaces {
@@ -133,6 +170,10 @@ impl PathMatcher {
                     ws_name, fragment
                 );
 
+                if PathValidator::reject_path_traversal(Path::new(fragment)).is_err() {
+                    continue;
+                }
+
                 if let Some(workspace_root) = &workspace.normalized_path {
                     let candidate = workspace_root.join(fragment);
 
@@ -140,10 +181,11 @@ impl PathMatcher {
                         info!("Match found: {}", candidate.display());
                         matches.push(WorkspaceMatch {
                             workspace_name: ws_name.to_string(),
-                            workspace_path: workspace_root.clone(),
+                            workspace_path: workspace_root.clone().into_path_buf(),
                             full_file_path: candidate,
                             last_seen: None,
                             last_active: None,
+                            fuzzy_score: None,
                         });
                     }
                 }
@@ -165,6 +207,7 @@ impl PathMatcher {
                     full_file_path: path,
                     last_seen: None,
                     last_active: None,
+                    fuzzy_score: None,
                 });
             }
         }
@@ -177,6 +220,57 @@ impl PathMatcher {
         Ok(matches)
     }
 
+    /// Finds a workspace whose `remote.origin.url` resolves to the same
+    /// `(host, owner, repo)` as a provider-passthrough link, so a link
+    /// resolves to the right local clone even when its directory was named
+    /// differently from the repo. Workspaces without a `.git` remote, or
+    /// whose remote doesn't parse, are silently skipped.
+    pub async fn find_workspace_by_remote(&self, host: &str, owner: &str, repo: &str) -> Result<PathBuf> {
+        let settings = self.settings_manager.get().await;
+        let host = host.to_lowercase();
+
+        for workspace in &settings.workspaces {
+            let Some(workspace_root) = &workspace.normalized_path else {
+                continue;
+            };
+
+            let workspace_str = workspace_root.to_string_lossy();
+            let Ok(output) =
+                run_git_command(&workspace_str, &["config", "--get", "remote.origin.url"])
+            else {
+                continue;
+            };
+            if !output.status.success() {
+                continue;
+            }
+
+            let remote_url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            let Some((remote_host, remote_owner, remote_repo)) =
+                normalize_remote_url(&remote_url)
+            else {
+                continue;
+            };
+
+            if remote_host == host && remote_owner == owner && remote_repo == repo {
+                debug!(
+                    "Matched provider-passthrough {}/{}/{} to workspace at {}",
+                    host,
+                    owner,
+                    repo,
+                    workspace_root.display()
+                );
+                return Ok(workspace_root.clone().into_path_buf());
+            }
+        }
+
+        bail!(
+            "No workspace found with remote matching {}/{}/{}",
+            host,
+            owner,
+            repo
+        );
+    }
+
     pub async fn sort_by_recent_usage(&self, matches: &mut Vec<WorkspaceMatch>) {
         for ws_match in matches.iter_mut() {
             ws_match.last_active = self
@@ -205,3 +299,121 @@ impl StrExt for str {
         self.to_lowercase() == other.to_lowercase()
     }
 }
+
+/// Splits a provider-passthrough `provider` string (e.g. `github.com/owner/repo`
+/// or `dev.azure.com/org/project/_git/repo`, as produced by `srcuri-core`) into
+/// `(host, owner)`, with `repo_name` supplying the repo itself. `owner` keeps
+/// every segment between the host and the trailing repo name, so nested
+/// GitLab groups and Azure DevOps org/project paths round-trip intact.
+pub fn provider_to_host_owner(provider: &str, repo_name: &str) -> Option<(String, String)> {
+    let segments: Vec<&str> = provider.split('/').filter(|s| !s.is_empty()).collect();
+    if segments.len() < 2 {
+        return None;
+    }
+
+    let host = segments[0].to_lowercase();
+    let owner_segments = if segments.last() == Some(&repo_name) {
+        &segments[1..segments.len() - 1]
+    } else {
+        &segments[1..]
+    };
+    if owner_segments.is_empty() {
+        return None;
+    }
+
+    Some((host, owner_segments.join("/")))
+}
+
+/// Normalizes a git remote URL into `(host, owner, repo)` for comparison,
+/// covering the forms git itself accepts: `https://host/owner/repo(.git)`
+/// (and `http://`), scp-style `git@host:owner/repo.git`, `ssh://git@host/owner/repo`,
+/// and `git://host/owner/repo`. The host is lowercased; owner/repo case is kept
+/// since some providers are case-sensitive there. Nested owners (GitLab
+/// subgroups) are preserved by taking everything before the final path segment.
+fn normalize_remote_url(url: &str) -> Option<(String, String, String)> {
+    let url = url.trim();
+
+    let (host, path) = if let Some(rest) = url.strip_prefix("ssh://") {
+        let rest = rest.rsplit_once('@').map(|(_, r)| r).unwrap_or(rest);
+        rest.split_once('/')?
+    } else if let Some(rest) = url.strip_prefix("git://") {
+        rest.split_once('/')?
+    } else if let Some(rest) = url.strip_prefix("https://") {
+        rest.split_once('/')?
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        rest.split_once('/')?
+    } else if let Some(at_pos) = url.find('@') {
+        // scp-style: git@host:owner/repo(.git)
+        url[at_pos + 1..].split_once(':')?
+    } else {
+        return None;
+    };
+
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path.rsplit_once('/')?;
+
+    if owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+
+    Some((host.to_lowercase(), owner.to_string(), repo.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_https_url() {
+        assert_eq!(
+            normalize_remote_url("https://github.com/owner/repo.git"),
+            Some(("github.com".to_string(), "owner".to_string(), "repo".to_string()))
+        );
+    }
+
+    #[test]
+    fn normalizes_scp_style_url() {
+        assert_eq!(
+            normalize_remote_url("git@github.com:owner/repo.git"),
+            Some(("github.com".to_string(), "owner".to_string(), "repo".to_string()))
+        );
+    }
+
+    #[test]
+    fn normalizes_ssh_scheme_url() {
+        assert_eq!(
+            normalize_remote_url("ssh://git@gitlab.com/group/subgroup/repo"),
+            Some((
+                "gitlab.com".to_string(),
+                "group/subgroup".to_string(),
+                "repo".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn normalizes_git_scheme_url() {
+        assert_eq!(
+            normalize_remote_url("git://host/owner/repo"),
+            Some(("host".to_string(), "owner".to_string(), "repo".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_unrecognized_url() {
+        assert_eq!(normalize_remote_url("not a remote"), None);
+    }
+
+    #[test]
+    fn splits_provider_into_host_and_owner() {
+        assert_eq!(
+            provider_to_host_owner("github.com/owner/repo", "repo"),
+            Some(("github.com".to_string(), "owner".to_string()))
+        );
+        assert_eq!(
+            provider_to_host_owner("gitlab.com/group/subgroup/project", "project"),
+            Some(("gitlab.com".to_string(), "group/subgroup".to_string()))
+        );
+    }
+}
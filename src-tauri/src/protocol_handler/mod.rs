@@ -1,15 +1,30 @@
+mod file_watcher;
 pub mod git;
+mod git_repo_cache;
 mod matcher;
 mod parser;
-
-pub use git::{GitHandler, WorkingTreeStatus};
+mod revision_resolver;
+mod workspace_index;
+
+pub use file_watcher::WorkspaceFileWatcher;
+pub use git::{
+    BlameLine, CheckoutStashResult, GitError, GitHandler, GitOperationState,
+    OperationActionResult, RestoreStashResult, WorkingTreeStatus, WorkingTreeStatusBatch,
+    WorktreeInfo, DEFAULT_MAX_WORKTREES, STATUS_BATCH_SIZE,
+};
+pub use git_repo_cache::GitRepoCache;
 pub use matcher::{PathMatcher, WorkspaceMatch};
-pub use parser::{GitRef, SrcuriParser, SrcuriRequest};
+pub use parser::{CloneStrategy, GitRef, PartialVersion, SrcuriParser, SrcuriRequest};
+pub use revision_resolver::{ResolvedBlob, RevisionResolver};
+pub use srcuri_core::Provider as GitProvider;
 
 use crate::dispatcher::EditorDispatcher;
+use crate::editors::OpenMode;
+use crate::path_validator::PathValidator;
 use crate::settings::SettingsManager;
 use crate::workspace_mru::ActiveWorkspaceTracker;
 use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::info;
 
@@ -17,6 +32,9 @@ pub struct ProtocolHandler {
     matcher: PathMatcher,
     settings_manager: Arc<SettingsManager>,
     dispatcher: Arc<EditorDispatcher>,
+    path_validator: Arc<PathValidator>,
+    git_cache: Arc<GitRepoCache>,
+    file_watcher: WorkspaceFileWatcher,
 }
 
 impl ProtocolHandler {
@@ -24,18 +42,50 @@ impl ProtocolHandler {
         settings_manager: Arc<SettingsManager>,
         dispatcher: Arc<EditorDispatcher>,
         workspace_tracker: Arc<ActiveWorkspaceTracker>,
+        path_validator: Arc<PathValidator>,
     ) -> Self {
         Self {
             matcher: PathMatcher::new(settings_manager.clone(), workspace_tracker),
             settings_manager,
+            file_watcher: WorkspaceFileWatcher::new(dispatcher.clone()),
             dispatcher,
+            path_validator,
+            git_cache: Arc::new(GitRepoCache::new()),
         }
     }
 
+    /// Shared git-metadata cache, also used by Tauri commands that mutate a
+    /// repository (checkout, worktree add) so they can take its per-repo
+    /// lock and invalidate stale entries after the mutation completes.
+    pub fn git_cache(&self) -> &Arc<GitRepoCache> {
+        &self.git_cache
+    }
+
+    /// Starts watch-and-reopen mode for a resolved `WorkspaceMatch`: once
+    /// `full_file_path` changes on disk, it's reopened with the same
+    /// `line`/`column`. Optional - callers opt in per file rather than every
+    /// open being watched by default.
+    pub fn watch_workspace_file(
+        &self,
+        workspace_path: PathBuf,
+        full_file_path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+    ) -> notify::Result<()> {
+        self.file_watcher.start(workspace_path, full_file_path, line, column)
+    }
+
+    /// Stops every watch registered under `workspace_path`, e.g. when that
+    /// workspace is removed from settings.
+    pub fn stop_watching_workspace(&self, workspace_path: &Path) {
+        self.file_watcher.stop(workspace_path);
+    }
+
     pub async fn handle_url(&self, url: &str) -> Result<HandleResult> {
         info!("Handling srcuri URL: {}", url);
 
         let request = SrcuriParser::parse(url).context("Failed to parse srcuri URL")?;
+        request.validate().context("Invalid srcuri URL")?;
 
         match request {
             SrcuriRequest::PartialPath { path, line, column } => {
@@ -98,9 +148,35 @@ impl ProtocolHandler {
                 )
                 .await
             }
+            SrcuriRequest::CloneOrg {
+                host,
+                owner,
+                include_forks,
+                include_archived,
+            } => {
+                self.handle_clone_org(&host, &owner, include_forks, include_archived)
+                    .await
+            }
         }
     }
 
+    async fn handle_clone_org(
+        &self,
+        host: &str,
+        owner: &str,
+        include_forks: bool,
+        include_archived: bool,
+    ) -> Result<HandleResult> {
+        info!("Handling bulk clone for {}/{}", host, owner);
+
+        Ok(HandleResult::ShowBulkCloneDialog {
+            host: host.to_string(),
+            owner: owner.to_string(),
+            include_forks,
+            include_archived,
+        })
+    }
+
     async fn handle_partial_path(
         &self,
         path: &str,
@@ -127,7 +203,7 @@ impl ProtocolHandler {
                     &workspace_match.full_file_path.to_string_lossy(),
                     line,
                     column,
-                    false,
+                    OpenMode::NewWindow,
                     None,
                 )
                 .await?;
@@ -160,8 +236,12 @@ impl ProtocolHandler {
 
         match self.matcher.find_workspace_path(workspace, path).await {
             Ok(full_path) => {
+                self.path_validator
+                    .check_workspace_path_policy(workspace, std::path::Path::new(path))
+                    .await?;
+
                 self.dispatcher
-                    .open(&full_path.to_string_lossy(), line, column, false, None)
+                    .open(&full_path.to_string_lossy(), line, column, OpenMode::NewWindow, None)
                     .await?;
                 Ok(HandleResult::Opened)
             }
@@ -184,6 +264,7 @@ impl ProtocolHandler {
                     line,
                     column,
                     git_ref: None,
+                    clone_strategy: CloneStrategy::for_ref(None),
                 })
             }
             Err(e) => Err(e),
@@ -204,7 +285,7 @@ impl ProtocolHandler {
             if self.settings_manager.allows_non_workspace_files().await {
                 info!("No workspace matches, attempting to open as absolute path");
                 self.dispatcher
-                    .open(full_path, line, column, false, None)
+                    .open(full_path, line, column, OpenMode::NewWindow, None)
                     .await?;
                 return Ok(HandleResult::Opened);
             } else {
@@ -227,7 +308,7 @@ impl ProtocolHandler {
                     &workspace_match.full_file_path.to_string_lossy(),
                     line,
                     column,
-                    false,
+                    OpenMode::NewWindow,
                     None,
                 )
                 .await?;
@@ -257,11 +338,14 @@ impl ProtocolHandler {
         column: Option<usize>,
         remote: Option<&str>,
     ) -> Result<HandleResult> {
-        let rev = match git_ref {
-            GitRef::Commit(s) | GitRef::Branch(s) | GitRef::Tag(s) => s.as_str(),
-        };
+        let is_jj_ref = matches!(git_ref, GitRef::Jj(_));
+        let requested_rev = git_ref.display_ref();
+        let requested_rev = requested_rev.as_ref();
 
-        info!("Handling revision path: {}/{} @ {}", workspace, path, rev);
+        info!(
+            "Handling revision path: {}/{} @ {}",
+            workspace, path, requested_rev
+        );
 
         let full_path = match self.matcher.find_workspace_path(workspace, path).await {
             Ok(p) => p,
@@ -284,6 +368,7 @@ impl ProtocolHandler {
                     line,
                     column,
                     git_ref: Some(git_ref.clone()),
+                    clone_strategy: CloneStrategy::for_ref(Some(git_ref)),
                 });
             }
             Err(e) => return Err(e),
@@ -293,26 +378,66 @@ impl ProtocolHandler {
             .parent()
             .context("Could not determine workspace path")?;
 
-        let git_root = GitHandler::find_git_root(workspace_path).ok_or_else(|| {
+        let git_root = self.git_cache.find_git_root(workspace_path).ok_or_else(|| {
             anyhow::anyhow!(
                 "Could not find git repository for workspace '{}'",
                 workspace
             )
         })?;
 
-        GitHandler::validate_revision(&git_root, rev)?;
+        // A `jj:`-qualified ref only resolves through a colocated jj repo;
+        // plain commit/branch/tag refs always take the git path below.
+        let resolved_rev = if is_jj_ref {
+            if !GitHandler::is_colocated_jj_repo(&git_root) {
+                bail!(
+                    "'{}' is a jj revision but '{}' is not a colocated jj/git repository",
+                    requested_rev,
+                    git_root.display()
+                );
+            }
+
+            let blocking_root = git_root.clone();
+            let blocking_revset = requested_rev.to_string();
+            tokio::task::spawn_blocking(move || {
+                GitHandler::resolve_jj_revision(&blocking_root, &blocking_revset)
+            })
+            .await
+            .context("jj revision lookup task panicked")??
+        } else {
+            requested_rev.to_string()
+        };
+        let rev = resolved_rev.as_str();
+
+        // All of this is read-only (rev validation, ref lookup, status), so
+        // it runs off the async executor without taking any repository lock
+        // — only a mutation (checkout, worktree add) needs to serialize
+        // against other access, and those happen elsewhere.
+        let git_cache = self.git_cache.clone();
+        let blocking_root = git_root.clone();
+        let blocking_rev = rev.to_string();
+        let blocking_path = path.to_string();
+        let (current_ref, dialog_state) = tokio::task::spawn_blocking(move || -> Result<_> {
+            GitHandler::validate_revision(&blocking_root, &blocking_rev)?;
+
+            let current_ref = git_cache.get_current_ref(&blocking_root)?;
+            if current_ref == blocking_rev || format!("origin/{}", current_ref) == blocking_rev {
+                return Ok((current_ref, None));
+            }
+
+            let state =
+                GitHandler::get_revision_dialog_state(&blocking_root, &blocking_path, &blocking_rev)?;
+            Ok((current_ref, Some(state)))
+        })
+        .await
+        .context("Git revision lookup task panicked")??;
 
-        if GitHandler::should_skip_revision_dialog(&git_root, rev)? {
+        let Some((checkout_available, checkout_blocked_reason, status)) = dialog_state else {
             info!("Already on target revision {}, opening directly", rev);
             self.dispatcher
-                .open(&full_path.to_string_lossy(), line, column, false, None)
+                .open(&full_path.to_string_lossy(), line, column, OpenMode::NewWindow, None)
                 .await?;
             return Ok(HandleResult::Opened);
-        }
-
-        let current_ref = GitHandler::get_current_ref(&git_root)?;
-        let (checkout_available, checkout_blocked_reason, status) =
-            GitHandler::get_revision_dialog_state(&git_root, path, rev)?;
+        };
 
         Ok(HandleResult::ShowRevisionDialog {
             workspace: workspace.to_string(),
@@ -330,6 +455,34 @@ impl ProtocolHandler {
         })
     }
 
+    /// Resolves `provider`/`repo_name` to a local workspace by matching git
+    /// remotes rather than names, and joins `path` onto it if the result
+    /// exists on disk. Returns `None` on any lookup or path-existence miss so
+    /// callers can fall through to their existing fallback.
+    async fn find_workspace_by_remote(
+        &self,
+        provider: &str,
+        repo_name: &str,
+        path: &str,
+    ) -> Option<std::path::PathBuf> {
+        let (host, owner) = matcher::provider_to_host_owner(provider, repo_name)?;
+        let workspace_root = self
+            .matcher
+            .find_workspace_by_remote(&host, &owner, repo_name)
+            .await
+            .ok()?;
+
+        crate::path_validator::PathValidator::reject_path_traversal(std::path::Path::new(path))
+            .ok()?;
+
+        let full_path = workspace_root.join(path);
+        if full_path.exists() {
+            Some(full_path)
+        } else {
+            None
+        }
+    }
+
     async fn handle_provider_passthrough(
         &self,
         provider: &str,
@@ -373,11 +526,40 @@ impl ProtocolHandler {
                 }
 
                 self.dispatcher
-                    .open(&full_path.to_string_lossy(), line, column, false, None)
+                    .open(&full_path.to_string_lossy(), line, column, OpenMode::NewWindow, None)
                     .await?;
                 Ok(HandleResult::Opened)
             }
             Err(_) => {
+                // The clone directory may not be named after the repo. Before
+                // giving up and falling back to the browser, try matching by
+                // the workspace's actual git remote instead of its folder name.
+                if let Some(full_path) = self
+                    .find_workspace_by_remote(provider, repo_name, path)
+                    .await
+                {
+                    info!("Matched provider-passthrough by git remote, opening locally");
+
+                    if let Some(ref git_ref) = git_ref {
+                        let remote = format!("https://{}", provider);
+                        return self
+                            .handle_revision_path(
+                                workspace_name,
+                                path,
+                                git_ref,
+                                line,
+                                column,
+                                Some(&remote),
+                            )
+                            .await;
+                    }
+
+                    self.dispatcher
+                        .open(&full_path.to_string_lossy(), line, column, OpenMode::NewWindow, None)
+                        .await?;
+                    return Ok(HandleResult::Opened);
+                }
+
                 let mut url = String::from("https://srcuri.com/");
                 url.push_str(provider_path.trim_start_matches('/'));
                 if let Some(frag) = fragment {
@@ -422,8 +604,15 @@ pub enum HandleResult {
         line: Option<usize>,
         column: Option<usize>,
         git_ref: Option<GitRef>,
+        clone_strategy: CloneStrategy,
     },
     OpenInBrowser {
         url: String,
     },
+    ShowBulkCloneDialog {
+        host: String,
+        owner: String,
+        include_forks: bool,
+        include_archived: bool,
+    },
 }
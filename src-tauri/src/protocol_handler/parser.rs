@@ -1,11 +1,194 @@
 use anyhow::{bail, Result};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::path::{Component, Path};
+use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GitRef {
     Commit(String),
     Branch(String),
     Tag(String),
+    /// A Jujutsu change ID, bookmark, or revset (e.g. `@-`), only meaningful
+    /// against a workspace colocated with a `.jj` directory. Resolved to the
+    /// underlying git commit SHA before any git operation runs.
+    Jj(String),
+    /// A loose version spec from `?version=` (e.g. `1.2` or `1.2.3-beta`),
+    /// resolved against a repo's tag names rather than naming one directly -
+    /// see `PartialVersion::resolve`.
+    Version(PartialVersion),
+}
+
+impl GitRef {
+    /// The ref as a revision string: the literal value for every variant but
+    /// `Version`, which has no single name to borrow and is instead
+    /// formatted from its parts. Resolving a `Version` against a repo's
+    /// actual tags (picking the highest match) happens downstream of
+    /// parsing, not here.
+    pub fn display_ref(&self) -> Cow<'_, str> {
+        match self {
+            GitRef::Commit(s) | GitRef::Branch(s) | GitRef::Tag(s) | GitRef::Jj(s) => {
+                Cow::Borrowed(s.as_str())
+            }
+            GitRef::Version(v) => Cow::Owned(v.to_string()),
+        }
+    }
+}
+
+/// A loose version spec parsed from `?version=`, mirroring cargo's
+/// `PartialVersion`/`PackageIdSpec` - `major` is required, everything after
+/// it narrows the match: an unset `minor`/`patch` is a wildcard, so `1.2`
+/// matches any `1.2.z`, and an unset `pre` excludes pre-releases entirely.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartialVersion {
+    pub major: u64,
+    pub minor: Option<u64>,
+    pub patch: Option<u64>,
+    pub pre: Option<String>,
+}
+
+impl PartialVersion {
+    /// Parses `1`, `1.2`, `1.2.3`, or `1.2.3-beta.1`. A leading `v` (common
+    /// on tag names) is not accepted here - strip it before calling, as
+    /// `resolve` already does for each candidate tag.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (core, pre) = match value.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (value, None),
+        };
+
+        let mut parts = core.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map(str::parse).transpose().ok()?;
+        let patch = parts.next().map(str::parse).transpose().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        // A patch without a minor (`1..3`) isn't a valid partial version.
+        if minor.is_none() && patch.is_some() {
+            return None;
+        }
+
+        Some(Self { major, minor, patch, pre })
+    }
+
+    /// Whether `full` - an actual `major.minor.patch[-pre]` parsed from a
+    /// real tag - satisfies this spec: every component this spec names must
+    /// match exactly, and `full` can only carry a pre-release if this spec
+    /// names that exact one, mirroring cargo's exclusion of pre-releases
+    /// from a bare version requirement.
+    fn matches(&self, full: &FullVersion) -> bool {
+        if full.major != self.major {
+            return false;
+        }
+        if self.minor.is_some_and(|minor| full.minor != minor) {
+            return false;
+        }
+        if self.patch.is_some_and(|patch| full.patch != patch) {
+            return false;
+        }
+        match (&self.pre, &full.pre) {
+            (None, None) => true,
+            (None, Some(_)) | (Some(_), None) => false,
+            (Some(want), Some(have)) => want == have,
+        }
+    }
+
+    /// Resolves this spec against a repo's tag names (e.g. `v1.2.3`),
+    /// stripping a leading `v` from each before comparing, and returns the
+    /// original name of the highest matching tag.
+    pub fn resolve<'a, I>(&self, tags: I) -> Option<&'a str>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        tags.into_iter()
+            .filter_map(|tag| {
+                let full = FullVersion::parse(tag.strip_prefix('v').unwrap_or(tag))?;
+                Some((tag, full))
+            })
+            .filter(|(_, full)| self.matches(full))
+            .max_by_key(|(_, full)| full.clone())
+            .map(|(tag, _)| tag)
+    }
+}
+
+impl fmt::Display for PartialVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.major)?;
+        if let Some(minor) = self.minor {
+            write!(f, ".{}", minor)?;
+        }
+        if let Some(patch) = self.patch {
+            write!(f, ".{}", patch)?;
+        }
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+/// A fully-specified `major.minor.patch[-pre]` parsed from a tag name, used
+/// only to check a `PartialVersion` match and to order candidates against
+/// each other - unlike `PartialVersion`, every numeric component here is
+/// required.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct FullVersion {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Option<String>,
+}
+
+impl FullVersion {
+    fn parse(value: &str) -> Option<Self> {
+        let (core, pre) = match value.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (value, None),
+        };
+
+        let mut parts = core.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self { major, minor, patch, pre })
+    }
+}
+
+/// How `GitHandler::clone` should fetch a repository that isn't on disk yet,
+/// so the UI can tell the user a full clone isn't about to happen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CloneStrategy {
+    /// Always true today: a `--filter=blob:none` partial clone avoids
+    /// downloading blob contents up front.
+    pub partial: bool,
+    /// Set when `git_ref` names a branch or tag, so the clone can also go
+    /// shallow (`--depth 1 --branch <ref>`). Bare commits and jj revsets
+    /// can't be passed to `--branch`, so they clone at full history depth
+    /// and fetch the single commit afterward instead.
+    pub shallow_ref: Option<String>,
+}
+
+impl CloneStrategy {
+    pub fn for_ref(git_ref: Option<&GitRef>) -> Self {
+        let shallow_ref = match git_ref {
+            Some(GitRef::Branch(name)) | Some(GitRef::Tag(name)) => Some(name.clone()),
+            _ => None,
+        };
+
+        Self {
+            partial: true,
+            shallow_ref,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -14,18 +197,26 @@ pub enum SrcuriRequest {
         path: String,
         line: Option<usize>,
         column: Option<usize>,
+        /// End of a `:10-20` / `:10:5-20:8` selection range; `None` means
+        /// the location is a single point, not a range.
+        end_line: Option<usize>,
+        end_column: Option<usize>,
     },
     WorkspacePath {
         workspace: String,
         path: String,
         line: Option<usize>,
         column: Option<usize>,
+        end_line: Option<usize>,
+        end_column: Option<usize>,
         remote: Option<String>,
     },
     FullPath {
         full_path: String,
         line: Option<usize>,
         column: Option<usize>,
+        end_line: Option<usize>,
+        end_column: Option<usize>,
     },
     RevisionPath {
         workspace: String,
@@ -33,6 +224,8 @@ pub enum SrcuriRequest {
         git_ref: GitRef,
         line: Option<usize>,
         column: Option<usize>,
+        end_line: Option<usize>,
+        end_column: Option<usize>,
         remote: Option<String>,
     },
     /// Provider-passthrough URL (e.g., srcuri://github.com/owner/repo/blob/main/file.rs#L42)
@@ -45,17 +238,472 @@ pub enum SrcuriRequest {
         path: String,
         line: Option<usize>,
         column: Option<usize>,
+        end_line: Option<usize>,
+        end_column: Option<usize>,
         git_ref: Option<GitRef>,
         /// Explicit workspace override via ?workspace= (escape hatch for dot-containing names)
         workspace_override: Option<String>,
         /// Fragment string (without leading '#') preserved for browser fallbacks
         fragment: Option<String>,
     },
+    /// Bulk-clone trigger (e.g. `srcuri://clone-org/github.com/my-org`),
+    /// offering to populate the source folder from an entire GitHub
+    /// org/user in one action rather than cloning a single repo.
+    CloneOrg {
+        host: String,
+        owner: String,
+        include_forks: bool,
+        include_archived: bool,
+    },
+}
+
+/// Error from [`SrcuriRequest::validate`], kept distinct from the generic
+/// `anyhow::Error` the rest of `SrcuriParser` returns so a caller (e.g. a
+/// match resolver scanning several workspaces) can decide policy - skip a
+/// single bad candidate - rather than having to match on message text.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SrcuriValidationError {
+    #[error("path escapes the workspace root via '..': {0}")]
+    PathEscapesRoot(String),
+
+    #[error("path contains a control character: {0}")]
+    ControlCharacter(String),
+
+    #[error("relative path must not be absolute: {0}")]
+    UnexpectedAbsolutePath(String),
+}
+
+impl SrcuriRequest {
+    /// Rejects a request whose resolved path could escape its workspace
+    /// root, carries an embedded NUL or other control byte, or (for the
+    /// variants that are supposed to be workspace-relative) is itself
+    /// absolute. This duplicates the depth-counting scheme
+    /// `PathValidator::reject_path_traversal` applies downstream once a
+    /// path is actually resolved against a workspace - it's re-checked
+    /// here, right on the parsed request, so a typed error is available
+    /// before any workspace lookup happens. `FullPath` is exempt from the
+    /// absolute-path check since being absolute is the entire point of
+    /// that variant.
+    pub fn validate(&self) -> Result<(), SrcuriValidationError> {
+        match self {
+            SrcuriRequest::PartialPath { path, .. }
+            | SrcuriRequest::WorkspacePath { path, .. }
+            | SrcuriRequest::RevisionPath { path, .. }
+            | SrcuriRequest::ProviderPassthrough { path, .. } => Self::validate_relative_path(path),
+            SrcuriRequest::FullPath { full_path, .. } => Self::validate_control_bytes(full_path),
+            SrcuriRequest::CloneOrg { .. } => Ok(()),
+        }
+    }
+
+    fn validate_control_bytes(value: &str) -> Result<(), SrcuriValidationError> {
+        if value.bytes().any(|b| b < 0x20 || b == 0x7f) {
+            return Err(SrcuriValidationError::ControlCharacter(value.to_string()));
+        }
+        Ok(())
+    }
+
+    fn validate_relative_path(path: &str) -> Result<(), SrcuriValidationError> {
+        Self::validate_control_bytes(path)?;
+
+        if Path::new(path).is_absolute() {
+            return Err(SrcuriValidationError::UnexpectedAbsolutePath(path.to_string()));
+        }
+
+        let mut depth: i64 = 0;
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(_) => depth += 1,
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    depth -= 1;
+                    if depth < 0 {
+                        return Err(SrcuriValidationError::PathEscapesRoot(path.to_string()));
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => {
+                    return Err(SrcuriValidationError::UnexpectedAbsolutePath(path.to_string()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The normalized `srcuri://` form of this request - a thin name for
+    /// the `Display` impl, for callers that want a string rather than a
+    /// formatter (copying a shareable link, deduplicating requests by key).
+    /// Parsing this string back reproduces an equal `SrcuriRequest`, modulo
+    /// the parts `Display` doesn't round-trip verbatim (`ProviderPassthrough`
+    /// reproduces its `provider_path` rather than re-deriving it from
+    /// `provider`/`path`/`git_ref`).
+    pub fn to_canonical_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl GitRef {
+    /// The query param key/value `SrcuriParser::parse_git_ref_param` would
+    /// read this back from - `commit=`/`branch=`/`tag=`/`jj=`/`version=`,
+    /// the inverse of that function's match arms.
+    fn query_param(&self) -> (&'static str, String) {
+        match self {
+            GitRef::Commit(v) => ("commit", v.clone()),
+            GitRef::Branch(v) => ("branch", v.clone()),
+            GitRef::Tag(v) => ("tag", v.clone()),
+            GitRef::Jj(v) => ("jj", v.clone()),
+            GitRef::Version(v) => ("version", v.to_string()),
+        }
+    }
+}
+
+/// WHATWG-style path-segment encode set - everything `SrcuriParser::parse`
+/// would otherwise misread as a structural delimiter within `workspace`,
+/// `path`, or `full_path` (`/` is deliberately left alone so a multi-segment
+/// path doesn't get each segment encoded individually).
+const PATH_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// WHATWG-style query-value encode set - a superset of [`PATH_ENCODE_SET`]
+/// that also escapes `&` and `=`, the pair/key-value delimiters within a
+/// query string.
+const QUERY_ENCODE_SET: &AsciiSet = &PATH_ENCODE_SET.add(b'&').add(b'=');
+
+fn encode_path_component(value: &str) -> std::borrow::Cow<'_, str> {
+    utf8_percent_encode(value, PATH_ENCODE_SET).into()
+}
+
+fn encode_query_component(value: &str) -> std::borrow::Cow<'_, str> {
+    utf8_percent_encode(value, QUERY_ENCODE_SET).into()
+}
+
+/// Formats the `:line[:column][-end_line[:end_column]]` suffix
+/// `parse_path_with_location` splits off, or an empty string if there's no
+/// location at all. An `end_line`/`end_column` with no `line`/`column` is
+/// nonsensical and treated the same as no range.
+fn location_suffix(
+    line: Option<usize>,
+    column: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+) -> String {
+    let Some(line) = line else {
+        return String::new();
+    };
+
+    let start = match column {
+        Some(column) => format!(":{}:{}", line, column),
+        None => format!(":{}", line),
+    };
+
+    let end = match (end_line, end_column) {
+        (Some(end_line), Some(end_column)) => format!("-{}:{}", end_line, end_column),
+        (Some(end_line), None) => format!("-{}", end_line),
+        (None, _) => String::new(),
+    };
+
+    format!("{}{}", start, end)
+}
+
+/// Joins non-empty `key=value` pairs into a leading-`?` query string,
+/// dropping any pair whose value is empty - the inverse of how
+/// `parse_*_param` treats an empty value as absent.
+fn query_suffix(params: &[(&str, &str)]) -> String {
+    let present: Vec<String> = params
+        .iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| format!("{}={}", key, encode_query_component(value)))
+        .collect();
+
+    if present.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", present.join("&"))
+    }
+}
+
+impl fmt::Display for SrcuriRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "srcuri://")?;
+
+        match self {
+            SrcuriRequest::PartialPath {
+                path,
+                line,
+                column,
+                end_line,
+                end_column,
+            } => {
+                write!(
+                    f,
+                    "{}{}",
+                    encode_path_component(path),
+                    location_suffix(*line, *column, *end_line, *end_column)
+                )
+            }
+            SrcuriRequest::WorkspacePath {
+                workspace,
+                path,
+                line,
+                column,
+                end_line,
+                end_column,
+                remote,
+            } => {
+                write!(
+                    f,
+                    "{}/{}{}{}",
+                    encode_path_component(workspace),
+                    encode_path_component(path),
+                    location_suffix(*line, *column, *end_line, *end_column),
+                    query_suffix(&[("remote", remote.as_deref().unwrap_or(""))]),
+                )
+            }
+            SrcuriRequest::FullPath {
+                full_path,
+                line,
+                column,
+                end_line,
+                end_column,
+            } => {
+                // `full_path` already carries its own leading `/`, so this
+                // deliberately does not add a `/` separator of its own - see
+                // `SrcuriParser::is_absolute_path`.
+                write!(
+                    f,
+                    "{}{}",
+                    encode_path_component(full_path),
+                    location_suffix(*line, *column, *end_line, *end_column)
+                )
+            }
+            SrcuriRequest::RevisionPath {
+                workspace,
+                path,
+                git_ref,
+                line,
+                column,
+                end_line,
+                end_column,
+                remote,
+            } => {
+                let (ref_key, ref_value) = git_ref.query_param();
+                write!(
+                    f,
+                    "{}/{}{}{}",
+                    encode_path_component(workspace),
+                    encode_path_component(path),
+                    location_suffix(*line, *column, *end_line, *end_column),
+                    query_suffix(&[
+                        (ref_key, ref_value.as_str()),
+                        ("remote", remote.as_deref().unwrap_or(""))
+                    ]),
+                )
+            }
+            SrcuriRequest::ProviderPassthrough {
+                provider_path,
+                fragment,
+                ..
+            } => {
+                // `provider_path` already carries its own query string (see
+                // `parse_provider_passthrough`), so it's reproduced verbatim
+                // rather than re-derived from `provider`/`path`/`git_ref`.
+                write!(f, "{}", provider_path)?;
+                if let Some(fragment) = fragment {
+                    write!(f, "#{}", fragment)?;
+                }
+                Ok(())
+            }
+            SrcuriRequest::CloneOrg {
+                host,
+                owner,
+                include_forks,
+                include_archived,
+            } => {
+                write!(
+                    f,
+                    "clone-org/{}/{}{}",
+                    host,
+                    owner,
+                    query_suffix(&[
+                        ("include_forks", if *include_forks { "true" } else { "" }),
+                        ("include_archived", if *include_archived { "true" } else { "" }),
+                    ]),
+                )
+            }
+        }
+    }
+}
+
+/// Incrementally builds a [`SrcuriRequest`] for callers that need to
+/// construct a `srcuri://` link programmatically rather than parse one -
+/// e.g. `SrcuriBuilder::workspace("repo").path("src/main.rs").line(42).git_ref(GitRef::Branch("main")).build()`.
+/// `workspace()` and `full_path()` are the two entry points (mirroring
+/// `SrcuriParser::parse`'s own `WorkspacePath`/`RevisionPath` vs. `FullPath`
+/// split); `.git_ref()` upgrades a workspace build from `WorkspacePath` to
+/// `RevisionPath`, same as a `commit=`/`branch=`/`tag=`/`jj=` query param
+/// does when parsing.
+#[derive(Default)]
+pub struct SrcuriBuilder {
+    workspace: Option<String>,
+    full_path: Option<String>,
+    path: Option<String>,
+    line: Option<usize>,
+    column: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+    remote: Option<String>,
+    git_ref: Option<GitRef>,
+}
+
+impl SrcuriBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts building a workspace-relative link, equivalent to
+    /// `SrcuriBuilder::new()` with `workspace` already set.
+    pub fn workspace(workspace: impl Into<String>) -> Self {
+        Self {
+            workspace: Some(workspace.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Starts building a link to an absolute path outside any workspace,
+    /// equivalent to `SrcuriBuilder::new()` with `full_path` already set.
+    pub fn full_path(full_path: impl Into<String>) -> Self {
+        Self {
+            full_path: Some(full_path.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets the end of a selection range; meaningless without `line()` also
+    /// being set, same as `end_column()`.
+    pub fn end_line(mut self, end_line: usize) -> Self {
+        self.end_line = Some(end_line);
+        self
+    }
+
+    pub fn end_column(mut self, end_column: usize) -> Self {
+        self.end_column = Some(end_column);
+        self
+    }
+
+    pub fn remote(mut self, remote: impl Into<String>) -> Self {
+        self.remote = Some(remote.into());
+        self
+    }
+
+    pub fn git_ref(mut self, git_ref: GitRef) -> Self {
+        self.git_ref = Some(git_ref);
+        self
+    }
+
+    /// Builds the request, enforcing the same constraints
+    /// `SrcuriParser::parse` enforces on the way in: a git ref or remote
+    /// needs a workspace, and a full path can't carry a git ref at all.
+    pub fn build(self) -> Result<SrcuriRequest> {
+        if let Some(full_path) = self.full_path {
+            if self.git_ref.is_some() {
+                bail!("Git reference parameters require a workspace, not a full path");
+            }
+            return Ok(SrcuriRequest::FullPath {
+                full_path,
+                line: self.line,
+                column: self.column,
+                end_line: self.end_line,
+                end_column: self.end_column,
+            });
+        }
+
+        let path = self
+            .path
+            .ok_or_else(|| anyhow::anyhow!("SrcuriBuilder requires path() (or full_path())"))?;
+
+        match (self.workspace, self.git_ref) {
+            (Some(workspace), Some(git_ref)) => Ok(SrcuriRequest::RevisionPath {
+                workspace,
+                path,
+                git_ref,
+                line: self.line,
+                column: self.column,
+                end_line: self.end_line,
+                end_column: self.end_column,
+                remote: self.remote,
+            }),
+            (Some(workspace), None) => Ok(SrcuriRequest::WorkspacePath {
+                workspace,
+                path,
+                line: self.line,
+                column: self.column,
+                end_line: self.end_line,
+                end_column: self.end_column,
+                remote: self.remote,
+            }),
+            (None, Some(_)) => bail!("Git reference parameters require workspace() to be set"),
+            (None, None) => Ok(SrcuriRequest::PartialPath {
+                path,
+                line: self.line,
+                column: self.column,
+                end_line: self.end_line,
+                end_column: self.end_column,
+            }),
+        }
+    }
 }
 
+/// Self-hosted provider hosts registered at runtime (e.g. a corporate
+/// GitLab or a Gitea/Forgejo instance on a custom domain), consulted by
+/// [`SrcuriParser::looks_like_provider_host`] and [`SrcuriParser::parse_provider_passthrough`]
+/// ahead of the built-in host/pattern heuristics. Process-wide rather than
+/// threaded through `parse`'s call sites since every other piece of
+/// `SrcuriParser`'s state (none) is likewise static - see
+/// `PathValidator`'s `SUSPICIOUS_PATTERNS` for the same pattern.
+static PROVIDER_REGISTRY: Lazy<Mutex<srcuri_core::ProviderRegistry>> =
+    Lazy::new(|| Mutex::new(srcuri_core::ProviderRegistry::new()));
+
 pub struct SrcuriParser;
 
 impl SrcuriParser {
+    /// Registers `host` (e.g. `git.mycorp.internal`) as a self-hosted
+    /// instance of `provider`, so a `srcuri://git.mycorp.internal/...` link
+    /// resolves without needing `host` to contain a dot or a known provider
+    /// label and without srcuri-core needing to know about it in advance.
+    pub fn register_provider_host(host: impl Into<String>, provider: srcuri_core::Provider) {
+        PROVIDER_REGISTRY.lock().register_host(host, provider);
+    }
+
+    /// Same as [`Self::register_provider_host`], but matches any host ending
+    /// in `suffix` (e.g. `.corp.example.com`) - for an organization that
+    /// mints a new subdomain per team rather than a single fixed host.
+    pub fn register_provider_host_suffix(suffix: impl Into<String>, provider: srcuri_core::Provider) {
+        PROVIDER_REGISTRY.lock().register_host_suffix(suffix, provider);
+    }
+
     pub fn parse(link: &str) -> Result<SrcuriRequest> {
         let link = link.trim();
 
@@ -84,17 +732,31 @@ impl SrcuriParser {
             (remainder_no_fragment, None)
         };
 
-        let git_ref = Self::parse_git_ref_param(query_part);
-        let remote = Self::parse_remote_param(query_part);
-        let workspace_override = Self::parse_workspace_param(query_part);
+        if let Some(rest) = path_part.strip_prefix("clone-org/") {
+            return Self::parse_clone_org(rest, query_part);
+        }
 
-        // Check if first segment contains a dot AND has additional path segments
-        // This indicates provider-passthrough (e.g., github.com/owner/repo)
-        // Single segments like "README.md" are filenames, not providers
+        let git_ref = Self::parse_git_ref_param(query_part)?;
+        let remote = Self::parse_remote_param(query_part)?;
+        let workspace_override = Self::parse_workspace_param(query_part)?;
+
+        // Strip a scheme users commonly paste in from a browser's address bar
+        // or a git remote (`https://git.internal.example:8443/...`) before
+        // looking for a provider host - srcuri-core expects a bare authority.
+        let path_part = path_part
+            .strip_prefix("https://")
+            .or_else(|| path_part.strip_prefix("http://"))
+            .or_else(|| path_part.strip_prefix("ssh://"))
+            .unwrap_or(path_part);
+
+        // Check if the first segment looks like a provider host AND has
+        // additional path segments. This indicates provider-passthrough
+        // (e.g., github.com/owner/repo or git.internal.example:8443/group/repo).
+        // Single segments like "README.md" are filenames, not providers.
         let segments: Vec<&str> = path_part.split('/').filter(|s| !s.is_empty()).collect();
         if segments.len() >= 3 {
             if let Some(first_segment) = segments.first() {
-                if first_segment.contains('.') && !first_segment.contains(':') {
+                if Self::looks_like_provider_host(first_segment) {
                     let provider_input = if let Some(query) = query_part {
                         if query.is_empty() {
                             path_part.to_string()
@@ -114,7 +776,15 @@ impl SrcuriParser {
             }
         }
 
-        let (file_path, line, column) = Self::parse_path_with_location(path_part)?;
+        let (file_path, line, column, end_line, end_column) =
+            Self::parse_path_with_location(path_part)?;
+
+        // Decode only after the `:line:col` suffix has been split off, so a
+        // percent-encoded colon in the position spec can't be mistaken for
+        // the real one. Everything downstream (the absolute-path check, the
+        // workspace/path split) then sees the real characters instead of
+        // escapes that could otherwise hide a `..` segment from those checks.
+        let file_path = Self::decode_component(&file_path)?;
 
         if Self::is_absolute_path(&file_path) {
             if git_ref.is_some() {
@@ -125,6 +795,8 @@ impl SrcuriParser {
                 full_path: file_path,
                 line,
                 column,
+                end_line,
+                end_column,
             });
         }
 
@@ -136,6 +808,8 @@ impl SrcuriParser {
                     git_ref,
                     line,
                     column,
+                    end_line,
+                    end_column,
                     remote,
                 });
             }
@@ -145,6 +819,8 @@ impl SrcuriParser {
                 path: relative_path,
                 line,
                 column,
+                end_line,
+                end_column,
                 remote,
             });
         }
@@ -157,9 +833,37 @@ impl SrcuriParser {
             path: file_path,
             line,
             column,
+            end_line,
+            end_column,
         })
     }
 
+    /// Provider hostnames short enough to appear without a dot on a
+    /// self-hosted instance (`gitlab:8443`) - mirrors the substring checks
+    /// srcuri-core's own provider detection uses.
+    const KNOWN_PROVIDER_LABELS: &[&str] =
+        &["github", "gitlab", "bitbucket", "gitea", "codeberg", "azure"];
+
+    /// Whether `segment` looks like a provider-passthrough host rather than a
+    /// filename: a dotted hostname, a known provider label, a host
+    /// registered via [`Self::register_provider_host`]/`_suffix`, or any of
+    /// those followed by an explicit `:<port>` - so `github.com`,
+    /// `git.internal.example:8443`, `gitlab:8443`, and a bare registered
+    /// shortname like `gitbox` all match, but `README.md` and
+    /// `file:8443.txt` (a port-less name with a stray colon) don't.
+    fn looks_like_provider_host(segment: &str) -> bool {
+        let host = match segment.split_once(':') {
+            Some((host, port)) if !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) => {
+                host
+            }
+            Some(_) => return false,
+            None => segment,
+        };
+        host.contains('.')
+            || Self::KNOWN_PROVIDER_LABELS.iter().any(|label| host.contains(label))
+            || PROVIDER_REGISTRY.lock().resolve(host).is_some()
+    }
+
     /// Parse provider-passthrough URL like srcuri://github.com/owner/repo/blob/main/file.rs#L42
     /// Uses srcuri-core for comprehensive provider URL parsing
     fn parse_provider_passthrough(
@@ -175,15 +879,29 @@ impl SrcuriParser {
             path.to_string()
         };
 
-        // Use srcuri-core for comprehensive provider URL parsing
-        let target = srcuri_core::parse_remote_url(&full_url)
+        // Use srcuri-core for comprehensive provider URL parsing, consulting
+        // any self-hosted hosts registered via `register_provider_host`
+        // ahead of srcuri-core's own built-in host/pattern heuristics.
+        let registry = PROVIDER_REGISTRY.lock();
+        let target = srcuri_core::parse_remote_url_with_registry(&full_url, Some(&*registry))
             .map_err(|e| anyhow::anyhow!("Failed to parse provider URL: {}", e))?;
-
-        let (fragment_line, fragment_column) = Self::parse_provider_fragment(fragment);
-
-        // Map srcuri-core's ref_value to our GitRef enum, preserving incoming
-        let git_ref =
-            incoming_git_ref.or_else(|| target.ref_value.map(|value| GitRef::Branch(value)));
+        drop(registry);
+
+        let (fragment_line, fragment_column, fragment_end_line, fragment_end_column) =
+            Self::parse_provider_fragment(fragment);
+
+        // Map srcuri-core's GitReference to our GitRef enum, preserving incoming.
+        // `Unknown` means the provider's URL didn't say which kind it was (most
+        // blob URLs accept either a branch, a tag, or a SHA in the same spot) -
+        // classify it from the ref string itself rather than defaulting to branch.
+        let git_ref = incoming_git_ref.or_else(|| {
+            target.ref_value.map(|value| match value {
+                srcuri_core::GitReference::Branch(v) => GitRef::Branch(v),
+                srcuri_core::GitReference::Tag(v) => GitRef::Tag(v),
+                srcuri_core::GitReference::Commit(v) => GitRef::Commit(v),
+                srcuri_core::GitReference::Unknown(v) => Self::classify_unknown_ref(v),
+            })
+        });
 
         Ok(SrcuriRequest::ProviderPassthrough {
             provider: target.remote,
@@ -192,47 +910,115 @@ impl SrcuriParser {
             path: target.file_path.unwrap_or_default(),
             line: fragment_line.or_else(|| target.line.map(|l| l as usize)),
             column: fragment_column,
+            end_line: fragment_end_line,
+            end_column: fragment_end_column,
             git_ref,
             workspace_override,
             fragment: fragment.map(|f| f.to_string()),
         })
     }
 
-    fn parse_provider_fragment(fragment: Option<&str>) -> (Option<usize>, Option<usize>) {
+    /// Classifies an ambiguous ref string (srcuri-core's `GitReference::Unknown`)
+    /// as a tag or - if it doesn't look like one - a branch. `Unknown` is only
+    /// ever produced for values that already failed srcuri-core's own SHA-shape
+    /// check (see `GitReference::from_shape`), so a commit case can't reach here.
+    fn classify_unknown_ref(value: String) -> GitRef {
+        if Self::looks_like_semver_tag(&value) {
+            GitRef::Tag(value)
+        } else {
+            GitRef::Branch(value)
+        }
+    }
+
+    /// An optional `v`/`V` prefix followed by a `MAJOR.MINOR[.PATCH]` core,
+    /// with an optional `-pre-release` or `+build` suffix ignored - cargo's
+    /// own partial-version parsing accepts the same shape.
+    fn looks_like_semver_tag(value: &str) -> bool {
+        let core = value
+            .strip_prefix('v')
+            .or_else(|| value.strip_prefix('V'))
+            .unwrap_or(value)
+            .split(['-', '+'])
+            .next()
+            .unwrap_or("");
+        let parts: Vec<&str> = core.split('.').collect();
+        (2..=3).contains(&parts.len())
+            && parts
+                .iter()
+                .all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+    }
+
+    /// Parses a location fragment into `(line, column, end_line, end_column)`,
+    /// covering GitHub (`#L10`, `#L10-L20`), GitLab (`#L10C5`,
+    /// `#L10C5-L20C8`), and Bitbucket (`#lines-5`, `#lines-5:10`,
+    /// `#lines-5-10`) styles. An end without a start is never produced -
+    /// every range form requires the start line (and, for GitLab, the start
+    /// column) to parse first.
+    fn parse_provider_fragment(
+        fragment: Option<&str>,
+    ) -> (Option<usize>, Option<usize>, Option<usize>, Option<usize>) {
         let fragment = match fragment {
             Some(frag) if !frag.is_empty() => frag,
-            _ => return (None, None),
+            _ => return (None, None, None, None),
         };
 
-        // GitHub/GitLab style (#L10, #L10C5, #L10-L20)
+        // GitHub/GitLab style (#L10, #L10C5, #L10-L20, #L10C5-L20C8)
         if let Some(rest) = fragment.strip_prefix('L') {
             let (line, remainder) = Self::parse_leading_number(rest);
             if let Some(line) = line {
                 if let Some(rem) = remainder {
                     if rem.starts_with('C') || rem.starts_with('c') {
                         let (_, col_rest) = rem.split_at(1);
-                        let (column, _) = Self::parse_leading_number(col_rest);
-                        return (Some(line), column);
+                        let (column, col_remainder) = Self::parse_leading_number(col_rest);
+                        let (end_line, end_column) = Self::parse_range_suffix(col_remainder);
+                        return (Some(line), column, end_line, end_column);
                     }
                     if rem.starts_with(':') {
                         let (_, col_rest) = rem.split_at(1);
                         let (column, _) = Self::parse_leading_number(col_rest);
-                        return (Some(line), column);
+                        return (Some(line), column, None, None);
+                    }
+                    if rem.starts_with('-') {
+                        let (end_line, end_column) = Self::parse_range_suffix(Some(rem));
+                        return (Some(line), None, end_line, end_column);
                     }
                 }
-                return (Some(line), None);
+                return (Some(line), None, None, None);
             }
         }
 
         // Bitbucket style (#lines-5, #lines-5:10, #lines-5-10)
         if let Some(rest) = fragment.strip_prefix("lines-") {
-            let (line, _) = Self::parse_leading_number(rest);
-            if line.is_some() {
-                return (line, None);
+            let (line, remainder) = Self::parse_leading_number(rest);
+            if let Some(line) = line {
+                let end_line = remainder.and_then(|rem| {
+                    let rem = rem.strip_prefix('-').or_else(|| rem.strip_prefix(':'))?;
+                    Self::parse_leading_number(rem).0
+                });
+                return (Some(line), None, end_line, None);
             }
         }
 
-        (None, None)
+        (None, None, None, None)
+    }
+
+    /// Parses a `-L<end_line>[C<end_column>]` range suffix trailing a
+    /// GitHub/GitLab start line or column, or `(None, None)` if `remainder`
+    /// isn't a `-`-led range at all.
+    fn parse_range_suffix(remainder: Option<&str>) -> (Option<usize>, Option<usize>) {
+        let Some(rest) = remainder.and_then(|rem| rem.strip_prefix('-')) else {
+            return (None, None);
+        };
+        let Some(after_l) = rest.strip_prefix('L').or_else(|| rest.strip_prefix('l')) else {
+            return (None, None);
+        };
+
+        let (end_line, col_remainder) = Self::parse_leading_number(after_l);
+        let end_column = col_remainder.and_then(|rem| {
+            let rem = rem.strip_prefix('C').or_else(|| rem.strip_prefix('c'))?;
+            Self::parse_leading_number(rem).0
+        });
+        (end_line, end_column)
     }
 
     fn parse_leading_number(input: &str) -> (Option<usize>, Option<&str>) {
@@ -261,97 +1047,221 @@ impl SrcuriParser {
         (digits.parse().ok(), remainder)
     }
 
-    fn parse_git_ref_param(query_part: Option<&str>) -> Option<GitRef> {
-        query_part.and_then(|q| {
-            for pair in q.split('&') {
-                if let Some((key, value)) = pair.split_once('=') {
-                    match key {
-                        "commit" | "sha" => return Some(GitRef::Commit(value.to_string())),
-                        "branch" => return Some(GitRef::Branch(value.to_string())),
-                        "tag" => return Some(GitRef::Tag(value.to_string())),
-                        _ => {}
-                    }
+    fn parse_git_ref_param(query_part: Option<&str>) -> Result<Option<GitRef>> {
+        let Some(q) = query_part else { return Ok(None) };
+        let mut found: Option<GitRef> = None;
+        for pair in q.split('&') {
+            let Some((key, value)) = pair.split_once('=') else { continue };
+            let git_ref = match key {
+                "commit" | "sha" => Some(GitRef::Commit(Self::decode_query_component(value)?)),
+                "branch" => Some(GitRef::Branch(Self::decode_query_component(value)?)),
+                "tag" => Some(GitRef::Tag(Self::decode_query_component(value)?)),
+                "jj" => Some(GitRef::Jj(Self::decode_query_component(value)?)),
+                "version" => {
+                    let decoded = Self::decode_query_component(value)?;
+                    let version = PartialVersion::parse(&decoded)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid version spec in ?version=: {:?}", decoded))?;
+                    Some(GitRef::Version(version))
+                }
+                _ => None,
+            };
+            if let Some(git_ref) = git_ref {
+                if found.is_some() {
+                    bail!(
+                        "commit=, branch=, tag=, jj=, and version= are mutually exclusive git references"
+                    );
                 }
+                found = Some(git_ref);
             }
-            None
-        })
+        }
+        Ok(found)
     }
 
-    fn parse_remote_param(query_part: Option<&str>) -> Option<String> {
-        query_part.and_then(|q| {
-            for pair in q.split('&') {
-                if let Some((key, value)) = pair.split_once('=') {
-                    if key == "remote" && !value.is_empty() {
-                        return Some(value.to_string());
-                    }
+    fn parse_remote_param(query_part: Option<&str>) -> Result<Option<String>> {
+        let Some(q) = query_part else { return Ok(None) };
+        for pair in q.split('&') {
+            if let Some((key, value)) = pair.split_once('=') {
+                if key == "remote" && !value.is_empty() {
+                    return Ok(Some(Self::decode_query_component(value)?));
                 }
             }
-            None
-        })
+        }
+        Ok(None)
     }
 
     /// Parse ?workspace= parameter (escape hatch for dot-containing workspace names)
-    fn parse_workspace_param(query_part: Option<&str>) -> Option<String> {
-        query_part.and_then(|q| {
-            for pair in q.split('&') {
-                if let Some((key, value)) = pair.split_once('=') {
-                    if key == "workspace" && !value.is_empty() {
-                        return Some(value.to_string());
-                    }
+    fn parse_workspace_param(query_part: Option<&str>) -> Result<Option<String>> {
+        let Some(q) = query_part else { return Ok(None) };
+        for pair in q.split('&') {
+            if let Some((key, value)) = pair.split_once('=') {
+                if key == "workspace" && !value.is_empty() {
+                    return Ok(Some(Self::decode_query_component(value)?));
                 }
             }
-            None
+        }
+        Ok(None)
+    }
+
+    /// Percent-decodes a path segment or query value, rejecting malformed
+    /// `%`-escapes rather than passing them through unchanged (the default
+    /// behavior of `percent_decode_str` on its own, which treats a stray
+    /// `%` or truncated escape as a literal `%`).
+    fn decode_component(value: &str) -> Result<String> {
+        Self::validate_percent_encoding(value)?;
+        Ok(percent_decode_str(value).decode_utf8_lossy().into_owned())
+    }
+
+    /// Same as [`decode_component`], but also treats a literal `+` as an
+    /// encoded space - the `application/x-www-form-urlencoded` convention
+    /// query strings use, which a path segment does not (a `+` there means
+    /// a literal `+`).
+    fn decode_query_component(value: &str) -> Result<String> {
+        Self::validate_percent_encoding(value)?;
+        let value = value.replace('+', " ");
+        Ok(percent_decode_str(&value).decode_utf8_lossy().into_owned())
+    }
+
+    fn validate_percent_encoding(value: &str) -> Result<()> {
+        let bytes = value.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' {
+                let valid = bytes
+                    .get(i + 1..i + 3)
+                    .is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit));
+                if !valid {
+                    bail!("Invalid percent-encoding in {:?}", value);
+                }
+                i += 3;
+            } else {
+                i += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the `<host>/<owner>` remainder of a `clone-org/` URL. Tokens
+    /// are never accepted here (or anywhere in a URL) — they're configured
+    /// under `defaults.git_host_tokens` instead, so they can't leak into
+    /// shell history or the git command log.
+    fn parse_clone_org(rest: &str, query_part: Option<&str>) -> Result<SrcuriRequest> {
+        let segments: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+        let [host, owner] = segments.as_slice() else {
+            bail!("clone-org URL requires a host and owner (e.g. srcuri://clone-org/github.com/my-org)");
+        };
+
+        Ok(SrcuriRequest::CloneOrg {
+            host: host.to_string(),
+            owner: owner.to_string(),
+            include_forks: Self::parse_bool_param(query_part, "include_forks"),
+            include_archived: Self::parse_bool_param(query_part, "include_archived"),
         })
     }
 
-    fn parse_path_with_location(path: &str) -> Result<(String, Option<usize>, Option<usize>)> {
-        let mut parts: Vec<&str> = path.rsplitn(3, ':').collect();
+    fn parse_bool_param(query_part: Option<&str>, key: &str) -> bool {
+        query_part
+            .and_then(|q| {
+                q.split('&').find_map(|pair| {
+                    let (k, value) = pair.split_once('=')?;
+                    (k == key).then(|| value == "true" || value == "1")
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    /// Splits a `:LINE[:COL][-END_LINE[:END_COL]]` location suffix off the
+    /// end of `path`. `rsplitn(4, ':')` caps the split at the 3 colons the
+    /// full range syntax can contain (`file.rs:10:5-20:8`); a malformed
+    /// token at any position falls back to dropping only what couldn't be
+    /// parsed, never erroring, same as the pre-range behavior.
+    fn parse_path_with_location(
+        path: &str,
+    ) -> Result<(String, Option<usize>, Option<usize>, Option<usize>, Option<usize>)> {
+        let mut parts: Vec<&str> = path.rsplitn(4, ':').collect();
         parts.reverse();
 
         match parts.len() {
             1 => {
                 // No colons in path
-                Ok((path.to_string(), None, None))
+                Ok((path.to_string(), None, None, None, None))
             }
             2 => {
-                // One colon: file.txt:LINE
-                if let Ok(line) = parts[1].parse::<usize>() {
-                    Ok((parts[0].to_string(), Some(line), None))
-                } else {
+                // One colon: file.txt:LINE or file.txt:LINE-END_LINE
+                let (line, end_line) = Self::parse_line_token(parts[1]);
+                match line {
+                    Some(line) => Ok((parts[0].to_string(), Some(line), None, end_line, None)),
                     // Malformed line number - use filename without colon suffix
-                    Ok((parts[0].to_string(), None, None))
+                    None => Ok((parts[0].to_string(), None, None, None, None)),
                 }
             }
             3 => {
-                // Two colons: file.txt:LINE:COL
-                if let (Ok(line), Ok(column)) =
-                    (parts[1].parse::<usize>(), parts[2].parse::<usize>())
-                {
-                    if column <= 120 {
-                        Ok((parts[0].to_string(), Some(line), Some(column)))
-                    } else {
-                        // Column out of range - keep line, drop column
-                        Ok((parts[0].to_string(), Some(line), None))
+                // Two colons: file.txt:LINE:COL or file.txt:LINE:COL-END_LINE
+                match parts[1].parse::<usize>() {
+                    Ok(line) => {
+                        let (column, end_line) = Self::parse_column_token(parts[2]);
+                        Ok((parts[0].to_string(), Some(line), column, end_line, None))
+                    }
+                    // Malformed line - use filename without colon suffix
+                    Err(_) => Ok((parts[0].to_string(), None, None, None, None)),
+                }
+            }
+            4 => {
+                // Three colons: file.txt:LINE:COL-END_LINE:END_COL
+                match parts[1].parse::<usize>() {
+                    Ok(line) => {
+                        let (column, end_line) = Self::parse_column_token(parts[2]);
+                        match end_line {
+                            Some(end_line) => {
+                                let end_column =
+                                    parts[3].parse::<usize>().ok().filter(|c| *c <= 120);
+                                Ok((parts[0].to_string(), Some(line), column, Some(end_line), end_column))
+                            }
+                            // parts[2] wasn't a `COL-END_LINE` range, so
+                            // parts[3] is an unrecognized extra colon
+                            // segment - keep what's recognized, drop the rest.
+                            None => Ok((parts[0].to_string(), Some(line), column, None, None)),
+                        }
                     }
-                } else if let Ok(line) = parts[1].parse::<usize>() {
-                    // Valid line, malformed column - keep line, drop column
-                    Ok((parts[0].to_string(), Some(line), None))
-                } else {
                     // Malformed line - use filename without colon suffix
-                    Ok((parts[0].to_string(), None, None))
+                    Err(_) => Ok((parts[0].to_string(), None, None, None, None)),
                 }
             }
             _ => {
-                // More than 2 colons - use first part as filename, ignore rest
-                Ok((parts[0].to_string(), None, None))
+                // More than 3 colons - use first part as filename, ignore rest
+                Ok((parts[0].to_string(), None, None, None, None))
             }
         }
     }
 
+    /// Parses a `LINE` or `LINE-END_LINE` token (no column involved).
+    fn parse_line_token(token: &str) -> (Option<usize>, Option<usize>) {
+        match token.split_once('-') {
+            Some((line, end_line)) => (line.parse().ok(), end_line.parse().ok()),
+            None => (token.parse().ok(), None),
+        }
+    }
+
+    /// Parses a `COL` or `COL-END_LINE` token, clamping `COL` to 120 like
+    /// the pre-range single-column case already did.
+    fn parse_column_token(token: &str) -> (Option<usize>, Option<usize>) {
+        match token.split_once('-') {
+            Some((column, end_line)) => (
+                column.parse::<usize>().ok().filter(|c| *c <= 120),
+                end_line.parse().ok(),
+            ),
+            None => (token.parse::<usize>().ok().filter(|c| *c <= 120), None),
+        }
+    }
+
     fn is_absolute_path(path: &str) -> bool {
         path.starts_with('/') || (path.len() > 2 && path.chars().nth(1) == Some(':'))
     }
 
+    /// Splits a decoded `workspace/relative/path` string on its first `/`.
+    /// Unlike a generic URL path, a filesystem path can't contain a literal
+    /// `/` within one segment - so an encoded `%2F` decoding into a real `/`
+    /// here is indistinguishable from, and correctly treated the same as, a
+    /// structural separator the caller typed directly.
     fn split_workspace_path(path: &str) -> Option<(String, String)> {
         let parts: Vec<&str> = path.splitn(2, '/').collect();
         if parts.len() == 2 {
@@ -375,6 +1285,8 @@ mod tests {
                 path: "README.md".to_string(),
                 line: None,
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -388,6 +1300,8 @@ mod tests {
                 path: "README.md".to_string(),
                 line: Some(25),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -401,6 +1315,8 @@ mod tests {
                 path: "README.md".to_string(),
                 line: Some(25),
                 column: Some(10),
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -415,6 +1331,8 @@ mod tests {
                 path: "README.md".to_string(),
                 line: None,
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -430,6 +1348,8 @@ mod tests {
                 path: "README.md".to_string(),
                 line: Some(25),
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -445,6 +1365,8 @@ mod tests {
                 path: "src/main.rs".to_string(),
                 line: Some(42),
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -460,6 +1382,8 @@ mod tests {
                 path: "src/main.rs".to_string(),
                 line: Some(42),
                 column: Some(7),
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -475,6 +1399,8 @@ mod tests {
                 full_path: "/Users/ebeland/apps/myproject/README.md".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -489,6 +1415,8 @@ mod tests {
                 full_path: "/devsrv1/deploy/current/myrepo/apps/user.rb".to_string(),
                 line: Some(23),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -502,6 +1430,8 @@ mod tests {
                 full_path: "/Users/ebeland/file.txt".to_string(),
                 line: Some(10),
                 column: Some(5),
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -518,6 +1448,8 @@ mod tests {
                 git_ref: GitRef::Commit("abc123def".to_string()),
                 line: Some(23),
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -534,6 +1466,8 @@ mod tests {
                 git_ref: GitRef::Commit("abc123def".to_string()),
                 line: Some(23),
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -550,6 +1484,8 @@ mod tests {
                 git_ref: GitRef::Branch("main".to_string()),
                 line: Some(1),
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -566,6 +1502,26 @@ mod tests {
                 git_ref: GitRef::Tag("v1.0.0".to_string()),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
+                remote: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_jj_param() {
+        let request = SrcuriParser::parse("srcuri://myproject/README.md:1?jj=@-").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::RevisionPath {
+                workspace: "myproject".to_string(),
+                path: "README.md".to_string(),
+                git_ref: GitRef::Jj("@-".to_string()),
+                line: Some(1),
+                column: None,
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -604,6 +1560,8 @@ mod tests {
                 path: "file.rs".to_string(),
                 line: None,
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -618,6 +1576,8 @@ mod tests {
                 path: "file".to_string(),
                 line: None,
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -631,6 +1591,8 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -644,58 +1606,113 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: Some(10),
                 column: Some(120),
+                end_line: None,
+                end_column: None,
             }
         );
     }
 
     #[test]
-    fn test_column_at_boundary_121_rejected() {
-        let request = SrcuriParser::parse("srcuri://file.txt:10:121").unwrap();
+    fn test_path_with_line_range() {
+        let request = SrcuriParser::parse("srcuri://file.rs:10-20").unwrap();
         assert_eq!(
             request,
             SrcuriRequest::PartialPath {
-                path: "file.txt".to_string(),
+                path: "file.rs".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: Some(20),
+                end_column: None,
             }
         );
     }
 
     #[test]
-    fn test_column_zero_accepted() {
-        let request = SrcuriParser::parse("srcuri://file.txt:10:0").unwrap();
+    fn test_path_with_line_and_column_range() {
+        let request = SrcuriParser::parse("srcuri://file.rs:10:5-20:8").unwrap();
         assert_eq!(
             request,
             SrcuriRequest::PartialPath {
-                path: "file.txt".to_string(),
+                path: "file.rs".to_string(),
                 line: Some(10),
-                column: Some(0),
+                column: Some(5),
+                end_line: Some(20),
+                end_column: Some(8),
             }
         );
     }
 
     #[test]
-    fn test_column_one_accepted() {
-        let request = SrcuriParser::parse("srcuri://file.txt:10:1").unwrap();
+    fn test_path_with_end_column_over_120_ignored() {
+        let request = SrcuriParser::parse("srcuri://file.rs:10:5-20:150").unwrap();
         assert_eq!(
             request,
             SrcuriRequest::PartialPath {
-                path: "file.txt".to_string(),
+                path: "file.rs".to_string(),
                 line: Some(10),
-                column: Some(1),
+                column: Some(5),
+                end_line: Some(20),
+                end_column: None,
             }
         );
     }
 
     #[test]
-    fn test_non_numeric_column_ignored() {
-        let request = SrcuriParser::parse("srcuri://file.txt:10:abc").unwrap();
-        assert_eq!(
+    fn test_column_at_boundary_121_rejected() {
+        let request = SrcuriParser::parse("srcuri://file.txt:10:121").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::PartialPath {
+                path: "file.txt".to_string(),
+                line: Some(10),
+                column: None,
+                end_line: None,
+                end_column: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_column_zero_accepted() {
+        let request = SrcuriParser::parse("srcuri://file.txt:10:0").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::PartialPath {
+                path: "file.txt".to_string(),
+                line: Some(10),
+                column: Some(0),
+                end_line: None,
+                end_column: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_column_one_accepted() {
+        let request = SrcuriParser::parse("srcuri://file.txt:10:1").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::PartialPath {
+                path: "file.txt".to_string(),
+                line: Some(10),
+                column: Some(1),
+                end_line: None,
+                end_column: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_non_numeric_column_ignored() {
+        let request = SrcuriParser::parse("srcuri://file.txt:10:abc").unwrap();
+        assert_eq!(
             request,
             SrcuriRequest::PartialPath {
                 path: "file.txt".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -710,6 +1727,8 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: None,
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -723,6 +1742,8 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -736,6 +1757,8 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -749,6 +1772,8 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -762,6 +1787,8 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -775,6 +1802,8 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -788,6 +1817,8 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: Some(10),
                 column: Some(5),
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -801,6 +1832,8 @@ mod tests {
                 path: "file:with:colons.txt".to_string(),
                 line: Some(10),
                 column: Some(5),
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -815,6 +1848,8 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: Some(10),
                 column: Some(120),
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -829,6 +1864,8 @@ mod tests {
                 full_path: "/home/user/file.txt".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
             }
         );
     }
@@ -845,6 +1882,8 @@ mod tests {
                 git_ref: GitRef::Commit("abc123".to_string()),
                 line: Some(10),
                 column: Some(5),
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -862,6 +1901,8 @@ mod tests {
                 git_ref: GitRef::Commit("abc123".to_string()),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -880,6 +1921,8 @@ mod tests {
                 path: "src/main.rs".to_string(),
                 line: Some(42),
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: Some("github.com/user/myproject".to_string()),
             }
         );
@@ -899,6 +1942,8 @@ mod tests {
                 git_ref: GitRef::Branch("main".to_string()),
                 line: Some(42),
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: Some("github.com/user/myproject".to_string()),
             }
         );
@@ -919,6 +1964,8 @@ mod tests {
                 path: "src/lib.rs".to_string(),
                 line: Some(42),
                 column: None,
+                end_line: None,
+                end_column: None,
                 git_ref: Some(GitRef::Branch("main".to_string())),
                 workspace_override: None,
                 fragment: Some("L42".to_string()),
@@ -926,6 +1973,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_provider_passthrough_classifies_ambiguous_ref_as_tag() {
+        let request =
+            SrcuriParser::parse("srcuri://github.com/owner/repo/blob/v1.2.0/src/lib.rs#L42")
+                .unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::ProviderPassthrough {
+                provider: "github.com/owner/repo".to_string(),
+                repo_name: "repo".to_string(),
+                provider_path: "github.com/owner/repo/blob/v1.2.0/src/lib.rs".to_string(),
+                path: "src/lib.rs".to_string(),
+                line: Some(42),
+                column: None,
+                end_line: None,
+                end_column: None,
+                git_ref: Some(GitRef::Tag("v1.2.0".to_string())),
+                workspace_override: None,
+                fragment: Some("L42".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_provider_passthrough_classifies_ambiguous_ref_as_branch() {
+        let request =
+            SrcuriParser::parse("srcuri://github.com/owner/repo/blob/feature-login/src/lib.rs#L42")
+                .unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::ProviderPassthrough {
+                provider: "github.com/owner/repo".to_string(),
+                repo_name: "repo".to_string(),
+                provider_path: "github.com/owner/repo/blob/feature-login/src/lib.rs".to_string(),
+                path: "src/lib.rs".to_string(),
+                line: Some(42),
+                column: None,
+                end_line: None,
+                end_column: None,
+                git_ref: Some(GitRef::Branch("feature-login".to_string())),
+                workspace_override: None,
+                fragment: Some("L42".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_provider_passthrough_explicit_commit_ref_overrides_classification() {
+        let request = SrcuriParser::parse(
+            "srcuri://github.com/owner/repo/blob/main/src/lib.rs?commit=v1.2.0#L42",
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::ProviderPassthrough {
+                provider: "github.com/owner/repo".to_string(),
+                repo_name: "repo".to_string(),
+                provider_path: "github.com/owner/repo/blob/main/src/lib.rs?commit=v1.2.0"
+                    .to_string(),
+                path: "src/lib.rs".to_string(),
+                line: Some(42),
+                column: None,
+                end_line: None,
+                end_column: None,
+                git_ref: Some(GitRef::Commit("v1.2.0".to_string())),
+                workspace_override: None,
+                fragment: Some("L42".to_string()),
+            }
+        );
+    }
+
     #[test]
     fn test_provider_passthrough_github_no_file() {
         let request = SrcuriParser::parse("srcuri://github.com/owner/repo").unwrap();
@@ -938,6 +2056,8 @@ mod tests {
                 path: "".to_string(),
                 line: None,
                 column: None,
+                end_line: None,
+                end_column: None,
                 git_ref: None,
                 workspace_override: None,
                 fragment: None,
@@ -960,6 +2080,8 @@ mod tests {
                 path: "file.py".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: None,
+                end_column: None,
                 git_ref: Some(GitRef::Branch("main".to_string())),
                 workspace_override: None,
                 fragment: Some("L10".to_string()),
@@ -981,6 +2103,8 @@ mod tests {
                 path: "file.rs".to_string(),
                 line: Some(10),
                 column: None,
+                end_line: Some(20),
+                end_column: None,
                 git_ref: Some(GitRef::Branch("main".to_string())),
                 workspace_override: None,
                 fragment: Some("L10-L20".to_string()),
@@ -988,6 +2112,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_provider_passthrough_gitlab_column_range() {
+        let request = SrcuriParser::parse(
+            "srcuri://gitlab.com/group/project/-/blob/main/file.py#L10C5-L20C8",
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::ProviderPassthrough {
+                provider: "gitlab.com/group/project".to_string(),
+                repo_name: "project".to_string(),
+                provider_path: "gitlab.com/group/project/-/blob/main/file.py".to_string(),
+                path: "file.py".to_string(),
+                line: Some(10),
+                column: Some(5),
+                end_line: Some(20),
+                end_column: Some(8),
+                git_ref: Some(GitRef::Branch("main".to_string())),
+                workspace_override: None,
+                fragment: Some("L10C5-L20C8".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_provider_passthrough_bitbucket_lines_range() {
+        let request = SrcuriParser::parse(
+            "srcuri://bitbucket.org/workspace/repo/src/main/file.txt#lines-5-10",
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::ProviderPassthrough {
+                provider: "bitbucket.org/workspace/repo".to_string(),
+                repo_name: "repo".to_string(),
+                provider_path: "bitbucket.org/workspace/repo/src/main/file.txt".to_string(),
+                path: "file.txt".to_string(),
+                line: Some(5),
+                column: None,
+                end_line: Some(10),
+                end_column: None,
+                git_ref: Some(GitRef::Branch("main".to_string())),
+                workspace_override: None,
+                fragment: Some("lines-5-10".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_provider_passthrough_bitbucket_lines_range_colon() {
+        let request = SrcuriParser::parse(
+            "srcuri://bitbucket.org/workspace/repo/src/main/file.txt#lines-5:10",
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::ProviderPassthrough {
+                provider: "bitbucket.org/workspace/repo".to_string(),
+                repo_name: "repo".to_string(),
+                provider_path: "bitbucket.org/workspace/repo/src/main/file.txt".to_string(),
+                path: "file.txt".to_string(),
+                line: Some(5),
+                column: None,
+                end_line: Some(10),
+                end_column: None,
+                git_ref: Some(GitRef::Branch("main".to_string())),
+                workspace_override: None,
+                fragment: Some("lines-5:10".to_string()),
+            }
+        );
+    }
+
     #[test]
     fn test_provider_passthrough_bitbucket_lines() {
         let request =
@@ -1002,6 +2198,8 @@ mod tests {
                 path: "file.txt".to_string(),
                 line: Some(5),
                 column: None,
+                end_line: None,
+                end_column: None,
                 git_ref: Some(GitRef::Branch("main".to_string())),
                 workspace_override: None,
                 fragment: Some("lines-5".to_string()),
@@ -1025,6 +2223,8 @@ mod tests {
                 path: "app.py".to_string(),
                 line: Some(15),
                 column: None,
+                end_line: None,
+                end_column: None,
                 git_ref: Some(GitRef::Branch("main".to_string())),
                 workspace_override: None,
                 fragment: Some("L15".to_string()),
@@ -1032,6 +2232,99 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_provider_passthrough_selfhosted_host_with_port() {
+        let request = SrcuriParser::parse(
+            "srcuri://git.internal.example:8443/group/repo/blob/main/f.rs#L10",
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::ProviderPassthrough {
+                provider: "git.internal.example/group/repo".to_string(),
+                repo_name: "repo".to_string(),
+                provider_path: "git.internal.example:8443/group/repo/blob/main/f.rs".to_string(),
+                path: "f.rs".to_string(),
+                line: Some(10),
+                column: None,
+                end_line: None,
+                end_column: None,
+                git_ref: Some(GitRef::Branch("main".to_string())),
+                workspace_override: None,
+                fragment: Some("L10".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_register_provider_host_resolves_a_bare_shortname_host() {
+        SrcuriParser::register_provider_host("gitbox-test-registry", srcuri_core::Provider::GitLab);
+        let request = SrcuriParser::parse(
+            "srcuri://gitbox-test-registry/group/repo/-/blob/main/f.rs#L10",
+        )
+        .unwrap();
+        let SrcuriRequest::ProviderPassthrough { repo_name, git_ref, .. } = request else {
+            panic!("expected ProviderPassthrough");
+        };
+        assert_eq!(repo_name, "repo");
+        assert_eq!(git_ref, Some(GitRef::Branch("main".to_string())));
+    }
+
+    #[test]
+    fn test_register_provider_host_suffix_matches_any_subdomain() {
+        SrcuriParser::register_provider_host_suffix(
+            ".test-registry-suffix.example",
+            srcuri_core::Provider::Gitea,
+        );
+        let request = SrcuriParser::parse(
+            "srcuri://git.team-a.test-registry-suffix.example/owner/repo/src/branch/main/f.rs#L5",
+        )
+        .unwrap();
+        let SrcuriRequest::ProviderPassthrough { repo_name, .. } = request else {
+            panic!("expected ProviderPassthrough");
+        };
+        assert_eq!(repo_name, "repo");
+    }
+
+    #[test]
+    fn test_provider_passthrough_strips_pasted_https_scheme() {
+        let request = SrcuriParser::parse(
+            "srcuri://https://github.com/owner/repo/blob/main/file.rs#L10",
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::ProviderPassthrough {
+                provider: "github.com/owner/repo".to_string(),
+                repo_name: "repo".to_string(),
+                provider_path: "github.com/owner/repo/blob/main/file.rs".to_string(),
+                path: "file.rs".to_string(),
+                line: Some(10),
+                column: None,
+                end_line: None,
+                end_column: None,
+                git_ref: Some(GitRef::Branch("main".to_string())),
+                workspace_override: None,
+                fragment: Some("L10".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_bare_filename_with_colon_port_shape_is_not_mistaken_for_a_host() {
+        let request = SrcuriParser::parse("srcuri://file:8443.txt").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::PartialPath {
+                path: "file:8443.txt".to_string(),
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }
+        );
+    }
+
     #[test]
     fn test_provider_passthrough_with_workspace_override() {
         // Escape hatch: ?workspace= allows using a dot-containing workspace name
@@ -1051,6 +2344,8 @@ mod tests {
                 path: "file.rs".to_string(),
                 line: Some(42),
                 column: None,
+                end_line: None,
+                end_column: None,
                 git_ref: Some(GitRef::Branch("main".to_string())),
                 workspace_override: Some("my.custom.workspace".to_string()),
                 fragment: Some("L42".to_string()),
@@ -1069,6 +2364,8 @@ mod tests {
                 path: "src/lib.rs".to_string(),
                 line: Some(42),
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -1088,6 +2385,8 @@ mod tests {
                 path: "owner".to_string(),
                 line: None,
                 column: None,
+                end_line: None,
+                end_column: None,
                 remote: None,
             }
         );
@@ -1107,6 +2406,8 @@ mod tests {
                 path: "src/lib.rs".to_string(),
                 line: Some(15),
                 column: Some(9),
+                end_line: None,
+                end_column: None,
                 git_ref: Some(GitRef::Branch("main".to_string())),
                 workspace_override: None,
                 fragment: Some("L15C9".to_string()),
@@ -1114,6 +2415,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_workspace_path_percent_encoded_name() {
+        let request = SrcuriParser::parse("srcuri://My%20Project/src/main.rs:5:10").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::WorkspacePath {
+                workspace: "My Project".to_string(),
+                path: "src/main.rs".to_string(),
+                line: Some(5),
+                column: Some(10),
+                end_line: None,
+                end_column: None,
+                remote: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_workspace_path_percent_encoded_traversal_decoded() {
+        // Decoding happens here in the parser; rejecting the escape itself is
+        // `PathValidator::reject_path_traversal`'s job once this reaches the
+        // matcher, so this just confirms the `..` survives decoding intact
+        // instead of being hidden behind `%2E%2E`.
+        let request =
+            SrcuriParser::parse("srcuri://myproject/%2E%2E/%2E%2E/etc/passwd:1:1").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::WorkspacePath {
+                workspace: "myproject".to_string(),
+                path: "../../etc/passwd".to_string(),
+                line: Some(1),
+                column: Some(1),
+                end_line: None,
+                end_column: None,
+                remote: None,
+            }
+        );
+    }
+
     #[test]
     fn test_provider_passthrough_preserves_query() {
         let request = SrcuriParser::parse(
@@ -1131,10 +2471,558 @@ mod tests {
                 path: "src/index.ts".to_string(),
                 line: None,
                 column: None,
+                end_line: None,
+                end_column: None,
                 git_ref: Some(GitRef::Branch("main".to_string())),
                 workspace_override: None,
                 fragment: None,
             }
         );
     }
+
+    // validate() tests
+
+    #[test]
+    fn validate_accepts_an_ordinary_workspace_path() {
+        let request = SrcuriParser::parse("srcuri://my-repo/src/main.rs").unwrap();
+        assert_eq!(request.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_traversal_that_escapes_the_workspace_root() {
+        let request =
+            SrcuriParser::parse("srcuri://myproject/%2E%2E/%2E%2E/etc/passwd:1:1").unwrap();
+        assert_eq!(
+            request.validate(),
+            Err(SrcuriValidationError::PathEscapesRoot(
+                "../../etc/passwd".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn validate_accepts_a_dotdot_that_stays_within_the_workspace() {
+        // `a/../b` never climbs above the root it started from, unlike
+        // `../b`, so this should be let through.
+        let request = SrcuriParser::parse("srcuri://my-repo/a/%2E%2E/b.rs").unwrap();
+        assert_eq!(request.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_embedded_nul_byte() {
+        let request = SrcuriRequest::WorkspacePath {
+            workspace: "my-repo".to_string(),
+            path: "evil\0.rs".to_string(),
+            line: None,
+            column: None,
+            end_line: None,
+            end_column: None,
+            remote: None,
+        };
+        assert_eq!(
+            request.validate(),
+            Err(SrcuriValidationError::ControlCharacter("evil\0.rs".to_string()))
+        );
+    }
+
+    #[test]
+    fn validate_rejects_absolute_relative_path() {
+        let request = SrcuriRequest::WorkspacePath {
+            workspace: "my-repo".to_string(),
+            path: "/etc/passwd".to_string(),
+            line: None,
+            column: None,
+            end_line: None,
+            end_column: None,
+            remote: None,
+        };
+        assert_eq!(
+            request.validate(),
+            Err(SrcuriValidationError::UnexpectedAbsolutePath(
+                "/etc/passwd".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn validate_allows_full_path_to_be_absolute() {
+        let request = SrcuriParser::parse("srcuri:///Users/dev/scratch/notes.md").unwrap();
+        assert_eq!(request.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_allows_clone_org() {
+        let request = SrcuriParser::parse("srcuri://clone-org/github.com/my-org").unwrap();
+        assert_eq!(request.validate(), Ok(()));
+    }
+
+    // percent-encoding tests
+
+    #[test]
+    fn test_percent_encoded_space_in_path() {
+        let request = SrcuriParser::parse("srcuri://my-repo/a%20file.rs").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::WorkspacePath {
+                workspace: "my-repo".to_string(),
+                path: "a file.rs".to_string(),
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+                remote: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_percent_encoded_hash_and_question_mark_in_filename() {
+        // A literal '#' or '?' in a filename must be escaped to survive the
+        // fragment/query split, or it would be mistaken for a delimiter.
+        let request = SrcuriParser::parse("srcuri://my-repo/weird%23name%3F.rs").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::WorkspacePath {
+                workspace: "my-repo".to_string(),
+                path: "weird#name?.rs".to_string(),
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+                remote: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_percent_encoded_colon_not_mistaken_for_location_separator() {
+        // The filename itself contains a literal ':' (escaped), which must
+        // not be confused with the real `:line:col` suffix that follows.
+        let request = SrcuriParser::parse("srcuri://my-repo/odd%3Aname.rs:10").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::WorkspacePath {
+                workspace: "my-repo".to_string(),
+                path: "odd:name.rs".to_string(),
+                line: Some(10),
+                column: None,
+                end_line: None,
+                end_column: None,
+                remote: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_percent_encoded_remote_and_git_ref_query_values() {
+        let request =
+            SrcuriParser::parse("srcuri://my-repo/src/main.rs?branch=feature%2Ffoo%23bar&remote=up%20stream")
+                .unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::RevisionPath {
+                workspace: "my-repo".to_string(),
+                path: "src/main.rs".to_string(),
+                git_ref: GitRef::Branch("feature/foo#bar".to_string()),
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+                remote: Some("up stream".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_plus_decodes_to_space_in_query_value_only() {
+        let request =
+            SrcuriParser::parse("srcuri://my-repo/src/main+file.rs?remote=up+stream").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::WorkspacePath {
+                workspace: "my-repo".to_string(),
+                path: "src/main+file.rs".to_string(),
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+                remote: Some("up stream".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_percent_encoded_workspace_override_round_trips() {
+        let request = SrcuriParser::parse(
+            "srcuri://github.com/owner/repo/blob/main/file.rs?workspace=my%2Erepo",
+        )
+        .unwrap();
+        let SrcuriRequest::ProviderPassthrough { workspace_override, .. } = request else {
+            panic!("expected ProviderPassthrough");
+        };
+        assert_eq!(workspace_override, Some("my.repo".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_percent_escape_in_path_is_rejected() {
+        let result = SrcuriParser::parse("srcuri://my-repo/broken%2gname.rs");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_truncated_percent_escape_at_end_of_path_is_rejected() {
+        let result = SrcuriParser::parse("srcuri://my-repo/broken%2");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_percent_escape_in_query_value_is_rejected() {
+        let result = SrcuriParser::parse("srcuri://my-repo/file.rs?remote=bad%zzvalue");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_version_query_param_parses_into_git_ref_version() {
+        let request = SrcuriParser::parse("srcuri://my-repo/src/main.rs?version=1.2").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::RevisionPath {
+                workspace: "my-repo".to_string(),
+                path: "src/main.rs".to_string(),
+                git_ref: GitRef::Version(PartialVersion {
+                    major: 1,
+                    minor: Some(2),
+                    patch: None,
+                    pre: None,
+                }),
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+                remote: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_version_query_param_is_rejected() {
+        let result = SrcuriParser::parse("srcuri://my-repo/src/main.rs?version=not-a-version");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_combining_tag_and_branch_params_is_rejected() {
+        let result = SrcuriParser::parse("srcuri://my-repo/src/main.rs?tag=v1&branch=main");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_combining_version_and_commit_params_is_rejected() {
+        let result =
+            SrcuriParser::parse("srcuri://my-repo/src/main.rs?version=1.2&commit=abc1234");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_partial_version_parse_variants() {
+        assert_eq!(
+            PartialVersion::parse("1"),
+            Some(PartialVersion { major: 1, minor: None, patch: None, pre: None })
+        );
+        assert_eq!(
+            PartialVersion::parse("1.2"),
+            Some(PartialVersion { major: 1, minor: Some(2), patch: None, pre: None })
+        );
+        assert_eq!(
+            PartialVersion::parse("1.2.3"),
+            Some(PartialVersion { major: 1, minor: Some(2), patch: Some(3), pre: None })
+        );
+        assert_eq!(
+            PartialVersion::parse("1.2.3-beta.1"),
+            Some(PartialVersion {
+                major: 1,
+                minor: Some(2),
+                patch: Some(3),
+                pre: Some("beta.1".to_string()),
+            })
+        );
+        assert_eq!(PartialVersion::parse("1..3"), None);
+        assert_eq!(PartialVersion::parse("1.2.3.4"), None);
+        assert_eq!(PartialVersion::parse("abc"), None);
+    }
+
+    #[test]
+    fn test_partial_version_resolve_picks_highest_matching_tag() {
+        let spec = PartialVersion::parse("1.2").unwrap();
+        let tags = vec!["v1.2.0", "v1.2.5", "v1.3.0", "v1.2.5-rc1"];
+        assert_eq!(spec.resolve(tags), Some("v1.2.5"));
+    }
+
+    #[test]
+    fn test_partial_version_resolve_excludes_prereleases_unless_named() {
+        let spec = PartialVersion::parse("2").unwrap();
+        let tags = vec!["v2.0.0-rc1"];
+        assert_eq!(spec.resolve(tags), None);
+
+        let spec = PartialVersion::parse("2.0.0-rc1").unwrap();
+        let tags = vec!["v2.0.0-rc1", "v2.0.0"];
+        assert_eq!(spec.resolve(tags), Some("v2.0.0-rc1"));
+    }
+
+    #[test]
+    fn test_partial_version_resolve_returns_none_without_a_match() {
+        let spec = PartialVersion::parse("9.9").unwrap();
+        let tags = vec!["v1.0.0", "v2.0.0"];
+        assert_eq!(spec.resolve(tags), None);
+    }
+
+    #[test]
+    fn test_git_ref_version_display_round_trips_through_query_param() {
+        let git_ref = GitRef::Version(PartialVersion {
+            major: 1,
+            minor: Some(2),
+            patch: None,
+            pre: None,
+        });
+        assert_eq!(git_ref.display_ref(), "1.2");
+    }
+
+    // clone-org tests
+
+    #[test]
+    fn test_clone_org_simple() {
+        let request = SrcuriParser::parse("srcuri://clone-org/github.com/my-org").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::CloneOrg {
+                host: "github.com".to_string(),
+                owner: "my-org".to_string(),
+                include_forks: false,
+                include_archived: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_clone_org_with_filters() {
+        let request = SrcuriParser::parse(
+            "srcuri://clone-org/github.com/my-org?include_forks=true&include_archived=1",
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::CloneOrg {
+                host: "github.com".to_string(),
+                owner: "my-org".to_string(),
+                include_forks: true,
+                include_archived: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_clone_org_self_hosted() {
+        let request =
+            SrcuriParser::parse("srcuri://clone-org/git.mycompany.com/engineering").unwrap();
+        assert_eq!(
+            request,
+            SrcuriRequest::CloneOrg {
+                host: "git.mycompany.com".to_string(),
+                owner: "engineering".to_string(),
+                include_forks: false,
+                include_archived: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_clone_org_missing_owner_fails() {
+        let result = SrcuriParser::parse("srcuri://clone-org/github.com");
+        assert!(result.is_err());
+    }
+
+    // Display / round-trip tests
+
+    /// Parses `url`, serializes it back via `Display`, re-parses that, and
+    /// asserts the two parsed values agree - parse -> serialize -> parse
+    /// stability, rather than an exact string match, since the serialized
+    /// form is free to differ cosmetically (e.g. percent-encoding) as long
+    /// as it parses back to the same request.
+    fn assert_round_trips(url: &str) {
+        let parsed = SrcuriParser::parse(url).unwrap();
+        let serialized = parsed.to_string();
+        let reparsed = SrcuriParser::parse(&serialized)
+            .unwrap_or_else(|e| panic!("{:?} did not re-parse ({}): {}", serialized, url, e));
+        assert_eq!(parsed, reparsed, "{:?} -> {:?}", url, serialized);
+    }
+
+    #[test]
+    fn round_trip_partial_path() {
+        assert_round_trips("srcuri://src/main.rs");
+    }
+
+    #[test]
+    fn round_trip_partial_path_with_line_and_column() {
+        assert_round_trips("srcuri://src/main.rs:42:7");
+    }
+
+    #[test]
+    fn round_trip_workspace_path() {
+        assert_round_trips("srcuri://my-repo/src/main.rs:42");
+    }
+
+    #[test]
+    fn round_trip_workspace_path_with_remote() {
+        assert_round_trips("srcuri://my-repo/src/main.rs?remote=origin");
+    }
+
+    #[test]
+    fn round_trip_full_path() {
+        assert_round_trips("srcuri:///Users/dev/scratch/notes.md:10");
+    }
+
+    #[test]
+    fn round_trip_revision_path_with_each_git_ref_kind() {
+        assert_round_trips("srcuri://my-repo/src/main.rs?commit=abc1234");
+        assert_round_trips("srcuri://my-repo/src/main.rs?branch=feature/foo");
+        assert_round_trips("srcuri://my-repo/src/main.rs?tag=v1.0.0");
+        assert_round_trips("srcuri://my-repo/src/main.rs?jj=abc123");
+    }
+
+    #[test]
+    fn round_trip_revision_path_with_remote() {
+        assert_round_trips("srcuri://my-repo/src/main.rs?branch=main&remote=upstream");
+    }
+
+    #[test]
+    fn round_trip_provider_passthrough_github() {
+        assert_round_trips("srcuri://github.com/owner/repo/blob/main/src/lib.rs#L10-L20");
+    }
+
+    #[test]
+    fn round_trip_provider_passthrough_azure_devops() {
+        assert_round_trips(
+            "srcuri://dev.azure.com/org/project/_git/repo?path=/src/index.ts&version=GBmain",
+        );
+    }
+
+    #[test]
+    fn round_trip_clone_org_without_filters() {
+        assert_round_trips("srcuri://clone-org/github.com/my-org");
+    }
+
+    #[test]
+    fn round_trip_clone_org_with_filters() {
+        assert_round_trips(
+            "srcuri://clone-org/github.com/my-org?include_forks=true&include_archived=true",
+        );
+    }
+
+    #[test]
+    fn round_trip_path_with_space_percent_encodes() {
+        assert_round_trips("srcuri://my repo/a file.rs");
+    }
+
+    #[test]
+    fn builder_produces_a_revision_path() {
+        let request = SrcuriBuilder::workspace("my-repo")
+            .path("src/main.rs")
+            .line(42)
+            .git_ref(GitRef::Branch("main".to_string()))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request,
+            SrcuriRequest::RevisionPath {
+                workspace: "my-repo".to_string(),
+                path: "src/main.rs".to_string(),
+                git_ref: GitRef::Branch("main".to_string()),
+                line: Some(42),
+                column: None,
+                end_line: None,
+                end_column: None,
+                remote: None,
+            }
+        );
+        assert_round_trips(&request.to_string());
+    }
+
+    #[test]
+    fn builder_produces_a_full_path() {
+        let request = SrcuriBuilder::full_path("/Users/dev/scratch/notes.md")
+            .line(10)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request,
+            SrcuriRequest::FullPath {
+                full_path: "/Users/dev/scratch/notes.md".to_string(),
+                line: Some(10),
+                column: None,
+                end_line: None,
+                end_column: None,
+            }
+        );
+    }
+
+    #[test]
+    fn builder_requires_a_path() {
+        let result = SrcuriBuilder::new().build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn builder_rejects_git_ref_without_workspace() {
+        let result = SrcuriBuilder::new()
+            .path("src/main.rs")
+            .git_ref(GitRef::Branch("main".to_string()))
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn builder_rejects_git_ref_with_full_path() {
+        let result = SrcuriBuilder::full_path("/Users/dev/notes.md")
+            .git_ref(GitRef::Branch("main".to_string()))
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_display_parse_round_trips_for_every_variant() {
+        let fixtures = [
+            "srcuri://README.md",
+            "srcuri://README.md:10",
+            "srcuri://README.md:10:5",
+            "srcuri://my-repo/src/main.rs",
+            "srcuri://my-repo/src/main.rs:10:5-20:8",
+            "srcuri://my-repo/src/main.rs?remote=upstream",
+            "srcuri:///Users/dev/scratch/notes.md",
+            "srcuri:///Users/ebeland/file.txt:10:5",
+            "srcuri://my-repo/src/main.rs?branch=main",
+            "srcuri://my-repo/src/main.rs?tag=v1.2.3",
+            "srcuri://my-repo/src/main.rs?commit=abc1234",
+            "srcuri://my-repo/src/main.rs?jj=%40-",
+            "srcuri://my-repo/src/main.rs?version=1.2",
+            "srcuri://my-repo/src/main.rs?branch=main&remote=upstream",
+            "srcuri://github.com/owner/repo/blob/main/file.rs#L10-L20",
+            "srcuri://clone-org/github.com/my-org",
+            "srcuri://clone-org/github.com/my-org?include_forks=true&include_archived=true",
+        ];
+
+        for fixture in fixtures {
+            let parsed = SrcuriParser::parse(fixture).unwrap();
+            let canonical = parsed.to_canonical_string();
+            let reparsed = SrcuriParser::parse(&canonical).unwrap_or_else(|e| {
+                panic!("canonical form {:?} of {:?} failed to reparse: {}", canonical, fixture, e)
+            });
+            assert_eq!(
+                parsed, reparsed,
+                "round-trip mismatch for {:?} (canonical: {:?})",
+                fixture, canonical
+            );
+        }
+    }
 }
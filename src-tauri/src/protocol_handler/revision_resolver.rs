@@ -0,0 +1,285 @@
+use super::git::GitError;
+use super::{GitRef, PartialVersion};
+use std::path::Path;
+
+/// A blob resolved from a [`GitRef`] by [`RevisionResolver::resolve`], with
+/// the original cursor position passed through unchanged so a caller can
+/// jump straight to it once the blob is open - this doesn't check anything
+/// out, it just reads the object straight out of the object database.
+#[derive(Debug, Clone)]
+pub struct ResolvedBlob {
+    pub commit_oid: String,
+    pub content: Vec<u8>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+/// Resolves a parsed `RevisionPath`/`WorkspacePath` (workspace root, file
+/// path, `GitRef`) straight to blob bytes through libgit2, rather than
+/// `GitHandler`'s usual `git` subprocess calls - useful for a caller (e.g. a
+/// preview pane) that wants to read a file at a revision without checking it
+/// out. Static methods only, like [`super::GitHandler`] and
+/// [`super::SrcuriParser`]: this has no state of its own beyond the
+/// repository it's asked to open.
+pub struct RevisionResolver;
+
+impl RevisionResolver {
+    /// Resolves `git_ref` against the repository at `workspace_path` and
+    /// reads `path`'s blob contents at that revision. This is blocking,
+    /// libgit2 I/O - a caller on an async executor should run it inside
+    /// `spawn_blocking`, the same way `GitHandler`'s read-only lookups do in
+    /// `ProtocolHandler::handle_revision_path`.
+    ///
+    /// `GitRef::Commit` resolves via `revparse_single`; `GitRef::Branch`
+    /// resolves local-first, then against `origin/<name>`; `GitRef::Tag` and
+    /// `GitRef::Version` (resolved against the repo's tag list via
+    /// [`PartialVersion::resolve`]) go through the tag list. `GitRef::Jj`
+    /// isn't a libgit2-resolvable rev - resolve it to a `GitRef::Commit` via
+    /// `GitHandler::resolve_jj_revision` first and pass that in instead.
+    ///
+    /// When `remote` names a remote the request could fetch from and the
+    /// ref isn't found in any ref already in the local repository, returns
+    /// [`GitError::NeedsFetch`] rather than [`GitError::RevisionNotFound`],
+    /// so a caller can offer to fetch instead of reporting a flat miss.
+    pub fn resolve(
+        workspace_path: &Path,
+        path: &str,
+        git_ref: &GitRef,
+        remote: Option<&str>,
+        line: Option<usize>,
+        column: Option<usize>,
+    ) -> Result<ResolvedBlob, GitError> {
+        let repo = git2::Repository::discover(workspace_path)
+            .map_err(|_| GitError::NotARepository(workspace_path.to_path_buf()))?;
+
+        let commit = match git_ref {
+            GitRef::Commit(rev) => Self::resolve_commit(&repo, rev)?,
+            GitRef::Branch(name) => Self::resolve_branch(&repo, name, remote)?,
+            GitRef::Tag(name) => Self::resolve_tag(&repo, name, remote)?,
+            GitRef::Version(spec) => Self::resolve_version(&repo, spec, remote)?,
+            GitRef::Jj(revset) => {
+                return Err(GitError::Other(anyhow::anyhow!(
+                    "jj revision '{}' must be resolved to a commit before calling RevisionResolver::resolve",
+                    revset
+                )));
+            }
+        };
+
+        let tree = commit.tree().map_err(|e| GitError::Other(e.into()))?;
+        let not_found = || GitError::FileNotFoundAtRevision {
+            file_path: path.to_string(),
+            rev: commit.id().to_string(),
+        };
+
+        let entry = tree.get_path(Path::new(path)).map_err(|_| not_found())?;
+        if entry.kind() != Some(git2::ObjectType::Blob) {
+            return Err(not_found());
+        }
+
+        let blob = entry
+            .to_object(&repo)
+            .and_then(|object| object.peel_to_blob())
+            .map_err(|e| GitError::Other(e.into()))?;
+
+        Ok(ResolvedBlob {
+            commit_oid: commit.id().to_string(),
+            content: blob.content().to_vec(),
+            line,
+            column,
+        })
+    }
+
+    fn resolve_commit<'repo>(
+        repo: &'repo git2::Repository,
+        rev: &str,
+    ) -> Result<git2::Commit<'repo>, GitError> {
+        repo.revparse_single(rev)
+            .map_err(|_| GitError::RevisionNotFound(rev.to_string()))?
+            .peel_to_commit()
+            .map_err(|e| GitError::Other(e.into()))
+    }
+
+    fn resolve_branch<'repo>(
+        repo: &'repo git2::Repository,
+        name: &str,
+        remote: Option<&str>,
+    ) -> Result<git2::Commit<'repo>, GitError> {
+        if let Ok(branch) = repo.find_branch(name, git2::BranchType::Local) {
+            return branch.get().peel_to_commit().map_err(|e| GitError::Other(e.into()));
+        }
+
+        let tracking_name = format!("origin/{}", name);
+        if let Ok(branch) = repo.find_branch(&tracking_name, git2::BranchType::Remote) {
+            return branch.get().peel_to_commit().map_err(|e| GitError::Other(e.into()));
+        }
+
+        Err(Self::not_found_or_needs_fetch(name, remote))
+    }
+
+    fn resolve_tag<'repo>(
+        repo: &'repo git2::Repository,
+        name: &str,
+        remote: Option<&str>,
+    ) -> Result<git2::Commit<'repo>, GitError> {
+        repo.find_reference(&format!("refs/tags/{}", name))
+            .map_err(|_| Self::not_found_or_needs_fetch(name, remote))?
+            .peel_to_commit()
+            .map_err(|e| GitError::Other(e.into()))
+    }
+
+    fn resolve_version<'repo>(
+        repo: &'repo git2::Repository,
+        spec: &PartialVersion,
+        remote: Option<&str>,
+    ) -> Result<git2::Commit<'repo>, GitError> {
+        let tag_names = repo.tag_names(None).map_err(|e| GitError::Other(e.into()))?;
+        let tags = tag_names.iter().flatten();
+        let resolved = spec
+            .resolve(tags)
+            .ok_or_else(|| Self::not_found_or_needs_fetch(&spec.to_string(), remote))?
+            .to_string();
+
+        Self::resolve_tag(repo, &resolved, remote)
+    }
+
+    /// `GitError::NeedsFetch` if the request named a `remote` the caller
+    /// could fetch from, else a plain `GitError::RevisionNotFound` - there's
+    /// nowhere to suggest fetching from otherwise.
+    fn not_found_or_needs_fetch(rev: &str, remote: Option<&str>) -> GitError {
+        match remote {
+            Some(remote) => GitError::NeedsFetch {
+                rev: rev.to_string(),
+                remote: remote.to_string(),
+            },
+            None => GitError::RevisionNotFound(rev.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn run(cmd: &mut Command, dir: &Path) {
+        let status = cmd.current_dir(dir).status().expect("status");
+        assert!(status.success(), "command failed");
+    }
+
+    fn capture(cmd: &mut Command, dir: &Path) -> String {
+        let output = cmd.current_dir(dir).output().expect("output");
+        assert!(output.status.success(), "command failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn init_repo() -> (TempDir, String) {
+        let temp = TempDir::new().expect("temp dir");
+        let work = temp.path();
+        run(Command::new("git").arg("init"), work);
+        run(Command::new("git").args(["branch", "-M", "main"]), work);
+        std::fs::write(work.join("a.txt"), "hello\n").unwrap();
+        run(Command::new("git").args(["add", "."]), work);
+        run(Command::new("git").args(["commit", "-m", "init"]), work);
+        run(Command::new("git").args(["tag", "v1.2.0"]), work);
+
+        std::fs::write(work.join("a.txt"), "hello again\n").unwrap();
+        run(Command::new("git").args(["commit", "-am", "update"]), work);
+        let head = capture(Command::new("git").args(["rev-parse", "HEAD"]), work);
+
+        (temp, head)
+    }
+
+    #[test]
+    fn resolves_a_bare_commit() {
+        let (temp, head) = init_repo();
+        let blob = RevisionResolver::resolve(
+            temp.path(),
+            "a.txt",
+            &GitRef::Commit(head.clone()),
+            None,
+            Some(1),
+            None,
+        )
+        .expect("resolve");
+
+        assert_eq!(blob.commit_oid, head);
+        assert_eq!(blob.content, b"hello again\n");
+        assert_eq!(blob.line, Some(1));
+    }
+
+    #[test]
+    fn resolves_a_local_branch() {
+        let (temp, head) = init_repo();
+        let blob =
+            RevisionResolver::resolve(temp.path(), "a.txt", &GitRef::Branch("main".to_string()), None, None, None)
+                .expect("resolve");
+        assert_eq!(blob.commit_oid, head);
+    }
+
+    #[test]
+    fn resolves_a_tag() {
+        let (temp, _head) = init_repo();
+        let blob = RevisionResolver::resolve(
+            temp.path(),
+            "a.txt",
+            &GitRef::Tag("v1.2.0".to_string()),
+            None,
+            None,
+            None,
+        )
+        .expect("resolve");
+        assert_eq!(blob.content, b"hello\n");
+    }
+
+    #[test]
+    fn resolves_a_partial_version_against_the_tag_list() {
+        let (temp, _head) = init_repo();
+        let git_ref = GitRef::Version(PartialVersion::parse("1.2").unwrap());
+        let blob = RevisionResolver::resolve(temp.path(), "a.txt", &git_ref, None, None, None)
+            .expect("resolve");
+        assert_eq!(blob.content, b"hello\n");
+    }
+
+    #[test]
+    fn missing_branch_without_a_remote_is_revision_not_found() {
+        let (temp, _head) = init_repo();
+        let result = RevisionResolver::resolve(
+            temp.path(),
+            "a.txt",
+            &GitRef::Branch("does-not-exist".to_string()),
+            None,
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(GitError::RevisionNotFound(_))));
+    }
+
+    #[test]
+    fn missing_branch_with_a_remote_needs_fetch() {
+        let (temp, _head) = init_repo();
+        let result = RevisionResolver::resolve(
+            temp.path(),
+            "a.txt",
+            &GitRef::Branch("does-not-exist".to_string()),
+            Some("https://example.com/owner/repo"),
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(GitError::NeedsFetch { .. })));
+    }
+
+    #[test]
+    fn missing_file_at_revision_is_reported_distinctly() {
+        let (temp, head) = init_repo();
+        let result = RevisionResolver::resolve(
+            temp.path(),
+            "does-not-exist.txt",
+            &GitRef::Commit(head),
+            None,
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(GitError::FileNotFoundAtRevision { .. })));
+    }
+}
@@ -0,0 +1,351 @@
+use ignore::WalkBuilder;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// How long a built index is trusted before a lookup forces a rebuild.
+const INDEX_TTL: Duration = Duration::from_secs(30);
+
+/// Upper bound on how many files `resolve_fuzzy` will score per workspace -
+/// Levenshtein is O(mn) per candidate, so an unbounded scan of a huge
+/// monorepo would make a single typo'd `srcuri://` link noticeably slow.
+const MAX_FUZZY_CANDIDATES: usize = 5_000;
+
+struct IndexedWorkspace {
+    /// Bare file name (e.g. `main.rs`) -> every absolute path with that name.
+    by_basename: HashMap<String, Vec<PathBuf>>,
+    /// Every `/`-joined trailing slice of a file's workspace-relative path
+    /// (e.g. for `src/app/main.rs`: `main.rs`, `app/main.rs`, `src/app/main.rs`)
+    /// -> the absolute paths it resolves to.
+    by_suffix: HashMap<String, Vec<PathBuf>>,
+    /// Full workspace-relative path of every indexed file, up to
+    /// `MAX_FUZZY_CANDIDATES` - the candidate pool `resolve_fuzzy` scores
+    /// against when an exact `by_suffix`/`by_basename` lookup misses.
+    relative_paths: Vec<String>,
+    built_at: Instant,
+}
+
+/// Per-workspace file index built with a single `.gitignore`-aware directory
+/// walk (via the `ignore` crate, which skips `.git`/other hidden dirs and
+/// honors `.gitignore`/`.ignore` the same way `fs_signal` does), so a
+/// partial-path `srcuri://` open resolves against a precomputed map instead
+/// of re-walking the tree on every lookup and instead of only matching files
+/// that happen to sit directly under the workspace root.
+///
+/// An entry is rebuilt lazily the first time it's looked up after
+/// `INDEX_TTL` has elapsed, so files created since the last build become
+/// resolvable without an explicit signal; `invalidate`/`invalidate_all` let
+/// a caller with an actual filesystem-change signal force that sooner.
+pub struct WorkspaceIndex {
+    workspaces: RwLock<HashMap<PathBuf, IndexedWorkspace>>,
+}
+
+impl WorkspaceIndex {
+    pub fn new() -> Self {
+        Self {
+            workspaces: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Forces the next lookup against `workspace_root` to rebuild.
+    pub fn invalidate(&self, workspace_root: &Path) {
+        self.workspaces.write().remove(workspace_root);
+    }
+
+    /// Forces every workspace's next lookup to rebuild, e.g. after settings
+    /// are reloaded and workspace roots may have changed.
+    pub fn invalidate_all(&self) {
+        self.workspaces.write().clear();
+    }
+
+    /// Resolves `partial_path` (a bare file name or a `/`-joined relative
+    /// suffix) against `workspace_root`, rebuilding the index first if it's
+    /// missing or stale. Returns every absolute path that matches, so the
+    /// caller can tell a unique hit from an ambiguous one.
+    pub fn resolve(&self, workspace_root: &Path, partial_path: &str) -> Vec<PathBuf> {
+        self.ensure_fresh(workspace_root);
+
+        let workspaces = self.workspaces.read();
+        let Some(index) = workspaces.get(workspace_root) else {
+            return Vec::new();
+        };
+
+        if let Some(matches) = index.by_suffix.get(partial_path) {
+            return matches.clone();
+        }
+
+        index
+            .by_basename
+            .get(partial_path)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Fuzzy fallback for when [`resolve`] finds nothing: scores every
+    /// indexed file's workspace-relative path against `partial_path` by
+    /// Levenshtein distance and returns the ones within a threshold
+    /// proportional to the query's length, closest first. A typo or a
+    /// slightly-wrong relative path still resolves to something instead of
+    /// an empty chooser.
+    pub fn resolve_fuzzy(&self, workspace_root: &Path, partial_path: &str) -> Vec<(PathBuf, usize)> {
+        self.ensure_fresh(workspace_root);
+
+        let workspaces = self.workspaces.read();
+        let Some(index) = workspaces.get(workspace_root) else {
+            return Vec::new();
+        };
+
+        // A query of length 1-2 tolerates a single edit; longer queries
+        // tolerate proportionally more so a long path with one typo'd
+        // component still clears the bar.
+        let threshold = (partial_path.chars().count() / 3).max(1);
+
+        let mut scored: Vec<(PathBuf, usize)> = index
+            .relative_paths
+            .iter()
+            .filter_map(|relative| {
+                let distance = levenshtein_distance(partial_path, relative);
+                if distance <= threshold {
+                    Some((workspace_root.join(relative), distance))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        scored.sort_by_key(|(_, distance)| *distance);
+        scored
+    }
+
+    fn ensure_fresh(&self, workspace_root: &Path) {
+        {
+            let workspaces = self.workspaces.read();
+            if let Some(index) = workspaces.get(workspace_root) {
+                if index.built_at.elapsed() < INDEX_TTL {
+                    return;
+                }
+            }
+        }
+
+        let index = Self::build(workspace_root);
+        self.workspaces
+            .write()
+            .insert(workspace_root.to_path_buf(), index);
+    }
+
+    fn build(workspace_root: &Path) -> IndexedWorkspace {
+        let mut by_basename: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        let mut by_suffix: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        let mut relative_paths: Vec<String> = Vec::new();
+        let mut file_count = 0usize;
+
+        for result in WalkBuilder::new(workspace_root).build() {
+            let entry = match result {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warn!(
+                        "Skipping unreadable entry while indexing {}: {}",
+                        workspace_root.display(),
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            if entry.path() == workspace_root {
+                continue;
+            }
+            if entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false) {
+                continue;
+            }
+
+            let Ok(relative) = entry.path().strip_prefix(workspace_root) else {
+                continue;
+            };
+            let Some(basename) = entry.path().file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+
+            by_basename
+                .entry(basename.to_string())
+                .or_default()
+                .push(entry.path().to_path_buf());
+
+            let components: Vec<&str> = relative
+                .components()
+                .filter_map(|c| c.as_os_str().to_str())
+                .collect();
+            for start in 0..components.len() {
+                let suffix = components[start..].join("/");
+                by_suffix
+                    .entry(suffix)
+                    .or_default()
+                    .push(entry.path().to_path_buf());
+            }
+
+            if relative_paths.len() < MAX_FUZZY_CANDIDATES {
+                relative_paths.push(components.join("/"));
+            }
+
+            file_count += 1;
+        }
+
+        debug!(
+            "Indexed {} files under {}",
+            file_count,
+            workspace_root.display()
+        );
+
+        IndexedWorkspace {
+            by_basename,
+            by_suffix,
+            relative_paths,
+            built_at: Instant::now(),
+        }
+    }
+}
+
+/// Classic edit-distance DP over a single rolling row of `usize`, with
+/// insertion/deletion/substitution all costing 1. `resolve_fuzzy`'s only
+/// caller - kept free rather than a method since it doesn't touch
+/// `WorkspaceIndex` state.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev_diag = row[0];
+        row[0] = i + 1;
+
+        for (j, &cb) in b_chars.iter().enumerate() {
+            let temp = row[j + 1];
+            row[j + 1] = if ca == cb {
+                prev_diag
+            } else {
+                1 + prev_diag.min(row[j]).min(row[j + 1])
+            };
+            prev_diag = temp;
+        }
+    }
+
+    row[b_chars.len()]
+}
+
+impl Default for WorkspaceIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn resolves_unique_basename_at_any_depth() {
+        let dir = TempDir::new().expect("tempdir");
+        fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        fs::write(dir.path().join("src/nested/main.rs"), "fn main() {}").unwrap();
+
+        let index = WorkspaceIndex::new();
+        let matches = index.resolve(dir.path(), "main.rs");
+
+        assert_eq!(matches, vec![dir.path().join("src/nested/main.rs")]);
+    }
+
+    #[test]
+    fn resolves_relative_suffix() {
+        let dir = TempDir::new().expect("tempdir");
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/main.rs"), "fn main() {}").unwrap();
+
+        let index = WorkspaceIndex::new();
+        let matches = index.resolve(dir.path(), "src/main.rs");
+
+        assert_eq!(matches, vec![dir.path().join("src/main.rs")]);
+    }
+
+    #[test]
+    fn reports_every_candidate_for_ambiguous_basename() {
+        let dir = TempDir::new().expect("tempdir");
+        fs::create_dir_all(dir.path().join("a")).unwrap();
+        fs::create_dir_all(dir.path().join("b")).unwrap();
+        fs::write(dir.path().join("a/main.rs"), "fn main() {}").unwrap();
+        fs::write(dir.path().join("b/main.rs"), "fn main() {}").unwrap();
+
+        let index = WorkspaceIndex::new();
+        let mut matches = index.resolve(dir.path(), "main.rs");
+        matches.sort();
+
+        let mut expected = vec![dir.path().join("a/main.rs"), dir.path().join("b/main.rs")];
+        expected.sort();
+
+        assert_eq!(matches, expected);
+    }
+
+    #[test]
+    fn skips_gitignored_files() {
+        let dir = TempDir::new().expect("tempdir");
+        fs::write(dir.path().join(".gitignore"), "ignored.rs\n").unwrap();
+        fs::write(dir.path().join("ignored.rs"), "fn main() {}").unwrap();
+        fs::write(dir.path().join("kept.rs"), "fn main() {}").unwrap();
+
+        let index = WorkspaceIndex::new();
+
+        assert!(index.resolve(dir.path(), "ignored.rs").is_empty());
+        assert_eq!(
+            index.resolve(dir.path(), "kept.rs"),
+            vec![dir.path().join("kept.rs")]
+        );
+    }
+
+    #[test]
+    fn invalidate_forces_rebuild() {
+        let dir = TempDir::new().expect("tempdir");
+        let index = WorkspaceIndex::new();
+
+        assert!(index.resolve(dir.path(), "late.rs").is_empty());
+
+        fs::write(dir.path().join("late.rs"), "fn main() {}").unwrap();
+        index.invalidate(dir.path());
+
+        assert_eq!(
+            index.resolve(dir.path(), "late.rs"),
+            vec![dir.path().join("late.rs")]
+        );
+    }
+
+    #[test]
+    fn levenshtein_distance_counts_single_edits() {
+        assert_eq!(levenshtein_distance("main.rs", "main.rs"), 0);
+        assert_eq!(levenshtein_distance("main.rs", "mian.rs"), 2);
+        assert_eq!(levenshtein_distance("src/main.rs", "src/mian.rs"), 2);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn resolve_fuzzy_finds_a_typo_within_threshold() {
+        let dir = TempDir::new().expect("tempdir");
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/main.rs"), "fn main() {}").unwrap();
+
+        let index = WorkspaceIndex::new();
+        assert!(index.resolve(dir.path(), "src/mian.rs").is_empty());
+
+        let matches = index.resolve_fuzzy(dir.path(), "src/mian.rs");
+        assert_eq!(matches, vec![(dir.path().join("src/main.rs"), 2)]);
+    }
+
+    #[test]
+    fn resolve_fuzzy_drops_candidates_past_the_threshold() {
+        let dir = TempDir::new().expect("tempdir");
+        fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
+
+        let index = WorkspaceIndex::new();
+        assert!(index.resolve_fuzzy(dir.path(), "completely_unrelated_name.txt").is_empty());
+    }
+}
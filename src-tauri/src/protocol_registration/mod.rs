@@ -14,6 +14,177 @@ pub struct ProtocolRegistrationStatus {
     pub details: String,
 }
 
+/// How Sorcery Desktop is packaged on this Linux install, detected from the
+/// environment AppImage/Flatpak/Snap each set up before launching the
+/// contained binary. Determines the `Exec=` target `create_desktop_file`
+/// must write, since `current_exe()` resolves to a transient mount point or
+/// sandboxed path for any of the three rather than something a `.desktop`
+/// file can reliably re-launch later.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LinuxPackaging {
+    /// `$APPIMAGE` - the stable path to the `.AppImage` file itself, as
+    /// opposed to `current_exe()`'s `/tmp/.mount_XXXX/...` FUSE mount,
+    /// which is a fresh temp directory every launch.
+    AppImage(String),
+    /// `/.flatpak-info` exists or `$FLATPAK_ID` is set - the app ID, used to
+    /// relaunch via `flatpak run <id>` since the sandboxed binary path isn't
+    /// reachable from outside the sandbox at all.
+    Flatpak(String),
+    /// `$SNAP` is set - the snap name, used to relaunch via the
+    /// `/snap/bin/<name>` wrapper snapd always keeps pointed at the
+    /// currently installed revision, rather than `$SNAP` itself (which is
+    /// revision-specific and changes on every update).
+    Snap(String),
+    Native,
+}
+
+#[cfg(target_os = "linux")]
+impl LinuxPackaging {
+    fn detect() -> Self {
+        if let Ok(appimage) = std::env::var("APPIMAGE") {
+            return LinuxPackaging::AppImage(appimage);
+        }
+        if let Some(app_id) = Self::flatpak_app_id() {
+            return LinuxPackaging::Flatpak(app_id);
+        }
+        if let Ok(snap_name) = std::env::var("SNAP_NAME") {
+            return LinuxPackaging::Snap(snap_name);
+        }
+        LinuxPackaging::Native
+    }
+
+    /// `$FLATPAK_ID` is the common case; `/.flatpak-info`'s `name=` field
+    /// under `[Application]` is the fallback for a sandbox that doesn't
+    /// export it.
+    fn flatpak_app_id() -> Option<String> {
+        if let Ok(id) = std::env::var("FLATPAK_ID") {
+            return Some(id);
+        }
+
+        let info = std::fs::read_to_string("/.flatpak-info").ok()?;
+        info.lines()
+            .find_map(|line| line.strip_prefix("name=").map(|s| s.trim().to_string()))
+    }
+
+    /// The `Exec=` target to write (or to compare a written one against) -
+    /// `current_exe` itself for a native install, since only a packaged one
+    /// has a registration-breaking transient path.
+    fn exec_target(&self, current_exe: &str) -> String {
+        match self {
+            LinuxPackaging::AppImage(path) => path.clone(),
+            LinuxPackaging::Flatpak(app_id) => format!("flatpak run {}", app_id),
+            LinuxPackaging::Snap(name) => format!("/snap/bin/{}", name),
+            LinuxPackaging::Native => current_exe.to_string(),
+        }
+    }
+
+    fn details_suffix(&self) -> Option<String> {
+        match self {
+            LinuxPackaging::AppImage(_) => Some("Packaging: AppImage".to_string()),
+            LinuxPackaging::Flatpak(app_id) => Some(format!("Packaging: Flatpak ({})", app_id)),
+            LinuxPackaging::Snap(name) => Some(format!("Packaging: Snap ({})", name)),
+            LinuxPackaging::Native => None,
+        }
+    }
+}
+
+/// Thin wrapper around the handful of LaunchServices C functions needed to
+/// register and query `srcuri://`'s default handler directly, instead of
+/// re-registering the whole app bundle with `lsregister -f` and guessing at
+/// the result by globbing `/Applications` and grepping `defaults read`
+/// output. These are C functions, not Objective-C methods, so they're linked
+/// straight against `CoreServices` rather than called via `objc`'s
+/// `msg_send!` (the pattern `tracker::detector`'s `get_frontmost_app_native`
+/// uses for actual Objective-C APIs) - `core-foundation`'s `CFString`/`CFURL`
+/// wrappers handle the `CFStringRef`/`CFURLRef` marshaling at the boundary.
+#[cfg(target_os = "macos")]
+mod launch_services {
+    use core_foundation::base::TCFType;
+    use core_foundation::string::{CFString, CFStringRef};
+    use core_foundation::url::{CFURL, CFURLRef};
+
+    #[link(name = "CoreServices", kind = "framework")]
+    extern "C" {
+        fn LSSetDefaultHandlerForURLScheme(
+            in_url_scheme: CFStringRef,
+            in_handler_bundle_id: CFStringRef,
+        ) -> i32;
+
+        fn LSCopyDefaultHandlerForURLScheme(in_url_scheme: CFStringRef) -> CFStringRef;
+
+        fn LSFindApplicationForInfo(
+            in_creator: u32,
+            in_bundle_id: CFStringRef,
+            in_name: CFStringRef,
+            out_app_ref: *mut std::ffi::c_void,
+            out_app_url: *mut CFURLRef,
+        ) -> i32;
+    }
+
+    const K_LSUNKNOWN_CREATOR: u32 = 0;
+
+    /// Claims `srcuri://` for `bundle_id` - deterministic, unlike re-running
+    /// `lsregister -f` and hoping the database picks up the right handler.
+    pub fn set_default_handler(bundle_id: &str) -> Result<(), i32> {
+        let scheme = CFString::new("srcuri");
+        let bundle_id = CFString::new(bundle_id);
+
+        let status = unsafe {
+            LSSetDefaultHandlerForURLScheme(
+                scheme.as_concrete_TypeRef(),
+                bundle_id.as_concrete_TypeRef(),
+            )
+        };
+
+        if status == 0 {
+            Ok(())
+        } else {
+            Err(status)
+        }
+    }
+
+    /// The bundle identifier currently registered as `srcuri://`'s default
+    /// handler, if any.
+    pub fn default_handler_bundle_id() -> Option<String> {
+        let scheme = CFString::new("srcuri");
+        let handler_ref = unsafe { LSCopyDefaultHandlerForURLScheme(scheme.as_concrete_TypeRef()) };
+
+        if handler_ref.is_null() {
+            return None;
+        }
+
+        // LSCopyDefaultHandlerForURLScheme's "Copy" naming means we own the
+        // returned reference, same as any other Core Foundation copy rule.
+        let handler = unsafe { CFString::wrap_under_create_rule(handler_ref) };
+        Some(handler.to_string())
+    }
+
+    /// Resolves a bundle identifier to the path of the `.app` bundle
+    /// LaunchServices has it registered against.
+    pub fn executable_path_for_bundle_id(bundle_id: &str) -> Option<String> {
+        let bundle_id = CFString::new(bundle_id);
+        let mut app_url: CFURLRef = std::ptr::null_mut();
+
+        let status = unsafe {
+            LSFindApplicationForInfo(
+                K_LSUNKNOWN_CREATOR,
+                bundle_id.as_concrete_TypeRef(),
+                std::ptr::null(),
+                std::ptr::null_mut(),
+                &mut app_url,
+            )
+        };
+
+        if status != 0 || app_url.is_null() {
+            return None;
+        }
+
+        let url = unsafe { CFURL::wrap_under_create_rule(app_url) };
+        url.to_path().map(|p| p.to_string_lossy().to_string())
+    }
+}
+
 /// Platform-specific protocol registration
 #[allow(dead_code)]
 pub struct ProtocolRegistration;
@@ -59,62 +230,304 @@ impl ProtocolRegistration {
         return Self::register_macos();
 
         #[cfg(target_os = "windows")]
-        anyhow::bail!("On Windows, protocol registration is handled by the installer. Please run the MSI installer.");
+        return Self::register_windows();
 
         #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
         anyhow::bail!("Protocol registration not supported on this platform.");
     }
 
-    #[cfg(target_os = "macos")]
-    fn register_macos() -> Result<()> {
-        use std::process::Command;
+    /// Fixes a registration left pointing at a stale executable - e.g. the
+    /// app moved or was reinstalled elsewhere after the `.desktop` file,
+    /// registry key, or LaunchServices entry was written. A no-op unless
+    /// `get_status` reports both `is_registered` and a mismatched
+    /// `executables_match`.
+    pub fn repair() -> Result<()> {
+        let status = Self::get_status();
+
+        if !status.is_registered || status.executables_match {
+            return Ok(());
+        }
+
+        tracing::info!(
+            "srcuri:// is registered to {:?} but the current executable is {} - repairing",
+            status.registered_executable,
+            status.current_executable
+        );
+
+        #[cfg(target_os = "linux")]
+        return Self::repair_linux();
+
+        #[cfg(target_os = "macos")]
+        return Self::register_macos();
+
+        #[cfg(target_os = "windows")]
+        return Self::repair_windows();
+
+        #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+        Ok(())
+    }
+
+    /// Removes the `.desktop` file / registry key / LaunchServices scheme
+    /// handler `register()` wrote, so uninstall and dev-cleanup flows don't
+    /// leave an orphaned deep-link handler pointing at a binary that's
+    /// about to disappear.
+    pub fn unregister() -> Result<()> {
+        #[cfg(target_os = "linux")]
+        return Self::unregister_linux();
+
+        #[cfg(target_os = "macos")]
+        return Self::unregister_macos();
+
+        #[cfg(target_os = "windows")]
+        return Self::unregister_windows();
+
+        #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+        Ok(())
+    }
+
+    /// Registers `srcuri://` under `HKEY_CURRENT_USER\Software\Classes`, the
+    /// way Enso and other self-contained Rust installers register a
+    /// protocol without admin rights - needed since portable/dev builds and
+    /// CI runs have no MSI to do it for them. A no-op if the MSI installer
+    /// already registered the handler system-wide under `HKEY_LOCAL_MACHINE`,
+    /// since that entry already covers every user on the machine.
+    #[cfg(target_os = "windows")]
+    fn register_windows() -> Result<()> {
+        if Self::registered_exe_hklm().is_some() {
+            tracing::info!(
+                "srcuri:// is already registered system-wide via HKEY_LOCAL_MACHINE (MSI install), skipping per-user registration"
+            );
+            return Ok(());
+        }
+
+        Self::write_hkcu_registration()
+    }
+
+    /// Fixes a `HKCU` registration pointing at a stale executable. Unlike
+    /// `register_windows`, this always rewrites `HKCU` even when `HKLM` has
+    /// an entry - if `HKLM` were the stale one, the MSI installer that wrote
+    /// it is the thing to rerun, and a per-user `HKCU` override is the only
+    /// repair this process can make on its own.
+    #[cfg(target_os = "windows")]
+    fn repair_windows() -> Result<()> {
+        Self::write_hkcu_registration()
+    }
+
+    /// Writes `srcuri://`'s command under `HKEY_CURRENT_USER\Software\Classes`,
+    /// shared by `register_windows` (the MSI-absent case) and `repair_windows`
+    /// (the stale-path case).
+    #[cfg(target_os = "windows")]
+    fn write_hkcu_registration() -> Result<()> {
+        use winreg::enums::HKEY_CURRENT_USER;
+        use winreg::RegKey;
 
         let current_exe = std::env::current_exe()?;
         let exe_str = current_exe.to_string_lossy();
 
-        // Find the .app bundle containing this executable
-        let app_bundle = if exe_str.contains(".app/Contents/MacOS/") {
-            let parts: Vec<&str> = exe_str.split(".app/Contents/MacOS/").collect();
-            if !parts.is_empty() {
-                format!("{}.app", parts[0])
-            } else {
-                anyhow::bail!(
-                    "Could not determine app bundle path from executable: {}",
-                    exe_str
-                );
+        tracing::info!("Registering srcuri:// protocol handler under HKCU\\Software\\Classes");
+
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let (srcuri_key, _) = hkcu.create_subkey("Software\\Classes\\srcuri")?;
+        srcuri_key.set_value("", &"URL:srcuri Protocol".to_string())?;
+        srcuri_key.set_value("URL Protocol", &String::new())?;
+
+        let (command_key, _) = srcuri_key.create_subkey("shell\\open\\command")?;
+        command_key.set_value("", &format!("\"{}\" \"%1\"", exe_str))?;
+
+        tracing::info!("Successfully registered srcuri:// protocol handler");
+        Ok(())
+    }
+
+    /// Deletes the `HKCU\Software\Classes\srcuri` tree `register_windows`
+    /// writes. Leaves any `HKLM` (MSI) registration alone, since that's the
+    /// installer's to remove, not this process's.
+    #[cfg(target_os = "windows")]
+    fn unregister_windows() -> Result<()> {
+        use winreg::enums::HKEY_CURRENT_USER;
+        use winreg::RegKey;
+
+        tracing::info!("Unregistering srcuri:// protocol handler from HKCU\\Software\\Classes");
+
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        match hkcu.delete_subkey_all("Software\\Classes\\srcuri") {
+            Ok(()) => {
+                tracing::info!("Successfully unregistered srcuri:// protocol handler");
+                Ok(())
             }
-        } else {
-            anyhow::bail!("Executable is not inside an app bundle: {}", exe_str);
-        };
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Pulls the executable path out of a `"<exe>" "%1"`-style command
+    /// string - shared by the `winreg`-backed readers (whose values include
+    /// the literal quotes as part of the stored string) and the `reg.exe`
+    /// text-output parser below.
+    #[cfg(target_os = "windows")]
+    fn exe_from_command_value(command: &str) -> String {
+        command
+            .trim()
+            .trim_start_matches('"')
+            .split('"')
+            .next()
+            .unwrap_or("")
+            .to_string()
+    }
+
+    /// The exe path `register_windows` (or an older per-user registration)
+    /// wrote under `HKEY_CURRENT_USER\Software\Classes\srcuri`, if any.
+    #[cfg(target_os = "windows")]
+    fn registered_exe_hkcu() -> Option<String> {
+        use winreg::enums::HKEY_CURRENT_USER;
+        use winreg::RegKey;
+
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let command_key =
+            hkcu.open_subkey("Software\\Classes\\srcuri\\shell\\open\\command").ok()?;
+        let command: String = command_key.get_value("").ok()?;
+        Some(Self::exe_from_command_value(&command))
+    }
+
+    /// The exe path registered system-wide under
+    /// `HKEY_LOCAL_MACHINE\Software\Classes\srcuri`, as written by the MSI
+    /// installer.
+    #[cfg(target_os = "windows")]
+    fn registered_exe_hklm() -> Option<String> {
+        use winreg::enums::HKEY_LOCAL_MACHINE;
+        use winreg::RegKey;
+
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let command_key =
+            hklm.open_subkey("Software\\Classes\\srcuri\\shell\\open\\command").ok()?;
+        let command: String = command_key.get_value("").ok()?;
+        Some(Self::exe_from_command_value(&command))
+    }
+
+    /// The exe path registered under the merged `HKEY_CLASSES_ROOT` view,
+    /// via `reg.exe` rather than `winreg` since `HKEY_CLASSES_ROOT` isn't a
+    /// real key either registration path wrote to directly - it's Windows's
+    /// read-only merge of `HKLM` and `HKCU`, so querying it is only useful
+    /// as a last-resort fallback for an entry neither `registered_exe_hkcu`
+    /// nor `registered_exe_hklm` found (e.g. written by some other tool).
+    #[cfg(target_os = "windows")]
+    fn registered_exe_hkcr() -> Option<String> {
+        use std::process::Command;
 
-        if !std::path::Path::new(&app_bundle).exists() {
-            anyhow::bail!("App bundle not found: {}", app_bundle);
+        let output = Command::new("reg")
+            .args([
+                "query",
+                "HKEY_CLASSES_ROOT\\srcuri\\shell\\open\\command",
+                "/ve",
+            ])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
         }
 
-        tracing::info!("Re-registering app bundle with lsregister: {}", app_bundle);
+        let reg_output = String::from_utf8_lossy(&output.stdout);
+        for line in reg_output.lines() {
+            if let Some((_, command)) = line.split_once("REG_SZ") {
+                return Some(Self::exe_from_command_value(command));
+            }
+        }
 
-        let status = Command::new("/System/Library/Frameworks/CoreServices.framework/Frameworks/LaunchServices.framework/Support/lsregister")
-            .args(["-f", &app_bundle])
-            .status()?;
+        None
+    }
 
-        if status.success() {
-            tracing::info!("Successfully re-registered protocol handler");
-            Ok(())
-        } else {
-            anyhow::bail!("lsregister failed with exit code: {:?}", status.code());
+    /// Claims `srcuri://` via `LSSetDefaultHandlerForURLScheme` instead of
+    /// re-running `lsregister -f` against the app bundle and hoping
+    /// LaunchServices' database picks the right handler back up.
+    #[cfg(target_os = "macos")]
+    fn register_macos() -> Result<()> {
+        let bundle_id = Self::main_bundle_identifier()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine this app's bundle identifier"))?;
+
+        tracing::info!(
+            "Registering srcuri:// as the default LaunchServices handler for {}",
+            bundle_id
+        );
+
+        launch_services::set_default_handler(&bundle_id).map_err(|status| {
+            anyhow::anyhow!(
+                "LSSetDefaultHandlerForURLScheme failed with status {}",
+                status
+            )
+        })?;
+
+        tracing::info!("Successfully registered srcuri:// protocol handler");
+        Ok(())
+    }
+
+    /// This process's bundle identifier, read via `NSBundle.mainBundle` the
+    /// same way `tracker::detector`'s `get_frontmost_app_native` reads
+    /// `NSWorkspace` state - `CFBundleGetMainBundle` is the Core Foundation
+    /// equivalent, but `NSBundle` is simpler to call given the repo already
+    /// links `objc`/`cocoa` for the same purpose elsewhere.
+    #[cfg(target_os = "macos")]
+    fn main_bundle_identifier() -> Option<String> {
+        use cocoa::base::nil;
+        use objc::runtime::{Class, Object};
+        use objc::{msg_send, sel, sel_impl};
+
+        unsafe {
+            let cls = Class::get("NSBundle")?;
+            let bundle: *mut Object = msg_send![cls, mainBundle];
+            if bundle.is_null() || bundle == nil as *mut Object {
+                return None;
+            }
+
+            let identifier: *mut Object = msg_send![bundle, bundleIdentifier];
+            if identifier.is_null() || identifier == nil as *mut Object {
+                return None;
+            }
+
+            let utf8: *const std::ffi::c_char = msg_send![identifier, UTF8String];
+            if utf8.is_null() {
+                return None;
+            }
+
+            let c_str = std::ffi::CStr::from_ptr(utf8);
+            c_str.to_str().ok().map(|s| s.to_string())
         }
     }
 
+    /// Clears `srcuri://`'s default handler. LaunchServices has no explicit
+    /// "unset" call - passing an empty bundle identifier to
+    /// `LSSetDefaultHandlerForURLScheme` is the established workaround other
+    /// LaunchServices-based tools use to disassociate a scheme.
+    #[cfg(target_os = "macos")]
+    fn unregister_macos() -> Result<()> {
+        tracing::info!("Unregistering srcuri:// protocol handler from LaunchServices");
+
+        launch_services::set_default_handler("").map_err(|status| {
+            anyhow::anyhow!(
+                "LSSetDefaultHandlerForURLScheme failed with status {}",
+                status
+            )
+        })?;
+
+        tracing::info!("Successfully unregistered srcuri:// protocol handler");
+        Ok(())
+    }
+
     #[cfg(target_os = "linux")]
     fn get_status_linux() -> ProtocolRegistrationStatus {
         use std::fs;
-        use std::process::Command;
 
         let current_exe = std::env::current_exe()
             .ok()
             .and_then(|p| p.to_str().map(|s| s.to_string()))
             .unwrap_or_else(|| "unknown".to_string());
 
+        let packaging = LinuxPackaging::detect();
+        // The `Exec=` target `create_desktop_file` actually wrote - for a
+        // packaged install this is a stable launcher path, not `current_exe`
+        // itself (AppImage's mount point, Flatpak/Snap's sandboxed path),
+        // so that's what `executables_match` needs to compare against.
+        let stable_launcher = packaging.exec_target(&current_exe);
+
         let is_registered = Self::is_registered_linux();
         let mut registered_exe = None;
         let mut details = String::new();
@@ -137,11 +550,15 @@ impl ProtocolRegistration {
             details = "Protocol not registered. Run the app to auto-register.".to_string();
         }
 
-        let executables_match = if let Some(ref reg_exe) = registered_exe {
-            reg_exe == &current_exe
-        } else {
-            false
-        };
+        if let Some(packaging_details) = packaging.details_suffix() {
+            details = if details.is_empty() {
+                packaging_details
+            } else {
+                format!("{} ({})", details, packaging_details)
+            };
+        }
+
+        let executables_match = registered_exe.as_deref() == Some(stable_launcher.as_str());
 
         ProtocolRegistrationStatus {
             is_registered,
@@ -172,9 +589,6 @@ impl ProtocolRegistration {
 
     #[cfg(target_os = "linux")]
     fn register_linux() -> Result<()> {
-        use std::fs;
-        use std::process::Command;
-
         tracing::info!("Registering srcuri:// protocol handler for Linux");
 
         // Ensure .desktop file exists
@@ -184,19 +598,99 @@ impl ProtocolRegistration {
             Self::create_desktop_file(&desktop_file_path)?;
         }
 
-        // Register as default handler
+        Self::set_xdg_mime_default()?;
+
+        tracing::info!("Successfully registered srcuri:// protocol handler");
+        Ok(())
+    }
+
+    /// Fixes a `.desktop` file left pointing at a stale `Exec=` target -
+    /// unlike `register_linux`, this rewrites the file unconditionally
+    /// instead of only when one's missing, since the whole point here is
+    /// that a stale one already exists.
+    #[cfg(target_os = "linux")]
+    fn repair_linux() -> Result<()> {
+        let desktop_file_path = Self::get_desktop_file_path()?;
+        Self::create_desktop_file(&desktop_file_path)?;
+        Self::set_xdg_mime_default()?;
+
+        tracing::info!("Successfully repaired srcuri:// protocol handler");
+        Ok(())
+    }
+
+    /// Removes the `.desktop` file and clears the `xdg-mime` default, so
+    /// uninstall/dev-cleanup flows don't leave an orphaned deep-link handler
+    /// pointing at a binary that's about to disappear.
+    #[cfg(target_os = "linux")]
+    fn unregister_linux() -> Result<()> {
+        use std::fs;
+
+        tracing::info!("Unregistering srcuri:// protocol handler for Linux");
+
+        let desktop_file_path = Self::get_desktop_file_path()?;
+        if desktop_file_path.exists() {
+            fs::remove_file(&desktop_file_path)?;
+        }
+
+        Self::clear_xdg_mimeapps_default()?;
+
+        if let Some(parent) = desktop_file_path.parent() {
+            let _ = std::process::Command::new("update-desktop-database")
+                .arg(parent)
+                .status();
+        }
+
+        tracing::info!("Successfully unregistered srcuri:// protocol handler");
+        Ok(())
+    }
+
+    /// Registers `srcuri.desktop` as the default handler for
+    /// `x-scheme-handler/srcuri`, shared by `register_linux` and
+    /// `repair_linux`.
+    #[cfg(target_os = "linux")]
+    fn set_xdg_mime_default() -> Result<()> {
+        use std::process::Command;
+
         let status = Command::new("xdg-mime")
             .args(["default", "srcuri.desktop", "x-scheme-handler/srcuri"])
             .status()?;
 
         if status.success() {
-            tracing::info!("Successfully registered srcuri:// protocol handler");
             Ok(())
         } else {
             anyhow::bail!("Failed to register protocol handler with xdg-mime");
         }
     }
 
+    /// `xdg-mime` has no "unset default" subcommand, so clearing the default
+    /// means dropping `x-scheme-handler/srcuri`'s line from
+    /// `~/.config/mimeapps.list`'s `[Default Applications]` section
+    /// directly, if one was ever written there.
+    #[cfg(target_os = "linux")]
+    fn clear_xdg_mimeapps_default() -> Result<()> {
+        use std::fs;
+
+        let Some(home) = dirs::home_dir() else {
+            return Ok(());
+        };
+        let mimeapps_path = home.join(".config/mimeapps.list");
+        let Ok(content) = fs::read_to_string(&mimeapps_path) else {
+            return Ok(());
+        };
+
+        let filtered: String = content
+            .lines()
+            .filter(|line| !line.starts_with("x-scheme-handler/srcuri="))
+            .map(|line| format!("{}\n", line))
+            .collect();
+
+        if filtered != content {
+            fs::write(&mimeapps_path, filtered)?;
+        }
+
+        Ok(())
+    }
+
     #[cfg(target_os = "linux")]
     fn get_desktop_file_path() -> Result<PathBuf> {
         // Check user applications first
@@ -234,9 +728,17 @@ impl ProtocolRegistration {
 
         tracing::info!("Creating desktop file at {:?}", path);
 
-        // Get the executable path
         let exe_path = std::env::current_exe()?;
-        let exe_path_str = exe_path.to_string_lossy();
+        let packaging = LinuxPackaging::detect();
+        let exec_target = packaging.exec_target(&exe_path.to_string_lossy());
+
+        if packaging != LinuxPackaging::Native {
+            tracing::info!(
+                "Detected {:?} packaging, writing Exec={} instead of the transient current_exe path",
+                packaging,
+                exec_target
+            );
+        }
 
         let desktop_content = format!(
             r#"[Desktop Entry]
@@ -251,7 +753,7 @@ Categories=Development;Utility;
 MimeType=x-scheme-handler/srcuri;
 StartupWMClass=sorcery-desktop
 "#,
-            exe_path_str
+            exec_target
         );
 
         fs::write(path, desktop_content)?;
@@ -269,91 +771,32 @@ StartupWMClass=sorcery-desktop
 
     #[cfg(target_os = "macos")]
     fn get_status_macos() -> ProtocolRegistrationStatus {
-        use std::process::Command;
-
         let current_exe = std::env::current_exe()
             .ok()
             .and_then(|p| p.to_str().map(|s| s.to_string()))
             .unwrap_or_else(|| "unknown".to_string());
 
-        let mut is_registered = false;
-        let mut registered_exe = None;
-        let mut details = String::from("Checking default handler...");
-
-        // Much faster approach: Use LSCopyDefaultHandlerForURLScheme equivalent
-        // We'll check common app locations first
-        let home = std::env::var("HOME").unwrap_or_default();
-        let possible_paths = vec![
-            "/Applications/Sorcery Desktop.app".to_string(),
-            "/Applications/srcuri.app".to_string(),
-            format!("{}/Applications/Sorcery Desktop.app", home),
-            format!("{}/Applications/srcuri.app", home),
-        ];
-
-        for app_path in &possible_paths {
-            let plist_path = format!("{}/Contents/Info.plist", app_path);
-            if std::path::Path::new(&plist_path).exists() {
-                // Check if this plist has srcuri in CFBundleURLSchemes
-                let output = Command::new("defaults")
-                    .args(["read", &plist_path, "CFBundleURLTypes"])
-                    .output();
-
-                if let Ok(output) = output {
-                    let plist_content = String::from_utf8_lossy(&output.stdout);
-                    if plist_content.contains("srcuri") {
-                        is_registered = true;
-                        // Get actual executable name from CFBundleExecutable
-                        let exe_name = Command::new("defaults")
-                            .args(["read", &plist_path, "CFBundleExecutable"])
-                            .output()
-                            .ok()
-                            .and_then(|o| String::from_utf8(o.stdout).ok())
-                            .map(|s| s.trim().to_string())
-                            .unwrap_or_else(|| "sorcery-desktop".to_string());
-                        let exe_path = format!("{}/Contents/MacOS/{}", app_path, exe_name);
-                        registered_exe = Some(exe_path);
-                        details = format!("App bundle: {}", app_path);
-                        break;
-                    }
-                }
-            }
-        }
+        let registered_bundle_id = launch_services::default_handler_bundle_id();
+        let registered_exe = registered_bundle_id
+            .as_deref()
+            .and_then(launch_services::executable_path_for_bundle_id);
 
-        // If not found in common locations, check if current executable is in an app bundle
-        if !is_registered && current_exe.contains(".app/Contents/MacOS/") {
-            let parts: Vec<&str> = current_exe.split(".app/Contents/MacOS/").collect();
-            if !parts.is_empty() {
-                let app_bundle = format!("{}.app", parts[0]);
-                let plist_path = format!("{}/Contents/Info.plist", app_bundle);
-
-                if std::path::Path::new(&plist_path).exists() {
-                    let output = Command::new("defaults")
-                        .args(["read", &plist_path, "CFBundleURLTypes"])
-                        .output();
-
-                    if let Ok(output) = output {
-                        let plist_content = String::from_utf8_lossy(&output.stdout);
-                        if plist_content.contains("srcuri") {
-                            is_registered = true;
-                            registered_exe = Some(current_exe.clone());
-                            details = format!("App bundle: {}", app_bundle);
-                        }
-                    }
-                }
+        let is_registered = registered_exe.is_some();
+        let details = match (&registered_bundle_id, &registered_exe) {
+            (Some(bundle_id), Some(exe)) => {
+                format!("LaunchServices default handler: {} ({})", bundle_id, exe)
+            }
+            (Some(bundle_id), None) => format!(
+                "LaunchServices default handler: {} (could not resolve its executable)",
+                bundle_id
+            ),
+            (None, _) => {
+                "Protocol not registered. Run the app once to register it.".to_string()
             }
-        }
-
-        if !is_registered {
-            details = "Protocol not registered. Run 'make install' or register via installer."
-                .to_string();
-        }
-
-        let executables_match = if let Some(ref reg_exe) = registered_exe {
-            reg_exe == &current_exe
-        } else {
-            false
         };
 
+        let executables_match = registered_exe.as_deref() == Some(current_exe.as_str());
+
         ProtocolRegistrationStatus {
             is_registered,
             registered_executable: registered_exe,
@@ -366,59 +809,34 @@ StartupWMClass=sorcery-desktop
 
     #[cfg(target_os = "windows")]
     fn get_status_windows() -> ProtocolRegistrationStatus {
-        use std::process::Command;
-
         let current_exe = std::env::current_exe()
             .ok()
             .and_then(|p| p.to_str().map(|s| s.to_string()))
             .unwrap_or_else(|| "unknown".to_string());
 
-        let mut is_registered = false;
-        let mut registered_exe = None;
-        let mut details = String::from("Checking Windows Registry...");
-
-        let output = Command::new("reg")
-            .args([
-                "query",
-                "HKEY_CLASSES_ROOT\\srcuri\\shell\\open\\command",
-                "/ve",
-            ])
-            .output();
-
-        if let Ok(output) = output {
-            if output.status.success() {
-                let reg_output = String::from_utf8_lossy(&output.stdout);
-                is_registered = true;
-
-                for line in reg_output.lines() {
-                    if line.contains("REG_SZ") {
-                        let parts: Vec<&str> = line.split("REG_SZ").collect();
-                        if parts.len() > 1 {
-                            let command = parts[1].trim();
-                            let exe_path = command
-                                .trim_start_matches('"')
-                                .split('"')
-                                .next()
-                                .unwrap_or("")
-                                .to_string();
-                            registered_exe = Some(exe_path);
-                            details = format!("Registry: HKEY_CLASSES_ROOT\\srcuri");
-                            break;
-                        }
-                    }
-                }
-            }
-        }
-
-        if !is_registered {
-            details = "Protocol not registered. Run the MSI installer.".to_string();
-        }
-
-        let executables_match = if let Some(ref reg_exe) = registered_exe {
-            reg_exe == &current_exe
-        } else {
-            false
-        };
+        // `HKEY_CLASSES_ROOT` is the merged view of `HKLM` and `HKCU`, so any
+        // of these three finding an entry means the protocol is registered -
+        // prefer the per-user `HKCU` entry `register_windows` writes, since
+        // that's the one a portable/dev/CI build will actually have.
+        let (registered_exe, details) = Self::registered_exe_hkcu()
+            .map(|exe| (Some(exe), "Registry: HKEY_CURRENT_USER\\Software\\Classes\\srcuri".to_string()))
+            .or_else(|| {
+                Self::registered_exe_hklm()
+                    .map(|exe| (Some(exe), "Registry: HKEY_LOCAL_MACHINE\\Software\\Classes\\srcuri".to_string()))
+            })
+            .or_else(|| {
+                Self::registered_exe_hkcr()
+                    .map(|exe| (Some(exe), "Registry: HKEY_CLASSES_ROOT\\srcuri".to_string()))
+            })
+            .unwrap_or_else(|| {
+                (
+                    None,
+                    "Protocol not registered. Run the MSI installer, or relaunch to register per-user.".to_string(),
+                )
+            });
+
+        let is_registered = registered_exe.is_some();
+        let executables_match = registered_exe.as_deref() == Some(current_exe.as_str());
 
         ProtocolRegistrationStatus {
             is_registered,
@@ -0,0 +1,236 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Default depth (directories below the scan root) the recursive repo
+/// walker descends before giving up on a branch, keeping a home-directory
+/// scan fast even under deeply nested folder trees.
+pub const DEFAULT_MAX_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, Default)]
+pub struct RepoScanResult {
+    pub repos: Vec<PathBuf>,
+}
+
+impl RepoScanResult {
+    pub fn count(&self) -> usize {
+        self.repos.len()
+    }
+}
+
+/// One level of the `.gitignore` stack: a directory's own ignore rules
+/// chained to its parent's, so a pattern matches relative to the directory
+/// that defined it, the same way git itself resolves nested ignore files.
+struct IgnoreFrame {
+    gitignore: Gitignore,
+    parent: Option<Arc<IgnoreFrame>>,
+}
+
+impl IgnoreFrame {
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if self.gitignore.matched(path, is_dir).is_ignore() {
+            return true;
+        }
+        match &self.parent {
+            Some(parent) => parent.is_ignored(path, is_dir),
+            None => false,
+        }
+    }
+}
+
+fn build_frame(dir: &Path, parent: Option<Arc<IgnoreFrame>>, extra_patterns: &[String]) -> IgnoreFrame {
+    let mut builder = GitignoreBuilder::new(dir);
+
+    for ignore_file in [".gitignore", ".ignore"] {
+        let ignore_path = dir.join(ignore_file);
+        if ignore_path.exists() {
+            if let Some(e) = builder.add(&ignore_path) {
+                warn!("Failed to parse {:?}: {}", ignore_path, e);
+            }
+        }
+    }
+
+    for pattern in extra_patterns {
+        if let Err(e) = builder.add_line(None, pattern) {
+            warn!("Failed to parse ignore pattern {:?}: {}", pattern, e);
+        }
+    }
+
+    let gitignore = builder.build().unwrap_or_else(|_| Gitignore::empty());
+    IgnoreFrame { gitignore, parent }
+}
+
+/// Recursively scans `root` for git repositories, honoring `.gitignore`/
+/// `.ignore` rules at every level plus `extra_ignore_patterns` (applied on
+/// top of those, e.g. from `defaults.workspace_scan_ignore_patterns`), so
+/// `target/`, `node_modules/`, etc. are never descended into. Descent is
+/// capped at `max_depth` directories below `root`. A directory containing a
+/// `.git` entry (file or directory, so both plain repos and
+/// worktrees/submodules count) is recorded as a repo and not descended into
+/// further — a repo's own tracked files aren't worth walking just to look
+/// for a git repo nested inside one.
+pub fn scan_for_repos(root: &Path, max_depth: usize, extra_ignore_patterns: &[String]) -> RepoScanResult {
+    let mut repos = Vec::new();
+    let root_frame = Arc::new(build_frame(root, None, extra_ignore_patterns));
+    walk(root, &root_frame, 0, max_depth, &mut repos);
+    RepoScanResult { repos }
+}
+
+/// [`scan_for_repos`] with [`DEFAULT_MAX_DEPTH`] and no extra ignore patterns.
+pub fn scan_for_repos_default(root: &Path) -> RepoScanResult {
+    scan_for_repos(root, DEFAULT_MAX_DEPTH, &[])
+}
+
+fn walk(
+    dir: &Path,
+    frame: &Arc<IgnoreFrame>,
+    depth: usize,
+    max_depth: usize,
+    repos: &mut Vec<PathBuf>,
+) {
+    if dir.join(".git").exists() {
+        repos.push(dir.to_path_buf());
+        return;
+    }
+
+    if depth >= max_depth {
+        return;
+    }
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            debug!("Skipping unreadable directory {:?}: {}", dir, e);
+            return;
+        }
+    };
+
+    let child_frame = Arc::new(build_frame(dir, Some(frame.clone()), &[]));
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if child_frame.is_ignored(&path, true) {
+            continue;
+        }
+
+        walk(&path, &child_frame, depth + 1, max_depth, repos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn make_repo(path: &Path) {
+        fs::create_dir_all(path.join(".git")).unwrap();
+    }
+
+    #[test]
+    fn finds_repo_one_level_deep() {
+        let dir = TempDir::new().expect("tempdir");
+        make_repo(&dir.path().join("repo-a"));
+
+        let result = scan_for_repos_default(dir.path());
+
+        assert_eq!(result.repos, vec![dir.path().join("repo-a")]);
+    }
+
+    #[test]
+    fn finds_repo_nested_under_an_org_folder() {
+        let dir = TempDir::new().expect("tempdir");
+        fs::create_dir_all(dir.path().join("org")).unwrap();
+        make_repo(&dir.path().join("org/repo-b"));
+
+        let result = scan_for_repos_default(dir.path());
+
+        assert_eq!(result.repos, vec![dir.path().join("org/repo-b")]);
+    }
+
+    #[test]
+    fn does_not_descend_into_a_found_repo() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = dir.path().join("repo-c");
+        make_repo(&repo);
+        fs::create_dir_all(repo.join("vendor/nested-repo/.git")).unwrap();
+
+        let result = scan_for_repos_default(dir.path());
+
+        assert_eq!(result.repos, vec![repo]);
+    }
+
+    #[test]
+    fn respects_gitignore_to_skip_a_subtree() {
+        let dir = TempDir::new().expect("tempdir");
+        fs::write(dir.path().join(".gitignore"), "ignored/\n").unwrap();
+        fs::create_dir_all(dir.path().join("ignored")).unwrap();
+        make_repo(&dir.path().join("ignored/repo-d"));
+        make_repo(&dir.path().join("kept-repo"));
+
+        let result = scan_for_repos_default(dir.path());
+
+        assert_eq!(result.repos, vec![dir.path().join("kept-repo")]);
+    }
+
+    #[test]
+    fn stops_descending_past_max_depth() {
+        let dir = TempDir::new().expect("tempdir");
+        make_repo(&dir.path().join("a/b/c/deep-repo"));
+
+        let result = scan_for_repos(dir.path(), 2, &[]);
+
+        assert!(result.repos.is_empty());
+    }
+
+    #[test]
+    fn respects_dot_ignore_to_skip_a_subtree() {
+        let dir = TempDir::new().expect("tempdir");
+        fs::write(dir.path().join(".ignore"), "ignored/\n").unwrap();
+        fs::create_dir_all(dir.path().join("ignored")).unwrap();
+        make_repo(&dir.path().join("ignored/repo-e"));
+        make_repo(&dir.path().join("kept-repo"));
+
+        let result = scan_for_repos_default(dir.path());
+
+        assert_eq!(result.repos, vec![dir.path().join("kept-repo")]);
+    }
+
+    #[test]
+    fn applies_extra_ignore_patterns() {
+        let dir = TempDir::new().expect("tempdir");
+        make_repo(&dir.path().join("node_modules/some-pkg"));
+        make_repo(&dir.path().join("kept-repo"));
+
+        let result = scan_for_repos(
+            dir.path(),
+            DEFAULT_MAX_DEPTH,
+            &["**/node_modules".to_string()],
+        );
+
+        assert_eq!(result.repos, vec![dir.path().join("kept-repo")]);
+    }
+
+    #[test]
+    fn counts_match_repos_len() {
+        let dir = TempDir::new().expect("tempdir");
+        make_repo(&dir.path().join("repo-a"));
+        make_repo(&dir.path().join("repo-b"));
+
+        let result = scan_for_repos_default(dir.path());
+
+        assert_eq!(result.count(), 2);
+    }
+}
@@ -0,0 +1,139 @@
+//! Environment sanitization for spawning external programs from a
+//! sandboxed build of Sorcery itself.
+//!
+//! AppImage/Flatpak/Snap all run our process inside a bundle whose own
+//! libraries are prepended to `PATH`-like variables (`LD_LIBRARY_PATH`,
+//! `GST_PLUGIN_PATH`, `XDG_DATA_DIRS`, ...) so *we* find the right copies.
+//! That same environment poisons any program we spawn - a system file
+//! manager or editor picks up our bundled libraries instead of its own and
+//! crashes. [`spawn_external`] rebuilds each such variable with the
+//! bundle's own entries stripped out before handing it to the child.
+
+use std::ffi::OsStr;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+
+/// Colon-separated variables worth cleaning - the ones AppImage/Flatpak/Snap
+/// are known to rewrite ahead of whatever the system already had set.
+const PATH_LIST_VARS: &[&str] = &[
+    "PATH",
+    "LD_LIBRARY_PATH",
+    "GST_PLUGIN_PATH",
+    "GST_PLUGIN_SYSTEM_PATH",
+    "XDG_DATA_DIRS",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SandboxKind {
+    AppImage,
+    Flatpak,
+    Snap,
+}
+
+/// Detects which packaging format (if any) the current process is running
+/// under, the same way each format's own launchers detect themselves.
+fn detect_sandbox() -> Option<SandboxKind> {
+    if std::env::var_os("APPIMAGE").is_some() {
+        return Some(SandboxKind::AppImage);
+    }
+
+    if std::env::var_os("FLATPAK_ID").is_some() || Path::new("/.flatpak-info").exists() {
+        return Some(SandboxKind::Flatpak);
+    }
+
+    if std::env::var_os("SNAP").is_some() {
+        return Some(SandboxKind::Snap);
+    }
+
+    None
+}
+
+/// The root directory that a contaminated path entry would live under for
+/// `kind`, so it can be filtered out of `PATH`-like variables.
+fn bundle_root(kind: SandboxKind) -> Option<PathBuf> {
+    match kind {
+        // The AppImage mounts itself and sets `APPDIR` to the mount point;
+        // `APPIMAGE` itself just points at the `.AppImage` file.
+        SandboxKind::AppImage => std::env::var_os("APPDIR").map(PathBuf::from),
+        SandboxKind::Flatpak => Some(PathBuf::from("/app")),
+        SandboxKind::Snap => std::env::var_os("SNAP").map(PathBuf::from),
+    }
+}
+
+/// Rebuilds a colon-separated path list, dropping entries under
+/// `bundle_root` and de-duplicating what's left while preserving the order
+/// of first occurrence.
+fn sanitize_path_list(value: &str, bundle_root: &Path) -> String {
+    let mut seen = std::collections::HashSet::new();
+
+    value
+        .split(':')
+        .filter(|entry| !entry.is_empty())
+        .filter(|entry| !Path::new(entry).starts_with(bundle_root))
+        .filter(|entry| seen.insert(*entry))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Applies sandbox cleanup to `cmd`'s environment in place. A no-op outside
+/// a detected sandbox.
+fn sanitize_env(cmd: &mut Command) {
+    let Some(kind) = detect_sandbox() else {
+        return;
+    };
+
+    let Some(bundle_root) = bundle_root(kind) else {
+        return;
+    };
+
+    for var in PATH_LIST_VARS {
+        let Ok(value) = std::env::var(var) else {
+            continue;
+        };
+
+        let cleaned = sanitize_path_list(&value, &bundle_root);
+        if cleaned.is_empty() {
+            cmd.env_remove(var);
+        } else {
+            cmd.env(var, cleaned);
+        }
+    }
+}
+
+/// Spawns `program` with `args` and a sandbox-cleaned environment. Use this
+/// (instead of `Command::new` directly) for every external program we
+/// launch that isn't one of our own `EditorManager`s - the log-directory
+/// opener today, any future one-off external spawn tomorrow.
+pub fn spawn_external<S: AsRef<OsStr>>(program: &str, args: &[S]) -> io::Result<Child> {
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    sanitize_env(&mut cmd);
+    cmd.spawn()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_path_list_strips_bundle_entries() {
+        let cleaned = sanitize_path_list(
+            "/tmp/bundle/usr/bin:/usr/bin:/usr/local/bin",
+            Path::new("/tmp/bundle"),
+        );
+        assert_eq!(cleaned, "/usr/bin:/usr/local/bin");
+    }
+
+    #[test]
+    fn sanitize_path_list_dedups_preserving_first_occurrence() {
+        let cleaned = sanitize_path_list("/usr/bin:/usr/local/bin:/usr/bin", Path::new("/opt/bundle"));
+        assert_eq!(cleaned, "/usr/bin:/usr/local/bin");
+    }
+
+    #[test]
+    fn sanitize_path_list_returns_empty_when_everything_is_bundled() {
+        let cleaned = sanitize_path_list("/tmp/bundle/bin:/tmp/bundle/usr/bin", Path::new("/tmp/bundle"));
+        assert_eq!(cleaned, "");
+    }
+}
@@ -1,4 +1,5 @@
 use super::models::WorkspaceConfig;
+use super::normalize::NormalizedPath;
 use super::SettingsManager;
 use anyhow::Result;
 use serde::Serialize;
@@ -49,10 +50,15 @@ impl WorkspaceSync {
             .workspaces
             .iter()
             .filter_map(|ws| ws.normalized_path.clone())
+            .map(NormalizedPath::into_path_buf)
             .collect();
 
         // Scan for git repos in the folder
-        let discovered = self.scan_folder(&folder);
+        let discovered = self.scan_folder(
+            &folder,
+            settings.defaults.workspace_scan_max_depth,
+            &settings.defaults.workspace_scan_ignore_patterns,
+        );
 
         // Add new repos
         for repo in &discovered {
@@ -73,23 +79,34 @@ impl WorkspaceSync {
             info!("Adding auto-discovered workspace: {}", name);
             result.added.push(name.clone());
 
+            // Route a newly-discovered workspace to the editor configured
+            // for its project kind (e.g. Rust -> one editor, Node ->
+            // another) instead of leaving `editor` empty and falling
+            // through to the global default.
+            let editor = crate::project_kind::detect(repo)
+                .and_then(|info| settings.defaults.project_kind_editors.get(&info.kind).cloned())
+                .unwrap_or_default();
+
             settings.workspaces.push(WorkspaceConfig {
                 path: repo.to_string_lossy().to_string(),
                 name: Some(name),
-                editor: String::new(),
+                editor,
                 auto_discovered: true,
-                normalized_path: Some(repo.clone()),
+                enable_paths: None,
+                disable_paths: None,
+                tags: Vec::new(),
+                normalized_path: Some(NormalizedPath::from_existing(repo.clone())),
             });
         }
 
         // Remove auto_discovered workspaces that no longer exist
-        let discovered_set: HashSet<&PathBuf> = discovered.iter().collect();
+        let discovered_set: HashSet<PathBuf> = discovered.iter().cloned().collect();
         let mut i = 0;
         while i < settings.workspaces.len() {
             let ws = &settings.workspaces[i];
             if ws.auto_discovered {
                 if let Some(ref path) = ws.normalized_path {
-                    if !discovered_set.contains(path) {
+                    if !discovered_set.contains(path.as_path()) {
                         let name = ws.name.clone().unwrap_or_else(|| ws.path.clone());
                         info!("Removing auto-discovered workspace (no longer exists): {}", name);
                         result.removed.push(name);
@@ -143,45 +160,26 @@ impl WorkspaceSync {
         Some(PathBuf::from(expanded.as_ref()))
     }
 
-    fn scan_folder(&self, folder: &PathBuf) -> Vec<PathBuf> {
+    fn scan_folder(
+        &self,
+        folder: &PathBuf,
+        max_depth: usize,
+        extra_ignore_patterns: &[String],
+    ) -> Vec<PathBuf> {
         debug!("Scanning default_workspaces_folder: {:?}", folder);
 
-        let entries = match std::fs::read_dir(folder) {
-            Ok(entries) => entries,
-            Err(e) => {
-                warn!("Failed to read default_workspaces_folder {:?}: {}", folder, e);
-                return Vec::new();
-            }
-        };
-
-        let mut repos = Vec::new();
-
-        for entry in entries.filter_map(|e| e.ok()) {
-            let path = entry.path();
-
-            // Skip non-directories
-            if !path.is_dir() {
-                continue;
-            }
-
-            // Skip dot-prefixed folders
-            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
-                continue;
-            };
-            if name.starts_with('.') {
-                continue;
-            }
-
-            // Only include folders with .git
-            let git_dir = path.join(".git");
-            if !git_dir.exists() {
-                continue;
-            }
-
-            repos.push(path);
+        if !folder.is_dir() {
+            warn!("default_workspaces_folder {:?} is not a directory", folder);
+            return Vec::new();
         }
 
-        debug!("Found {} git repos in {:?}", repos.len(), folder);
-        repos
+        // Recurses a few levels deep (honoring .gitignore/.ignore and
+        // defaults.workspace_scan_ignore_patterns along the way) so repos
+        // kept under an org/grouping folder (e.g. ~/code/org/repo) are
+        // picked up, not just ones directly under `folder`.
+        let result = crate::repo_discovery::scan_for_repos(folder, max_depth, extra_ignore_patterns);
+
+        debug!("Found {} git repos in {:?}", result.repos.len(), folder);
+        result.repos
     }
 }
@@ -0,0 +1,102 @@
+/// A fully-commented example `settings.yaml`, covering every
+/// `DefaultEditorConfig` key plus a sample `WorkspaceConfig`, so someone
+/// starting a config from scratch (or running with `--print-config`) can
+/// see what's available without cross-referencing `models.rs`.
+pub fn generate_example_config() -> String {
+    r#"# Sorcery Desktop settings.
+#
+# Normally managed through the Settings window - edit this file by hand
+# only if you know what you're doing. Run with `--print-config` to print
+# this commented example again.
+
+defaults:
+  # Editor id opened by default: "vscode", "cursor", "vscodium", "idea",
+  # "webstorm", "vim", "neovim", "emacs", "zed", "sublime", ...
+  editor: vscode
+
+  # Whether srcuri:// links for files outside every configured workspace
+  # are allowed to open at all.
+  allow_non_workspace_files: false
+
+  # Terminal used to launch terminal-based editors (vim, neovim, ...):
+  # "auto" detects one, or name one explicitly - "alacritty", "kitty",
+  # "wezterm", "gnome-terminal" (Linux), "konsole" (Linux), "xterm"
+  # (Linux), "iterm2" or "terminal" (macOS).
+  preferred_terminal: auto
+
+  # Folder scanned for git repositories to auto-discover as workspaces.
+  default_workspaces_folder: ~/code
+
+  # Automatically switch a workspace back to its default branch once its
+  # working tree is clean.
+  auto_switch_clean_branches: true
+
+  # Workspace paths excluded from auto-discovery.
+  ignored_workspaces: []
+
+  # Worker threads used to probe workspace activity concurrently. Omit to
+  # use the number of CPUs.
+  # probe_worker_threads: 4
+
+  # Allow opening files that classify as executables (blocked by default).
+  allow_executable_files: false
+
+  # Explicit binary path or launch command per editor id, required for
+  # any editor whose `requires_configuration()` is true.
+  editor_paths: {}
+
+  # Personal access token per git host, used by bulk-clone to list and
+  # clone private repositories.
+  git_host_tokens: {}
+
+  # Fallback editor/path-policy per workspace tag.
+  tag_defaults: {}
+
+  # Default editor per detected project kind (e.g. "rust", "node").
+  project_kind_editors: {}
+
+  # Custom shorthand prefix -> host mapping for git_url::expand, e.g.
+  # `work: { host: git.corp.example.com, use_ssh: true }` so `work:team/repo`
+  # clones from an internal host.
+  custom_git_vendors: {}
+
+  # Self-hosted GitLab/Gitea/etc. instances on a custom domain, so
+  # srcuri:// provider-passthrough links against them resolve. Each entry
+  # is `{ host, provider, match_subdomains }`; `provider` is one of
+  # "git_hub", "git_lab", "bitbucket", "gitea", "codeberg", "azure_dev_ops",
+  # and `match_subdomains` (default false) registers `host` as a suffix
+  # (e.g. `corp.example.com` matches any subdomain of it) instead of an
+  # exact hostname. For example:
+  # self_hosted_providers:
+  #   - host: git.corp.example.com
+  #     provider: gitlab
+  self_hosted_providers: []
+
+# One entry per workspace, e.g.:
+# workspaces:
+#   - path: ~/code/my-project
+#     name: my-project
+#     editor: vscode
+#     tags: [work]
+workspaces: []
+
+# Optional git-backed sync of this settings file across machines. Unset
+# (the default) leaves sync entirely opt-in - see `settings_sync::SyncManager`.
+# sync:
+#   remote: git@github.com:me/my-sorcery-settings.git
+"#
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_example_config_parses_back_as_settings() {
+        let example = generate_example_config();
+        let settings: super::super::models::Settings = serde_yaml::from_str(&example).unwrap();
+        assert_eq!(settings.defaults.editor, "vscode");
+        assert!(settings.workspaces.is_empty());
+    }
+}
@@ -0,0 +1,345 @@
+//! Layered configuration resolution for `Settings`: built-in defaults,
+//! overridden by an optional system-wide config, overridden by the
+//! per-user `config_path`, overridden by a handful of `SORCERY_DEFAULTS_*`
+//! environment variables, overridden last by in-process runtime overrides
+//! (`SettingsManager::set_runtime_override`) - the same precedence order
+//! Cargo resolves `.cargo/config.toml` against `CARGO_*` env vars against
+//! CLI flags.
+//!
+//! [`Merge`] composes two already-parsed file layers (system, user);
+//! [`EnvOverrides`] and [`apply_runtime_overrides`] apply the two layers
+//! above that, which are scalar overrides rather than full YAML documents.
+
+use super::models::{DefaultEditorConfig, Settings, SyncConfig, WorkspaceConfig};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Which layer supplied a config key's currently-effective value, reported
+/// by `SettingsManager::config_origin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigLayer {
+    Default,
+    System,
+    User,
+    Env,
+    Runtime,
+}
+
+/// Merges `other` on top of `self`: wherever `other` differs from this
+/// type's own built-in default, that field wins; otherwise `self`'s value
+/// is kept. This can't distinguish "this layer explicitly set the value
+/// back to its built-in default" from "this layer never touched it at
+/// all" - a known, accepted limitation of merging without wrapping every
+/// field in `Option` (which `record_origins` shares, for the same reason).
+pub trait Merge {
+    fn merge(self, other: Self) -> Self;
+}
+
+impl Merge for DefaultEditorConfig {
+    fn merge(self, other: Self) -> Self {
+        let defaults = DefaultEditorConfig::default();
+        Self {
+            editor: if other.editor != defaults.editor { other.editor } else { self.editor },
+            allow_non_workspace_files: if other.allow_non_workspace_files != defaults.allow_non_workspace_files {
+                other.allow_non_workspace_files
+            } else {
+                self.allow_non_workspace_files
+            },
+            preferred_terminal: if other.preferred_terminal != defaults.preferred_terminal {
+                other.preferred_terminal
+            } else {
+                self.preferred_terminal
+            },
+            default_workspaces_folder: if other.default_workspaces_folder != defaults.default_workspaces_folder {
+                other.default_workspaces_folder
+            } else {
+                self.default_workspaces_folder
+            },
+            auto_switch_clean_branches: if other.auto_switch_clean_branches != defaults.auto_switch_clean_branches {
+                other.auto_switch_clean_branches
+            } else {
+                self.auto_switch_clean_branches
+            },
+            ignored_workspaces: if !other.ignored_workspaces.is_empty() {
+                other.ignored_workspaces
+            } else {
+                self.ignored_workspaces
+            },
+            probe_worker_threads: other.probe_worker_threads.or(self.probe_worker_threads),
+            allow_executable_files: if other.allow_executable_files != defaults.allow_executable_files {
+                other.allow_executable_files
+            } else {
+                self.allow_executable_files
+            },
+            editor_paths: merge_maps(self.editor_paths, other.editor_paths),
+            git_host_tokens: merge_maps(self.git_host_tokens, other.git_host_tokens),
+            tag_defaults: merge_maps(self.tag_defaults, other.tag_defaults),
+            project_kind_editors: merge_maps(self.project_kind_editors, other.project_kind_editors),
+            custom_git_vendors: merge_maps(self.custom_git_vendors, other.custom_git_vendors),
+            workspace_scan_max_depth: if other.workspace_scan_max_depth != defaults.workspace_scan_max_depth {
+                other.workspace_scan_max_depth
+            } else {
+                self.workspace_scan_max_depth
+            },
+            workspace_scan_ignore_patterns: if !other.workspace_scan_ignore_patterns.is_empty() {
+                other.workspace_scan_ignore_patterns
+            } else {
+                self.workspace_scan_ignore_patterns
+            },
+            generate_compilation_db: if other.generate_compilation_db != defaults.generate_compilation_db {
+                other.generate_compilation_db
+            } else {
+                self.generate_compilation_db
+            },
+        }
+    }
+}
+
+impl Merge for WorkspaceConfig {
+    fn merge(self, other: Self) -> Self {
+        Self {
+            path: self.path,
+            name: other.name.or(self.name),
+            editor: if !other.editor.is_empty() { other.editor } else { self.editor },
+            auto_discovered: other.auto_discovered || self.auto_discovered,
+            enable_paths: other.enable_paths.or(self.enable_paths),
+            disable_paths: other.disable_paths.or(self.disable_paths),
+            tags: if !other.tags.is_empty() { other.tags } else { self.tags },
+            normalized_path: self.normalized_path,
+        }
+    }
+}
+
+impl Merge for SyncConfig {
+    fn merge(self, other: Self) -> Self {
+        Self {
+            remote: other.remote.or(self.remote),
+        }
+    }
+}
+
+impl Merge for Settings {
+    fn merge(self, other: Self) -> Self {
+        Self {
+            defaults: self.defaults.merge(other.defaults),
+            workspaces: merge_workspaces(self.workspaces, other.workspaces),
+            sync: self.sync.merge(other.sync),
+        }
+    }
+}
+
+fn merge_maps<K: std::hash::Hash + Eq, V>(mut base: HashMap<K, V>, overlay: HashMap<K, V>) -> HashMap<K, V> {
+    base.extend(overlay);
+    base
+}
+
+/// Merges two workspace lists by `path`: an entry present in both layers
+/// is field-merged via `WorkspaceConfig::merge` (overlay wins per-field,
+/// keeping `base`'s position in the list); an entry only in `overlay` is
+/// appended, in the overlay's own order; an entry only in `base` passes
+/// through unchanged. Never drops a workspace one layer configured just
+/// because a higher layer's list doesn't mention it.
+fn merge_workspaces(base: Vec<WorkspaceConfig>, overlay: Vec<WorkspaceConfig>) -> Vec<WorkspaceConfig> {
+    let overlay_order: Vec<String> = overlay.iter().map(|workspace| workspace.path.clone()).collect();
+    let mut overlay_by_path: HashMap<String, WorkspaceConfig> =
+        overlay.into_iter().map(|workspace| (workspace.path.clone(), workspace)).collect();
+
+    let mut result = Vec::with_capacity(overlay_order.len());
+    for workspace in base {
+        match overlay_by_path.remove(&workspace.path) {
+            Some(overlay_workspace) => result.push(workspace.merge(overlay_workspace)),
+            None => result.push(workspace),
+        }
+    }
+
+    for path in overlay_order {
+        if let Some(workspace) = overlay_by_path.remove(&path) {
+            result.push(workspace);
+        }
+    }
+
+    result
+}
+
+/// Records which of the three env/runtime-overridable `defaults` keys
+/// `incoming` sets to something other than its type's built-in default,
+/// attributing each one to `layer`. See [`Merge`]'s doc comment for why
+/// this can't tell "set to the default on purpose" from "not set".
+pub fn record_origins(incoming: &Settings, layer: ConfigLayer, origins: &mut HashMap<String, ConfigLayer>) {
+    let defaults = DefaultEditorConfig::default();
+
+    if incoming.defaults.editor != defaults.editor {
+        origins.insert("defaults.editor".to_string(), layer);
+    }
+    if incoming.defaults.preferred_terminal != defaults.preferred_terminal {
+        origins.insert("defaults.preferred_terminal".to_string(), layer);
+    }
+    if incoming.defaults.allow_non_workspace_files != defaults.allow_non_workspace_files {
+        origins.insert("defaults.allow_non_workspace_files".to_string(), layer);
+    }
+}
+
+/// `SORCERY_DEFAULTS_<FIELD>` overrides for the handful of `defaults`
+/// fields simple enough to be worth setting without editing
+/// `settings.yaml` - `SORCERY_DEFAULTS_EDITOR`,
+/// `SORCERY_DEFAULTS_PREFERRED_TERMINAL`,
+/// `SORCERY_DEFAULTS_ALLOW_NON_WORKSPACE_FILES` - mirroring how
+/// `SettingsManager::get_default_editor`/`get_preferred_terminal`/
+/// `allows_non_workspace_files` name the fields a caller actually reads.
+#[derive(Debug, Clone, Default)]
+pub struct EnvOverrides {
+    pub editor: Option<String>,
+    pub preferred_terminal: Option<String>,
+    pub allow_non_workspace_files: Option<bool>,
+}
+
+impl EnvOverrides {
+    pub fn from_env() -> Self {
+        Self {
+            editor: non_empty_env("SORCERY_DEFAULTS_EDITOR"),
+            preferred_terminal: non_empty_env("SORCERY_DEFAULTS_PREFERRED_TERMINAL"),
+            allow_non_workspace_files: non_empty_env("SORCERY_DEFAULTS_ALLOW_NON_WORKSPACE_FILES")
+                .and_then(|value| value.parse().ok()),
+        }
+    }
+
+    /// Applies every set override onto `settings.defaults`, recording each
+    /// one as `ConfigLayer::Env` in `origins`.
+    pub fn apply(&self, settings: &mut Settings, origins: &mut HashMap<String, ConfigLayer>) {
+        if let Some(editor) = &self.editor {
+            settings.defaults.editor = editor.clone();
+            origins.insert("defaults.editor".to_string(), ConfigLayer::Env);
+        }
+        if let Some(terminal) = &self.preferred_terminal {
+            settings.defaults.preferred_terminal = terminal.clone();
+            origins.insert("defaults.preferred_terminal".to_string(), ConfigLayer::Env);
+        }
+        if let Some(allow) = self.allow_non_workspace_files {
+            settings.defaults.allow_non_workspace_files = allow;
+            origins.insert("defaults.allow_non_workspace_files".to_string(), ConfigLayer::Env);
+        }
+    }
+}
+
+fn non_empty_env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// Applies `SettingsManager::set_runtime_override` entries onto
+/// `settings.defaults`, keyed the same way `config_origin` reports them
+/// (`"defaults.editor"`, ...). An unrecognized key, or a value that fails
+/// to parse for a non-`String` field, is ignored rather than erroring -
+/// the same best-effort stance `validation::warn_unknown_references`
+/// takes with bad YAML content.
+pub fn apply_runtime_overrides(
+    settings: &mut Settings,
+    overrides: &HashMap<String, String>,
+    origins: &mut HashMap<String, ConfigLayer>,
+) {
+    if let Some(value) = overrides.get("defaults.editor") {
+        settings.defaults.editor = value.clone();
+        origins.insert("defaults.editor".to_string(), ConfigLayer::Runtime);
+    }
+    if let Some(value) = overrides.get("defaults.preferred_terminal") {
+        settings.defaults.preferred_terminal = value.clone();
+        origins.insert("defaults.preferred_terminal".to_string(), ConfigLayer::Runtime);
+    }
+    if let Some(value) = overrides.get("defaults.allow_non_workspace_files") {
+        if let Ok(parsed) = value.parse::<bool>() {
+            settings.defaults.allow_non_workspace_files = parsed;
+            origins.insert("defaults.allow_non_workspace_files".to_string(), ConfigLayer::Runtime);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_keeps_base_scalar_when_other_is_default() {
+        let base = DefaultEditorConfig { editor: "idea".to_string(), ..DefaultEditorConfig::default() };
+        let merged = base.merge(DefaultEditorConfig::default());
+        assert_eq!(merged.editor, "idea");
+    }
+
+    #[test]
+    fn merge_prefers_other_scalar_when_it_differs_from_default() {
+        let base = DefaultEditorConfig::default();
+        let overlay = DefaultEditorConfig { editor: "idea".to_string(), ..DefaultEditorConfig::default() };
+        let merged = base.merge(overlay);
+        assert_eq!(merged.editor, "idea");
+    }
+
+    #[test]
+    fn merge_workspaces_appends_new_and_field_merges_shared_paths() {
+        let base = vec![
+            WorkspaceConfig {
+                path: "/a".to_string(),
+                name: None,
+                editor: "vscode".to_string(),
+                auto_discovered: false,
+                enable_paths: None,
+                disable_paths: None,
+                tags: Vec::new(),
+                normalized_path: None,
+            },
+        ];
+        let overlay = vec![
+            WorkspaceConfig {
+                path: "/a".to_string(),
+                name: Some("A".to_string()),
+                editor: String::new(),
+                auto_discovered: false,
+                enable_paths: None,
+                disable_paths: None,
+                tags: Vec::new(),
+                normalized_path: None,
+            },
+            WorkspaceConfig {
+                path: "/b".to_string(),
+                name: None,
+                editor: "idea".to_string(),
+                auto_discovered: false,
+                enable_paths: None,
+                disable_paths: None,
+                tags: Vec::new(),
+                normalized_path: None,
+            },
+        ];
+
+        let merged = merge_workspaces(base, overlay);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].path, "/a");
+        assert_eq!(merged[0].name.as_deref(), Some("A"));
+        assert_eq!(merged[0].editor, "vscode");
+        assert_eq!(merged[1].path, "/b");
+    }
+
+    #[test]
+    fn record_origins_only_flags_keys_that_differ_from_default() {
+        let mut settings = Settings::default();
+        settings.defaults.editor = "idea".to_string();
+
+        let mut origins = HashMap::new();
+        record_origins(&settings, ConfigLayer::User, &mut origins);
+
+        assert_eq!(origins.get("defaults.editor"), Some(&ConfigLayer::User));
+        assert!(!origins.contains_key("defaults.preferred_terminal"));
+    }
+
+    #[test]
+    fn apply_runtime_overrides_ignores_unparseable_bool() {
+        let mut settings = Settings::default();
+        let mut overrides = HashMap::new();
+        overrides.insert("defaults.allow_non_workspace_files".to_string(), "not-a-bool".to_string());
+
+        let mut origins = HashMap::new();
+        apply_runtime_overrides(&mut settings, &overrides, &mut origins);
+
+        assert!(!settings.defaults.allow_non_workspace_files);
+        assert!(!origins.contains_key("defaults.allow_non_workspace_files"));
+    }
+}
@@ -1,13 +1,54 @@
+use super::layering::{self, ConfigLayer};
 use super::models::{Settings, WorkspaceConfig};
+use super::normalize::{self, NormalizedPath};
+use super::validation;
 use anyhow::{Context, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tracing::{info, warn};
 
+/// How long to wait after the last raw filesystem event before reloading,
+/// so an editor's write-then-rename settles into a single reload instead
+/// of one per intermediate write - same rationale as
+/// `workspace_watcher::WorkspaceWatcher`'s `DEBOUNCE`.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Emitted over `SettingsManager::watch()` whenever `config_path` changes
+/// on disk and is picked up by `start_watching`. A `ParseError` means the
+/// reload failed - the previous in-memory `Settings` (and whatever
+/// `get()` returns) is left untouched, matching glazewm's resilient
+/// `reload_config`: bad config on disk never clobbers a good config
+/// already loaded.
+#[derive(Debug, Clone)]
+pub enum SettingsReloadEvent {
+    Reloaded(Settings),
+    ParseError(String),
+}
+
 pub struct SettingsManager {
     config_path: PathBuf,
+    /// The system + user + env layers merged together (see `layering`),
+    /// without `runtime_overrides` applied. Kept separately from
+    /// `settings` so clearing a runtime override can fall back to this
+    /// rather than needing a full reload from disk.
+    base_settings: RwLock<Settings>,
+    /// Which layer last set each tracked key, among `base_settings`'s own
+    /// layers (system/user/env) - recomputed on every `load()`/`save()`.
+    layer_origins: RwLock<HashMap<String, ConfigLayer>>,
+    /// In-process overrides set via `set_runtime_override`, keyed the same
+    /// way `config_origin` reports them (e.g. `"defaults.editor"`).
+    runtime_overrides: RwLock<HashMap<String, String>>,
+    /// `base_settings` with `runtime_overrides` applied on top - the
+    /// merged view `get()` and every other getter reads.
     settings: Arc<RwLock<Settings>>,
+    /// Which layer last set each tracked key in `settings`, including
+    /// `runtime_overrides`.
+    origins: RwLock<HashMap<String, ConfigLayer>>,
+    reload_tx: broadcast::Sender<SettingsReloadEvent>,
 }
 
 impl SettingsManager {
@@ -22,9 +63,16 @@ impl SettingsManager {
             Settings::with_detected_workspaces_folder()
         };
 
+        let (reload_tx, _) = broadcast::channel(16);
+
         Ok(Self {
             config_path,
+            base_settings: RwLock::new(initial.clone()),
+            layer_origins: RwLock::new(HashMap::new()),
+            runtime_overrides: RwLock::new(HashMap::new()),
             settings: Arc::new(RwLock::new(initial)),
+            origins: RwLock::new(HashMap::new()),
+            reload_tx,
         })
     }
 
@@ -42,29 +90,198 @@ impl SettingsManager {
         &self.config_path
     }
 
-    pub async fn load(&self) -> Result<()> {
-        if !self.config_path.exists() {
-            info!("No existing settings file found, using defaults");
-            return Ok(());
+    /// The optional system-wide config layer, consulted between the
+    /// built-in defaults and the per-user `config_path` - read-only from
+    /// Sorcery's point of view, an administrator's to manage. Only
+    /// meaningful on platforms with an `/etc`; elsewhere there's no
+    /// equivalent shared location, so this layer is simply absent.
+    #[cfg(unix)]
+    fn system_config_path() -> Option<PathBuf> {
+        Some(PathBuf::from("/etc/sorcery-desktop/settings.yaml"))
+    }
+
+    #[cfg(not(unix))]
+    fn system_config_path() -> Option<PathBuf> {
+        None
+    }
+
+    async fn load_layer(path: &Path) -> Result<Option<Settings>> {
+        if !path.exists() {
+            return Ok(None);
         }
 
-        let contents = tokio::fs::read_to_string(&self.config_path)
+        let contents = tokio::fs::read_to_string(path)
             .await
-            .context("Failed to read settings file")?;
+            .context("Failed to read config layer")?;
+        let settings: Settings =
+            serde_yaml::from_str(&contents).context("Failed to parse config layer YAML")?;
+        Ok(Some(settings))
+    }
 
-        let mut settings: Settings =
-            serde_yaml::from_str(&contents).context("Failed to parse YAML settings")?;
+    /// Merges `user_settings` on top of the built-in defaults, the
+    /// system-wide layer (if present) and `SORCERY_DEFAULTS_*` env
+    /// overrides, normalizing workspace paths on the final result. Shared
+    /// by `load()` (where `user_settings` comes from `config_path`) and
+    /// `save()` (where it's whatever the caller just persisted).
+    async fn merge_with_lower_layers(
+        &self,
+        user_settings: Settings,
+    ) -> Result<(Settings, HashMap<String, ConfigLayer>)> {
+        use layering::Merge;
+
+        let mut settings = Settings::default();
+        let mut origins = HashMap::new();
+
+        if let Some(system_path) = Self::system_config_path() {
+            match Self::load_layer(&system_path).await {
+                Ok(Some(system_settings)) => {
+                    layering::record_origins(&system_settings, ConfigLayer::System, &mut origins);
+                    settings = settings.merge(system_settings);
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Failed to load system config {:?}, skipping: {}", system_path, e),
+            }
+        }
+
+        layering::record_origins(&user_settings, ConfigLayer::User, &mut origins);
+        settings = settings.merge(user_settings);
+
+        layering::EnvOverrides::from_env().apply(&mut settings, &mut origins);
 
         self.normalize_workspace_paths(&mut settings).await?;
 
-        let mut current = self.settings.write().await;
-        *current = settings;
+        Ok((settings, origins))
+    }
+
+    /// Loads `settings.yaml`, warning about deprecated keys and unknown
+    /// editor/terminal references rather than silently ignoring them, then
+    /// merges it with the system-wide and env-var layers (see `layering`)
+    /// into `base_settings`.
+    /// `known_editor_ids`/`known_terminal_names` come from the caller since
+    /// `settings` has no dependency on the `editors` module.
+    pub async fn load(&self, known_editor_ids: &[String], known_terminal_names: &[&str]) -> Result<()> {
+        let user_settings = if !self.config_path.exists() {
+            info!("No existing settings file found, using defaults");
+            Settings::default()
+        } else {
+            let contents = tokio::fs::read_to_string(&self.config_path)
+                .await
+                .context("Failed to read settings file")?;
+
+            let raw: serde_yaml::Value =
+                serde_yaml::from_str(&contents).context("Failed to parse YAML settings")?;
+            validation::warn_deprecated_aliases(&raw);
+
+            serde_yaml::from_value(raw).with_context(|| {
+                "Invalid settings.yaml - check for unknown or misspelled keys (run with \
+                 --print-config for a fully-commented example)"
+                    .to_string()
+            })?
+        };
+
+        let (settings, origins) = self.merge_with_lower_layers(user_settings).await?;
+
+        validation::warn_unknown_references(&settings, known_editor_ids, known_terminal_names);
+
+        *self.base_settings.write().await = settings;
+        *self.layer_origins.write().await = origins;
+        self.recompute_effective().await;
 
         info!("Settings loaded from {:?}", self.config_path);
         Ok(())
     }
 
-    pub async fn save(&self, mut settings: Settings) -> Result<()> {
+    /// Subscribes to hot-reload notifications from `start_watching`. Each
+    /// subscriber receives every event from the point it subscribes
+    /// onward; a subscriber that falls too far behind the channel's
+    /// capacity sees `RecvError::Lagged` rather than blocking the watcher.
+    pub fn watch(&self) -> broadcast::Receiver<SettingsReloadEvent> {
+        self.reload_tx.subscribe()
+    }
+
+    /// Spawns a task that watches `config_path` for changes and re-runs
+    /// `load()` (debounced over `RELOAD_DEBOUNCE`, same batching idea as
+    /// `workspace_watcher::WorkspaceWatcher`), broadcasting the outcome
+    /// over `watch()` so subscribers - the editor registry, the URL
+    /// router - pick up an edited `settings.yaml` without a restart.
+    /// Watches the config directory rather than the file itself so an
+    /// editor's atomic rename-on-save doesn't orphan the watch, the same
+    /// approach `protocol_handler::WorkspaceFileWatcher` takes.
+    pub fn start_watching(
+        self: Arc<Self>,
+        known_editor_ids: Vec<String>,
+        known_terminal_names: &'static [&'static str],
+    ) {
+        let Some(parent) = self.config_path.parent().map(Path::to_path_buf) else {
+            warn!("Settings file {:?} has no parent directory, not watching", self.config_path);
+            return;
+        };
+        let Some(file_name) = self.config_path.file_name().map(|n| n.to_owned()) else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                if let Ok(event) = res {
+                    if event.paths.iter().any(|p| p.file_name() == Some(file_name.as_os_str())) {
+                        let _ = tx.send(());
+                    }
+                }
+            });
+
+            let mut watcher = match watcher {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    warn!("Failed to create settings file watcher: {}", e);
+                    return;
+                }
+            };
+
+            if let Err(e) = watcher.watch(&parent, RecursiveMode::NonRecursive) {
+                warn!("Failed to watch {:?} for settings changes: {}", parent, e);
+                return;
+            }
+
+            info!("Watching {:?} for settings changes", self.config_path);
+
+            loop {
+                if rx.recv().await.is_none() {
+                    return;
+                }
+                Self::drain_and_settle(&mut rx).await;
+
+                match self.load(&known_editor_ids, known_terminal_names).await {
+                    Ok(()) => {
+                        let settings = self.get().await;
+                        info!("Reloaded settings from {:?}", self.config_path);
+                        let _ = self.reload_tx.send(SettingsReloadEvent::Reloaded(settings));
+                    }
+                    Err(e) => {
+                        warn!("Failed to reload settings from {:?}: {}", self.config_path, e);
+                        let _ = self.reload_tx.send(SettingsReloadEvent::ParseError(e.to_string()));
+                    }
+                }
+            }
+        });
+    }
+
+    /// Swallows further events arriving within `RELOAD_DEBOUNCE`, so a
+    /// burst of writes collapses into a single reload.
+    async fn drain_and_settle(rx: &mut mpsc::UnboundedReceiver<()>) {
+        loop {
+            tokio::select! {
+                event = rx.recv() => {
+                    if event.is_none() {
+                        return;
+                    }
+                }
+                _ = tokio::time::sleep(RELOAD_DEBOUNCE) => return,
+            }
+        }
+    }
+
+    pub async fn save(&self, settings: Settings) -> Result<()> {
         let yaml_string =
             serde_yaml::to_string(&settings).context("Failed to serialize settings to YAML")?;
 
@@ -72,26 +289,74 @@ impl SettingsManager {
             .await
             .context("Failed to write settings file")?;
 
-        // Normalize paths before storing in memory
-        self.normalize_workspace_paths(&mut settings).await?;
+        let (merged, origins) = self.merge_with_lower_layers(settings).await?;
 
-        let mut current = self.settings.write().await;
-        *current = settings;
+        *self.base_settings.write().await = merged;
+        *self.layer_origins.write().await = origins;
+        self.recompute_effective().await;
 
         info!("Settings saved to {:?}", self.config_path);
         Ok(())
     }
 
+    /// Applies `runtime_overrides` on top of `base_settings` and stores the
+    /// result as the merged view everything else reads. Called after every
+    /// `load()`/`save()` and every `set_runtime_override`/
+    /// `clear_runtime_override`, so the effective value never requires a
+    /// file reload to pick up a runtime change.
+    async fn recompute_effective(&self) {
+        let mut effective = self.base_settings.read().await.clone();
+        let mut origins = self.layer_origins.read().await.clone();
+
+        let runtime = self.runtime_overrides.read().await;
+        layering::apply_runtime_overrides(&mut effective, &runtime, &mut origins);
+        drop(runtime);
+
+        *self.origins.write().await = origins;
+        *self.settings.write().await = effective;
+    }
+
+    /// Sets an in-process override for `key` (e.g. `"defaults.editor"`),
+    /// applied on top of every file/env layer until cleared or the process
+    /// exits - useful for a `--editor` CLI flag or a debug toggle that
+    /// shouldn't require editing `settings.yaml`. Takes effect immediately
+    /// rather than waiting for the next reload.
+    pub async fn set_runtime_override(&self, key: &str, value: &str) {
+        self.runtime_overrides.write().await.insert(key.to_string(), value.to_string());
+        self.recompute_effective().await;
+    }
+
+    /// Removes a runtime override set via `set_runtime_override`, falling
+    /// back to whatever the file/env layers resolve to for `key`.
+    pub async fn clear_runtime_override(&self, key: &str) {
+        self.runtime_overrides.write().await.remove(key);
+        self.recompute_effective().await;
+    }
+
+    /// Which layer supplied the currently-effective value of `key` - one of
+    /// `"defaults.editor"`, `"defaults.preferred_terminal"`,
+    /// `"defaults.allow_non_workspace_files"`, the only keys simple enough
+    /// to have dedicated env-var/runtime overrides. `None` means the
+    /// built-in default is in effect, i.e. no layer above it set anything.
+    pub async fn config_origin(&self, key: &str) -> Option<ConfigLayer> {
+        self.origins.read().await.get(key).copied()
+    }
+
     pub async fn get(&self) -> Settings {
         self.settings.read().await.clone()
     }
 
+    /// Compares `path` against each workspace's `normalized_path` in
+    /// normalized form on both sides, so a caller's relative or `~`-prefixed
+    /// path still matches a workspace regardless of symlinks or whether
+    /// either path currently exists on disk.
     pub async fn get_workspace_for_path(&self, path: &Path) -> Option<WorkspaceConfig> {
+        let query = normalize::normalize_lexical(&path.to_string_lossy());
         let settings = self.settings.read().await;
 
         for workspace in &settings.workspaces {
             if let Some(normalized) = &workspace.normalized_path {
-                if path.starts_with(normalized) {
+                if query.as_path().starts_with(normalized.as_path()) {
                     return Some(workspace.clone());
                 }
             }
@@ -115,16 +380,38 @@ impl SettingsManager {
         settings.defaults.preferred_terminal.clone()
     }
 
+    pub async fn generates_compilation_db(&self) -> bool {
+        let settings = self.settings.read().await;
+        settings.defaults.generate_compilation_db
+    }
+
+    /// Explicit binary path or launch command configured for `editor_id`
+    /// under `defaults.editor_paths`, if any.
+    pub async fn get_editor_path(&self, editor_id: &str) -> Option<String> {
+        let settings = self.settings.read().await;
+        settings.defaults.editor_paths.get(editor_id).cloned()
+    }
+
+    /// Personal access token configured for `host` (e.g. `github.com`)
+    /// under `defaults.git_host_tokens`, if any.
+    pub async fn get_git_host_token(&self, host: &str) -> Option<String> {
+        let settings = self.settings.read().await;
+        settings.defaults.git_host_tokens.get(host).cloned()
+    }
+
+    /// Editor configured under `defaults.tag_defaults` for the first of
+    /// `tags` that has one, used as a workspace's editor fallback when the
+    /// workspace itself doesn't set an `editor`.
+    pub async fn resolve_tag_editor(&self, tags: &[String]) -> Option<String> {
+        let settings = self.settings.read().await;
+        tags.iter()
+            .find_map(|tag| settings.defaults.tag_defaults.get(tag))
+            .and_then(|defaults| defaults.editor.clone())
+    }
+
     async fn normalize_workspace_paths(&self, settings: &mut Settings) -> Result<()> {
         for workspace in &mut settings.workspaces {
-            match Self::normalize_path(&workspace.path) {
-                Ok(normalized) => {
-                    workspace.normalized_path = Some(normalized);
-                }
-                Err(e) => {
-                    warn!("Failed to normalize path '{}': {}", workspace.path, e);
-                }
-            }
+            workspace.normalized_path = Some(Self::normalize_path(&workspace.path));
         }
 
         // Validate workspace names
@@ -161,18 +448,12 @@ impl SettingsManager {
         }
     }
 
-    fn normalize_path(path: &str) -> Result<PathBuf> {
-        let expanded = shellexpand::tilde(path);
-        let path = Path::new(expanded.as_ref());
-
-        if path.is_absolute() {
-            Ok(path.to_path_buf())
-        } else {
-            std::env::current_dir()
-                .context("Failed to get current directory")?
-                .join(path)
-                .canonicalize()
-                .context("Failed to canonicalize path")
-        }
+    /// Lexically normalizes `path` (see `normalize::normalize_lexical`),
+    /// then attempts a symlink-resolving `canonicalize()` on top - purely
+    /// as a best-effort refinement, so a workspace whose path doesn't
+    /// exist yet (not yet cloned, an offline network mount) still gets a
+    /// stable, comparable `normalized_path` instead of being left `None`.
+    fn normalize_path(path: &str) -> NormalizedPath {
+        normalize::normalize_lexical(path).canonicalized()
     }
 }
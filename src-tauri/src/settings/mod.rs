@@ -1,8 +1,18 @@
 mod discovery;
+mod example;
+mod layering;
 mod manager;
 mod models;
+mod normalize;
+mod validation;
 
 pub use discovery::{SyncResult, WorkspaceSync};
-pub use manager::SettingsManager;
+pub use example::generate_example_config;
+pub use layering::ConfigLayer;
+pub use manager::{SettingsManager, SettingsReloadEvent};
 #[allow(unused_imports)]
-pub use models::{LastSeenData, Settings, WorkspaceConfig};
+pub use models::{
+    GitVendorConfig, LastSeenData, ProviderKind, SelfHostedProviderConfig, Settings,
+    WorkspaceConfig,
+};
+pub use normalize::{normalize_lexical, NormalizedPath};
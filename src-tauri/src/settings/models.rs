@@ -1,14 +1,20 @@
+use super::normalize::NormalizedPath;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use url::Url;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Settings {
     #[serde(default)]
     pub defaults: DefaultEditorConfig,
 
     #[serde(default, alias = "repos")]
     pub workspaces: Vec<WorkspaceConfig>,
+
+    #[serde(default)]
+    pub sync: SyncConfig,
 }
 
 impl Default for Settings {
@@ -16,6 +22,7 @@ impl Default for Settings {
         Self {
             defaults: DefaultEditorConfig::default(),
             workspaces: Vec::new(),
+            sync: SyncConfig::default(),
         }
     }
 }
@@ -27,6 +34,7 @@ impl Settings {
         Self {
             defaults: DefaultEditorConfig::with_detected_workspaces_folder(),
             workspaces: Vec::new(),
+            sync: SyncConfig::default(),
         }
     }
 }
@@ -50,6 +58,75 @@ pub struct DefaultEditorConfig {
 
     #[serde(default)]
     pub ignored_workspaces: Vec<String>,
+
+    /// Number of worker threads used to probe workspace activity
+    /// concurrently. `None` means "use the number of CPUs", mirroring
+    /// czkawka's `get_number_of_threads`.
+    #[serde(default)]
+    pub probe_worker_threads: Option<usize>,
+
+    /// When false (the default), `PathValidator` rejects paths that
+    /// `file_types::classify` resolves to `FileCategory::Executable`,
+    /// replacing the old hardcoded extension blocklist with a setting users
+    /// can opt out of.
+    #[serde(default = "default_allow_executable_files")]
+    pub allow_executable_files: bool,
+
+    /// Explicit binary path or launch command per editor id, required for
+    /// any editor whose `EditorManager::requires_configuration` is `true`
+    /// before `EditorDispatcher` will attempt to launch it.
+    #[serde(default)]
+    pub editor_paths: HashMap<String, String>,
+
+    /// Personal access token per git host (e.g. `github.com`), used by
+    /// `git_host::bulk_clone` to list and clone private repositories and to
+    /// avoid the low unauthenticated GitHub API rate limit.
+    #[serde(default)]
+    pub git_host_tokens: HashMap<String, String>,
+
+    /// Fallback editor/path-policy per workspace tag, consulted when a
+    /// tagged `WorkspaceConfig` doesn't set its own `editor`/`enable_paths`/
+    /// `disable_paths`. See `TagDefaults`.
+    #[serde(default)]
+    pub tag_defaults: HashMap<String, TagDefaults>,
+
+    /// Default editor per `project_kind::ProjectInfo::kind` (e.g. `"rust"`,
+    /// `"node"`), used by `WorkspaceSync::sync` to set the `editor` of a
+    /// newly auto-discovered workspace instead of leaving it empty.
+    #[serde(default)]
+    pub project_kind_editors: HashMap<String, String>,
+
+    /// Custom shorthand prefix -> host mapping for `git_url::expand`, e.g.
+    /// `work` -> `git.corp.example.com` so `work:team/repo` clones from an
+    /// internal host the same way `gh:owner/repo` clones from GitHub.
+    #[serde(default)]
+    pub custom_git_vendors: HashMap<String, GitVendorConfig>,
+
+    /// How many directories below `default_workspaces_folder` `WorkspaceSync`
+    /// descends looking for repos. See `repo_discovery::DEFAULT_MAX_DEPTH`.
+    #[serde(default = "default_workspace_scan_max_depth")]
+    pub workspace_scan_max_depth: usize,
+
+    /// Extra gitignore-style glob patterns (e.g. `**/node_modules`,
+    /// `**/target`) applied on top of each scanned directory's own
+    /// `.gitignore`/`.ignore`, so a workspace scan can prune heavy
+    /// directories the repo itself doesn't ignore.
+    #[serde(default)]
+    pub workspace_scan_ignore_patterns: Vec<String>,
+
+    /// When true, `EditorDispatcher` sets `OpenOptions.generate_compilation_db`
+    /// on every open, so editors that can produce one (currently
+    /// `XcodeManager`) refresh `compile_commands.json` for external
+    /// LSP/indexers. Off by default since it's an extra build invocation.
+    #[serde(default)]
+    pub generate_compilation_db: bool,
+
+    /// Self-hosted GitLab/Gitea/etc. instances on a custom domain, registered
+    /// with `SrcuriParser::register_provider_host`/`_suffix` at startup so a
+    /// `srcuri://` provider-passthrough link against one resolves without
+    /// `host` needing a dot or a known provider label in it.
+    #[serde(default)]
+    pub self_hosted_providers: Vec<SelfHostedProviderConfig>,
 }
 
 fn default_editor() -> String {
@@ -65,14 +142,7 @@ fn default_terminal() -> String {
 }
 
 fn count_git_repos(dir: &std::path::Path) -> usize {
-    std::fs::read_dir(dir)
-        .map(|entries| {
-            entries
-                .filter_map(|e| e.ok())
-                .filter(|e| e.path().join(".git").is_dir())
-                .count()
-        })
-        .unwrap_or(0)
+    crate::repo_discovery::scan_for_repos_default(dir).count()
 }
 
 fn default_workspaces_folder() -> String {
@@ -113,6 +183,14 @@ fn default_auto_switch_clean_branches() -> bool {
     true
 }
 
+fn default_allow_executable_files() -> bool {
+    false
+}
+
+fn default_workspace_scan_max_depth() -> usize {
+    crate::repo_discovery::DEFAULT_MAX_DEPTH
+}
+
 impl Default for DefaultEditorConfig {
     fn default() -> Self {
         Self {
@@ -122,6 +200,17 @@ impl Default for DefaultEditorConfig {
             default_workspaces_folder: default_workspaces_folder(),
             auto_switch_clean_branches: default_auto_switch_clean_branches(),
             ignored_workspaces: Vec::new(),
+            probe_worker_threads: None,
+            allow_executable_files: default_allow_executable_files(),
+            editor_paths: HashMap::new(),
+            git_host_tokens: HashMap::new(),
+            tag_defaults: HashMap::new(),
+            project_kind_editors: HashMap::new(),
+            custom_git_vendors: HashMap::new(),
+            workspace_scan_max_depth: default_workspace_scan_max_depth(),
+            workspace_scan_ignore_patterns: Vec::new(),
+            generate_compilation_db: false,
+            self_hosted_providers: Vec::new(),
         }
     }
 }
@@ -148,8 +237,100 @@ pub struct WorkspaceConfig {
     #[serde(default)]
     pub auto_discovered: bool,
 
+    /// Glob patterns (e.g. `src/**`, `tests/**`) a `srcuri://` link must
+    /// match at least one of to open a file in this workspace. `None` or an
+    /// empty list means no restriction, matching pre-existing behavior.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_paths: Option<Vec<String>>,
+
+    /// Glob patterns (e.g. `target/**`, `.env`) that always block a
+    /// `srcuri://` link from opening a matching path, even if it also
+    /// matches `enable_paths`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_paths: Option<Vec<String>>,
+
+    /// Free-form labels (e.g. `work`, `rust`, `client-x`) for grouping and
+    /// filtering workspaces, borrowed from the tag model `fw` uses for its
+    /// workspace directories. A tag with a matching entry in
+    /// `defaults.tag_defaults` also supplies a fallback editor and
+    /// enable/disable path policy when this workspace doesn't set its own.
+    #[serde(default)]
+    pub tags: Vec<String>,
+
     #[serde(skip)]
-    pub normalized_path: Option<PathBuf>,
+    pub normalized_path: Option<NormalizedPath>,
+}
+
+/// Fallback editor and path policy shared by every workspace tagged with a
+/// given label, so tagging a workspace `work` (say) can carry that group's
+/// conventions without repeating them on each individual `WorkspaceConfig`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TagDefaults {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_paths: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_paths: Option<Vec<String>>,
+}
+
+/// One entry of `DefaultEditorConfig::custom_git_vendors`: the host a
+/// shorthand prefix expands to, and whether `git_url::expand` should build
+/// an SSH (`git@host:path.git`) or HTTPS (`https://host/path`) URL for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitVendorConfig {
+    pub host: String,
+    #[serde(default)]
+    pub use_ssh: bool,
+}
+
+/// One entry of `DefaultEditorConfig::self_hosted_providers`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfHostedProviderConfig {
+    pub host: String,
+    pub provider: ProviderKind,
+    /// When true, `host` is registered as a suffix (matches any subdomain,
+    /// e.g. `.corp.example.com`) rather than as an exact hostname.
+    #[serde(default)]
+    pub match_subdomains: bool,
+}
+
+/// Mirrors `srcuri_core::Provider` with `Serialize`/`Deserialize` so it can
+/// appear in `settings.yaml` - `srcuri_core::Provider` itself doesn't derive
+/// either, srcuri-core having no serde dependency of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderKind {
+    GitHub,
+    GitLab,
+    Bitbucket,
+    Gitea,
+    Codeberg,
+    AzureDevOps,
+}
+
+impl ProviderKind {
+    pub fn to_provider(self) -> srcuri_core::Provider {
+        match self {
+            ProviderKind::GitHub => srcuri_core::Provider::GitHub,
+            ProviderKind::GitLab => srcuri_core::Provider::GitLab,
+            ProviderKind::Bitbucket => srcuri_core::Provider::Bitbucket,
+            ProviderKind::Gitea => srcuri_core::Provider::Gitea,
+            ProviderKind::Codeberg => srcuri_core::Provider::Codeberg,
+            ProviderKind::AzureDevOps => srcuri_core::Provider::AzureDevOps,
+        }
+    }
+}
+
+/// Git-backed sync of `settings.yaml` across machines - see
+/// `settings_sync::SyncManager`. Empty (no `remote`) by default, which
+/// leaves sync entirely opt-in.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<Url>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -0,0 +1,143 @@
+//! Purely lexical path normalization that doesn't require the path to
+//! exist on disk - unlike `Path::canonicalize`, which fails outright for a
+//! workspace that hasn't been cloned yet or a network mount that's
+//! currently offline, leaving a workspace permanently unmatchable.
+//! Mirrors the homesync `Normalize`/`NormalPathBuf` approach: expand `~`,
+//! join a relative path against the current directory, and resolve
+//! `.`/`..` components by hand, with `canonicalize` demoted to an optional
+//! best-effort refinement on top.
+
+use std::path::{Component, Path, PathBuf};
+
+/// A workspace path after lexical normalization - always computable
+/// regardless of whether the path exists, unlike a `canonicalize()`'d
+/// path. `Deref`s to `Path` so existing call sites that compare, join, or
+/// display a `normalized_path` don't need to change.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NormalizedPath(PathBuf);
+
+impl NormalizedPath {
+    pub fn as_path(&self) -> &Path {
+        &self.0
+    }
+
+    pub fn into_path_buf(self) -> PathBuf {
+        self.0
+    }
+
+    /// Wraps `path` as already-normalized without re-running the lexical
+    /// algorithm - for a path a caller already knows is canonical, e.g. one
+    /// a filesystem walk (`repo_discovery::scan_for_repos`) just returned.
+    pub fn from_existing(path: PathBuf) -> Self {
+        Self(path)
+    }
+
+    /// Best-effort symlink-resolving refinement on top of the lexical
+    /// result - attempted but never required, since the point of lexical
+    /// normalization is to still produce a usable path when this fails
+    /// (path doesn't exist yet, offline network mount, permission denied).
+    pub fn canonicalized(&self) -> NormalizedPath {
+        match self.0.canonicalize() {
+            Ok(resolved) => NormalizedPath(resolved),
+            Err(_) => self.clone(),
+        }
+    }
+}
+
+impl std::ops::Deref for NormalizedPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for NormalizedPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for NormalizedPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.display())
+    }
+}
+
+/// Expands `~`, joins a relative `path` against `std::env::current_dir()`,
+/// then lexically resolves `.`/`..` components - never touches the
+/// filesystem beyond reading the current directory, so it always
+/// succeeds.
+pub fn normalize_lexical(path: &str) -> NormalizedPath {
+    let expanded = shellexpand::tilde(path);
+    let expanded = Path::new(expanded.as_ref());
+
+    let joined = if expanded.is_absolute() {
+        expanded.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(expanded))
+            .unwrap_or_else(|_| expanded.to_path_buf())
+    };
+
+    NormalizedPath(resolve_dot_components(&joined))
+}
+
+/// Resolves `.` and `..` components purely lexically: `.` is dropped, `..`
+/// pops the last pushed `Normal` component, is absorbed at the root (the
+/// parent of `/` is `/`), and is otherwise kept (a relative path with more
+/// `..` than it has components, e.g. `../../x`). Doesn't consult the
+/// filesystem, so it doesn't follow symlinks - `NormalizedPath::canonicalized`
+/// is the opt-in refinement for that.
+fn resolve_dot_components(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match result.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    result.pop();
+                }
+                Some(Component::RootDir) => {}
+                _ => result.push(component),
+            },
+            other => result.push(other),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_dot_and_dotdot_components() {
+        let normalized = normalize_lexical("/a/b/../c/./d");
+        assert_eq!(normalized.as_path(), Path::new("/a/c/d"));
+    }
+
+    #[test]
+    fn joins_relative_paths_against_current_dir() {
+        let cwd = std::env::current_dir().unwrap();
+        let normalized = normalize_lexical("some/relative/path");
+        assert_eq!(normalized.as_path(), cwd.join("some/relative/path"));
+    }
+
+    #[test]
+    fn collapses_a_parent_dir_component_at_the_root() {
+        let normalized = normalize_lexical("/../escaped");
+        assert_eq!(normalized.as_path(), Path::new("/escaped"));
+    }
+
+    #[test]
+    fn succeeds_for_a_path_that_does_not_exist() {
+        let normalized = normalize_lexical("/definitely/does/not/exist/anywhere");
+        assert_eq!(
+            normalized.as_path(),
+            Path::new("/definitely/does/not/exist/anywhere")
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use super::models::Settings;
+use tracing::warn;
+
+/// `(deprecated key, current key)` pairs accepted at the document root via
+/// `#[serde(alias = ...)]`. Kept functional so an old `settings.yaml` still
+/// loads, but worth nudging users off of - see [`warn_deprecated_aliases`].
+const DEPRECATED_TOP_LEVEL_ALIASES: &[(&str, &str)] = &[("repos", "workspaces")];
+
+/// Same idea as `DEPRECATED_TOP_LEVEL_ALIASES`, but for keys nested under
+/// `defaults`.
+const DEPRECATED_DEFAULTS_ALIASES: &[(&str, &str)] = &[("repo_base_dir", "default_workspaces_folder")];
+
+/// Scans the raw YAML document for deprecated key names `#[serde(alias =
+/// ...)]` accepts silently, and warns recommending the current name -
+/// inspired by topgrade's `check_deprecated!`, so a config keeps working
+/// but the user learns to migrate rather than carrying a stale key forever.
+pub fn warn_deprecated_aliases(raw: &serde_yaml::Value) {
+    let Some(mapping) = raw.as_mapping() else {
+        return;
+    };
+
+    for (deprecated, current) in DEPRECATED_TOP_LEVEL_ALIASES {
+        if mapping.contains_key(serde_yaml::Value::from(*deprecated)) {
+            warn!(
+                "settings.yaml: key '{}' is deprecated, use '{}' instead",
+                deprecated, current
+            );
+        }
+    }
+
+    let Some(defaults) = mapping
+        .get(serde_yaml::Value::from("defaults"))
+        .and_then(|v| v.as_mapping())
+    else {
+        return;
+    };
+
+    for (deprecated, current) in DEPRECATED_DEFAULTS_ALIASES {
+        if defaults.contains_key(serde_yaml::Value::from(*deprecated)) {
+            warn!(
+                "settings.yaml: key 'defaults.{}' is deprecated, use 'defaults.{}' instead",
+                deprecated, current
+            );
+        }
+    }
+}
+
+/// Cross-references a loaded `Settings` against what's actually installed.
+/// Unlike `deny_unknown_fields`, a mismatch here isn't fatal - a typo'd
+/// editor id or terminal name would otherwise just silently never match
+/// anything at dispatch time, so this surfaces it as a warning up front
+/// instead.
+pub fn warn_unknown_references(
+    settings: &Settings,
+    known_editor_ids: &[String],
+    known_terminal_names: &[&str],
+) {
+    let is_known_editor = |id: &str| known_editor_ids.iter().any(|known| known == id);
+
+    if !settings.defaults.editor.is_empty() && !is_known_editor(&settings.defaults.editor) {
+        warn!(
+            "settings.yaml: defaults.editor '{}' does not match any known editor id",
+            settings.defaults.editor
+        );
+    }
+
+    let terminal = &settings.defaults.preferred_terminal;
+    if terminal != "auto"
+        && !known_terminal_names
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(terminal))
+    {
+        warn!(
+            "settings.yaml: defaults.preferred_terminal '{}' is not a recognized built-in \
+             terminal name - it will only work if it matches an installed XDG terminal's name",
+            terminal
+        );
+    }
+
+    for workspace in &settings.workspaces {
+        if workspace.editor.is_empty() || is_known_editor(&workspace.editor) {
+            continue;
+        }
+        warn!(
+            "settings.yaml: workspace '{}' has editor '{}', which does not match any known editor id",
+            workspace.name.as_deref().unwrap_or(&workspace.path),
+            workspace.editor
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::models::WorkspaceConfig;
+
+    #[test]
+    fn warn_deprecated_aliases_tolerates_empty_mapping() {
+        let raw: serde_yaml::Value = serde_yaml::from_str("{}").unwrap();
+        warn_deprecated_aliases(&raw);
+    }
+
+    #[test]
+    fn warn_unknown_references_accepts_empty_editor_as_unset() {
+        let mut settings = Settings::default();
+        settings.defaults.editor = String::new();
+        warn_unknown_references(&settings, &[], &[]);
+    }
+
+    #[test]
+    fn warn_unknown_references_accepts_known_workspace_editor() {
+        let mut settings = Settings::default();
+        settings.workspaces.push(WorkspaceConfig {
+            path: "/tmp/repo".to_string(),
+            name: None,
+            editor: "vscode".to_string(),
+            auto_discovered: false,
+            enable_paths: None,
+            disable_paths: None,
+            tags: Vec::new(),
+            normalized_path: None,
+        });
+
+        warn_unknown_references(&settings, &["vscode".to_string()], &["auto"]);
+    }
+}
@@ -0,0 +1,289 @@
+//! Git-backed sync of `settings.yaml` across machines, modeled on homesync's
+//! daemon/push/pull flow: a plain git repo lives in the same directory as
+//! `settings.yaml`, `commit_settings` is called right after every
+//! `SettingsManager::save`, and `sync_push`/`sync_pull` fetch/merge against
+//! whatever remote the user configured under `sync.remote`.
+//!
+//! Every git invocation goes through `git_command_log::run_git_command`, the
+//! same CLI-subprocess helper `protocol_handler::git` uses for clone/checkout/
+//! push - `gix` is reserved in this codebase for read-only introspection
+//! (see `workspace_mru::git_signals`) and is never used for a mutating or
+//! remote-talking operation.
+
+use crate::git_command_log::run_git_command;
+use crate::settings::SettingsManager;
+use anyhow::Context;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::info;
+use url::Url;
+
+/// The file `SyncManager` tracks inside its repo - always the same name
+/// `SettingsManager::config_path` writes, never a full path, so every git
+/// invocation below stays relative to `repo_dir`.
+const SETTINGS_FILE_NAME: &str = "settings.yaml";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SyncError {
+    #[error("No sync remote is configured - set sync.remote in settings.yaml first")]
+    NotConfigured,
+
+    #[error("git command failed: {stderr}")]
+    CommandFailed { stderr: String },
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl SyncError {
+    fn command_failed(output: &std::process::Output) -> Self {
+        Self::CommandFailed {
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }
+    }
+}
+
+/// Outcome of [`SyncManager::sync_pull`].
+#[derive(Debug, Clone, Serialize)]
+pub enum SyncPullOutcome {
+    /// The remote had nothing `settings.yaml` didn't already have locally.
+    UpToDate,
+    /// The remote copy was merged in and `SettingsManager::load` re-ran to
+    /// apply it.
+    Pulled,
+    /// `settings.yaml` has uncommitted local edits that conflict with the
+    /// remote copy - nothing was merged, `diff` is `git diff` between the
+    /// working tree and the fetched remote tip so the caller can show the
+    /// user what would be overwritten.
+    Conflict { diff: String },
+}
+
+/// Keeps `settings.yaml` under version control in a plain git repo next to
+/// it, and syncs that repo against a user-configured remote. Construction
+/// just records `repo_dir`; the repo itself is created lazily by
+/// `ensure_repo_initialized` the first time it's actually needed, so a user
+/// who never sets `sync.remote` never gets a `.git` directory they didn't
+/// ask for.
+pub struct SyncManager {
+    settings_manager: Arc<SettingsManager>,
+    repo_dir: PathBuf,
+}
+
+impl SyncManager {
+    pub fn new(settings_manager: Arc<SettingsManager>) -> Self {
+        let repo_dir = settings_manager
+            .config_path()
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        Self { settings_manager, repo_dir }
+    }
+
+    fn repo_dir_str(&self) -> String {
+        self.repo_dir.to_string_lossy().to_string()
+    }
+
+    fn is_repo_initialized(&self) -> bool {
+        self.repo_dir.join(".git").exists()
+    }
+
+    /// Idempotent `git init` in `repo_dir` - a no-op if `.git` already
+    /// exists.
+    fn ensure_repo_initialized(&self) -> Result<(), SyncError> {
+        if self.is_repo_initialized() {
+            return Ok(());
+        }
+
+        let dir = self.repo_dir_str();
+        let output =
+            run_git_command(&dir, &["init"]).context("Failed to execute git init")?;
+        if !output.status.success() {
+            return Err(SyncError::command_failed(&output));
+        }
+
+        info!("Initialized settings sync repo in {:?}", self.repo_dir);
+        Ok(())
+    }
+
+    /// Points `origin` at `remote`, adding it if missing or updating it if
+    /// it points somewhere else - so changing `sync.remote` and syncing
+    /// again just works without the user having to fix up `origin` by hand.
+    fn ensure_remote_configured(&self, remote: &Url) -> Result<(), SyncError> {
+        let dir = self.repo_dir_str();
+        let remote_str = remote.as_str();
+
+        let current = run_git_command(&dir, &["remote", "get-url", "origin"])
+            .context("Failed to execute git remote get-url")?;
+
+        if current.status.success() {
+            let current_url = String::from_utf8_lossy(&current.stdout).trim().to_string();
+            if current_url == remote_str {
+                return Ok(());
+            }
+            let output = run_git_command(&dir, &["remote", "set-url", "origin", remote_str])
+                .context("Failed to execute git remote set-url")?;
+            if !output.status.success() {
+                return Err(SyncError::command_failed(&output));
+            }
+        } else {
+            let output = run_git_command(&dir, &["remote", "add", "origin", remote_str])
+                .context("Failed to execute git remote add")?;
+            if !output.status.success() {
+                return Err(SyncError::command_failed(&output));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stages and commits `settings.yaml` if it changed since the last
+    /// commit, a no-op otherwise. Meant to be called right after
+    /// `SettingsManager::save`, making `save` the natural commit point
+    /// without `SettingsManager` itself knowing sync exists.
+    pub fn commit_settings(&self) -> Result<(), SyncError> {
+        self.ensure_repo_initialized()?;
+        let dir = self.repo_dir_str();
+
+        let add = run_git_command(&dir, &["add", "--", SETTINGS_FILE_NAME])
+            .context("Failed to execute git add")?;
+        if !add.status.success() {
+            return Err(SyncError::command_failed(&add));
+        }
+
+        let staged = run_git_command(&dir, &["diff", "--cached", "--quiet"])
+            .context("Failed to execute git diff --cached")?;
+        if staged.status.success() {
+            // Nothing staged - settings.yaml didn't actually change.
+            return Ok(());
+        }
+
+        let commit = run_git_command(&dir, &["commit", "-m", "Update settings.yaml"])
+            .context("Failed to execute git commit")?;
+        if !commit.status.success() {
+            return Err(SyncError::command_failed(&commit));
+        }
+
+        info!("Committed settings.yaml to sync repo");
+        Ok(())
+    }
+
+    /// Returns the currently checked-out branch name, falling back to
+    /// `"master"` for a just-`init`'d repo that has no commits (and
+    /// therefore no branch) yet.
+    fn current_branch(&self) -> Result<String, SyncError> {
+        let dir = self.repo_dir_str();
+        let output = run_git_command(&dir, &["rev-parse", "--abbrev-ref", "HEAD"])
+            .context("Failed to execute git rev-parse")?;
+
+        if !output.status.success() {
+            return Ok("master".to_string());
+        }
+
+        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(if branch.is_empty() { "master".to_string() } else { branch })
+    }
+
+    async fn configured_remote(&self) -> Result<Url, SyncError> {
+        let settings = self.settings_manager.get().await;
+        settings.sync.remote.clone().ok_or(SyncError::NotConfigured)
+    }
+
+    /// Commits any pending `settings.yaml` changes and pushes the current
+    /// branch to `origin`.
+    pub async fn sync_push(&self) -> Result<(), SyncError> {
+        let remote = self.configured_remote().await?;
+        self.ensure_repo_initialized()?;
+        self.ensure_remote_configured(&remote)?;
+        self.commit_settings()?;
+
+        let dir = self.repo_dir_str();
+        let branch = self.current_branch()?;
+        let output = run_git_command(&dir, &["push", "origin", &branch])
+            .context("Failed to execute git push")?;
+        if !output.status.success() {
+            return Err(SyncError::command_failed(&output));
+        }
+
+        info!("Pushed settings sync repo to origin/{}", branch);
+        Ok(())
+    }
+
+    /// Fetches `origin`, then either fast-forward merges it and reloads
+    /// settings, or - if `settings.yaml` has uncommitted local edits that
+    /// conflict with the fetched copy - leaves the working tree untouched
+    /// and returns a diff instead of overwriting blindly.
+    /// `known_editor_ids`/`known_terminal_names` are forwarded to
+    /// `SettingsManager::load` for the same reason `start_watching` needs
+    /// them: `settings` has no dependency on the `editors` module.
+    pub async fn sync_pull(
+        &self,
+        known_editor_ids: &[String],
+        known_terminal_names: &[&str],
+    ) -> Result<SyncPullOutcome, SyncError> {
+        let remote = self.configured_remote().await?;
+        self.ensure_repo_initialized()?;
+        self.ensure_remote_configured(&remote)?;
+
+        let dir = self.repo_dir_str();
+
+        let status = run_git_command(&dir, &["status", "--porcelain", "--", SETTINGS_FILE_NAME])
+            .context("Failed to execute git status")?;
+        if !status.status.success() {
+            return Err(SyncError::command_failed(&status));
+        }
+        let is_dirty = !String::from_utf8_lossy(&status.stdout).trim().is_empty();
+
+        let fetch = run_git_command(&dir, &["fetch", "origin"])
+            .context("Failed to execute git fetch")?;
+        if !fetch.status.success() {
+            return Err(SyncError::command_failed(&fetch));
+        }
+
+        let branch = self.current_branch()?;
+        let remote_ref = format!("origin/{}", branch);
+
+        if is_dirty {
+            // `git diff <ref> -- path` compares the index, not the working
+            // tree, against `ref` - for an untracked settings.yaml (no index
+            // entry at all) that shows up as the file being deleted rather
+            // than the real content difference. `add -N` (intent-to-add)
+            // records the file in the index at its current working-tree
+            // content without staging it for commit, so the diff below is
+            // computed against real bytes either way.
+            let intent_to_add = run_git_command(&dir, &["add", "-N", "--", SETTINGS_FILE_NAME])
+                .context("Failed to execute git add -N")?;
+            if !intent_to_add.status.success() {
+                return Err(SyncError::command_failed(&intent_to_add));
+            }
+
+            let diff = run_git_command(&dir, &["diff", &remote_ref, "--", SETTINGS_FILE_NAME])
+                .context("Failed to execute git diff")?;
+            if !diff.status.success() {
+                return Err(SyncError::command_failed(&diff));
+            }
+            return Ok(SyncPullOutcome::Conflict {
+                diff: String::from_utf8_lossy(&diff.stdout).to_string(),
+            });
+        }
+
+        let merge = run_git_command(&dir, &["merge", "--ff-only", &remote_ref])
+            .context("Failed to execute git merge")?;
+        if !merge.status.success() {
+            return Err(SyncError::command_failed(&merge));
+        }
+
+        if String::from_utf8_lossy(&merge.stdout).contains("Already up to date") {
+            return Ok(SyncPullOutcome::UpToDate);
+        }
+
+        self.settings_manager
+            .load(known_editor_ids, known_terminal_names)
+            .await
+            .map_err(SyncError::Other)?;
+
+        info!("Pulled settings sync repo from {}", remote_ref);
+        Ok(SyncPullOutcome::Pulled)
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A parsed freedesktop `.desktop` entry, limited to the fields we care
+/// about for mapping a window to one of our known editor ids. See the
+/// Desktop Entry Specification for the full key set.
+#[derive(Debug, Clone)]
+struct DesktopEntry {
+    name: String,
+    exec: String,
+    wm_class: Option<String>,
+    mime_types: Option<String>,
+    /// `Terminal=true` - the entry's `Exec` expects to run attached to a
+    /// terminal emulator rather than as a standalone GUI process.
+    terminal: bool,
+}
+
+impl DesktopEntry {
+    /// The `Exec` value with field codes (`%f`, `%F`, `%u`, `%U`, `%i`, `%c`,
+    /// `%k`, `%%`, ...) stripped, so the remainder can be split into an argv.
+    fn exec_without_field_codes(&self) -> String {
+        let mut result = String::with_capacity(self.exec.len());
+        let mut chars = self.exec.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c == '%' {
+                if let Some(&code) = chars.peek() {
+                    chars.next();
+                    if code == '%' {
+                        result.push('%');
+                    }
+                    continue;
+                }
+            }
+            result.push(c);
+        }
+
+        result.trim().to_string()
+    }
+
+    /// The resolved path to the binary `Exec` launches, i.e. the first
+    /// whitespace-separated token once field codes are stripped.
+    fn binary_path(&self) -> Option<PathBuf> {
+        self.exec_without_field_codes()
+            .split_whitespace()
+            .next()
+            .map(PathBuf::from)
+    }
+}
+
+/// Directories to scan for `.desktop` files, per the XDG Base Directory
+/// Specification: `$XDG_DATA_HOME/applications` followed by
+/// `applications` under each `$XDG_DATA_DIRS` entry - plus flatpak's
+/// per-user and system export dirs explicitly, since those are only on
+/// `$XDG_DATA_DIRS` because flatpak's session hook put them there, and we
+/// can't assume we inherited that hook's environment (e.g. running from an
+/// AppImage with a scrubbed env, or spawned by a non-login process).
+fn xdg_application_dirs() -> Vec<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local/share")));
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .unwrap_or_else(|_| "/usr/local/share:/usr/share".to_string());
+
+    let mut dirs: Vec<PathBuf> = data_home
+        .into_iter()
+        .chain(data_dirs.split(':').filter(|d| !d.is_empty()).map(PathBuf::from))
+        .map(|dir| dir.join("applications"))
+        .collect();
+
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".local/share/flatpak/exports/share/applications"));
+    }
+    dirs.push(PathBuf::from("/var/lib/flatpak/exports/share/applications"));
+
+    dirs
+}
+
+/// Parses the `[Desktop Entry]` group of a `.desktop` file, ignoring any
+/// other groups (e.g. `[Desktop Action ...]`) and requiring `Name`/`Exec`.
+fn parse_desktop_entry(contents: &str) -> Option<DesktopEntry> {
+    let mut in_desktop_entry = false;
+    let mut name = None;
+    let mut exec = None;
+    let mut wm_class = None;
+    let mut mime_types = None;
+    let mut terminal = false;
+
+    for line in contents.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            in_desktop_entry = line == "[Desktop Entry]";
+            continue;
+        }
+
+        if !in_desktop_entry {
+            continue;
+        }
+
+        let (key, value) = line.split_once('=')?;
+        match key.trim() {
+            "Name" => name = Some(value.trim().to_string()),
+            "Exec" => exec = Some(value.trim().to_string()),
+            "StartupWMClass" => wm_class = Some(value.trim().to_string()),
+            "MimeType" => mime_types = Some(value.trim().to_string()),
+            "Terminal" => terminal = value.trim().eq_ignore_ascii_case("true"),
+            _ => {}
+        }
+    }
+
+    Some(DesktopEntry {
+        name: name?,
+        exec: exec?,
+        wm_class,
+        mime_types,
+        terminal,
+    })
+}
+
+fn scan_desktop_entries() -> Vec<DesktopEntry> {
+    let mut entries = Vec::new();
+
+    for dir in xdg_application_dirs() {
+        let Ok(read_dir) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("desktop") {
+                continue;
+            }
+
+            if let Ok(contents) = std::fs::read_to_string(&path) {
+                if let Some(desktop_entry) = parse_desktop_entry(&contents) {
+                    entries.push(desktop_entry);
+                }
+            }
+        }
+    }
+
+    entries
+}
+
+/// Maps a `.desktop` entry's `Name` to one of our known editor ids. Mirrors
+/// the substring table in `detector::map_window_title_to_editor`, since the
+/// `Name` key is the closest thing to a canonical editor name we get without
+/// hardcoding exec paths per distro or packaging format.
+fn editor_id_for_name(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    let id = match name.as_str() {
+        s if s.contains("visual studio code") => "vscode",
+        s if s.contains("cursor") => "cursor",
+        s if s.contains("vscodium") => "vscodium",
+        s if s.contains("roo code") => "roo",
+        s if s.contains("windsurf") => "windsurf",
+        s if s.contains("intellij idea") => "idea",
+        s if s.contains("rubymine") => "rubymine",
+        s if s.contains("goland") => "goland",
+        s if s.contains("webstorm") => "webstorm",
+        s if s.contains("pycharm") => "pycharm",
+        s if s.contains("phpstorm") => "phpstorm",
+        s if s.contains("rider") => "rider",
+        s if s.contains("clion") => "clion",
+        s if s.contains("datagrip") => "datagrip",
+        s if s.contains("android studio") => "androidstudio",
+        s if s.contains("fleet") => "fleet",
+        s if s.contains("neovim") => "neovim",
+        s if s.contains("vim") => "vim",
+        s if s.contains("emacs") => "emacs",
+        s if s.contains("zed") => "zed",
+        s if s.contains("sublime text") => "sublime",
+        _ => return None,
+    };
+    Some(id)
+}
+
+/// Lookup from WM class and resolved binary, built by scanning installed
+/// `.desktop` files once. Lets us resolve a window to an editor id by its
+/// (locale-independent) WM class instead of its title, and resolve an
+/// editor's real executable from its `Exec` line instead of a hardcoded
+/// per-distro path.
+pub struct DesktopEntryRegistry {
+    by_wm_class: HashMap<String, String>,
+    by_editor_id: HashMap<String, PathBuf>,
+}
+
+impl DesktopEntryRegistry {
+    pub fn scan() -> Self {
+        let mut by_wm_class = HashMap::new();
+        let mut by_editor_id = HashMap::new();
+
+        for entry in scan_desktop_entries() {
+            let Some(editor_id) = editor_id_for_name(&entry.name) else {
+                continue;
+            };
+
+            if let Some(wm_class) = &entry.wm_class {
+                by_wm_class
+                    .entry(wm_class.to_lowercase())
+                    .or_insert_with(|| editor_id.to_string());
+            }
+
+            if let Some(binary) = entry.binary_path() {
+                by_editor_id
+                    .entry(editor_id.to_string())
+                    .or_insert(binary);
+            }
+        }
+
+        Self { by_wm_class, by_editor_id }
+    }
+
+    /// Resolves a window's WM class (as reported by `xdotool
+    /// getwindowclassname` or similar) to one of our editor ids.
+    pub fn editor_for_wm_class(&self, wm_class: &str) -> Option<String> {
+        self.by_wm_class.get(&wm_class.to_lowercase()).cloned()
+    }
+
+    /// The real executable for an editor id, resolved from the `Exec` line
+    /// of its `.desktop` entry with field codes stripped.
+    pub fn find_binary(&self, editor_id: &str) -> Option<PathBuf> {
+        self.by_editor_id.get(editor_id).cloned()
+    }
+}
+
+/// A `.desktop` entry capable of opening plain text/source files, surfaced
+/// for "Open With" style selection rather than matched against our known
+/// editor ids the way [`DesktopEntryRegistry`] is.
+pub struct TextEditorEntry {
+    pub id: String,
+    pub name: String,
+    /// Raw `Exec` value, field codes (`%f`, `%U`, ...) and all - left for
+    /// the caller to expand for the file it actually opens.
+    pub exec: String,
+    /// `Terminal=true` on the source entry - the caller should run `exec`
+    /// through a terminal emulator rather than spawn it directly.
+    pub terminal: bool,
+}
+
+fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    let mut last_was_dash = false;
+
+    for c in name.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+            last_was_dash = false;
+        } else if !last_was_dash {
+            slug.push('-');
+            last_was_dash = true;
+        }
+    }
+
+    slug.trim_matches('-').to_string()
+}
+
+/// Scans installed `.desktop` files for ones declaring `text/*` or
+/// `*-source` MIME handling, for populating an ad-hoc editor picker -
+/// unlike [`DesktopEntryRegistry::scan`], this isn't limited to apps we
+/// recognize by name.
+pub fn list_text_editors() -> Vec<TextEditorEntry> {
+    scan_desktop_entries()
+        .into_iter()
+        .filter(|entry| {
+            entry
+                .mime_types
+                .as_deref()
+                .is_some_and(|mime_types| mime_types.contains("text/") || mime_types.contains("-source"))
+        })
+        .map(|entry| TextEditorEntry {
+            id: format!("external:{}", slugify(&entry.name)),
+            name: entry.name,
+            exec: entry.exec,
+            terminal: entry.terminal,
+        })
+        .collect()
+}
+
+/// Scans installed `.desktop` files for ones declaring `mime` (or a
+/// `<type>/*` glob covering it) in their `MimeType` list - the per-file-type
+/// counterpart to `list_text_editors`'s fixed `text/*` roster, for
+/// populating an "Open With" menu scoped to one specific file.
+pub fn list_openers_for_mime(mime: &str) -> Vec<TextEditorEntry> {
+    let glob = mime.split('/').next().map(|prefix| format!("{}/*", prefix));
+
+    scan_desktop_entries()
+        .into_iter()
+        .filter(|entry| {
+            entry.mime_types.as_deref().is_some_and(|mime_types| {
+                mime_types
+                    .split(';')
+                    .any(|declared| declared == mime || Some(declared) == glob.as_deref())
+            })
+        })
+        .map(|entry| TextEditorEntry {
+            id: format!("external:{}", slugify(&entry.name)),
+            name: entry.name,
+            exec: entry.exec,
+            terminal: entry.terminal,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_desktop_entry_extracts_known_keys() {
+        let contents = "[Desktop Entry]\n\
+                         Name=Visual Studio Code\n\
+                         Exec=/usr/share/code/code --unity-launch %F\n\
+                         StartupWMClass=Code\n\
+                         MimeType=text/plain;\n";
+
+        let entry = parse_desktop_entry(contents).unwrap();
+        assert_eq!(entry.name, "Visual Studio Code");
+        assert_eq!(entry.exec, "/usr/share/code/code --unity-launch %F");
+        assert_eq!(entry.wm_class.as_deref(), Some("Code"));
+    }
+
+    #[test]
+    fn parse_desktop_entry_ignores_other_groups() {
+        let contents = "[Desktop Action new-window]\n\
+                         Name=New Window\n\
+                         Exec=code -n\n\
+                         [Desktop Entry]\n\
+                         Name=Visual Studio Code\n\
+                         Exec=/usr/share/code/code %F\n";
+
+        let entry = parse_desktop_entry(contents).unwrap();
+        assert_eq!(entry.name, "Visual Studio Code");
+        assert_eq!(entry.exec, "/usr/share/code/code %F");
+    }
+
+    #[test]
+    fn parse_desktop_entry_requires_name_and_exec() {
+        let contents = "[Desktop Entry]\nStartupWMClass=Code\n";
+        assert!(parse_desktop_entry(contents).is_none());
+    }
+
+    #[test]
+    fn parse_desktop_entry_reads_terminal_flag() {
+        let contents = "[Desktop Entry]\nName=Nano\nExec=nano %F\nTerminal=true\n";
+        assert!(parse_desktop_entry(contents).unwrap().terminal);
+
+        let contents = "[Desktop Entry]\nName=Visual Studio Code\nExec=code %F\n";
+        assert!(!parse_desktop_entry(contents).unwrap().terminal);
+    }
+
+    #[test]
+    fn exec_without_field_codes_strips_codes_and_trims() {
+        let entry = DesktopEntry {
+            name: "Visual Studio Code".to_string(),
+            exec: "/usr/share/code/code --unity-launch %F".to_string(),
+            wm_class: None,
+            mime_types: None,
+            terminal: false,
+        };
+        assert_eq!(
+            entry.exec_without_field_codes(),
+            "/usr/share/code/code --unity-launch"
+        );
+    }
+
+    #[test]
+    fn exec_without_field_codes_keeps_literal_percent() {
+        let entry = DesktopEntry {
+            name: "Some App".to_string(),
+            exec: "/usr/bin/someapp --progress=100%% %f".to_string(),
+            wm_class: None,
+            mime_types: None,
+            terminal: false,
+        };
+        assert_eq!(
+            entry.exec_without_field_codes(),
+            "/usr/bin/someapp --progress=100%"
+        );
+    }
+
+    #[test]
+    fn binary_path_takes_first_token() {
+        let entry = DesktopEntry {
+            name: "Cursor".to_string(),
+            exec: "/opt/cursor/cursor --no-sandbox %F".to_string(),
+            wm_class: None,
+            mime_types: None,
+            terminal: false,
+        };
+        assert_eq!(entry.binary_path(), Some(PathBuf::from("/opt/cursor/cursor")));
+    }
+
+    #[test]
+    fn editor_id_for_name_matches_known_editors() {
+        assert_eq!(editor_id_for_name("Visual Studio Code"), Some("vscode"));
+        assert_eq!(editor_id_for_name("IntelliJ IDEA Ultimate"), Some("idea"));
+        assert_eq!(editor_id_for_name("Some Random App"), None);
+    }
+
+    #[test]
+    fn slugify_lowercases_and_collapses_separators() {
+        assert_eq!(slugify("Visual Studio Code"), "visual-studio-code");
+        assert_eq!(slugify("Geany!!"), "geany");
+    }
+
+    #[test]
+    fn text_editor_entry_requires_text_or_source_mimetype() {
+        let text_app = DesktopEntry {
+            name: "Geany".to_string(),
+            exec: "/usr/bin/geany %F".to_string(),
+            wm_class: None,
+            mime_types: Some("text/plain;text/x-csrc;".to_string()),
+            terminal: false,
+        };
+        let image_app = DesktopEntry {
+            name: "Image Viewer".to_string(),
+            exec: "/usr/bin/eog %U".to_string(),
+            wm_class: None,
+            mime_types: Some("image/png;image/jpeg;".to_string()),
+            terminal: false,
+        };
+
+        assert!(text_app
+            .mime_types
+            .as_deref()
+            .is_some_and(|m| m.contains("text/")));
+        assert!(!image_app
+            .mime_types
+            .as_deref()
+            .is_some_and(|m| m.contains("text/") || m.contains("-source")));
+    }
+}
@@ -1,6 +1,11 @@
 use std::process::Command;
 use tracing::debug;
 
+#[cfg(target_os = "linux")]
+use super::desktop_entries::DesktopEntryRegistry;
+#[cfg(target_os = "linux")]
+use super::wayland;
+
 pub async fn detect_active_editor() -> Option<String> {
     #[cfg(target_os = "macos")]
     return detect_active_editor_macos().await;
@@ -204,6 +209,33 @@ fn map_window_title_to_editor(title: &str) -> Option<String> {
 
 #[cfg(target_os = "linux")]
 async fn detect_active_editor_linux() -> Option<String> {
+    // `xdotool`/`wmctrl` only work on X11 (including XWayland clients) and
+    // return nothing on a native Wayland session, so try the compositor's
+    // own focused-window query first.
+    if let Some(app_id) = tokio::task::spawn_blocking(wayland::detect_focused_app_id)
+        .await
+        .ok()
+        .flatten()
+    {
+        if let Some(editor_id) = DesktopEntryRegistry::scan().editor_for_wm_class(&app_id) {
+            return Some(editor_id);
+        }
+
+        if let Some(editor_id) = map_window_title_to_editor(&app_id) {
+            return Some(editor_id);
+        }
+    }
+
+    // WM class is a more reliable signal than the window title: it's not
+    // localized and doesn't change as the user switches files, but it's
+    // only as good as our `.desktop` registry, so fall back to title
+    // matching when it's unset or unrecognized.
+    if let Some(wm_class) = try_xdotool_wm_class().await {
+        if let Some(editor_id) = DesktopEntryRegistry::scan().editor_for_wm_class(&wm_class) {
+            return Some(editor_id);
+        }
+    }
+
     if let Some(title) = try_xdotool().await {
         return map_window_title_to_editor(&title);
     }
@@ -215,6 +247,24 @@ async fn detect_active_editor_linux() -> Option<String> {
     None
 }
 
+#[cfg(target_os = "linux")]
+async fn try_xdotool_wm_class() -> Option<String> {
+    let output = Command::new("xdotool")
+        .args(["getactivewindow", "getwindowclassname"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .to_lowercase(),
+    )
+}
+
 #[cfg(target_os = "linux")]
 async fn try_xdotool() -> Option<String> {
     let output = Command::new("xdotool")
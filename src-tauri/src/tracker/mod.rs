@@ -1,4 +1,11 @@
+#[cfg(target_os = "linux")]
+pub(crate) mod desktop_entries;
 mod detector;
+#[cfg(target_os = "linux")]
+mod wayland;
+
+#[cfg(target_os = "linux")]
+pub(crate) use desktop_entries::DesktopEntryRegistry;
 
 use crate::editors::EditorRegistry;
 use crate::settings::LastSeenData;
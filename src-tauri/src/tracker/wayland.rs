@@ -0,0 +1,198 @@
+use std::process::Command;
+use tracing::debug;
+
+/// Returns the compositor's best answer for "what's focused right now",
+/// trying Wayland-native sources in order since none of them are
+/// universally available: Sway/wlroots, Hyprland, then GNOME Shell. Yields
+/// an app-id/WM-class-like string (already lowercased) suitable for the
+/// same matching `detect_active_editor_linux` does with `xdotool`'s output.
+pub fn detect_focused_app_id() -> Option<String> {
+    try_sway().or_else(try_hyprland).or_else(try_gnome_shell)
+}
+
+fn try_sway() -> Option<String> {
+    let output = Command::new("swaymsg")
+        .args(["-t", "get_tree"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let tree: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    find_focused_node(&tree)
+}
+
+/// `get_tree` returns the whole container tree; walk it looking for the
+/// node with `"focused": true` and read its `app_id` (native Wayland
+/// clients) or `window_properties.class` (XWayland clients).
+fn find_focused_node(node: &serde_json::Value) -> Option<String> {
+    if node.get("focused").and_then(|v| v.as_bool()) == Some(true) {
+        if let Some(app_id) = node.get("app_id").and_then(|v| v.as_str()) {
+            if !app_id.is_empty() {
+                return Some(app_id.to_lowercase());
+            }
+        }
+
+        if let Some(class) = node
+            .get("window_properties")
+            .and_then(|props| props.get("class"))
+            .and_then(|v| v.as_str())
+        {
+            return Some(class.to_lowercase());
+        }
+
+        if let Some(name) = node.get("name").and_then(|v| v.as_str()) {
+            return Some(name.to_lowercase());
+        }
+    }
+
+    for child_key in ["nodes", "floating_nodes"] {
+        if let Some(children) = node.get(child_key).and_then(|v| v.as_array()) {
+            for child in children {
+                if let Some(found) = find_focused_node(child) {
+                    return Some(found);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn try_hyprland() -> Option<String> {
+    let output = Command::new("hyprctl")
+        .args(["activewindow", "-j"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let window: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+
+    if let Some(class) = window.get("class").and_then(|v| v.as_str()) {
+        if !class.is_empty() {
+            return Some(class.to_lowercase());
+        }
+    }
+
+    window
+        .get("title")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_lowercase())
+}
+
+/// GNOME Shell has no D-Bus API for "give me the focused window's class",
+/// so this shells out to the Shell's JS `Eval` method (only available when
+/// looking-glass eval mode is enabled) to read `app_id`/`wm_class` off
+/// `global.display.focus_window`. If that's unavailable, we fall back to
+/// resolving the focused window's PID the same way and reading its
+/// `/proc/<pid>/comm`.
+fn try_gnome_shell() -> Option<String> {
+    if let Some(wm_class) = eval_gnome_shell_js(
+        "global.display.focus_window ? (global.display.focus_window.get_wm_class() || '') : ''",
+    ) {
+        if !wm_class.is_empty() {
+            return Some(wm_class.to_lowercase());
+        }
+    }
+
+    let pid_str = eval_gnome_shell_js(
+        "global.display.focus_window ? global.display.focus_window.get_pid().toString() : ''",
+    )?;
+    let pid: u32 = pid_str.trim().parse().ok()?;
+
+    let comm = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    let comm = comm.trim();
+    if comm.is_empty() {
+        None
+    } else {
+        Some(comm.to_lowercase())
+    }
+}
+
+/// Runs a snippet through GNOME Shell's `org.gnome.Shell.Eval` and returns
+/// the string result, unwrapping the `(true, "...")` tuple `gdbus` prints
+/// and the shell's own quoting of the returned value.
+fn eval_gnome_shell_js(js: &str) -> Option<String> {
+    let output = Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.gnome.Shell",
+            "--object-path",
+            "/org/gnome/Shell",
+            "--method",
+            "org.gnome.Shell.Eval",
+            js,
+        ])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        debug!(
+            "gdbus Eval failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Looks like: (true, '"some-app-id"')
+    if !stdout.trim_start().starts_with("(true,") {
+        return None;
+    }
+
+    let quoted = stdout
+        .splitn(2, ',')
+        .nth(1)?
+        .trim()
+        .trim_end_matches(')')
+        .trim();
+    let unwrapped = quoted.trim_matches(|c| c == '\'' || c == '"');
+    Some(unwrapped.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn find_focused_node_reads_app_id_of_native_wayland_client() {
+        let tree = json!({
+            "nodes": [
+                {"focused": false, "app_id": "firefox"},
+                {"focused": true, "app_id": "Code"}
+            ]
+        });
+        assert_eq!(find_focused_node(&tree), Some("code".to_string()));
+    }
+
+    #[test]
+    fn find_focused_node_falls_back_to_xwayland_class() {
+        let tree = json!({
+            "floating_nodes": [
+                {
+                    "focused": true,
+                    "app_id": null,
+                    "window_properties": {"class": "jetbrains-idea"}
+                }
+            ]
+        });
+        assert_eq!(
+            find_focused_node(&tree),
+            Some("jetbrains-idea".to_string())
+        );
+    }
+
+    #[test]
+    fn find_focused_node_returns_none_without_a_focused_node() {
+        let tree = json!({"nodes": [{"focused": false, "app_id": "firefox"}]});
+        assert_eq!(find_focused_node(&tree), None);
+    }
+}
@@ -1,16 +1,29 @@
+use crate::file_types;
+use ignore::WalkBuilder;
+use std::collections::VecDeque;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use tracing::debug;
 
-const ALLOW_DIRS: [&str; 9] = [
-    "src", "app", "lib", "packages", "test", "spec", "include", "bin", "scripts",
-];
-
 fn mtime(path: &Path) -> Option<SystemTime> {
     fs::metadata(path).ok()?.modified().ok()
 }
 
+/// Whether `path`'s mtime should count toward a workspace's recency signal.
+/// Directories and files without a recognized binary/generated-artifact
+/// extension always count; a rebuilt `.zip` or re-exported `.png` doesn't.
+fn counts_toward_activity(path: &Path) -> bool {
+    !file_types::classify(path).is_ignored_for_activity()
+}
+
+/// Walks `root` breadth-first (so shallow files are counted before we
+/// descend into subdirectories), honoring `.gitignore`/`.ignore` and
+/// skipping VCS/build directories the same way `git status` would. Each
+/// directory is visited one level at a time with `WalkBuilder` so ignore
+/// rules from parent directories are still respected even though we're
+/// driving our own queue instead of `ignore`'s own (depth-first) iterator.
+/// Capped by `max_entries` to stay fast on huge trees.
 pub fn fs_recent_mtime(root: &Path, max_entries: usize) -> Option<SystemTime> {
     let mut best: Option<SystemTime> = None;
 
@@ -18,47 +31,41 @@ pub fn fs_recent_mtime(root: &Path, max_entries: usize) -> Option<SystemTime> {
         best = Some(best.map_or(t, |b| b.max(t)));
     }
 
-    for dir_name in &ALLOW_DIRS {
-        if let Some(t) = mtime(&root.join(dir_name)) {
-            best = Some(best.map_or(t, |b| b.max(t)));
-        }
-    }
-
     let mut seen = 0usize;
+    let mut queue: VecDeque<PathBuf> = VecDeque::new();
+    queue.push_back(root.to_path_buf());
 
-    {
-        if let Ok(rd) = fs::read_dir(root) {
-            for entry in rd.flatten() {
-                if seen >= max_entries {
-                    break;
-                }
-                seen += 1;
-                if let Ok(md) = entry.metadata() {
-                    if let Ok(t) = md.modified() {
-                        best = Some(best.map_or(t, |b| b.max(t)));
-                    }
-                }
-            }
-        }
-    }
-
-    for dir_name in &ALLOW_DIRS {
+    while let Some(dir) = queue.pop_front() {
         if seen >= max_entries {
             break;
         }
-        let path = root.join(dir_name);
-        if let Ok(rd) = fs::read_dir(&path) {
-            for entry in rd.flatten() {
-                if seen >= max_entries {
-                    break;
-                }
-                seen += 1;
-                if let Ok(md) = entry.metadata() {
-                    if let Ok(t) = md.modified() {
-                        best = Some(best.map_or(t, |b| b.max(t)));
-                    }
+
+        let walker = WalkBuilder::new(&dir).max_depth(Some(1)).build();
+
+        for result in walker {
+            if seen >= max_entries {
+                break;
+            }
+
+            let entry = match result {
+                Ok(entry) => entry,
+                Err(_) => continue,
+            };
+
+            if entry.path() == dir {
+                continue;
+            }
+            seen += 1;
+
+            if counts_toward_activity(entry.path()) {
+                if let Some(t) = mtime(entry.path()) {
+                    best = Some(best.map_or(t, |b| b.max(t)));
                 }
             }
+
+            if entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false) {
+                queue.push_back(entry.path().to_path_buf());
+            }
         }
     }
 
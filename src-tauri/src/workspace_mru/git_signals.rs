@@ -1,39 +1,49 @@
-use git2::{Repository, Status, StatusOptions, StatusShow};
 use std::cmp;
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::Path;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tracing::{debug, warn};
 
-pub fn head_reflog_time(repo_path: &Path) -> Option<SystemTime> {
-    let repo = match Repository::open(repo_path) {
-        Ok(r) => r,
+fn open_repo(repo_path: &Path) -> Option<gix::Repository> {
+    match gix::open(repo_path) {
+        Ok(repo) => Some(repo),
         Err(e) => {
             debug!(
                 "Failed to open Git repository at {}: {}",
                 repo_path.display(),
                 e
             );
-            return None;
+            None
         }
-    };
+    }
+}
 
-    let log = match repo.reflog("HEAD") {
-        Ok(l) => l,
+/// Reads the HEAD reflog's most recent entry timestamp directly from
+/// `.git/logs/HEAD` (starship's own approach to "when was this repo last
+/// touched") rather than through `gix`'s own reflog iterator, since a plain
+/// line read is all a single timestamp needs and skips building up a parsed
+/// `RefEdit` history we'd otherwise throw away.
+pub fn head_reflog_time(repo_path: &Path) -> Option<SystemTime> {
+    let repo = open_repo(repo_path)?;
+
+    let reflog_path = repo.git_dir().join("logs").join("HEAD");
+    let file = match fs::File::open(&reflog_path) {
+        Ok(f) => f,
         Err(e) => {
-            debug!("Failed to read HEAD reflog: {}", e);
+            debug!("No HEAD reflog at {}: {}", reflog_path.display(), e);
             return None;
         }
     };
 
-    if log.len() == 0 {
-        debug!("HEAD reflog is empty");
-        return None;
-    }
+    let last_line = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.is_empty())
+        .last()?;
 
-    let entry = log.get(log.len() - 1)?;
-    let when = entry.committer().when();
-    let timestamp = UNIX_EPOCH + Duration::from_secs(when.seconds() as u64);
+    let seconds = parse_reflog_timestamp(&last_line)?;
+    let timestamp = UNIX_EPOCH + Duration::from_secs(seconds);
 
     debug!(
         "Git reflog time for {}: {:?}",
@@ -43,18 +53,25 @@ pub fn head_reflog_time(repo_path: &Path) -> Option<SystemTime> {
     Some(timestamp)
 }
 
-pub fn latest_uncommitted_mtime(repo_path: &Path) -> Option<SystemTime> {
-    let repo = match Repository::open(repo_path) {
-        Ok(r) => r,
-        Err(e) => {
-            debug!(
-                "Failed to open Git repository at {}: {}",
-                repo_path.display(),
-                e
-            );
-            return None;
-        }
+/// Pulls the unix-seconds timestamp out of a `.git/logs/HEAD` line. The
+/// format is `<old-sha> <new-sha> <name> <email> <seconds> <tz>\t<message>` -
+/// since `<name>` can itself contain spaces, the committer identity is
+/// located by its closing `>` instead of by a fixed field count, and the
+/// timestamp is the first whitespace-separated token after it.
+fn parse_reflog_timestamp(line: &str) -> Option<u64> {
+    let header = match line.find('\t') {
+        Some(idx) => &line[..idx],
+        None => line,
     };
+    let email_end = header.rfind('>')?;
+    header[email_end + 1..].split_whitespace().next()?.parse().ok()
+}
+
+/// Diffs the worktree and index against HEAD the way `gix status` does
+/// internally, returning the mtime of the most recently modified
+/// not-yet-committed file - untracked, modified, or staged alike.
+pub fn latest_uncommitted_mtime(repo_path: &Path) -> Option<SystemTime> {
+    let repo = open_repo(repo_path)?;
 
     let workdir = match repo.workdir() {
         Some(w) => w,
@@ -64,51 +81,37 @@ pub fn latest_uncommitted_mtime(repo_path: &Path) -> Option<SystemTime> {
         }
     };
 
-    let mut opts = StatusOptions::new();
-    opts.show(StatusShow::IndexAndWorkdir)
-        .include_untracked(true)
-        .recurse_untracked_dirs(false)
-        .exclude_submodules(true)
-        .renames_head_to_index(true)
-        .renames_index_to_workdir(true)
-        .no_refresh(false);
-
-    let statuses = match repo.statuses(Some(&mut opts)) {
+    let status = match repo.status(gix::progress::Discard) {
         Ok(s) => s,
         Err(e) => {
-            warn!("Failed to get git status: {}", e);
+            warn!("Failed to compute git status for {}: {}", repo_path.display(), e);
             return None;
         }
     };
 
-    let mut latest: Option<SystemTime> = None;
+    let items = match status.into_index_worktree_iter(Vec::new()) {
+        Ok(items) => items,
+        Err(e) => {
+            warn!(
+                "Failed to diff worktree against index for {}: {}",
+                repo_path.display(),
+                e
+            );
+            return None;
+        }
+    };
 
-    for entry in statuses.iter() {
-        let status = entry.status();
-        let interesting = status.intersects(
-            Status::WT_MODIFIED
-                | Status::WT_NEW
-                | Status::WT_DELETED
-                | Status::WT_TYPECHANGE
-                | Status::WT_RENAMED
-                | Status::INDEX_MODIFIED
-                | Status::INDEX_NEW
-                | Status::INDEX_DELETED
-                | Status::INDEX_TYPECHANGE
-                | Status::INDEX_RENAMED,
-        );
+    let mut latest: Option<SystemTime> = None;
 
-        if interesting {
-            if let Some(rel) = entry.path() {
-                let path = workdir.join(rel);
-                if let Ok(md) = fs::metadata(&path) {
-                    if let Ok(time) = md.modified() {
-                        latest = Some(match latest {
-                            Some(cur) => cmp::max(cur, time),
-                            None => time,
-                        });
-                    }
-                }
+    for item in items {
+        let Ok(item) = item else { continue };
+        let Some(rela_path) = item.rela_path() else {
+            continue;
+        };
+        let path = workdir.join(gix::path::from_bstr(rela_path));
+        if let Ok(md) = fs::metadata(&path) {
+            if let Ok(time) = md.modified() {
+                latest = Some(latest.map_or(time, |cur| cmp::max(cur, time)));
             }
         }
     }
@@ -164,4 +167,15 @@ mod tests {
         let result = latest_uncommitted_mtime(fake_path);
         assert!(result.is_none(), "Should return None for nonexistent repo");
     }
+
+    #[test]
+    fn test_parse_reflog_timestamp() {
+        let line = "0000000000000000000000000000000000000000 abc123 Jane Doe <jane@example.com> 1700000000 -0700\tcommit (initial): init";
+        assert_eq!(parse_reflog_timestamp(line), Some(1_700_000_000));
+    }
+
+    #[test]
+    fn test_parse_reflog_timestamp_rejects_malformed_line() {
+        assert_eq!(parse_reflog_timestamp("not a reflog line"), None);
+    }
 }
@@ -1,18 +1,21 @@
 mod fs_signal;
 mod git_signals;
 mod models;
+mod monitor;
 mod probe;
 mod process;
 
 pub use models::{WorkspaceActivity, WorkspaceMruData};
 
-use crate::settings::SettingsManager;
+use crate::settings::{SettingsManager, WorkspaceConfig};
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use parking_lot::Mutex;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::SystemTime;
 use sysinfo::System;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, Semaphore};
 use tokio::time::{interval, Duration};
 use tracing::{info, warn};
 
@@ -21,6 +24,12 @@ pub struct ActiveWorkspaceTracker {
     mru_path: PathBuf,
     settings_manager: Arc<SettingsManager>,
     system: Arc<RwLock<System>>,
+    /// Fingerprint of the data as of the last successful `save`, so a tick
+    /// that didn't change any `last_active` value can skip the write.
+    last_saved_fingerprint: Mutex<Option<u64>>,
+    /// Event-driven `last_active` signal, so a workspace with a live watch
+    /// doesn't need the expensive `fs_recent_mtime` walk on every tick.
+    monitor: Arc<monitor::WorkspaceMonitor>,
 }
 
 impl ActiveWorkspaceTracker {
@@ -33,6 +42,8 @@ impl ActiveWorkspaceTracker {
             mru_path,
             settings_manager,
             system: Arc::new(RwLock::new(System::new())),
+            last_saved_fingerprint: Mutex::new(None),
+            monitor: monitor::WorkspaceMonitor::new(),
         }
     }
 
@@ -58,6 +69,9 @@ impl ActiveWorkspaceTracker {
 
         let data: WorkspaceMruData =
             serde_yaml::from_str(&contents).context("Failed to parse YAML workspace MRU data")?;
+        let data = data.migrate();
+
+        *self.last_saved_fingerprint.lock() = Some(Self::fingerprint(&data));
 
         let mut current = self.mru_data.write().await;
         *current = data;
@@ -66,15 +80,54 @@ impl ActiveWorkspaceTracker {
         Ok(())
     }
 
+    /// Deterministic digest of the parts of `WorkspaceMruData` that change
+    /// on a tick or an open, used to detect a no-op so `save` can skip the
+    /// write. Workspaces are sorted first since `HashMap` iteration order
+    /// isn't stable across mutations. `rank` is a `f64`, which isn't
+    /// `Hash`, so it's hashed via its bit pattern instead.
+    fn fingerprint(data: &WorkspaceMruData) -> u64 {
+        let mut entries: Vec<_> = data.workspaces.iter().collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        entries.len().hash(&mut hasher);
+        for (path, activity) in entries {
+            path.hash(&mut hasher);
+            activity.last_active.hash(&mut hasher);
+            activity.rank.to_bits().hash(&mut hasher);
+            activity.last_access.hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// Writes via a sibling temp file + rename so a crash mid-write can't
+    /// truncate `workspace_mru.yaml`, and skips the write entirely when
+    /// nothing has changed since the last save.
     async fn save(&self) -> Result<()> {
         let data = self.mru_data.read().await.clone();
 
+        let fingerprint = Self::fingerprint(&data);
+        if *self.last_saved_fingerprint.lock() == Some(fingerprint) {
+            return Ok(());
+        }
+
         let yaml_string =
             serde_yaml::to_string(&data).context("Failed to serialize workspace MRU data")?;
 
-        tokio::fs::write(&self.mru_path, yaml_string)
+        let mut tmp_path = self.mru_path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        tokio::fs::write(&tmp_path, yaml_string)
             .await
-            .context("Failed to write workspace MRU file")?;
+            .context("Failed to write workspace MRU temp file")?;
+
+        tokio::fs::rename(&tmp_path, &self.mru_path)
+            .await
+            .context("Failed to move workspace MRU temp file into place")?;
+
+        *self.last_saved_fingerprint.lock() = Some(fingerprint);
 
         info!("Workspace MRU data saved to {:?}", self.mru_path);
         Ok(())
@@ -91,6 +144,18 @@ impl ActiveWorkspaceTracker {
         }
     }
 
+    /// Number of workspaces probed concurrently. Defaults to the CPU count,
+    /// mirroring czkawka's `get_number_of_threads`, but can be overridden via
+    /// `defaults.probe_worker_threads` in settings for machines where a
+    /// smaller pool is preferable (e.g. many workspaces on spinning disks).
+    fn worker_count(configured: Option<usize>) -> usize {
+        configured.filter(|n| *n > 0).unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(4)
+        })
+    }
+
     async fn update_workspace_activity(&self) {
         let settings = self.settings_manager.get().await;
 
@@ -99,18 +164,83 @@ impl ActiveWorkspaceTracker {
             process::refresh_process_snapshot(&mut sys);
         }
 
-        let sys = self.system.read().await;
+        // Hold one shared read guard for the duration of the probing pass so
+        // every worker sees the same process snapshot without re-locking per
+        // workspace.
+        let sys_guard = Arc::new(self.system.clone().read_owned().await);
+        let semaphore = Arc::new(Semaphore::new(Self::worker_count(
+            settings.defaults.probe_worker_threads,
+        )));
+
+        let mut tasks = Vec::with_capacity(settings.workspaces.len());
 
         for workspace_config in &settings.workspaces {
-            if let Some(workspace_path) = &workspace_config.normalized_path {
-                let probe_result = probe::probe_workspace(workspace_path, &sys);
-
-                if let Some(last_active) = probe_result.last_active {
-                    let mut mru_data = self.mru_data.write().await;
-                    mru_data
-                        .workspaces
-                        .insert(workspace_path.clone(), WorkspaceActivity { last_active });
+            let Some(workspace_path) = workspace_config
+                .normalized_path
+                .as_ref()
+                .map(|p| p.as_path().to_path_buf())
+            else {
+                continue;
+            };
+
+            self.monitor.watch(&workspace_path);
+
+            let sys_guard = sys_guard.clone();
+            let semaphore = semaphore.clone();
+            let monitor = self.monitor.clone();
+
+            tasks.push(tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.ok()?;
+                let result_path = workspace_path.clone();
+                let is_watched = monitor.is_watched(&workspace_path);
+                let watched_active = if is_watched {
+                    monitor.last_active(&workspace_path).await
+                } else {
+                    None
+                };
+
+                let mut probe_result = tokio::task::spawn_blocking(move || {
+                    if is_watched {
+                        probe::probe_workspace_without_fs_walk(&workspace_path, &sys_guard)
+                    } else {
+                        probe::probe_workspace(&workspace_path, &sys_guard)
+                    }
+                })
+                .await
+                .ok()?;
+
+                if is_watched {
+                    probe_result.from_fs = watched_active;
+                    probe_result.compute_last_active();
                 }
+
+                probe_result
+                    .last_active
+                    .map(|last_active| (result_path, last_active))
+            }));
+        }
+
+        // Merge results under a single write lock, bounding the critical
+        // section to the merge itself rather than the probing work.
+        let mut activity_updates = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            if let Ok(Some(update)) = task.await {
+                activity_updates.push(update);
+            }
+        }
+
+        if !activity_updates.is_empty() {
+            let mut mru_data = self.mru_data.write().await;
+            for (workspace_path, last_active) in activity_updates {
+                mru_data
+                    .workspaces
+                    .entry(workspace_path)
+                    .and_modify(|activity| activity.last_active = last_active)
+                    .or_insert_with(|| WorkspaceActivity {
+                        last_active,
+                        rank: 0.0,
+                        last_access: 0,
+                    });
             }
         }
 
@@ -125,4 +255,95 @@ impl ActiveWorkspaceTracker {
             .get(workspace_path)
             .map(|activity| activity.last_active)
     }
+
+    /// Bumps `workspace_path`'s frecency rank by one and refreshes its
+    /// `last_access` - call this on every successful workspace open (see
+    /// `EditorDispatcher::open`), not from the passive activity prober.
+    pub async fn record_open(&self, workspace_path: &Path) {
+        let now = Self::unix_now();
+
+        {
+            let mut mru_data = self.mru_data.write().await;
+            let activity = mru_data
+                .workspaces
+                .entry(workspace_path.to_path_buf())
+                .or_insert_with(|| WorkspaceActivity {
+                    last_active: SystemTime::now(),
+                    rank: 0.0,
+                    last_access: 0,
+                });
+            activity.rank += 1.0;
+            activity.last_access = now;
+
+            mru_data.age_ranks_if_needed();
+        }
+
+        if let Err(e) = self.save().await {
+            warn!("Failed to save workspace MRU data after recording open: {}", e);
+        }
+    }
+
+    /// Workspace paths ordered by descending frecency score (ties broken by
+    /// path for a stable order), for ranking `WorkspaceConfig` listings by
+    /// what's actually used rather than merely what was touched last.
+    pub async fn ranked_workspaces(&self) -> Vec<PathBuf> {
+        let now = Self::unix_now();
+        let data = self.mru_data.read().await;
+
+        let mut ranked: Vec<(PathBuf, f64)> = data
+            .workspaces
+            .iter()
+            .map(|(path, activity)| (path.clone(), activity.frecency_score(now)))
+            .collect();
+
+        ranked.sort_by(|(path_a, score_a), (path_b, score_b)| {
+            score_b
+                .partial_cmp(score_a)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| path_a.cmp(path_b))
+        });
+
+        ranked.into_iter().map(|(path, _)| path).collect()
+    }
+
+    /// Configured workspaces ordered by `WorkspaceActivity::last_active`
+    /// descending - distinct from `ranked_workspaces`'s frecency ordering,
+    /// for a "recently touched" UI surface that cares about raw recency
+    /// rather than how often a workspace has been opened through Sorcery
+    /// itself. A workspace with no probed activity yet (never touched by
+    /// `update_workspace_activity`) sorts after every workspace that has
+    /// one, in its original configured order.
+    pub async fn workspaces_by_mru(&self) -> Vec<WorkspaceConfig> {
+        let settings = self.settings_manager.get().await;
+        let mru_data = self.mru_data.read().await;
+
+        let mut workspaces: Vec<(Option<SystemTime>, WorkspaceConfig)> = settings
+            .workspaces
+            .iter()
+            .map(|workspace| {
+                let last_active = workspace
+                    .normalized_path
+                    .as_ref()
+                    .and_then(|path| mru_data.workspaces.get(path.as_path()))
+                    .map(|activity| activity.last_active);
+                (last_active, workspace.clone())
+            })
+            .collect();
+
+        workspaces.sort_by(|(a, _), (b, _)| match (a, b) {
+            (Some(a), Some(b)) => b.cmp(a),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+
+        workspaces.into_iter().map(|(_, workspace)| workspace).collect()
+    }
+
+    fn unix_now() -> i64 {
+        SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0)
+    }
 }
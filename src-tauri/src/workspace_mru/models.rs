@@ -6,6 +6,9 @@ use std::time::SystemTime;
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Probe {
     pub from_process: Option<SystemTime>,
+    /// Which signal `from_process` came from - absent when `from_process`
+    /// is, set alongside it otherwise. See `process::ProcessSignal`.
+    pub process_signal: Option<super::process::ProcessSignal>,
     pub from_reflog: Option<SystemTime>,
     pub from_uncommitted: Option<SystemTime>,
     pub from_fs: Option<SystemTime>,
@@ -29,9 +32,134 @@ impl Probe {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceActivity {
     pub last_active: SystemTime,
+
+    /// zoxide-style frecency score, incremented by 1 on every open recorded
+    /// via `ActiveWorkspaceTracker::record_open`. Absent in files written
+    /// before frecency tracking existed, which deserialize as `0.0`.
+    #[serde(default)]
+    pub rank: f64,
+
+    /// Unix timestamp (seconds) of the open that last bumped `rank`, used
+    /// to compute `frecency_score`'s decay factor. `0` decays to the
+    /// lowest tier, same as a workspace that's never been opened.
+    #[serde(default)]
+    pub last_access: i64,
+}
+
+impl WorkspaceActivity {
+    /// `rank` weighted by how long ago `last_access` was, zoxide-style: a
+    /// step decay rather than a smooth one, so a workspace opened an hour
+    /// ago still clearly outranks one last opened a month ago regardless of
+    /// how many times each was opened historically.
+    pub fn frecency_score(&self, now: i64) -> f64 {
+        let age_secs = (now - self.last_access).max(0);
+        let decay = if age_secs < 3600 {
+            4.0
+        } else if age_secs < 86_400 {
+            2.0
+        } else if age_secs < 604_800 {
+            0.5
+        } else {
+            0.25
+        };
+        self.rank * decay
+    }
 }
 
+/// On-disk schema version for `WorkspaceMruData`. Bump this and add a case
+/// to `WorkspaceMruData::migrate` whenever the shape changes, so files
+/// written by an older build still load instead of failing the whole parse.
+pub const CURRENT_MRU_VERSION: u32 = 1;
+
+/// Once the sum of every `WorkspaceActivity::rank` exceeds this, every rank
+/// is aged down - zoxide's own aging threshold, chosen to bound the total
+/// without aging down a database that's still mostly idle.
+const RANK_AGING_CAP: f64 = 9000.0;
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceMruData {
     pub workspaces: HashMap<PathBuf, WorkspaceActivity>,
+
+    /// Absent in files written before versioning was introduced, which
+    /// deserialize as `0` via `#[serde(default)]` and get migrated forward.
+    #[serde(default)]
+    pub version: u32,
+}
+
+impl WorkspaceMruData {
+    /// Brings data loaded from disk up to `CURRENT_MRU_VERSION`. The only
+    /// version so far (0, pre-versioning) has an identical shape, so this is
+    /// currently just a version bump; future schema changes add a match arm
+    /// here rather than a new function.
+    pub fn migrate(mut self) -> Self {
+        self.version = CURRENT_MRU_VERSION;
+        self
+    }
+
+    /// Keeps the total frecency rank bounded: once it exceeds
+    /// `RANK_AGING_CAP`, every entry's rank is scaled down by 0.9 and
+    /// entries that decay below the noise floor are dropped, so the
+    /// database doesn't grow without bound on a long-running install.
+    pub(crate) fn age_ranks_if_needed(&mut self) {
+        let total: f64 = self.workspaces.values().map(|activity| activity.rank).sum();
+        if total <= RANK_AGING_CAP {
+            return;
+        }
+
+        self.workspaces.retain(|_, activity| {
+            activity.rank *= 0.9;
+            activity.rank >= 1.0
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(rank: f64, last_access: i64) -> WorkspaceActivity {
+        WorkspaceActivity {
+            last_active: SystemTime::now(),
+            rank,
+            last_access,
+        }
+    }
+
+    #[test]
+    fn frecency_score_applies_step_decay_by_age() {
+        let now = 1_000_000;
+
+        assert_eq!(activity(10.0, now - 60).frecency_score(now), 40.0);
+        assert_eq!(activity(10.0, now - 7_200).frecency_score(now), 20.0);
+        assert_eq!(activity(10.0, now - 100_000).frecency_score(now), 5.0);
+        assert_eq!(activity(10.0, now - 1_000_000).frecency_score(now), 2.5);
+    }
+
+    #[test]
+    fn frecency_score_clamps_future_last_access_to_zero_age() {
+        let now = 1_000_000;
+        assert_eq!(activity(10.0, now + 60).frecency_score(now), 40.0);
+    }
+
+    #[test]
+    fn age_ranks_if_needed_is_noop_under_cap() {
+        let mut data = WorkspaceMruData::default();
+        data.workspaces.insert(PathBuf::from("/a"), activity(100.0, 0));
+
+        data.age_ranks_if_needed();
+
+        assert_eq!(data.workspaces[&PathBuf::from("/a")].rank, 100.0);
+    }
+
+    #[test]
+    fn age_ranks_if_needed_scales_down_and_evicts_below_noise_floor() {
+        let mut data = WorkspaceMruData::default();
+        data.workspaces.insert(PathBuf::from("/heavy"), activity(9000.0, 0));
+        data.workspaces.insert(PathBuf::from("/light"), activity(1.0, 0));
+
+        data.age_ranks_if_needed();
+
+        assert_eq!(data.workspaces[&PathBuf::from("/heavy")].rank, 8100.0);
+        assert!(!data.workspaces.contains_key(&PathBuf::from("/light")));
+    }
 }
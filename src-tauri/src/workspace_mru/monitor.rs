@@ -0,0 +1,193 @@
+//! Event-driven alternative to `probe::fs_recent_mtime`'s polling walk: one
+//! shared `notify` watcher covers every workspace root, and raw events are
+//! debounced into a per-root `last_active` update the same way
+//! `WorkspaceWatcher` debounces its own sync passes.
+//!
+//! A workspace whose watch couldn't be established (most commonly inotify's
+//! per-user watch descriptor limit being exhausted on Linux) is recorded in
+//! `unwatchable` so `ActiveWorkspaceTracker` keeps polling it the old way
+//! instead of waiting on events that will never arrive.
+
+use crate::file_types;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::{Mutex, RwLock as SyncRwLock};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{mpsc, RwLock};
+use tracing::{debug, warn};
+
+/// How long to wait after the last qualifying filesystem event for a
+/// workspace before updating its `last_active` - same window
+/// `WorkspaceWatcher` uses, so a save-triggered rebuild collapses into one
+/// update instead of one per touched file.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+pub struct WorkspaceMonitor {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+    ignores: SyncRwLock<HashMap<PathBuf, Gitignore>>,
+    unwatchable: SyncRwLock<HashSet<PathBuf>>,
+    last_active: RwLock<HashMap<PathBuf, SystemTime>>,
+}
+
+impl WorkspaceMonitor {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            watcher: Mutex::new(None),
+            ignores: SyncRwLock::new(HashMap::new()),
+            unwatchable: SyncRwLock::new(HashSet::new()),
+            last_active: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Whether `root` currently has a live watch - `ActiveWorkspaceTracker`
+    /// uses this to decide whether it can skip the filesystem walk in
+    /// `probe::probe_workspace` and merge in `last_active` instead.
+    pub fn is_watched(&self, root: &Path) -> bool {
+        self.ignores.read().contains_key(root)
+    }
+
+    /// Workspaces a watch couldn't be established for, so the caller can
+    /// keep running the full polling probe against them.
+    pub fn unwatchable_workspaces(&self) -> HashSet<PathBuf> {
+        self.unwatchable.read().clone()
+    }
+
+    pub async fn last_active(&self, root: &Path) -> Option<SystemTime> {
+        self.last_active.read().await.get(root).copied()
+    }
+
+    /// Registers a recursive watch on `root`, lazily creating the shared
+    /// watcher and its event-coalescing task on first call. A no-op if
+    /// `root` is already watched or was already marked `unwatchable` -
+    /// callers are expected to call this on every tick, since a workspace
+    /// can be added to settings at any time.
+    pub fn watch(self: &Arc<Self>, root: &Path) {
+        if self.is_watched(root) || self.unwatchable.read().contains(root) {
+            return;
+        }
+
+        let ignore = Self::build_ignore(root);
+
+        let mut watcher_guard = self.watcher.lock();
+        if watcher_guard.is_none() {
+            let (tx, rx) = mpsc::unbounded_channel();
+            match notify::recommended_watcher(move |res: notify::Result<Event>| {
+                if let Ok(event) = res {
+                    let _ = tx.send(event);
+                }
+            }) {
+                Ok(new_watcher) => {
+                    *watcher_guard = Some(new_watcher);
+                    tokio::spawn(Arc::clone(self).run_event_loop(rx));
+                }
+                Err(e) => {
+                    warn!("Failed to create workspace activity watcher: {}", e);
+                    return;
+                }
+            }
+        }
+
+        match watcher_guard
+            .as_mut()
+            .expect("watcher created above")
+            .watch(root, RecursiveMode::Recursive)
+        {
+            Ok(()) => {
+                debug!("Watching workspace {} for activity", root.display());
+                self.ignores.write().insert(root.to_path_buf(), ignore);
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to watch {} for activity ({}), falling back to polling for it",
+                    root.display(),
+                    e
+                );
+                self.unwatchable.write().insert(root.to_path_buf());
+            }
+        }
+    }
+
+    fn build_ignore(root: &Path) -> Gitignore {
+        let mut builder = GitignoreBuilder::new(root);
+        if let Some(e) = builder.add(root.join(".gitignore")) {
+            debug!("No .gitignore for {}: {}", root.display(), e);
+        }
+        if let Some(e) = builder.add(root.join(".ignore")) {
+            debug!("No .ignore for {}: {}", root.display(), e);
+        }
+        builder.build().unwrap_or_else(|e| {
+            warn!("Failed to parse ignore rules for {}: {}", root.display(), e);
+            Gitignore::empty()
+        })
+    }
+
+    /// Coalesces raw events arriving on `rx` into a per-root `last_active`
+    /// update, swallowing anything that arrives within `DEBOUNCE` of the
+    /// previous one the same way `WorkspaceWatcher::drain_and_settle` does.
+    async fn run_event_loop(self: Arc<Self>, mut rx: mpsc::UnboundedReceiver<Event>) {
+        loop {
+            let Some(event) = rx.recv().await else {
+                return;
+            };
+            let mut touched: HashSet<PathBuf> = self.matching_roots(&event).into_iter().collect();
+
+            loop {
+                tokio::select! {
+                    event = rx.recv() => {
+                        match event {
+                            Some(event) => touched.extend(self.matching_roots(&event)),
+                            None => return,
+                        }
+                    }
+                    _ = tokio::time::sleep(DEBOUNCE) => break,
+                }
+            }
+
+            if touched.is_empty() {
+                continue;
+            }
+
+            let now = SystemTime::now();
+            let mut last_active = self.last_active.write().await;
+            for root in touched {
+                debug!("Workspace {} marked active via filesystem watch", root.display());
+                last_active.insert(root, now);
+            }
+        }
+    }
+
+    /// Workspace roots `event` counts as activity for - empty for any event
+    /// kind other than create/modify/remove, any path outside a watched
+    /// root, any path matched by that root's `.gitignore`/`.ignore`, or any
+    /// path `file_types::classify` considers generated/binary churn (editor
+    /// swap files, `target/` output, ...).
+    fn matching_roots(&self, event: &Event) -> Vec<PathBuf> {
+        if !matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) {
+            return Vec::new();
+        }
+
+        let ignores = self.ignores.read();
+        let mut roots = Vec::new();
+        for path in &event.paths {
+            for (root, ignore) in ignores.iter() {
+                if !path.starts_with(root) {
+                    continue;
+                }
+                if ignore.matched(path, path.is_dir()).is_ignore() {
+                    continue;
+                }
+                if file_types::classify(path).is_ignored_for_activity() {
+                    continue;
+                }
+                roots.push(root.clone());
+            }
+        }
+        roots
+    }
+}
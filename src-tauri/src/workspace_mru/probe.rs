@@ -9,21 +9,16 @@ use tracing::debug;
 const MAX_FS_ENTRIES: usize = 400;
 
 pub fn probe_workspace(workspace_path: &Path, sys: &System) -> Probe {
-    let mut probe = Probe::default();
-
-    probe.from_process = process::check_running_process(workspace_path, sys);
-
-    probe.from_reflog = git_signals::head_reflog_time(workspace_path);
-    probe.from_uncommitted = git_signals::latest_uncommitted_mtime(workspace_path);
+    let mut probe = probe_workspace_without_fs_walk(workspace_path, sys);
 
     probe.from_fs = fs_signal::fs_recent_mtime(workspace_path, MAX_FS_ENTRIES);
-
     probe.compute_last_active();
 
     debug!(
-        "Workspace probe for {}: process={:?}, reflog={:?}, uncommitted={:?}, fs={:?}, last_active={:?}",
+        "Workspace probe for {}: process={:?} ({:?}), reflog={:?}, uncommitted={:?}, fs={:?}, last_active={:?}",
         workspace_path.display(),
         probe.from_process,
+        probe.process_signal,
         probe.from_reflog,
         probe.from_uncommitted,
         probe.from_fs,
@@ -33,6 +28,25 @@ pub fn probe_workspace(workspace_path: &Path, sys: &System) -> Probe {
     probe
 }
 
+/// Same as [`probe_workspace`] but without the `fs_recent_mtime` walk - used
+/// when `monitor::WorkspaceMonitor` already has a live watch on this
+/// workspace, so the caller merges in the watch's continuously-updated
+/// signal instead of paying for a redundant directory walk.
+pub fn probe_workspace_without_fs_walk(workspace_path: &Path, sys: &System) -> Probe {
+    let mut probe = Probe::default();
+
+    if let Some(process_match) = process::check_running_process(workspace_path, sys) {
+        probe.from_process = Some(process_match.when);
+        probe.process_signal = Some(process_match.signal);
+    }
+    probe.from_reflog = git_signals::head_reflog_time(workspace_path);
+    probe.from_uncommitted = git_signals::latest_uncommitted_mtime(workspace_path);
+
+    probe.compute_last_active();
+
+    probe
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
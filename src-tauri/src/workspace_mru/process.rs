@@ -1,14 +1,56 @@
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::time::SystemTime;
-use sysinfo::{ProcessRefreshKind, System, UpdateKind};
+use sysinfo::{Pid, Process, ProcessRefreshKind, System, UpdateKind};
 use tracing::debug;
 
 pub fn refresh_process_snapshot(sys: &mut System) {
-    let kind = ProcessRefreshKind::new().with_cwd(UpdateKind::Always);
+    let kind = ProcessRefreshKind::new()
+        .with_cwd(UpdateKind::Always)
+        .with_cmd(UpdateKind::Always);
     sys.refresh_processes_specifics(kind);
 }
 
-pub fn check_running_process(root: &Path, sys: &System) -> Option<SystemTime> {
+/// Which signal matched a process to a workspace, ordered weakest-to-strongest
+/// by [`rank`](ProcessSignal::rank) - lets `Probe` distinguish "someone is
+/// actively editing a file here" from the weaker "a shell happens to be
+/// cd'd here".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProcessSignal {
+    /// The process's cwd is under the workspace root - true of any shell or
+    /// tool launched from there, whether or not it's touching a file in it.
+    Cwd,
+    /// A command-line argument resolves under the workspace root - a GUI
+    /// editor or language server invoked with `$HOME` as its cwd but a file
+    /// or project path from the workspace on its command line.
+    CommandLineArg,
+    /// One of the process's open file handles resolves under the workspace
+    /// root - the strongest signal, since it means the process has a file
+    /// there open right now.
+    OpenFile,
+}
+
+impl ProcessSignal {
+    fn rank(self) -> u8 {
+        match self {
+            ProcessSignal::Cwd => 0,
+            ProcessSignal::CommandLineArg => 1,
+            ProcessSignal::OpenFile => 2,
+        }
+    }
+}
+
+pub struct ProcessMatch {
+    pub when: SystemTime,
+    pub signal: ProcessSignal,
+}
+
+/// Scans every running process for one whose cwd, command-line arguments, or
+/// open file handles resolve under `root`, returning the strongest signal
+/// found (see [`ProcessSignal`]'s ranking). Keeps scanning past a `Cwd` or
+/// `CommandLineArg` match in case a later process matches more strongly, but
+/// stops as soon as an `OpenFile` match is found since nothing outranks it.
+pub fn check_running_process(root: &Path, sys: &System) -> Option<ProcessMatch> {
     let canon_root = match root.canonicalize() {
         Ok(p) => p,
         Err(_) => {
@@ -17,25 +59,117 @@ pub fn check_running_process(root: &Path, sys: &System) -> Option<SystemTime> {
         }
     };
 
+    let mut best: Option<ProcessMatch> = None;
+
     for process in sys.processes().values() {
-        if let Some(cwd) = process.cwd() {
-            if let Ok(canon_cwd) = cwd.canonicalize() {
-                if canon_cwd.starts_with(&canon_root) {
-                    debug!(
-                        "Found running process in workspace {}: {} (pid: {})",
-                        root.display(),
-                        process.name(),
-                        process.pid()
-                    );
-                    return Some(SystemTime::now());
-                }
+        let Some(signal) = process_matches_root(process, &canon_root) else {
+            continue;
+        };
+
+        debug!(
+            "Found process matching workspace {} via {:?}: {} (pid: {})",
+            root.display(),
+            signal,
+            process.name(),
+            process.pid()
+        );
+
+        let is_stronger = best
+            .as_ref()
+            .map_or(true, |current| signal.rank() > current.signal.rank());
+        if is_stronger {
+            best = Some(ProcessMatch {
+                when: SystemTime::now(),
+                signal,
+            });
+        }
+
+        if signal == ProcessSignal::OpenFile {
+            break;
+        }
+    }
+
+    best
+}
+
+fn process_matches_root(process: &Process, canon_root: &Path) -> Option<ProcessSignal> {
+    if let Some(cwd) = process.cwd() {
+        if let Ok(canon_cwd) = cwd.canonicalize() {
+            if canon_cwd.starts_with(canon_root) {
+                return Some(ProcessSignal::Cwd);
             }
         }
     }
 
+    if process
+        .cmd()
+        .iter()
+        .any(|arg| path_under_root(Path::new(arg), canon_root))
+    {
+        return Some(ProcessSignal::CommandLineArg);
+    }
+
+    if open_files_under_root(process.pid(), canon_root) {
+        return Some(ProcessSignal::OpenFile);
+    }
+
     None
 }
 
+/// Resolves `candidate` against `canon_root` without requiring the target to
+/// exist - `canonicalize` fails for plenty of legitimate command-line
+/// arguments (a file about to be created, a glob, a flag value that isn't a
+/// path at all), so this falls back to a raw prefix comparison instead of
+/// treating every canonicalize failure as a non-match.
+fn path_under_root(candidate: &Path, canon_root: &Path) -> bool {
+    match candidate.canonicalize() {
+        Ok(canon) => canon.starts_with(canon_root),
+        Err(_) => candidate.starts_with(canon_root),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn open_files_under_root(pid: Pid, canon_root: &Path) -> bool {
+    let fd_dir = Path::new("/proc").join(pid.to_string()).join("fd");
+    let Ok(entries) = std::fs::read_dir(&fd_dir) else {
+        return false;
+    };
+
+    entries.flatten().any(|entry| {
+        std::fs::read_link(entry.path())
+            .map(|target| target.starts_with(canon_root))
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn open_files_under_root(pid: Pid, canon_root: &Path) -> bool {
+    // No syscall-level equivalent of `/proc/<pid>/fd` on macOS short of
+    // linking against the same private frameworks `lsof` itself uses, so
+    // shell out to it instead - this runs inside `spawn_blocking` (see
+    // `probe::probe_workspace`), so a blocking subprocess here doesn't stall
+    // the async runtime.
+    let Ok(output) = std::process::Command::new("lsof")
+        .args(["-a", "-p", &pid.to_string(), "-Fn"])
+        .output()
+    else {
+        return false;
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix('n'))
+        .any(|path| Path::new(path).starts_with(canon_root))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn open_files_under_root(_pid: Pid, _canon_root: &Path) -> bool {
+    // Windows' equivalent requires walking every process's handle table via
+    // NtQuerySystemInformation, which isn't worth the complexity here - the
+    // cwd and command-line signals still cover it.
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,4 +204,22 @@ mod tests {
             "Should not detect process in nonexistent path"
         );
     }
+
+    #[test]
+    fn process_signal_ranks_open_file_above_command_line_above_cwd() {
+        assert!(ProcessSignal::OpenFile.rank() > ProcessSignal::CommandLineArg.rank());
+        assert!(ProcessSignal::CommandLineArg.rank() > ProcessSignal::Cwd.rank());
+    }
+
+    #[test]
+    fn path_under_root_falls_back_to_prefix_match_for_nonexistent_path() {
+        assert!(path_under_root(
+            Path::new("/nonexistent/workspace/new_file.rs"),
+            Path::new("/nonexistent/workspace")
+        ));
+        assert!(!path_under_root(
+            Path::new("/nonexistent/elsewhere/new_file.rs"),
+            Path::new("/nonexistent/workspace")
+        ));
+    }
 }
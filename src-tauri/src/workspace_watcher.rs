@@ -0,0 +1,148 @@
+//! Live filesystem watching for `default_workspaces_folder`, so a repo that
+//! gets cloned into (or removed from) that folder shows up in the sidebar
+//! without waiting on a manual refresh.
+//!
+//! Raw filesystem events are coalesced over `DEBOUNCE` before each settled
+//! batch triggers a `WorkspaceSync::sync()` pass - we reuse its existing
+//! incremental add/remove diff (which already respects `ignored_workspaces`
+//! and `normalized_path`) rather than reimplementing it here.
+
+use crate::settings::{SettingsManager, WorkspaceSync};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+/// How long to wait after the last raw filesystem event before running a
+/// sync pass, so a `git clone`'s burst of file writes settles into one pass
+/// instead of dozens.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// How often to re-check `default_workspaces_folder` for changes while
+/// otherwise idle, so editing it in settings takes effect without an app
+/// restart.
+const RECONFIGURE_POLL: Duration = Duration::from_secs(5);
+
+pub struct WorkspaceWatcher {
+    settings_manager: Arc<SettingsManager>,
+    workspace_sync: Arc<WorkspaceSync>,
+}
+
+impl WorkspaceWatcher {
+    pub fn new(settings_manager: Arc<SettingsManager>, workspace_sync: Arc<WorkspaceSync>) -> Self {
+        Self {
+            settings_manager,
+            workspace_sync,
+        }
+    }
+
+    /// Runs until the process exits, (re)creating the OS-native watcher
+    /// whenever `default_workspaces_folder` changes and running a sync pass
+    /// on every settled batch of events.
+    pub async fn run(self: Arc<Self>, app_handle: AppHandle) {
+        let mut watched_folder: Option<PathBuf> = None;
+        let mut events: Option<mpsc::UnboundedReceiver<()>> = None;
+        let mut watcher: Option<RecommendedWatcher> = None;
+
+        loop {
+            let configured = self.configured_folder().await;
+
+            if configured != watched_folder {
+                match &configured {
+                    Some(folder) => info!("Watching {:?} for workspace changes", folder),
+                    None => debug!("No default_workspaces_folder configured, pausing watcher"),
+                }
+
+                watcher = None;
+                events = None;
+
+                if let Some(folder) = &configured {
+                    match Self::watch(folder) {
+                        Ok((new_watcher, rx)) => {
+                            watcher = Some(new_watcher);
+                            events = Some(rx);
+                        }
+                        Err(e) => warn!("Failed to watch {:?}: {}", folder, e),
+                    }
+                }
+
+                watched_folder = configured;
+            }
+
+            let Some(rx) = events.as_mut() else {
+                tokio::time::sleep(RECONFIGURE_POLL).await;
+                continue;
+            };
+
+            tokio::select! {
+                event = rx.recv() => {
+                    if event.is_none() {
+                        // Watcher half of the channel dropped (e.g. the
+                        // watched folder was removed out from under us) -
+                        // fall back to polling until it reappears.
+                        watcher = None;
+                        events = None;
+                        continue;
+                    }
+                    self.drain_and_settle(rx).await;
+                    self.sync_and_emit(&app_handle).await;
+                }
+                _ = tokio::time::sleep(RECONFIGURE_POLL) => {}
+            }
+        }
+    }
+
+    async fn configured_folder(&self) -> Option<PathBuf> {
+        let settings = self.settings_manager.get().await;
+        let raw = &settings.defaults.default_workspaces_folder;
+        if raw.is_empty() {
+            return None;
+        }
+
+        let expanded = shellexpand::tilde(raw);
+        let path = PathBuf::from(expanded.as_ref());
+        path.is_dir().then_some(path)
+    }
+
+    fn watch(
+        folder: &PathBuf,
+    ) -> notify::Result<(RecommendedWatcher, mpsc::UnboundedReceiver<()>)> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if res.is_ok() {
+                let _ = tx.send(());
+            }
+        })?;
+        watcher.watch(folder, RecursiveMode::Recursive)?;
+        Ok((watcher, rx))
+    }
+
+    /// Swallows any further events that arrive within `DEBOUNCE`, so a burst
+    /// of writes collapses into one sync pass instead of one per file.
+    async fn drain_and_settle(&self, rx: &mut mpsc::UnboundedReceiver<()>) {
+        loop {
+            tokio::select! {
+                event = rx.recv() => {
+                    if event.is_none() {
+                        return;
+                    }
+                }
+                _ = tokio::time::sleep(DEBOUNCE) => return,
+            }
+        }
+    }
+
+    async fn sync_and_emit(&self, app_handle: &AppHandle) {
+        match self.workspace_sync.sync().await {
+            Ok(result) => {
+                if !result.added.is_empty() || !result.removed.is_empty() {
+                    let _ = app_handle.emit("workspace-sync-result", &result);
+                }
+            }
+            Err(e) => warn!("Workspace watcher sync failed: {}", e),
+        }
+    }
+}
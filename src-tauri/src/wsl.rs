@@ -0,0 +1,104 @@
+//! Detection and path translation for WSL-backed clone/open targets.
+//!
+//! On Windows, a WSL distro's filesystem is reachable through a UNC path
+//! like `\\wsl$\Ubuntu\home\user\repo` (or the newer `\\wsl.localhost\...`
+//! alias), and some deep links address the same target with a `wsl://`
+//! URI instead. Either form needs translating to the distro's own
+//! `/home/user/repo` form before running git/file operations *inside* the
+//! distro, and back to the UNC form before handing a path to something
+//! that resolves paths as the Windows host sees them. This is the
+//! hand-rolled equivalent of the `wslpath -w`/`-u` CLI tool that ships
+//! with WSL itself.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WslTarget {
+    pub distro: String,
+    /// Linux-side absolute path, e.g. `/home/user/repo`.
+    pub linux_path: String,
+}
+
+const UNC_PREFIXES: &[&str] = &[r"\\wsl$\", r"\\wsl.localhost\"];
+const URI_PREFIX: &str = "wsl://";
+
+/// Detects whether `path` addresses a WSL distro - either the `\\wsl$\` /
+/// `\\wsl.localhost\` UNC form Windows mounts distros under, or a
+/// `wsl://<distro>/<path>` deep-link URI - and splits it into the distro
+/// name and the Linux-side path. Returns `None` for anything else,
+/// including a plain Windows or Unix path.
+pub fn detect(path: &str) -> Option<WslTarget> {
+    for prefix in UNC_PREFIXES {
+        if let Some(rest) = path.strip_prefix(prefix) {
+            return split_distro_and_path(rest, '\\');
+        }
+    }
+
+    if let Some(rest) = path.strip_prefix(URI_PREFIX) {
+        return split_distro_and_path(rest, '/');
+    }
+
+    None
+}
+
+fn split_distro_and_path(rest: &str, separator: char) -> Option<WslTarget> {
+    let (distro, tail) = rest.split_once(separator).unwrap_or((rest, ""));
+    if distro.is_empty() {
+        return None;
+    }
+
+    let linux_path = format!("/{}", tail.replace('\\', "/").trim_start_matches('/'));
+
+    Some(WslTarget {
+        distro: distro.to_string(),
+        linux_path,
+    })
+}
+
+/// Builds the `\\wsl$\<distro>\...` UNC form of `target`, the Windows-side
+/// view of a WSL-backed path - the equivalent of `wslpath -w`.
+pub fn to_windows_unc(target: &WslTarget) -> String {
+    let tail = target.linux_path.trim_start_matches('/').replace('/', "\\");
+    format!(r"\\wsl$\{}\{}", target.distro, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_wsl_dollar_unc_path() {
+        let target = detect(r"\\wsl$\Ubuntu\home\user\repo").unwrap();
+        assert_eq!(target.distro, "Ubuntu");
+        assert_eq!(target.linux_path, "/home/user/repo");
+    }
+
+    #[test]
+    fn detects_wsl_localhost_unc_path() {
+        let target = detect(r"\\wsl.localhost\Debian\home\user\repo").unwrap();
+        assert_eq!(target.distro, "Debian");
+        assert_eq!(target.linux_path, "/home/user/repo");
+    }
+
+    #[test]
+    fn detects_wsl_uri() {
+        let target = detect("wsl://Ubuntu/home/user/repo").unwrap();
+        assert_eq!(target.distro, "Ubuntu");
+        assert_eq!(target.linux_path, "/home/user/repo");
+    }
+
+    #[test]
+    fn ignores_plain_windows_and_unix_paths() {
+        assert_eq!(detect(r"C:\Users\user\repo"), None);
+        assert_eq!(detect("/home/user/repo"), None);
+    }
+
+    #[test]
+    fn round_trips_to_windows_unc() {
+        let target = WslTarget {
+            distro: "Ubuntu".to_string(),
+            linux_path: "/home/user/repo".to_string(),
+        };
+        assert_eq!(to_windows_unc(&target), r"\\wsl$\Ubuntu\home\user\repo");
+    }
+}
@@ -1,5 +1,11 @@
 mod parser;
 mod types;
 
-pub use parser::{detect_provider, extract_path_line_suffix, parse_remote_url};
-pub use types::{ParseError, Provider, SrcuriTarget};
+pub use parser::{
+    canonicalize_remote, detect_provider, extract_path_line_suffix, parse_remote_url,
+    parse_remote_url_with_registry, parse_spec,
+};
+pub use types::{
+    ArchiveFormat, GitReference, ParseError, Protocol, Provider, ProviderRegistry, RefSpec,
+    Resource, SrcuriTarget,
+};
@@ -1,37 +1,208 @@
-use crate::types::{ParseError, Provider, SrcuriTarget};
+use crate::types::{
+    GitReference, ParseError, Protocol, Provider, ProviderRegistry, RefSpec, Resource,
+    SrcuriTarget,
+};
 use url::Url;
 
 /// Parse a remote URL in various formats:
 /// - Full URL: `https://github.com/owner/repo/blob/main/file.rs#L42`
 /// - Path-style: `github.com/owner/repo/blob/main/file.rs:42`
 /// - With https:// in path: `https://github.com/owner/repo/...`
+/// - SSH: `git@github.com:owner/repo.git`, `ssh://git@gitlab.com/group/project.git`
 pub fn parse_remote_url(remote_url: &str) -> Result<SrcuriTarget, ParseError> {
-    // Extract line number from :N suffix if present (for path-style URLs)
-    let (url_part, path_line) = extract_path_line_suffix(remote_url);
+    parse_remote_url_with_registry(remote_url, None)
+}
+
+/// Same as [`parse_remote_url`], but consults `registry` (if given) before
+/// the built-in pattern/host heuristics - so a self-hosted Gitea/Forgejo/etc.
+/// instance registered with it resolves deterministically instead of relying
+/// on its URL happening to contain a recognizable marker.
+pub fn parse_remote_url_with_registry(
+    remote_url: &str,
+    registry: Option<&ProviderRegistry>,
+) -> Result<SrcuriTarget, ParseError> {
+    let mut target = if let Some(https_equivalent) = ssh_to_https_url(remote_url) {
+        let mut target = parse_normalized_url(&https_equivalent, remote_url, registry)?;
+        target.protocol = Some(Protocol::Ssh);
+        target
+    } else {
+        // Extract line number from :N suffix if present (for path-style URLs)
+        let (url_part, path_line) = extract_path_line_suffix(remote_url);
+
+        // Normalize to full URL
+        let normalized = normalize_to_url(url_part);
+
+        let mut target = parse_normalized_url(&normalized, remote_url, registry)?;
+
+        // Override line with path-extracted line if present and no line from fragment
+        if target.line.is_none() {
+            target.line = path_line;
+        }
+        target.protocol = Some(Protocol::Https);
+        target
+    };
+
+    target.remote = canonicalize_remote(&target.remote);
+
+    Ok(target)
+}
+
+/// Collapses the variant spellings of a remote that all name the same
+/// repository - `www.Github.com/Owner/Repo.git/`, `github.com/owner/repo`,
+/// etc. - down to one identity, modeled on cargo's `canonicalize_url`/`ident`
+/// (`sources::git::source`) which does the same thing for `Cargo.lock`
+/// source keys. Only the host is case-folded for providers whose paths are
+/// themselves case-sensitive (GitLab); elsewhere the whole remote is folded,
+/// since GitHub/Bitbucket/Gitea/Azure all treat owner/repo case-insensitively
+/// and a stable cache key shouldn't depend on which case the caller typed.
+pub fn canonicalize_remote(remote: &str) -> String {
+    let trimmed = remote
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .trim_end_matches('/');
+
+    let (host, rest) = match trimmed.split_once('/') {
+        Some((host, rest)) => (host, Some(rest)),
+        None => (trimmed, None),
+    };
+
+    let mut host = host.to_lowercase();
+    if let Some(stripped) = host.strip_prefix("www.") {
+        host = stripped.to_string();
+    }
+    for default_port in [":443", ":80", ":22"] {
+        if let Some(stripped) = host.strip_suffix(default_port) {
+            host = stripped.to_string();
+            break;
+        }
+    }
+
+    let preserve_path_case = host.contains("gitlab");
 
-    // Normalize to full URL
-    let normalized = normalize_to_url(url_part);
+    match rest {
+        Some(path) if preserve_path_case => format!("{}/{}", host, path),
+        Some(path) => format!("{}/{}", host, path.to_lowercase()),
+        None => host,
+    }
+}
+
+/// Parse a compact repo spec instead of a full URL, as osoy does with
+/// `<[[domain/]author/]package>`:
+/// - `owner/repo` defaults to `github.com/owner/repo`
+/// - `host/owner/repo` is a repo on that host
+/// - `gl:group/project`, `cb:owner/repo`, `az:org/project/repo` pick the
+///   provider explicitly regardless of what `detect_provider`'s heuristics
+///   would otherwise guess
+///
+/// A real URL (anything with `://`) or anything else this doesn't recognize
+/// as one of the above shapes (including an scp-style SSH remote, which also
+/// contains a `:`) is handed to [`parse_remote_url`] unchanged.
+pub fn parse_spec(spec: &str) -> Result<SrcuriTarget, ParseError> {
+    if spec.contains("://") {
+        return parse_remote_url(spec);
+    }
+
+    if let Some((prefix, rest)) = spec.split_once(':') {
+        return match expand_provider_prefix(prefix, rest) {
+            Some(expanded) => parse_remote_url(&expanded),
+            None => parse_remote_url(spec),
+        };
+    }
+
+    let segment_count = spec.split('/').filter(|s| !s.is_empty()).count();
+    match segment_count {
+        2 => parse_remote_url(&format!("https://github.com/{}", spec)),
+        3 => parse_remote_url(&format!("https://{}", spec)),
+        _ => parse_remote_url(spec),
+    }
+}
+
+/// Expands a `gl:`/`cb:`/`az:` shorthand prefix into the full `https://...`
+/// URL [`parse_spec`] feeds back through [`parse_remote_url`]. Returns `None`
+/// for any other prefix, including one that just looks like it (e.g. an SSH
+/// remote's `user@host`).
+fn expand_provider_prefix(prefix: &str, rest: &str) -> Option<String> {
+    match prefix {
+        "gl" => Some(format!("https://gitlab.com/{}", rest)),
+        "cb" => Some(format!("https://codeberg.org/{}", rest)),
+        "az" => {
+            let parts: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+            match parts.as_slice() {
+                [org, project, repo] => {
+                    Some(format!("https://dev.azure.com/{}/{}/_git/{}", org, project, repo))
+                }
+                [org, repo] => Some(format!("https://dev.azure.com/{}/_git/{}", org, repo)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
 
-    let url = Url::parse(&normalized)
-        .map_err(|e| ParseError::new(format!("Invalid URL: {}", e), remote_url))?;
+fn parse_normalized_url(
+    normalized: &str,
+    original: &str,
+    registry: Option<&ProviderRegistry>,
+) -> Result<SrcuriTarget, ParseError> {
+    let url = Url::parse(normalized)
+        .map_err(|e| ParseError::new(format!("Invalid URL: {}", e), original))?;
 
-    let provider = detect_provider(&url)
-        .ok_or_else(|| ParseError::new("Unrecognized repository provider", remote_url))?;
+    let provider = detect_provider_with_registry(&url, registry)
+        .ok_or_else(|| ParseError::new("Unrecognized repository provider", original))?;
 
-    let mut target = match provider {
+    match provider {
         Provider::GitHub => parse_github(&url),
         Provider::GitLab => parse_gitlab(&url),
         Provider::Bitbucket => parse_bitbucket(&url),
         Provider::Gitea | Provider::Codeberg => parse_gitea(&url, provider),
         Provider::AzureDevOps => parse_azure(&url),
-    }?;
+    }
+}
 
-    // Override line with path-extracted line if present and no line from fragment
-    if target.line.is_none() {
-        target.line = path_line;
+/// [`detect_provider`], but checking `registry`'s explicit host mappings
+/// first so a configured self-hosted instance doesn't depend on the
+/// pattern/host heuristics below recognizing it.
+fn detect_provider_with_registry(url: &Url, registry: Option<&ProviderRegistry>) -> Option<Provider> {
+    if let Some(registry) = registry {
+        if let Some(provider) = url.host_str().and_then(|host| registry.resolve(host)) {
+            return Some(provider);
+        }
     }
 
-    Ok(target)
+    detect_provider(url)
+}
+
+/// Converts an scp-like (`user@host:owner/repo.git`) or explicit `ssh://`
+/// git remote into the `https://host/owner/repo` shape the rest of this
+/// module already knows how to parse, so an SSH origin gets the same
+/// provider detection and `remote`/`repo_name` output as its HTTPS
+/// equivalent instead of a second, parallel implementation. Returns `None`
+/// for anything else, including a bare `host:LINE` path (e.g. a malformed
+/// `file.rs:42`), which looks like scp syntax at a glance but isn't one -
+/// `extract_path_line_suffix` handles that shape instead.
+fn ssh_to_https_url(input: &str) -> Option<String> {
+    let (host, path) = if let Some(rest) = input.strip_prefix("ssh://") {
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next().unwrap_or(authority);
+        let host = host.split(':').next()?; // drop an explicit port
+        (host, path)
+    } else if !input.contains("://") {
+        let (user_host, path) = input.split_once(':')?;
+        if !user_host.contains('@') || path.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let host = user_host.rsplit('@').next()?;
+        (host, path)
+    } else {
+        return None;
+    };
+
+    let path = path.trim_end_matches(".git");
+    if path.is_empty() {
+        return None;
+    }
+
+    Some(format!("https://{}/{}", host, path))
 }
 
 /// Extract :N line suffix from end of path-style URL
@@ -108,6 +279,13 @@ pub fn detect_provider(url: &Url) -> Option<Provider> {
         return Some(Provider::AzureDevOps);
     }
 
+    // Bitbucket Server/Data Center's `/projects/KEY/repos/NAME/browse/...`
+    // grammar, distinct from Bitbucket Cloud's `/src/` - the host alone gives
+    // no hint for a self-hosted instance, so the path shape is all we have.
+    if path.starts_with("/projects/") && path.contains("/repos/") && path.contains("/browse") {
+        return Some(Provider::Bitbucket);
+    }
+
     if path.contains("/blob/")
         || path.contains("/tree/")
         || path.contains("/blame/")
@@ -144,6 +322,35 @@ fn parse_github(url: &Url) -> Result<SrcuriTarget, ParseError> {
     let path = url.path();
     let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
 
+    // Gists live on a separate host with their own grammar entirely -
+    // `/{user}/{id}` for an owned gist, bare `/{id}` for an anonymous one -
+    // not a repo at all, so this must be handled before anything below
+    // assumes `segments[0]`/`segments[1]` are an owner/repo pair.
+    if host == "gist.github.com" {
+        let (remote, id) = match segments.as_slice() {
+            [user, id] => (format!("gist.github.com/{}/{}", user, id), *id),
+            [id] => (format!("gist.github.com/{}", id), *id),
+            _ => {
+                return Err(ParseError::new(
+                    "Gist URL must have an id",
+                    url.as_str(),
+                ));
+            }
+        };
+        return Ok(SrcuriTarget {
+            remote,
+            repo_name: id.to_string(),
+            ref_value: None,
+            file_path: None,
+            line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Gist,
+            is_absolute: false,
+            protocol: None,
+        });
+    }
+
     // Handle codespaces.new domain: https://codespaces.new/owner/repo?params
     if host == "codespaces.new" {
         if segments.len() >= 2 {
@@ -156,7 +363,11 @@ fn parse_github(url: &Url) -> Result<SrcuriTarget, ParseError> {
                 ref_value: None,
                 file_path: None,
                 line: None,
+                end_line: None,
+                ref_spec: None,
+                resource: Resource::Repo,
                 is_absolute: false,
+                protocol: None,
             });
         }
         return Err(ParseError::new(
@@ -183,7 +394,11 @@ fn parse_github(url: &Url) -> Result<SrcuriTarget, ParseError> {
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
@@ -207,7 +422,53 @@ fn parse_github(url: &Url) -> Result<SrcuriTarget, ParseError> {
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
+            is_absolute: false,
+            protocol: None,
+        });
+    }
+
+    // GitHub's branch-compare view: /{owner}/{repo}/compare/{base}...{head}
+    if segments.get(2) == Some(&"compare") {
+        if let Some(spec) = segments.get(3).and_then(|s| parse_compare_spec(s)) {
+            return Ok(SrcuriTarget {
+                remote,
+                repo_name: repo.to_string(),
+                ref_value: None,
+                file_path: None,
+                line: None,
+                end_line: None,
+                ref_spec: Some(spec),
+                resource: Resource::Repo,
+                is_absolute: false,
+                protocol: None,
+            });
+        }
+    }
+
+    // GitHub's wiki: /{owner}/{repo}/wiki[/{page}] - a page is its own
+    // separate git repo under the hood, but the URL names a page, not a
+    // ref, so `ref_value` stays unset; a bare `/wiki` with no page segment
+    // is the wiki's landing page, which GitHub itself resolves to `Home`.
+    if segments.get(2) == Some(&"wiki") {
+        let page = if segments.len() > 3 {
+            segments[3..].join("/")
+        } else {
+            "Home".to_string()
+        };
+        return Ok(SrcuriTarget {
+            remote,
+            repo_name: repo.to_string(),
+            ref_value: None,
+            file_path: Some(page),
+            line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Wiki,
             is_absolute: false,
+            protocol: None,
         });
     }
 
@@ -224,7 +485,11 @@ fn parse_github(url: &Url) -> Result<SrcuriTarget, ParseError> {
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
@@ -236,17 +501,21 @@ fn parse_github(url: &Url) -> Result<SrcuriTarget, ParseError> {
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
-    let ref_value = segments.get(3).map(|s| s.to_string());
+    let ref_value = segments.get(3).map(|s| GitReference::from_shape(s.to_string()));
     let file_path = if segments.len() > 4 {
         Some(segments[4..].join("/"))
     } else {
         None
     };
-    let line = extract_github_line(url.fragment());
+    let (line, end_line) = extract_github_line(url.fragment());
 
     Ok(SrcuriTarget {
         remote,
@@ -254,7 +523,11 @@ fn parse_github(url: &Url) -> Result<SrcuriTarget, ParseError> {
         ref_value,
         file_path,
         line,
+        end_line,
+        ref_spec: None,
+        resource: Resource::Repo,
         is_absolute: false,
+        protocol: None,
     })
 }
 
@@ -272,7 +545,7 @@ fn parse_gitlab(url: &Url) -> Result<SrcuriTarget, ParseError> {
 
         // Check for edit/:ref/... pattern
         if segments.len() >= 7 && segments[5] == "edit" {
-            let ref_value = Some(segments[6].to_string());
+            let ref_value = Some(GitReference::from_shape(segments[6].to_string()));
 
             // Determine file path - several patterns possible:
             // 1. edit/:ref/-/:path (standard with -/ separator)
@@ -298,13 +571,19 @@ fn parse_gitlab(url: &Url) -> Result<SrcuriTarget, ParseError> {
                 None
             };
 
+            let (line, end_line) = extract_github_line(url.fragment());
+
             return Ok(SrcuriTarget {
                 remote,
                 repo_name: project.to_string(),
                 ref_value,
                 file_path,
-                line: extract_github_line(url.fragment()),
+                line,
+                end_line,
+                ref_spec: None,
+                resource: Resource::Repo,
                 is_absolute: false,
+                protocol: None,
             });
         }
 
@@ -313,10 +592,14 @@ fn parse_gitlab(url: &Url) -> Result<SrcuriTarget, ParseError> {
             return Ok(SrcuriTarget {
                 remote,
                 repo_name: project.to_string(),
-                ref_value: Some(segments[6].to_string()),
+                ref_value: Some(GitReference::from_shape(segments[6].to_string())),
                 file_path: None,
                 line: None,
+                end_line: None,
+                ref_spec: None,
+                resource: Resource::Repo,
                 is_absolute: false,
+                protocol: None,
             });
         }
 
@@ -327,7 +610,11 @@ fn parse_gitlab(url: &Url) -> Result<SrcuriTarget, ParseError> {
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
@@ -350,7 +637,11 @@ fn parse_gitlab(url: &Url) -> Result<SrcuriTarget, ParseError> {
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
@@ -364,12 +655,35 @@ fn parse_gitlab(url: &Url) -> Result<SrcuriTarget, ParseError> {
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
     let dash_idx = dash_pos.unwrap();
     let view_type = segments.get(dash_idx + 1).copied();
+
+    // GitLab's branch-compare view: /-/compare/{base}...{head}
+    if view_type == Some("compare") {
+        if let Some(spec) = segments.get(dash_idx + 2).and_then(|s| parse_compare_spec(s)) {
+            return Ok(SrcuriTarget {
+                remote,
+                repo_name: project.to_string(),
+                ref_value: None,
+                file_path: None,
+                line: None,
+                end_line: None,
+                ref_spec: Some(spec),
+                resource: Resource::Repo,
+                is_absolute: false,
+                protocol: None,
+            });
+        }
+    }
+
     if !matches!(
         view_type,
         Some("blob") | Some("tree") | Some("blame") | Some("raw")
@@ -380,17 +694,23 @@ fn parse_gitlab(url: &Url) -> Result<SrcuriTarget, ParseError> {
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
-    let ref_value = segments.get(dash_idx + 2).map(|s| s.to_string());
+    let ref_value = segments
+        .get(dash_idx + 2)
+        .map(|s| GitReference::from_shape(s.to_string()));
     let file_path = if segments.len() > dash_idx + 3 {
         Some(segments[dash_idx + 3..].join("/"))
     } else {
         None
     };
-    let line = extract_github_line(url.fragment());
+    let (line, end_line) = extract_github_line(url.fragment());
 
     Ok(SrcuriTarget {
         remote,
@@ -398,7 +718,11 @@ fn parse_gitlab(url: &Url) -> Result<SrcuriTarget, ParseError> {
         ref_value,
         file_path,
         line,
+        end_line,
+        ref_spec: None,
+        resource: Resource::Repo,
         is_absolute: false,
+        protocol: None,
     })
 }
 
@@ -407,6 +731,14 @@ fn parse_bitbucket(url: &Url) -> Result<SrcuriTarget, ParseError> {
     let path = url.path();
     let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
 
+    // Bitbucket Server/Data Center uses a completely different URL grammar
+    // from Bitbucket Cloud's `/src/` - `/projects/KEY/repos/NAME/browse/...` -
+    // with no provider-name hint in the host, so this is the only place that
+    // can tell the two apart.
+    if segments.first() == Some(&"projects") && segments.get(2) == Some(&"repos") {
+        return parse_bitbucket_server(url, host, &segments);
+    }
+
     if segments.len() < 2 {
         return Err(ParseError::new(
             "Bitbucket URL must have workspace and repo",
@@ -426,10 +758,41 @@ fn parse_bitbucket(url: &Url) -> Result<SrcuriTarget, ParseError> {
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
+    // Bitbucket's branch-compare view: /branches/compare/{head}%0D{base}
+    // (a literal carriage return, not a `.`/`..` separator, between the two).
+    if segments.get(2) == Some(&"branches") && segments.get(3) == Some(&"compare") {
+        if let Some(spec) = segments
+            .get(4)
+            .and_then(|s| s.split_once("%0D"))
+            .map(|(head, base)| RefSpec::Range {
+                base: base.to_string(),
+                head: head.to_string(),
+                symmetric: false,
+            })
+        {
+            return Ok(SrcuriTarget {
+                remote,
+                repo_name: repo.to_string(),
+                ref_value: None,
+                file_path: None,
+                line: None,
+                end_line: None,
+                ref_spec: Some(spec),
+                resource: Resource::Repo,
+                is_absolute: false,
+                protocol: None,
+            });
+        }
+    }
+
     // Check for /src/ pattern
     if segments.get(2) != Some(&"src") {
         return Ok(SrcuriTarget {
@@ -438,17 +801,72 @@ fn parse_bitbucket(url: &Url) -> Result<SrcuriTarget, ParseError> {
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
-    let ref_value = segments.get(3).map(|s| s.to_string());
+    let ref_value = segments.get(3).map(|s| GitReference::from_shape(s.to_string()));
     let file_path = if segments.len() > 4 {
         Some(segments[4..].join("/"))
     } else {
         None
     };
-    let line = extract_bitbucket_line(url.fragment());
+    let (line, end_line) = extract_bitbucket_line(url.fragment());
+
+    Ok(SrcuriTarget {
+        remote,
+        repo_name: repo.to_string(),
+        ref_value,
+        file_path,
+        line,
+        end_line,
+        ref_spec: None,
+        resource: Resource::Repo,
+        is_absolute: false,
+        protocol: None,
+    })
+}
+
+/// Parses Bitbucket Server/Data Center's `/projects/KEY/repos/NAME/browse/path#N`
+/// shape - unlike Bitbucket Cloud, the ref lives in an `at=refs/heads/...` query
+/// param rather than the path, and the line fragment is a bare number (or
+/// `N-M` range) with no `lines-` prefix.
+fn parse_bitbucket_server(
+    url: &Url,
+    host: &str,
+    segments: &[&str],
+) -> Result<SrcuriTarget, ParseError> {
+    let key = segments.get(1).ok_or_else(|| {
+        ParseError::new("Bitbucket Server URL must have a project key", url.as_str())
+    })?;
+    let repo = segments.get(3).ok_or_else(|| {
+        ParseError::new("Bitbucket Server URL must have a repo after /repos/", url.as_str())
+    })?;
+    let remote = format!("{}/projects/{}/repos/{}", host, key, repo);
+
+    let ref_value = url.query_pairs().find(|(k, _)| k == "at").map(|(_, v)| {
+        let value = v.into_owned();
+        if let Some(branch) = value.strip_prefix("refs/heads/") {
+            GitReference::Branch(branch.to_string())
+        } else if let Some(tag) = value.strip_prefix("refs/tags/") {
+            GitReference::Tag(tag.to_string())
+        } else {
+            GitReference::from_shape(value)
+        }
+    });
+
+    // segments[4] is "browse"; the file path (if any) follows it.
+    let file_path = if segments.len() > 5 {
+        Some(segments[5..].join("/"))
+    } else {
+        None
+    };
+
+    let (line, end_line) = extract_bitbucket_server_line(url.fragment());
 
     Ok(SrcuriTarget {
         remote,
@@ -456,7 +874,11 @@ fn parse_bitbucket(url: &Url) -> Result<SrcuriTarget, ParseError> {
         ref_value,
         file_path,
         line,
+        end_line,
+        ref_spec: None,
+        resource: Resource::Repo,
         is_absolute: false,
+        protocol: None,
     })
 }
 
@@ -487,7 +909,11 @@ fn parse_gitea(url: &Url, provider: Provider) -> Result<SrcuriTarget, ParseError
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
@@ -499,7 +925,11 @@ fn parse_gitea(url: &Url, provider: Provider) -> Result<SrcuriTarget, ParseError
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
@@ -511,17 +941,32 @@ fn parse_gitea(url: &Url, provider: Provider) -> Result<SrcuriTarget, ParseError
             ref_value: None,
             file_path: None,
             line: None,
+            end_line: None,
+            ref_spec: None,
+            resource: Resource::Repo,
             is_absolute: false,
+            protocol: None,
         });
     }
 
-    let ref_value = segments.get(4).map(|s| s.to_string());
+    // Gitea's URL carries the ref's kind explicitly, unlike GitHub/GitLab/
+    // Bitbucket's blob URLs - use it instead of falling back to the SHA-shape
+    // heuristic.
+    let ref_value = segments.get(4).map(|s| {
+        let value = s.to_string();
+        match ref_type {
+            Some("branch") => GitReference::Branch(value),
+            Some("tag") => GitReference::Tag(value),
+            Some("commit") => GitReference::Commit(value),
+            _ => GitReference::from_shape(value),
+        }
+    });
     let file_path = if segments.len() > 5 {
         Some(segments[5..].join("/"))
     } else {
         None
     };
-    let line = extract_github_line(url.fragment());
+    let (line, end_line) = extract_github_line(url.fragment());
 
     Ok(SrcuriTarget {
         remote,
@@ -529,7 +974,11 @@ fn parse_gitea(url: &Url, provider: Provider) -> Result<SrcuriTarget, ParseError
         ref_value,
         file_path,
         line,
+        end_line,
+        ref_spec: None,
+        resource: Resource::Repo,
         is_absolute: false,
+        protocol: None,
     })
 }
 
@@ -560,6 +1009,7 @@ fn parse_azure(url: &Url) -> Result<SrcuriTarget, ParseError> {
     let mut file_path = None;
     let mut ref_value = None;
     let mut line = None;
+    let mut end_line = None;
 
     for (key, value) in url.query_pairs() {
         match key.as_ref() {
@@ -570,57 +1020,141 @@ fn parse_azure(url: &Url) -> Result<SrcuriTarget, ParseError> {
                 }
             }
             "version" => {
-                // Strip GB/GT/GC prefix
+                // GB/GT/GC prefix tells us branch/tag/commit directly
                 if value.len() >= 2 {
-                    ref_value = Some(value[2..].to_string());
+                    let name = value[2..].to_string();
+                    ref_value = Some(match &value[..2] {
+                        "GB" => GitReference::Branch(name),
+                        "GT" => GitReference::Tag(name),
+                        "GC" => GitReference::Commit(name),
+                        _ => GitReference::from_shape(name),
+                    });
                 }
             }
             "line" => {
                 line = value.parse().ok();
             }
+            "lineEnd" => {
+                end_line = value.parse().ok();
+            }
             _ => {}
         }
     }
 
+    // A single-line selection often still carries a redundant lineEnd equal
+    // to line - normalize that to None the same as the other providers'
+    // single-line anchors.
+    if end_line == line {
+        end_line = None;
+    }
+
     Ok(SrcuriTarget {
         remote,
         repo_name: repo.to_string(),
         ref_value,
         file_path,
         line,
+        end_line,
+        ref_spec: None,
+        resource: Resource::Repo,
         is_absolute: false,
+        protocol: None,
     })
 }
 
-fn extract_github_line(fragment: Option<&str>) -> Option<u32> {
-    let fragment = fragment?;
+/// Parses GitHub/GitLab/Gitea's `#L10` or `#L10-L20`/`#L10-20` fragment into
+/// `(line, end_line)`, normalizing a same-value range to a single-line anchor
+/// (`end_line: None`) the same as a fragment with no range at all.
+fn extract_github_line(fragment: Option<&str>) -> (Option<u32>, Option<u32>) {
+    let Some(fragment) = fragment else {
+        return (None, None);
+    };
     if !fragment.starts_with('L') {
-        return None;
+        return (None, None);
     }
     let rest = &fragment[1..];
-    let num_str = rest.split('-').next()?;
-    let num_str = num_str.trim_start_matches('L');
-    num_str.parse().ok()
+    let mut parts = rest.splitn(2, '-');
+    let Some(start) = parts.next().and_then(|s| s.parse::<u32>().ok()) else {
+        return (None, None);
+    };
+    let end = parts
+        .next()
+        .map(|s| s.trim_start_matches('L'))
+        .and_then(|s| s.parse::<u32>().ok())
+        .filter(|&end| end != start);
+
+    (Some(start), end)
 }
 
-fn extract_bitbucket_line(fragment: Option<&str>) -> Option<u32> {
-    let fragment = fragment?;
+/// Parses Bitbucket's `#lines-5` or `#lines-5:10`/`#lines-10-20` fragment
+/// into `(line, end_line)`, with the same single-line normalization as
+/// [`extract_github_line`].
+fn extract_bitbucket_line(fragment: Option<&str>) -> (Option<u32>, Option<u32>) {
+    let Some(fragment) = fragment else {
+        return (None, None);
+    };
     if !fragment.starts_with("lines-") {
-        return None;
+        return (None, None);
     }
     let rest = &fragment[6..]; // e.g., "5", "5:10", or "10-20"
                                // Try colon separator first (lines-5:10), then dash (lines-10-20)
-    let num_str = if rest.contains(':') {
-        rest.split(':').next()?
-    } else {
-        rest.split('-').next().unwrap_or(rest)
+    let separator = if rest.contains(':') { ':' } else { '-' };
+    let mut parts = rest.splitn(2, separator);
+    let Some(start) = parts.next().and_then(|s| s.parse::<u32>().ok()) else {
+        return (None, None);
     };
-    num_str.parse().ok()
+    let end = parts
+        .next()
+        .and_then(|s| s.parse::<u32>().ok())
+        .filter(|&end| end != start);
+
+    (Some(start), end)
+}
+
+/// Parses Bitbucket Server's bare `#42` or `#42-50` line fragment into
+/// `(line, end_line)` - no `lines-` prefix, unlike Bitbucket Cloud.
+/// Parses GitHub/GitLab's `{base}...{head}` (symmetric, git's own "merge-base"
+/// diff) or `{base}..{head}` (asymmetric, direct two-dot range) compare spec.
+/// Three dots are checked first since `split_once("..")` would otherwise
+/// split a `...` spec at the wrong point.
+fn parse_compare_spec(spec: &str) -> Option<RefSpec> {
+    if let Some((base, head)) = spec.split_once("...") {
+        return Some(RefSpec::Range {
+            base: base.to_string(),
+            head: head.to_string(),
+            symmetric: true,
+        });
+    }
+    if let Some((base, head)) = spec.split_once("..") {
+        return Some(RefSpec::Range {
+            base: base.to_string(),
+            head: head.to_string(),
+            symmetric: false,
+        });
+    }
+    None
+}
+
+fn extract_bitbucket_server_line(fragment: Option<&str>) -> (Option<u32>, Option<u32>) {
+    let Some(fragment) = fragment else {
+        return (None, None);
+    };
+    let mut parts = fragment.splitn(2, '-');
+    let Some(start) = parts.next().and_then(|s| s.parse::<u32>().ok()) else {
+        return (None, None);
+    };
+    let end = parts
+        .next()
+        .and_then(|s| s.parse::<u32>().ok())
+        .filter(|&end| end != start);
+
+    (Some(start), end)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::ArchiveFormat;
 
     // ==================== GitHub Tests ====================
 
@@ -640,7 +1174,7 @@ mod tests {
             parse_remote_url("https://github.com/owner/repo/blob/main/src/lib.rs").unwrap();
         assert_eq!(result.remote, "github.com/owner/repo");
         assert_eq!(result.repo_name, "repo");
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("main".to_string())));
         assert_eq!(result.file_path, Some("src/lib.rs".to_string()));
         assert_eq!(result.line, None);
     }
@@ -649,7 +1183,7 @@ mod tests {
     fn github_blob_with_sha() {
         let result =
             parse_remote_url("https://github.com/owner/repo/blob/abc123def456/file.rs").unwrap();
-        assert_eq!(result.ref_value, Some("abc123def456".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Commit("abc123def456".to_string())));
     }
 
     #[test]
@@ -658,27 +1192,37 @@ mod tests {
             parse_remote_url("https://github.com/owner/repo/blob/main/src/lib.rs#L42").unwrap();
         assert_eq!(result.file_path, Some("src/lib.rs".to_string()));
         assert_eq!(result.line, Some(42));
+        assert_eq!(result.end_line, None);
     }
 
     #[test]
     fn github_blob_with_line_range() {
         let result =
             parse_remote_url("https://github.com/owner/repo/blob/main/file.rs#L10-L20").unwrap();
-        assert_eq!(result.line, Some(10)); // Takes first line only
+        assert_eq!(result.line, Some(10));
+        assert_eq!(result.end_line, Some(20));
+    }
+
+    #[test]
+    fn github_blob_with_line_range_no_repeated_l() {
+        let result =
+            parse_remote_url("https://github.com/owner/repo/blob/main/file.rs#L10-20").unwrap();
+        assert_eq!(result.line, Some(10));
+        assert_eq!(result.end_line, Some(20));
     }
 
     #[test]
     fn github_tree_directory() {
         let result =
             parse_remote_url("https://github.com/owner/repo/tree/main/src/components").unwrap();
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("main".to_string())));
         assert_eq!(result.file_path, Some("src/components".to_string()));
     }
 
     #[test]
     fn github_tree_root() {
         let result = parse_remote_url("https://github.com/owner/repo/tree/develop").unwrap();
-        assert_eq!(result.ref_value, Some("develop".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("develop".to_string())));
         assert_eq!(result.file_path, None);
     }
 
@@ -686,7 +1230,7 @@ mod tests {
     fn github_blame() {
         let result =
             parse_remote_url("https://github.com/owner/repo/blame/main/src/main.rs#L100").unwrap();
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("main".to_string())));
         assert_eq!(result.file_path, Some("src/main.rs".to_string()));
         assert_eq!(result.line, Some(100));
     }
@@ -694,7 +1238,7 @@ mod tests {
     #[test]
     fn github_raw() {
         let result = parse_remote_url("https://github.com/owner/repo/raw/main/README.md").unwrap();
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("main".to_string())));
         assert_eq!(result.file_path, Some("README.md".to_string()));
     }
 
@@ -721,7 +1265,7 @@ mod tests {
         let result =
             parse_remote_url("https://gitlab.com/group/project/-/blob/master/lib/file.rb").unwrap();
         assert_eq!(result.remote, "gitlab.com/group/project");
-        assert_eq!(result.ref_value, Some("master".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("master".to_string())));
         assert_eq!(result.file_path, Some("lib/file.rb".to_string()));
     }
 
@@ -730,13 +1274,25 @@ mod tests {
         let result =
             parse_remote_url("https://gitlab.com/group/project/-/blob/master/file.rb#L12").unwrap();
         assert_eq!(result.line, Some(12));
+        assert_eq!(result.end_line, None);
+    }
+
+    #[test]
+    fn gitlab_blob_with_line_range() {
+        // GitLab's own range syntax omits the second `L` (`#L5-10`), unlike
+        // GitHub's `#L5-L10`.
+        let result =
+            parse_remote_url("https://gitlab.com/group/project/-/blob/master/file.rb#L5-10")
+                .unwrap();
+        assert_eq!(result.line, Some(5));
+        assert_eq!(result.end_line, Some(10));
     }
 
     #[test]
     fn gitlab_tree() {
         let result =
             parse_remote_url("https://gitlab.com/group/project/-/tree/develop/src").unwrap();
-        assert_eq!(result.ref_value, Some("develop".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("develop".to_string())));
         assert_eq!(result.file_path, Some("src".to_string()));
     }
 
@@ -745,7 +1301,7 @@ mod tests {
         let result =
             parse_remote_url("https://gitlab.com/group/project/-/blame/main/config.yml#L50")
                 .unwrap();
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("main".to_string())));
         assert_eq!(result.line, Some(50));
     }
 
@@ -753,7 +1309,7 @@ mod tests {
     fn gitlab_raw() {
         let result =
             parse_remote_url("https://gitlab.com/group/project/-/raw/main/script.sh").unwrap();
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("main".to_string())));
         assert_eq!(result.file_path, Some("script.sh".to_string()));
     }
 
@@ -763,7 +1319,7 @@ mod tests {
             parse_remote_url("https://gitlab.mycompany.com/team/app/-/blob/develop/main.py#L10")
                 .unwrap();
         assert_eq!(result.remote, "gitlab.mycompany.com/team/app");
-        assert_eq!(result.ref_value, Some("develop".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("develop".to_string())));
         assert_eq!(result.line, Some(10));
     }
 
@@ -789,7 +1345,7 @@ mod tests {
     fn bitbucket_src_with_branch() {
         let result =
             parse_remote_url("https://bitbucket.org/workspace/repo/src/master/README.md").unwrap();
-        assert_eq!(result.ref_value, Some("master".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("master".to_string())));
         assert_eq!(result.file_path, Some("README.md".to_string()));
     }
 
@@ -799,6 +1355,7 @@ mod tests {
             parse_remote_url("https://bitbucket.org/workspace/repo/src/master/file.py#lines-5")
                 .unwrap();
         assert_eq!(result.line, Some(5));
+        assert_eq!(result.end_line, None);
     }
 
     #[test]
@@ -806,7 +1363,18 @@ mod tests {
         let result =
             parse_remote_url("https://bitbucket.org/workspace/repo/src/master/file.py#lines-5:10")
                 .unwrap();
-        assert_eq!(result.line, Some(5)); // Takes first line only
+        assert_eq!(result.line, Some(5));
+        assert_eq!(result.end_line, Some(10));
+    }
+
+    #[test]
+    fn bitbucket_line_range_dash() {
+        let result = parse_remote_url(
+            "https://bitbucket.org/workspace/repo/src/master/file.py#lines-10-20",
+        )
+        .unwrap();
+        assert_eq!(result.line, Some(10));
+        assert_eq!(result.end_line, Some(20));
     }
 
     #[test]
@@ -819,6 +1387,46 @@ mod tests {
         assert_eq!(result.line, Some(100));
     }
 
+    #[test]
+    fn bitbucket_server_browse_with_branch_and_line() {
+        let result = parse_remote_url(
+            "https://git.mycorp.com/projects/FOO/repos/bar/browse/src/main.rs?at=refs%2Fheads%2Fdevelop#42",
+        )
+        .unwrap();
+        assert_eq!(result.remote, "git.mycorp.com/projects/foo/repos/bar");
+        assert_eq!(result.repo_name, "bar");
+        assert_eq!(result.ref_value, Some(GitReference::Branch("develop".to_string())));
+        assert_eq!(result.file_path, Some("src/main.rs".to_string()));
+        assert_eq!(result.line, Some(42));
+        assert_eq!(result.end_line, None);
+    }
+
+    #[test]
+    fn bitbucket_server_browse_with_tag_and_line_range() {
+        let result = parse_remote_url(
+            "https://git.mycorp.com/projects/FOO/repos/bar/browse/file.py?at=refs%2Ftags%2Fv1.0#10-20",
+        )
+        .unwrap();
+        assert_eq!(result.ref_value, Some(GitReference::Tag("v1.0".to_string())));
+        assert_eq!(result.line, Some(10));
+        assert_eq!(result.end_line, Some(20));
+    }
+
+    #[test]
+    fn bitbucket_server_browse_without_at_or_path() {
+        let result =
+            parse_remote_url("https://git.mycorp.com/projects/FOO/repos/bar/browse").unwrap();
+        assert_eq!(result.remote, "git.mycorp.com/projects/foo/repos/bar");
+        assert_eq!(result.ref_value, None);
+        assert_eq!(result.file_path, None);
+    }
+
+    #[test]
+    fn detect_bitbucket_server_by_path_pattern() {
+        let url = Url::parse("https://git.mycorp.com/projects/FOO/repos/bar/browse").unwrap();
+        assert_eq!(detect_provider(&url), Some(Provider::Bitbucket));
+    }
+
     // ==================== Gitea Tests ====================
 
     #[test]
@@ -833,21 +1441,21 @@ mod tests {
     fn gitea_src_branch() {
         let result =
             parse_remote_url("https://gitea.com/org/repo/src/branch/main/cmd/main.go").unwrap();
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Branch("main".to_string())));
         assert_eq!(result.file_path, Some("cmd/main.go".to_string()));
     }
 
     #[test]
     fn gitea_src_tag() {
         let result = parse_remote_url("https://gitea.com/org/repo/src/tag/v1.0.0/file.go").unwrap();
-        assert_eq!(result.ref_value, Some("v1.0.0".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Tag("v1.0.0".to_string())));
     }
 
     #[test]
     fn gitea_src_commit() {
         let result =
             parse_remote_url("https://gitea.com/org/repo/src/commit/abc123/file.go").unwrap();
-        assert_eq!(result.ref_value, Some("abc123".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Commit("abc123".to_string())));
     }
 
     #[test]
@@ -864,7 +1472,7 @@ mod tests {
         )
         .unwrap();
         assert_eq!(result.remote, "git.mycompany.com/team/project");
-        assert_eq!(result.ref_value, Some("develop".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Branch("develop".to_string())));
         assert_eq!(result.line, Some(15));
     }
 
@@ -882,7 +1490,7 @@ mod tests {
         let result =
             parse_remote_url("https://codeberg.org/user/repo/src/branch/main/file.go#L10").unwrap();
         assert_eq!(result.remote, "codeberg.org/user/repo");
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Branch("main".to_string())));
         assert_eq!(result.line, Some(10));
     }
 
@@ -890,7 +1498,7 @@ mod tests {
     fn codeberg_src_tag() {
         let result =
             parse_remote_url("https://codeberg.org/user/repo/src/tag/v2.0/README.md").unwrap();
-        assert_eq!(result.ref_value, Some("v2.0".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Tag("v2.0".to_string())));
     }
 
     // ==================== Azure DevOps Tests ====================
@@ -916,7 +1524,7 @@ mod tests {
             "https://dev.azure.com/org/project/_git/repo?path=/src/index.ts&version=GBmain",
         )
         .unwrap();
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Branch("main".to_string())));
         assert_eq!(result.file_path, Some("src/index.ts".to_string()));
     }
 
@@ -927,6 +1535,27 @@ mod tests {
         )
         .unwrap();
         assert_eq!(result.line, Some(12));
+        assert_eq!(result.end_line, None);
+    }
+
+    #[test]
+    fn azure_with_line_range() {
+        let result = parse_remote_url(
+            "https://dev.azure.com/org/project/_git/repo?path=/file.ts&version=GBmain&line=12&lineEnd=18",
+        )
+        .unwrap();
+        assert_eq!(result.line, Some(12));
+        assert_eq!(result.end_line, Some(18));
+    }
+
+    #[test]
+    fn azure_line_end_equal_to_line_normalizes_to_none() {
+        let result = parse_remote_url(
+            "https://dev.azure.com/org/project/_git/repo?path=/file.ts&version=GBmain&line=12&lineEnd=12",
+        )
+        .unwrap();
+        assert_eq!(result.line, Some(12));
+        assert_eq!(result.end_line, None);
     }
 
     #[test]
@@ -934,21 +1563,21 @@ mod tests {
         let result =
             parse_remote_url("https://dev.azure.com/org/_git/repo?version=GBfeature/my-branch")
                 .unwrap();
-        assert_eq!(result.ref_value, Some("feature/my-branch".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Branch("feature/my-branch".to_string())));
     }
 
     #[test]
     fn azure_version_tag_prefix() {
         let result =
             parse_remote_url("https://dev.azure.com/org/_git/repo?version=GTv1.0.0").unwrap();
-        assert_eq!(result.ref_value, Some("v1.0.0".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Tag("v1.0.0".to_string())));
     }
 
     #[test]
     fn azure_version_commit_prefix() {
         let result =
             parse_remote_url("https://dev.azure.com/org/_git/repo?version=GCabc123def").unwrap();
-        assert_eq!(result.ref_value, Some("abc123def".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Commit("abc123def".to_string())));
     }
 
     #[test]
@@ -956,7 +1585,7 @@ mod tests {
         let result = parse_remote_url("https://dev.azure.com/org/project/_git/repo?path=/src/components/App.tsx&version=GBmain&line=42").unwrap();
         assert_eq!(result.remote, "dev.azure.com/org/project/_git/repo");
         assert_eq!(result.repo_name, "repo");
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Branch("main".to_string())));
         assert_eq!(result.file_path, Some("src/components/App.tsx".to_string()));
         assert_eq!(result.line, Some(42));
     }
@@ -967,7 +1596,7 @@ mod tests {
     fn path_style_github_no_https() {
         let result = parse_remote_url("github.com/owner/repo/blob/main/file.rs").unwrap();
         assert_eq!(result.remote, "github.com/owner/repo");
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("main".to_string())));
         assert_eq!(result.file_path, Some("file.rs".to_string()));
     }
 
@@ -994,7 +1623,7 @@ mod tests {
                 .unwrap();
         assert_eq!(result.remote, "github.dev/ericbeland/enhanced_errors");
         assert_eq!(result.repo_name, "enhanced_errors");
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("main".to_string())));
         assert_eq!(result.file_path, Some("Gemfile".to_string()));
     }
 
@@ -1011,7 +1640,9 @@ mod tests {
     #[test]
     fn codespaces_new_basic() {
         let result = parse_remote_url("https://codespaces.new/OWNER/REPO").unwrap();
-        assert_eq!(result.remote, "github.com/OWNER/REPO");
+        // `remote` is canonicalized (case-folded for a case-insensitive host
+        // like GitHub); `repo_name` keeps the caller's original casing.
+        assert_eq!(result.remote, "github.com/owner/repo");
         assert_eq!(result.repo_name, "REPO");
     }
 
@@ -1031,7 +1662,7 @@ mod tests {
                 .unwrap();
         assert_eq!(result.remote, "gitlab.com/paynearme/juno");
         assert_eq!(result.repo_name, "juno");
-        assert_eq!(result.ref_value, Some("main".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("main".to_string())));
         assert_eq!(result.file_path, Some("Gemfile".to_string()));
     }
 
@@ -1042,7 +1673,7 @@ mod tests {
         )
         .unwrap();
         assert_eq!(result.remote, "gitlab.com/group/project");
-        assert_eq!(result.ref_value, Some("develop".to_string()));
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("develop".to_string())));
         assert_eq!(result.file_path, Some("src/lib/file.rb".to_string()));
     }
 
@@ -1084,6 +1715,616 @@ mod tests {
         assert_eq!(detect_provider(&url), Some(Provider::AzureDevOps));
     }
 
+    // ==================== ProviderRegistry ====================
+
+    #[test]
+    fn registry_resolves_unrecognizable_self_hosted_gitea() {
+        // No `/src/branch/...` marker and no gitea-ish host name - the
+        // built-in heuristics alone can't tell this apart from an
+        // unrecognized provider.
+        let mut registry = ProviderRegistry::new();
+        registry.register_host("git.example.com", Provider::Gitea);
+
+        let result =
+            parse_remote_url_with_registry("https://git.example.com/owner/repo", Some(&registry))
+                .unwrap();
+        assert_eq!(result.remote, "git.example.com/owner/repo");
+    }
+
+    #[test]
+    fn registry_host_suffix_matches_subdomains() {
+        let mut registry = ProviderRegistry::new();
+        registry.register_host_suffix(".corp.example.com", Provider::GitLab);
+
+        let result = parse_remote_url_with_registry(
+            "https://git.corp.example.com/group/project",
+            Some(&registry),
+        )
+        .unwrap();
+        assert_eq!(result.remote, "git.corp.example.com/group/project");
+    }
+
+    #[test]
+    fn registry_exact_host_wins_over_suffix() {
+        let mut registry = ProviderRegistry::new();
+        registry.register_host_suffix(".example.com", Provider::Gitea);
+        registry.register_host("git.example.com", Provider::GitLab);
+
+        assert_eq!(registry.resolve("git.example.com"), Some(Provider::GitLab));
+    }
+
+    #[test]
+    fn registry_falls_back_to_heuristics_when_host_unmapped() {
+        let registry = ProviderRegistry::new();
+        let result =
+            parse_remote_url_with_registry("https://github.com/owner/repo", Some(&registry))
+                .unwrap();
+        assert_eq!(result.remote, "github.com/owner/repo");
+    }
+
+    #[test]
+    fn registry_maps_github_enterprise_host() {
+        // GitHub Enterprise's host gives no hint on its own - only a
+        // registered mapping tells the parser which rules to apply.
+        let mut registry = ProviderRegistry::new();
+        registry.register_host("github.mycorp.com", Provider::GitHub);
+
+        let result = parse_remote_url_with_registry(
+            "https://github.mycorp.com/owner/repo/blob/main/file.rs",
+            Some(&registry),
+        )
+        .unwrap();
+        assert_eq!(result.remote, "github.mycorp.com/owner/repo");
+        assert_eq!(result.file_path, Some("file.rs".to_string()));
+    }
+
+    #[test]
+    fn registry_maps_bitbucket_server_host_even_without_path_hint() {
+        let mut registry = ProviderRegistry::new();
+        registry.register_host("git.mycorp.com", Provider::Bitbucket);
+
+        let result = parse_remote_url_with_registry(
+            "https://git.mycorp.com/projects/FOO/repos/bar",
+            Some(&registry),
+        )
+        .unwrap();
+        assert_eq!(result.remote, "git.mycorp.com/projects/foo/repos/bar");
+        assert_eq!(result.repo_name, "bar");
+    }
+
+    // ==================== SSH Tests ====================
+
+    #[test]
+    fn ssh_scp_style_github() {
+        let result = parse_remote_url("git@github.com:owner/repo.git").unwrap();
+        assert_eq!(result.remote, "github.com/owner/repo");
+        assert_eq!(result.repo_name, "repo");
+        assert_eq!(result.protocol, Some(Protocol::Ssh));
+    }
+
+    #[test]
+    fn ssh_scp_style_gitlab() {
+        let result = parse_remote_url("git@gitlab.com:group/project.git").unwrap();
+        assert_eq!(result.remote, "gitlab.com/group/project");
+        assert_eq!(result.repo_name, "project");
+        assert_eq!(result.protocol, Some(Protocol::Ssh));
+    }
+
+    #[test]
+    fn ssh_explicit_scheme() {
+        let result = parse_remote_url("ssh://git@gitlab.com/group/project.git").unwrap();
+        assert_eq!(result.remote, "gitlab.com/group/project");
+        assert_eq!(result.repo_name, "project");
+        assert_eq!(result.protocol, Some(Protocol::Ssh));
+    }
+
+    #[test]
+    fn ssh_explicit_scheme_with_port() {
+        let result = parse_remote_url("ssh://git@github.com:22/owner/repo.git").unwrap();
+        assert_eq!(result.remote, "github.com/owner/repo");
+        assert_eq!(result.repo_name, "repo");
+    }
+
+    #[test]
+    fn ssh_without_git_suffix() {
+        let result = parse_remote_url("git@github.com:owner/repo").unwrap();
+        assert_eq!(result.remote, "github.com/owner/repo");
+        assert_eq!(result.repo_name, "repo");
+    }
+
+    #[test]
+    fn https_target_has_https_protocol() {
+        let result = parse_remote_url("https://github.com/owner/repo").unwrap();
+        assert_eq!(result.protocol, Some(Protocol::Https));
+    }
+
+    #[test]
+    fn scp_style_not_confused_with_line_suffix() {
+        // `file.rs:42` has no `@`, so it must not be mistaken for scp syntax
+        // even though it has the same `host:path` shape.
+        assert!(ssh_to_https_url("file.rs:42").is_none());
+    }
+
+    // ==================== Compact Spec Tests ====================
+
+    #[test]
+    fn spec_bare_owner_repo_defaults_to_github() {
+        let result = parse_spec("owner/repo").unwrap();
+        assert_eq!(result.remote, "github.com/owner/repo");
+        assert_eq!(result.repo_name, "repo");
+    }
+
+    #[test]
+    fn spec_host_owner_repo() {
+        let result = parse_spec("gitlab.mycompany.com/team/app").unwrap();
+        assert_eq!(result.remote, "gitlab.mycompany.com/team/app");
+    }
+
+    #[test]
+    fn spec_gitlab_prefix() {
+        let result = parse_spec("gl:group/project").unwrap();
+        assert_eq!(result.remote, "gitlab.com/group/project");
+    }
+
+    #[test]
+    fn spec_codeberg_prefix() {
+        let result = parse_spec("cb:owner/repo").unwrap();
+        assert_eq!(result.remote, "codeberg.org/owner/repo");
+    }
+
+    #[test]
+    fn spec_azure_prefix_with_project() {
+        let result = parse_spec("az:org/project/repo").unwrap();
+        assert_eq!(result.remote, "dev.azure.com/org/project/_git/repo");
+    }
+
+    #[test]
+    fn spec_azure_prefix_without_project() {
+        let result = parse_spec("az:org/repo").unwrap();
+        assert_eq!(result.remote, "dev.azure.com/org/_git/repo");
+    }
+
+    #[test]
+    fn spec_full_url_passes_through() {
+        let result = parse_spec("https://github.com/owner/repo/blob/main/file.rs#L10").unwrap();
+        assert_eq!(result.remote, "github.com/owner/repo");
+        assert_eq!(result.file_path, Some("file.rs".to_string()));
+        assert_eq!(result.line, Some(10));
+    }
+
+    #[test]
+    fn spec_scp_style_ssh_passes_through() {
+        // Has a `:` but isn't a `gl:`/`cb:`/`az:` prefix - falls back to the
+        // regular SSH-aware parser instead of being misread as a shorthand.
+        let result = parse_spec("git@github.com:owner/repo.git").unwrap();
+        assert_eq!(result.remote, "github.com/owner/repo");
+        assert_eq!(result.protocol, Some(Protocol::Ssh));
+    }
+
+    #[test]
+    fn spec_path_style_line_suffix_passes_through() {
+        let result = parse_spec("github.com/owner/repo/blob/main/file.rs:42").unwrap();
+        assert_eq!(result.line, Some(42));
+    }
+
+    // ==================== GitReference Classification ====================
+
+    #[test]
+    fn github_blob_treats_short_hex_as_branch() {
+        // 6 hex chars is below the 7-char minimum for a SHA prefix, so it's
+        // classified as Unknown (a plausible branch name) rather than Commit.
+        let result =
+            parse_remote_url("https://github.com/owner/repo/blob/ab12cd/file.rs").unwrap();
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("ab12cd".to_string())));
+    }
+
+    #[test]
+    fn github_blob_full_sha_is_commit() {
+        let result = parse_remote_url(
+            "https://github.com/owner/repo/blob/0123456789abcdef0123456789abcdef01234567/file.rs",
+        )
+        .unwrap();
+        assert!(matches!(result.ref_value, Some(GitReference::Commit(_))));
+    }
+
+    #[test]
+    fn gitea_explicit_kind_wins_over_sha_shape() {
+        // Gitea's own "branch" marker is trusted even for a SHA-shaped name,
+        // since a repo can legitimately have a hex-looking branch.
+        let result =
+            parse_remote_url("https://gitea.com/org/repo/src/branch/abc123d/file.go").unwrap();
+        assert_eq!(result.ref_value, Some(GitReference::Branch("abc123d".to_string())));
+    }
+
+    // ==================== Remote Canonicalization ====================
+
+    #[test]
+    fn canonicalize_remote_variants_collapse_to_one_identity() {
+        let canonical = "github.com/owner/repo";
+        assert_eq!(canonicalize_remote("github.com/Owner/Repo.git"), canonical);
+        assert_eq!(canonicalize_remote("www.github.com/owner/repo/"), canonical);
+        assert_eq!(canonicalize_remote("github.com/owner/repo"), canonical);
+    }
+
+    #[test]
+    fn canonicalize_remote_parsed_variants_collapse_to_one_identity() {
+        let github = parse_remote_url("https://github.com/Owner/Repo.git").unwrap();
+        let www = parse_remote_url("https://www.github.com/owner/repo/").unwrap();
+        let ssh = parse_remote_url("git@github.com:owner/repo").unwrap();
+        assert_eq!(github.remote, "github.com/owner/repo");
+        assert_eq!(www.remote, "github.com/owner/repo");
+        assert_eq!(ssh.remote, "github.com/owner/repo");
+    }
+
+    #[test]
+    fn canonicalize_remote_strips_default_port() {
+        assert_eq!(canonicalize_remote("github.com:443/owner/repo"), "github.com/owner/repo");
+    }
+
+    #[test]
+    fn canonicalize_remote_preserves_gitlab_path_case() {
+        assert_eq!(
+            canonicalize_remote("GitLab.com/Group/Project"),
+            "gitlab.com/Group/Project"
+        );
+    }
+
+    #[test]
+    fn canonicalize_remote_host_only_has_no_trailing_slash() {
+        assert_eq!(canonicalize_remote("github.com/"), "github.com");
+    }
+
+    // ==================== Round-trip Tests ====================
+    // `SrcuriTarget::to_view_url` (added alongside `to_raw_url`/`to_edit_url`
+    // for permalinks) is the inverse of `parse_remote_url` - these confirm
+    // re-parsing its output reproduces the fields that matter for a link,
+    // rather than just spot-checking the URL string.
+
+    fn assert_round_trips(original_url: &str) {
+        let original = parse_remote_url(original_url).unwrap();
+        let rebuilt = original.to_view_url().expect("target has a file_path");
+        let reparsed = parse_remote_url(&rebuilt).unwrap();
+
+        assert_eq!(reparsed.remote, original.remote);
+        assert_eq!(reparsed.repo_name, original.repo_name);
+        assert_eq!(reparsed.ref_value, original.ref_value);
+        assert_eq!(reparsed.file_path, original.file_path);
+        assert_eq!(reparsed.line, original.line);
+    }
+
+    #[test]
+    fn round_trip_github_blob_with_line() {
+        assert_round_trips("https://github.com/owner/repo/blob/main/src/lib.rs#L42");
+    }
+
+    #[test]
+    fn round_trip_gitlab_blob_with_line() {
+        assert_round_trips("https://gitlab.com/group/project/-/blob/master/lib/file.rb#L12");
+    }
+
+    #[test]
+    fn round_trip_bitbucket_src_with_line() {
+        assert_round_trips("https://bitbucket.org/workspace/repo/src/master/file.py#lines-5");
+    }
+
+    #[test]
+    fn round_trip_codeberg_src_with_line() {
+        assert_round_trips("https://codeberg.org/user/repo/src/branch/main/file.go#L10");
+    }
+
+    #[test]
+    fn round_trip_selfhosted_gitea_with_line() {
+        assert_round_trips(
+            "https://git.mycompany.com/team/project/src/branch/develop/app.go#L15",
+        );
+    }
+
+    #[test]
+    fn round_trip_without_line() {
+        // No fragment at all on either side of the trip.
+        assert_round_trips("https://github.com/owner/repo/blob/main/src/lib.rs");
+    }
+
+    #[test]
+    fn to_view_url_none_for_repo_only_target() {
+        // Degenerate case: no file_path means no sensible web URL to build.
+        let target = parse_remote_url("https://github.com/owner/repo").unwrap();
+        assert_eq!(target.to_view_url(), None);
+    }
+
+    // ==================== Blame/History URL Tests ====================
+
+    #[test]
+    fn github_blame_and_history_urls() {
+        let target =
+            parse_remote_url("https://github.com/owner/repo/blob/main/src/lib.rs#L42").unwrap();
+        assert_eq!(
+            target.to_blame_url().unwrap(),
+            "https://github.com/owner/repo/blame/main/src/lib.rs#L42"
+        );
+        assert_eq!(
+            target.to_history_url().unwrap(),
+            "https://github.com/owner/repo/commits/main/src/lib.rs"
+        );
+    }
+
+    #[test]
+    fn gitlab_blame_and_history_urls() {
+        let target =
+            parse_remote_url("https://gitlab.com/group/project/-/blob/master/lib/file.rb#L12")
+                .unwrap();
+        assert_eq!(
+            target.to_blame_url().unwrap(),
+            "https://gitlab.com/group/project/-/blame/master/lib/file.rb#L12"
+        );
+        assert_eq!(
+            target.to_history_url().unwrap(),
+            "https://gitlab.com/group/project/-/commits/master/lib/file.rb"
+        );
+    }
+
+    #[test]
+    fn bitbucket_blame_url_and_no_history() {
+        let target =
+            parse_remote_url("https://bitbucket.org/workspace/repo/src/master/file.py#lines-5")
+                .unwrap();
+        assert_eq!(
+            target.to_blame_url().unwrap(),
+            "https://bitbucket.org/workspace/repo/annotate/master/file.py#lines-5"
+        );
+        // No well-known single-file history URL shape on Bitbucket.
+        assert_eq!(target.to_history_url(), None);
+    }
+
+    #[test]
+    fn codeberg_blame_and_history_urls() {
+        let target =
+            parse_remote_url("https://codeberg.org/user/repo/src/branch/main/file.go#L10")
+                .unwrap();
+        assert_eq!(
+            target.to_blame_url().unwrap(),
+            "https://codeberg.org/user/repo/blame/branch/main/file.go#L10"
+        );
+        assert_eq!(
+            target.to_history_url().unwrap(),
+            "https://codeberg.org/user/repo/commits/branch/main/file.go"
+        );
+    }
+
+    #[test]
+    fn azure_blame_and_history_urls_use_a_tab_param() {
+        let target = parse_remote_url(
+            "https://dev.azure.com/org/project/_git/repo?path=/src/app.py&version=GBmain&line=7",
+        )
+        .unwrap();
+        assert_eq!(
+            target.to_blame_url().unwrap(),
+            "https://dev.azure.com/org/project/_git/repo?path=/src/app.py&version=GBmain&line=7&_a=annotate"
+        );
+        assert_eq!(
+            target.to_history_url().unwrap(),
+            "https://dev.azure.com/org/project/_git/repo?path=/src/app.py&version=GBmain&line=7&_a=history"
+        );
+    }
+
+    // ==================== Archive/Raw Download URL Tests ====================
+
+    #[test]
+    fn github_archive_and_raw_urls() {
+        let target =
+            parse_remote_url("https://github.com/owner/repo/blob/main/src/lib.rs").unwrap();
+        assert_eq!(
+            target.to_archive_url(ArchiveFormat::TarGz).unwrap(),
+            "https://codeload.github.com/owner/repo/tar.gz/main"
+        );
+        assert_eq!(
+            target.to_archive_url(ArchiveFormat::Zip).unwrap(),
+            "https://codeload.github.com/owner/repo/zip/main"
+        );
+        assert_eq!(
+            target.to_raw_url().unwrap(),
+            "https://raw.githubusercontent.com/owner/repo/main/src/lib.rs"
+        );
+    }
+
+    #[test]
+    fn gitlab_archive_and_raw_urls() {
+        let target =
+            parse_remote_url("https://gitlab.com/group/project/-/blob/master/lib/file.rb")
+                .unwrap();
+        assert_eq!(
+            target.to_archive_url(ArchiveFormat::TarGz).unwrap(),
+            "https://gitlab.com/group/project/-/archive/master/project-master.tar.gz"
+        );
+        assert_eq!(
+            target.to_raw_url().unwrap(),
+            "https://gitlab.com/group/project/-/raw/master/lib/file.rb"
+        );
+    }
+
+    #[test]
+    fn bitbucket_archive_and_raw_urls() {
+        let target =
+            parse_remote_url("https://bitbucket.org/workspace/repo/src/master/file.py").unwrap();
+        assert_eq!(
+            target.to_archive_url(ArchiveFormat::TarGz).unwrap(),
+            "https://bitbucket.org/workspace/repo/get/master.tar.gz"
+        );
+        assert_eq!(
+            target.to_raw_url().unwrap(),
+            "https://bitbucket.org/workspace/repo/raw/master/file.py"
+        );
+    }
+
+    #[test]
+    fn codeberg_archive_and_raw_urls() {
+        let target =
+            parse_remote_url("https://codeberg.org/user/repo/src/branch/main/file.go").unwrap();
+        assert_eq!(
+            target.to_archive_url(ArchiveFormat::TarGz).unwrap(),
+            "https://codeberg.org/user/repo/archive/main.tar.gz"
+        );
+        assert_eq!(
+            target.to_raw_url().unwrap(),
+            "https://codeberg.org/user/repo/raw/branch/main/file.go"
+        );
+    }
+
+    #[test]
+    fn azure_has_no_well_known_archive_url() {
+        let target =
+            parse_remote_url("https://dev.azure.com/org/project/_git/repo").unwrap();
+        assert_eq!(target.to_archive_url(ArchiveFormat::TarGz), None);
+    }
+
+    // ==================== Compare/Diff URL Tests ====================
+
+    #[test]
+    fn github_compare_symmetric() {
+        let result =
+            parse_remote_url("https://github.com/owner/repo/compare/main...feature").unwrap();
+        assert_eq!(
+            result.ref_spec,
+            Some(RefSpec::Range {
+                base: "main".to_string(),
+                head: "feature".to_string(),
+                symmetric: true,
+            })
+        );
+        assert_eq!(result.ref_value, None);
+    }
+
+    #[test]
+    fn github_compare_asymmetric() {
+        let result =
+            parse_remote_url("https://github.com/owner/repo/compare/main..feature").unwrap();
+        assert_eq!(
+            result.ref_spec,
+            Some(RefSpec::Range {
+                base: "main".to_string(),
+                head: "feature".to_string(),
+                symmetric: false,
+            })
+        );
+    }
+
+    #[test]
+    fn gitlab_compare_symmetric() {
+        let result = parse_remote_url("https://gitlab.com/group/project/-/compare/main...feature")
+            .unwrap();
+        assert_eq!(
+            result.ref_spec,
+            Some(RefSpec::Range {
+                base: "main".to_string(),
+                head: "feature".to_string(),
+                symmetric: true,
+            })
+        );
+    }
+
+    #[test]
+    fn bitbucket_compare() {
+        let result = parse_remote_url(
+            "https://bitbucket.org/workspace/repo/branches/compare/feature%0Dmain",
+        )
+        .unwrap();
+        assert_eq!(
+            result.ref_spec,
+            Some(RefSpec::Range {
+                base: "main".to_string(),
+                head: "feature".to_string(),
+                symmetric: false,
+            })
+        );
+    }
+
+    #[test]
+    fn ordinary_blob_url_has_no_ref_spec() {
+        // `ref_spec` is only ever set by a compare URL - a plain blob URL
+        // leaves it `None` even though `ref_value` names a single ref.
+        let result =
+            parse_remote_url("https://github.com/owner/repo/blob/main/src/lib.rs").unwrap();
+        assert_eq!(result.ref_spec, None);
+        assert_eq!(result.ref_value, Some(GitReference::Unknown("main".to_string())));
+    }
+
+    // ==================== Wiki/Gist Resource Tests ====================
+
+    #[test]
+    fn github_wiki_page() {
+        let result = parse_remote_url("https://github.com/owner/repo/wiki/Getting-Started").unwrap();
+        assert_eq!(result.resource, Resource::Wiki);
+        assert_eq!(result.remote, "github.com/owner/repo");
+        assert_eq!(result.file_path, Some("Getting-Started".to_string()));
+        assert_eq!(result.ref_value, None);
+    }
+
+    #[test]
+    fn github_wiki_root_defaults_to_home_page() {
+        let result = parse_remote_url("https://github.com/owner/repo/wiki").unwrap();
+        assert_eq!(result.resource, Resource::Wiki);
+        assert_eq!(result.file_path, Some("Home".to_string()));
+    }
+
+    #[test]
+    fn github_gist_with_owner() {
+        let result = parse_remote_url("https://gist.github.com/octocat/abc123def456").unwrap();
+        assert_eq!(result.resource, Resource::Gist);
+        assert_eq!(result.remote, "gist.github.com/octocat/abc123def456");
+        assert_eq!(result.repo_name, "abc123def456");
+        assert_eq!(result.file_path, None);
+    }
+
+    #[test]
+    fn github_gist_anonymous() {
+        let result = parse_remote_url("https://gist.github.com/abc123def456").unwrap();
+        assert_eq!(result.resource, Resource::Gist);
+        assert_eq!(result.remote, "gist.github.com/abc123def456");
+        assert_eq!(result.repo_name, "abc123def456");
+    }
+
+    #[test]
+    fn ordinary_blob_url_has_repo_resource() {
+        let result =
+            parse_remote_url("https://github.com/owner/repo/blob/main/src/lib.rs").unwrap();
+        assert_eq!(result.resource, Resource::Repo);
+    }
+
+    #[test]
+    fn wiki_view_url_points_at_the_page() {
+        let result = parse_remote_url("https://github.com/owner/repo/wiki/Getting-Started").unwrap();
+        assert_eq!(
+            result.to_view_url(),
+            Some("https://github.com/owner/repo/wiki/Getting-Started".to_string())
+        );
+    }
+
+    #[test]
+    fn wiki_has_no_blame_or_history_or_raw_url() {
+        let result = parse_remote_url("https://github.com/owner/repo/wiki/Getting-Started").unwrap();
+        assert_eq!(result.to_blame_url(), None);
+        assert_eq!(result.to_history_url(), None);
+        assert_eq!(result.to_raw_url(), None);
+        assert_eq!(result.to_edit_url(), None);
+        assert_eq!(result.to_archive_url(ArchiveFormat::TarGz), None);
+    }
+
+    #[test]
+    fn gist_view_url_points_at_the_gist() {
+        let result = parse_remote_url("https://gist.github.com/octocat/abc123def456").unwrap();
+        assert_eq!(
+            result.to_view_url(),
+            Some("https://gist.github.com/octocat/abc123def456".to_string())
+        );
+    }
+
+    #[test]
+    fn gist_has_no_blame_or_history_url() {
+        let result = parse_remote_url("https://gist.github.com/octocat/abc123def456").unwrap();
+        assert_eq!(result.to_blame_url(), None);
+        assert_eq!(result.to_history_url(), None);
+    }
+
     // ==================== Error Cases ====================
 
     #[test]
@@ -1,13 +1,108 @@
 use std::fmt;
 
+/// Which transport the remote URL a `SrcuriTarget` was parsed from used -
+/// recorded so callers (e.g. the clone-strategy prompt) can tell an SSH
+/// origin from an HTTPS one without re-parsing `remote`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Protocol {
+    Ssh,
+    Https,
+}
+
+/// What kind of git reference `SrcuriTarget::ref_value` names, mirroring
+/// `GitRef` in the protocol handler crate. Some providers say so directly
+/// (Azure's `GB`/`GT`/`GC` version prefix, Gitea's `/src/branch|tag|commit/`
+/// segment); everyone else's blob/tree URLs use the same path shape for a
+/// branch, tag, or commit, so `Unknown` is the honest answer unless the
+/// value happens to look like a SHA.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GitReference {
+    Branch(String),
+    Tag(String),
+    Commit(String),
+    Unknown(String),
+}
+
+impl GitReference {
+    /// The bare ref name/SHA, regardless of kind - what most callers (e.g.
+    /// building a `/blob/<ref>/` URL) actually want.
+    pub fn as_str(&self) -> &str {
+        match self {
+            GitReference::Branch(v)
+            | GitReference::Tag(v)
+            | GitReference::Commit(v)
+            | GitReference::Unknown(v) => v,
+        }
+    }
+
+    /// Classifies `value` by shape alone - a SHA-like hex string becomes
+    /// `Commit`, anything else `Unknown` - for providers whose URL format
+    /// doesn't carry an explicit branch/tag/commit marker.
+    pub(crate) fn from_shape(value: String) -> Self {
+        if is_commit_sha_shape(&value) {
+            GitReference::Commit(value)
+        } else {
+            GitReference::Unknown(value)
+        }
+    }
+}
+
+/// Whether `value` looks like a commit SHA (full 40-char or an abbreviated
+/// 7+ char prefix) rather than a branch or tag name.
+pub(crate) fn is_commit_sha_shape(value: &str) -> bool {
+    (7..=40).contains(&value.len()) && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// A single ref or a two-endpoint compare/diff range, the way git's own
+/// `revparse` distinguishes them. `Single` mirrors `ref_value`'s bare name;
+/// `Range` only comes from a provider's "compare" URL shape (GitHub/GitLab's
+/// `base...head`, Bitbucket's `branches/compare/head%0Dbase`) - ordinary
+/// blob/tree URLs have no range, so `SrcuriTarget::ref_spec` is `None` for
+/// those rather than a redundant `Some(Single(..))` of `ref_value`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefSpec {
+    Single(String),
+    /// `symmetric` is `true` for GitHub/GitLab's `base...head` ("show commits
+    /// reachable from head but not the merge-base with base") vs `false` for
+    /// `base..head` (direct two-dot range).
+    Range {
+        base: String,
+        head: String,
+        symmetric: bool,
+    },
+}
+
+/// Which kind of thing a URL addresses - an ordinary repo file/tree, a wiki
+/// page, or a standalone gist. Wikis and gists don't have a blob/tree shape
+/// (no blame, no per-file commit history, no raw/edit/archive URL in the
+/// same sense), so `to_blame_url`/`to_history_url`/etc. consult this instead
+/// of assuming every target is a repo the way they used to.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Resource {
+    #[default]
+    Repo,
+    Wiki,
+    Gist,
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct SrcuriTarget {
     pub remote: String,
     pub repo_name: String,
-    pub ref_value: Option<String>,
+    pub ref_value: Option<GitReference>,
+    /// Set only for a provider's compare/diff URL - `None` everywhere else,
+    /// even when `ref_value` names a single ref/SHA. See [`RefSpec`].
+    pub ref_spec: Option<RefSpec>,
+    /// `Repo` unless the URL was a GitHub wiki page or gist - see [`Resource`].
+    pub resource: Resource,
     pub file_path: Option<String>,
     pub line: Option<u32>,
+    pub end_line: Option<u32>,
     pub is_absolute: bool,
+    /// `None` for targets built directly (e.g. `Default::default()` in
+    /// tests) rather than through `parse_remote_url`, which always fills
+    /// this in.
+    pub protocol: Option<Protocol>,
 }
 
 impl SrcuriTarget {
@@ -25,7 +120,7 @@ impl SrcuriTarget {
 
         let mut query_parts = Vec::new();
         if let Some(ref branch) = self.ref_value {
-            query_parts.push(format!("branch={}", branch));
+            query_parts.push(format!("branch={}", branch.as_str()));
         }
         // Include https:// prefix for git clone compatibility
         query_parts.push(format!("remote=https://{}", self.remote));
@@ -40,56 +135,318 @@ impl SrcuriTarget {
 
     /// Construct a URL to view this file on the remote provider (GitHub, GitLab, etc.)
     /// Returns None if there's no remote or no file path to view.
+    ///
+    /// When `ref_value` looks like a commit SHA rather than a branch name, Gitea/Codeberg
+    /// URLs switch their `branch`/`commit` path segment accordingly so the link stays a
+    /// true permalink instead of silently resolving through a `branch` segment. GitHub and
+    /// GitLab blob URLs accept either a branch or a SHA in the same position, so no path
+    /// shape change is needed there - the SHA just flows through `ref_or_default`.
     pub fn to_view_url(&self) -> Option<String> {
-        if self.remote.is_empty() || self.file_path.is_none() {
+        if self.remote.is_empty() {
             return None;
         }
 
+        match self.resource {
+            // A wiki page's "view" is the wiki itself - `self.remote` is
+            // already `host/owner/repo`, same as a repo target's.
+            Resource::Wiki => {
+                let page = self.file_path.as_ref()?;
+                return Some(format!("https://{}/wiki/{}", self.remote, page));
+            }
+            // A gist has no file-tree view to redirect into - `self.remote`
+            // is already the gist's full `gist.github.com/[user/]id` path.
+            Resource::Gist => return Some(format!("https://{}", self.remote)),
+            Resource::Repo => {}
+        }
+
         let file_path = self.file_path.as_ref()?;
-        let branch = self.ref_value.as_deref().unwrap_or("main");
+        let ref_or_default = self.ref_value.as_ref().map(GitReference::as_str).unwrap_or("main");
         let remote_lower = self.remote.to_lowercase();
+        let gitea_ref_segment = if self.is_commit_sha() { "commit" } else { "branch" };
 
         // Determine provider and construct appropriate URL
         let base_url = if remote_lower.contains("github.com") {
-            // GitHub: https://github.com/owner/repo/blob/branch/path#L42
-            format!("https://{}/blob/{}/{}", self.remote, branch, file_path)
+            // GitHub: https://github.com/owner/repo/blob/{branch-or-sha}/path#L42
+            format!("https://{}/blob/{}/{}", self.remote, ref_or_default, file_path)
         } else if remote_lower.contains("gitlab") {
-            // GitLab: https://gitlab.com/owner/repo/-/blob/branch/path#L42
-            format!("https://{}/-/blob/{}/{}", self.remote, branch, file_path)
+            // GitLab: https://gitlab.com/owner/repo/-/blob/{branch-or-sha}/path#L42
+            format!("https://{}/-/blob/{}/{}", self.remote, ref_or_default, file_path)
         } else if remote_lower.contains("bitbucket") {
-            // Bitbucket: https://bitbucket.org/owner/repo/src/branch/path#lines-42
-            format!("https://{}/src/{}/{}", self.remote, branch, file_path)
+            // Bitbucket: https://bitbucket.org/owner/repo/src/{branch-or-sha}/path#lines-42
+            format!("https://{}/src/{}/{}", self.remote, ref_or_default, file_path)
         } else if remote_lower.contains("codeberg.org") {
-            // Codeberg (Gitea-based): https://codeberg.org/owner/repo/src/branch/main/path#L42
+            // Codeberg (Gitea-based): https://codeberg.org/owner/repo/src/{branch|commit}/ref/path#L42
             format!(
-                "https://{}/src/branch/{}/{}",
-                self.remote, branch, file_path
+                "https://{}/src/{}/{}/{}",
+                self.remote, gitea_ref_segment, ref_or_default, file_path
             )
         } else if remote_lower.contains("dev.azure.com")
             || remote_lower.contains("visualstudio.com")
         {
-            // Azure DevOps: complex URL structure, return basic for now
-            format!("https://{}", self.remote)
+            // Azure DevOps has no separate path shape for this - it's the
+            // same `?path=...&version=...` query URL `to_blame_url`/
+            // `to_history_url` build, just without an `_a` tab selector.
+            return Some(self.azure_file_query_url(file_path, ref_or_default));
         } else {
             // Generic Gitea/other: use Gitea-style URL
             format!(
-                "https://{}/src/branch/{}/{}",
-                self.remote, branch, file_path
+                "https://{}/src/{}/{}/{}",
+                self.remote, gitea_ref_segment, ref_or_default, file_path
             )
         };
 
-        // Append line number fragment
-        let url = if let Some(line) = self.line {
-            if remote_lower.contains("bitbucket") {
-                format!("{}#lines-{}", base_url, line)
-            } else {
-                format!("{}#L{}", base_url, line)
-            }
+        Some(format!("{}{}", base_url, self.line_fragment(&remote_lower)))
+    }
+
+    /// Construct a URL to the raw (unrendered) file content on the remote provider.
+    /// Returns None if there's no remote or no file path, if the resource isn't
+    /// an ordinary repo file (a wiki page or gist has no raw-content URL in the
+    /// same sense), or if the provider has no well-known raw-content URL shape
+    /// (Azure DevOps).
+    pub fn to_raw_url(&self) -> Option<String> {
+        if self.remote.is_empty() || self.file_path.is_none() || self.resource != Resource::Repo {
+            return None;
+        }
+
+        let file_path = self.file_path.as_ref()?;
+        let ref_or_default = self.ref_value.as_ref().map(GitReference::as_str).unwrap_or("main");
+        let remote_lower = self.remote.to_lowercase();
+
+        if remote_lower.contains("github.com") {
+            // raw.githubusercontent.com mirrors the owner/repo path, no "github.com" host
+            let owner_repo = self.remote.splitn(2, '/').nth(1)?;
+            Some(format!(
+                "https://raw.githubusercontent.com/{}/{}/{}",
+                owner_repo, ref_or_default, file_path
+            ))
+        } else if remote_lower.contains("gitlab") {
+            Some(format!(
+                "https://{}/-/raw/{}/{}",
+                self.remote, ref_or_default, file_path
+            ))
+        } else if remote_lower.contains("bitbucket") {
+            Some(format!(
+                "https://{}/raw/{}/{}",
+                self.remote, ref_or_default, file_path
+            ))
+        } else if remote_lower.contains("dev.azure.com") || remote_lower.contains("visualstudio.com") {
+            None
+        } else {
+            // Codeberg and generic Gitea instances
+            let ref_segment = if self.is_commit_sha() { "commit" } else { "branch" };
+            Some(format!(
+                "https://{}/raw/{}/{}/{}",
+                self.remote, ref_segment, ref_or_default, file_path
+            ))
+        }
+    }
+
+    /// Construct a machine-fetchable archive download URL (tarball/zipball) for
+    /// this target's ref - lets callers fetch a repo's contents without a full
+    /// clone. Returns None if there's no remote, if the resource isn't an
+    /// ordinary repo (a wiki or gist has no repo archive), or if the provider
+    /// has no well-known archive URL shape (Azure DevOps).
+    pub fn to_archive_url(&self, format: ArchiveFormat) -> Option<String> {
+        if self.remote.is_empty() || self.resource != Resource::Repo {
+            return None;
+        }
+
+        let ref_or_default = self.ref_value.as_ref().map(GitReference::as_str).unwrap_or("main");
+        let remote_lower = self.remote.to_lowercase();
+        let ext = format.extension();
+
+        if remote_lower.contains("github.com") {
+            // codeload.github.com mirrors the owner/repo path, no "github.com" host
+            let owner_repo = self.remote.splitn(2, '/').nth(1)?;
+            Some(format!(
+                "https://codeload.github.com/{}/{}/{}",
+                owner_repo, ext, ref_or_default
+            ))
+        } else if remote_lower.contains("gitlab") {
+            Some(format!(
+                "https://{}/-/archive/{}/{}-{}.{}",
+                self.remote, ref_or_default, self.repo_name, ref_or_default, ext
+            ))
+        } else if remote_lower.contains("bitbucket") {
+            Some(format!("https://{}/get/{}.{}", self.remote, ref_or_default, ext))
+        } else if remote_lower.contains("dev.azure.com") || remote_lower.contains("visualstudio.com") {
+            None
+        } else {
+            // Codeberg and generic Gitea instances
+            Some(format!("https://{}/archive/{}.{}", self.remote, ref_or_default, ext))
+        }
+    }
+
+    /// Construct a URL to the remote provider's web editor for this file.
+    /// Returns None if there's no remote or no file path, if the resource isn't
+    /// an ordinary repo file (wikis/gists have their own, unrelated edit UIs),
+    /// or if the provider has no well-known web-editor URL shape (Bitbucket,
+    /// Azure DevOps).
+    pub fn to_edit_url(&self) -> Option<String> {
+        if self.remote.is_empty() || self.file_path.is_none() || self.resource != Resource::Repo {
+            return None;
+        }
+
+        let file_path = self.file_path.as_ref()?;
+        let ref_or_default = self.ref_value.as_ref().map(GitReference::as_str).unwrap_or("main");
+        let remote_lower = self.remote.to_lowercase();
+
+        if remote_lower.contains("github.com") {
+            Some(format!(
+                "https://{}/edit/{}/{}",
+                self.remote, ref_or_default, file_path
+            ))
+        } else if remote_lower.contains("gitlab") {
+            Some(format!(
+                "https://{}/-/edit/{}/{}",
+                self.remote, ref_or_default, file_path
+            ))
+        } else if remote_lower.contains("bitbucket") || remote_lower.contains("dev.azure.com")
+            || remote_lower.contains("visualstudio.com")
+        {
+            None
+        } else {
+            // Codeberg and generic Gitea instances use `_edit` rather than `edit`
+            let ref_segment = if self.is_commit_sha() { "commit" } else { "branch" };
+            Some(format!(
+                "https://{}/_edit/{}/{}/{}",
+                self.remote, ref_segment, ref_or_default, file_path
+            ))
+        }
+    }
+
+    /// Construct a URL to the remote provider's blame/annotate view for this file.
+    /// Returns None if there's no remote or no file path to view, or if the
+    /// resource isn't an ordinary repo file - wikis and gists have no blame view.
+    pub fn to_blame_url(&self) -> Option<String> {
+        if self.remote.is_empty() || self.file_path.is_none() || self.resource != Resource::Repo {
+            return None;
+        }
+
+        let file_path = self.file_path.as_ref()?;
+        let ref_or_default = self.ref_value.as_ref().map(GitReference::as_str).unwrap_or("main");
+        let remote_lower = self.remote.to_lowercase();
+        let gitea_ref_segment = if self.is_commit_sha() { "commit" } else { "branch" };
+
+        let base_url = if remote_lower.contains("github.com") {
+            format!("https://{}/blame/{}/{}", self.remote, ref_or_default, file_path)
+        } else if remote_lower.contains("gitlab") {
+            format!("https://{}/-/blame/{}/{}", self.remote, ref_or_default, file_path)
+        } else if remote_lower.contains("bitbucket") {
+            format!("https://{}/annotate/{}/{}", self.remote, ref_or_default, file_path)
+        } else if remote_lower.contains("dev.azure.com")
+            || remote_lower.contains("visualstudio.com")
+        {
+            return Some(format!(
+                "{}&_a=annotate",
+                self.azure_file_query_url(file_path, ref_or_default)
+            ));
         } else {
-            base_url
+            // Codeberg and generic Gitea instances
+            format!(
+                "https://{}/blame/{}/{}/{}",
+                self.remote, gitea_ref_segment, ref_or_default, file_path
+            )
+        };
+
+        Some(format!("{}{}", base_url, self.line_fragment(&remote_lower)))
+    }
+
+    /// Construct a URL to the remote provider's commit-history view for this file.
+    /// Returns None if there's no remote or no file path, if the resource isn't an
+    /// ordinary repo file (wikis and gists have no per-file history view in the
+    /// same shape), or if the provider has no well-known per-file history URL
+    /// shape (Bitbucket).
+    pub fn to_history_url(&self) -> Option<String> {
+        if self.remote.is_empty() || self.file_path.is_none() || self.resource != Resource::Repo {
+            return None;
+        }
+
+        let file_path = self.file_path.as_ref()?;
+        let ref_or_default = self.ref_value.as_ref().map(GitReference::as_str).unwrap_or("main");
+        let remote_lower = self.remote.to_lowercase();
+
+        if remote_lower.contains("github.com") {
+            Some(format!(
+                "https://{}/commits/{}/{}",
+                self.remote, ref_or_default, file_path
+            ))
+        } else if remote_lower.contains("gitlab") {
+            Some(format!(
+                "https://{}/-/commits/{}/{}",
+                self.remote, ref_or_default, file_path
+            ))
+        } else if remote_lower.contains("bitbucket") {
+            None
+        } else if remote_lower.contains("dev.azure.com")
+            || remote_lower.contains("visualstudio.com")
+        {
+            Some(format!(
+                "{}&_a=history",
+                self.azure_file_query_url(file_path, ref_or_default)
+            ))
+        } else {
+            // Codeberg and generic Gitea instances
+            let ref_segment = if self.is_commit_sha() { "commit" } else { "branch" };
+            Some(format!(
+                "https://{}/commits/{}/{}/{}",
+                self.remote, ref_segment, ref_or_default, file_path
+            ))
+        }
+    }
+
+    /// The Azure DevOps `?path=...&version=GB{ref}&line=...` file query URL shared
+    /// by `to_view_url`/`to_blame_url`/`to_history_url` - unlike the other providers,
+    /// Azure has one URL shape per file and picks content/annotate/history via the
+    /// `_a` query param rather than a different path.
+    fn azure_file_query_url(&self, file_path: &str, ref_or_default: &str) -> String {
+        let version_prefix = match &self.ref_value {
+            Some(GitReference::Tag(_)) => "GT",
+            Some(GitReference::Commit(_)) => "GC",
+            _ => "GB",
+        };
+
+        let mut url = format!(
+            "https://{}?path=/{}&version={}{}",
+            self.remote, file_path, version_prefix, ref_or_default
+        );
+
+        if let Some(line) = self.line {
+            url.push_str(&format!("&line={}", line));
+            if let Some(end_line) = self.end_line {
+                if end_line != line {
+                    url.push_str(&format!("&lineEnd={}", end_line));
+                }
+            }
+        }
+
+        url
+    }
+
+    /// The `#L10`/`#L10-L20` (or Bitbucket's `#lines-10:20`) fragment for `self.line`/
+    /// `self.end_line`, or an empty string when there's no line to point at.
+    fn line_fragment(&self, remote_lower: &str) -> String {
+        let Some(line) = self.line else {
+            return String::new();
         };
 
-        Some(url)
+        if remote_lower.contains("bitbucket") {
+            match self.end_line {
+                Some(end_line) if end_line != line => format!("#lines-{}:{}", line, end_line),
+                _ => format!("#lines-{}", line),
+            }
+        } else {
+            match self.end_line {
+                Some(end_line) if end_line != line => format!("#L{}-L{}", line, end_line),
+                _ => format!("#L{}", line),
+            }
+        }
+    }
+
+    /// Whether `ref_value` was classified as a commit rather than a branch
+    /// or tag - see [`GitReference`] for how that classification happens.
+    fn is_commit_sha(&self) -> bool {
+        matches!(self.ref_value, Some(GitReference::Commit(_)))
     }
 
     /// Get a human-readable name for the remote provider
@@ -113,6 +470,23 @@ impl SrcuriTarget {
     }
 }
 
+/// Which compressed format `SrcuriTarget::to_archive_url` should build a
+/// download link for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveFormat {
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::TarGz => "tar.gz",
+            ArchiveFormat::Zip => "zip",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Provider {
     GitHub,
@@ -123,6 +497,49 @@ pub enum Provider {
     AzureDevOps,
 }
 
+/// Explicit `(host, Provider)` mappings for self-hosted instances that
+/// `detect_provider`'s pattern/host heuristics can't recognize on their own -
+/// a Gitea/Forgejo instance on a host with no `gitea`/`codeberg` in its name,
+/// reached before the URL shape (`/src/branch/...`) gives anything away.
+/// Consulted before the built-in heuristics by `parse_remote_url_with_registry`,
+/// so a configured host always resolves deterministically.
+#[derive(Debug, Clone, Default)]
+pub struct ProviderRegistry {
+    hosts: std::collections::HashMap<String, Provider>,
+    host_suffixes: Vec<(String, Provider)>,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps an exact host (e.g. `git.example.com`) to `provider`.
+    pub fn register_host(&mut self, host: impl Into<String>, provider: Provider) {
+        self.hosts.insert(host.into(), provider);
+    }
+
+    /// Maps any host ending in `suffix` (e.g. `.corp.example.com`) to
+    /// `provider`. Checked after exact host matches, in registration order.
+    pub fn register_host_suffix(&mut self, suffix: impl Into<String>, provider: Provider) {
+        self.host_suffixes.push((suffix.into(), provider));
+    }
+
+    /// Looks `host` up against the registered exact-host and host-suffix
+    /// mappings, exact matches taking priority. Exposed beyond this crate so
+    /// a caller can reuse the same registry to decide whether a host is
+    /// provider-passthrough-shaped at all, ahead of calling
+    /// `parse_remote_url_with_registry` to actually parse it.
+    pub fn resolve(&self, host: &str) -> Option<Provider> {
+        self.hosts.get(host).copied().or_else(|| {
+            self.host_suffixes
+                .iter()
+                .find(|(suffix, _)| host.ends_with(suffix.as_str()))
+                .map(|(_, provider)| *provider)
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ParseError {
     pub message: String,